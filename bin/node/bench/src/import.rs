@@ -30,7 +30,7 @@
 
 use std::borrow::Cow;
 
-use node_testing::bench::{BenchDb, Profile, BlockType, KeyTypes};
+use node_testing::bench::{BenchDb, Profile, BlockType, KeyTypes, DatabaseType};
 use node_primitives::Block;
 use sc_client_api::backend::Backend;
 use sp_runtime::generic::BlockId;
@@ -72,6 +72,7 @@ pub struct ImportBenchmarkDescription {
 	pub key_types: KeyTypes,
 	pub block_type: BlockType,
 	pub size: SizeType,
+	pub database_type: DatabaseType,
 }
 
 pub struct ImportBenchmark {
@@ -99,6 +100,16 @@ impl core::BenchmarkDescription for ImportBenchmarkDescription {
 			BlockType::RandomTransfersKeepAlive(_) => path.push("transfer_keep_alive"),
 			BlockType::RandomTransfersReaping(_) => path.push("transfer_reaping"),
 			BlockType::Noop(_) => path.push("noop"),
+			BlockType::Votes(_) => path.push("votes"),
+			BlockType::DelegationCascades(_) => path.push("delegation_cascades"),
+			BlockType::VoteRemovals(_) => path.push("vote_removals"),
+			BlockType::IdentitySets(_) => path.push("identity_sets"),
+			BlockType::SocietyBids(_) => path.push("society_bids"),
+		}
+
+		match self.database_type {
+			DatabaseType::RocksDb => path.push("rocksdb"),
+			DatabaseType::ParityDb => path.push("paritydb"),
 		}
 
 		path.push(&format!("{}", self.size));
@@ -109,6 +120,7 @@ impl core::BenchmarkDescription for ImportBenchmarkDescription {
 	fn setup(self: Box<Self>) -> Box<dyn core::Benchmark> {
 		let profile = self.profile;
 		let mut bench_db = BenchDb::with_key_types(
+			self.database_type,
 			50_000,
 			self.key_types
 		);
@@ -122,9 +134,10 @@ impl core::BenchmarkDescription for ImportBenchmarkDescription {
 
 	fn name(&self) -> Cow<'static, str> {
 		format!(
-			"Import benchmark ({:?}, {:?})",
+			"Import benchmark ({:?}, {:?}, {:?})",
 			self.block_type,
 			self.profile,
+			self.database_type,
 		).into()
 	}
 }
@@ -160,7 +173,7 @@ impl core::Benchmark for ImportBenchmark {
 			target: "bench-logistics",
 			"usage info: {}",
 			context.backend.usage_info()
-				.expect("RocksDB backend always provides usage info!"),
+				.expect("Database backend always provides usage info!"),
 		);
 
 		elapsed
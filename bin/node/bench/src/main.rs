@@ -26,7 +26,7 @@ use crate::core::{run_benchmark, Mode as BenchmarkMode};
 use crate::tempdb::DatabaseType;
 use import::{ImportBenchmarkDescription, SizeType};
 use trie::{TrieReadBenchmarkDescription, TrieWriteBenchmarkDescription, DatabaseSize};
-use node_testing::bench::{Profile, KeyTypes, BlockType};
+use node_testing::bench::{Profile, KeyTypes, BlockType, DatabaseType as BenchDataBaseType};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -89,19 +89,27 @@ fn main() {
 				BlockType::RandomTransfersKeepAlive(txs),
 				BlockType::RandomTransfersReaping(txs),
 				BlockType::Noop(txs),
+				BlockType::Votes(txs),
+				BlockType::DelegationCascades(txs),
+				BlockType::VoteRemovals(txs),
+				BlockType::IdentitySets(txs),
+				BlockType::SocietyBids(txs),
 			].iter() {
-				import_benchmarks.push((profile.clone(), size.clone(), block_type.clone()));
+				for database_type in [BenchDataBaseType::RocksDb, BenchDataBaseType::ParityDb].iter() {
+					import_benchmarks.push((profile.clone(), size.clone(), block_type.clone(), database_type.clone()));
+				}
 			}
 		}
 	}
 
 	let benchmarks = matrix!(
-		(profile, size, block_type) in import_benchmarks.iter() =>
+		(profile, size, block_type, database_type) in import_benchmarks.iter() =>
 			ImportBenchmarkDescription {
 				profile: *profile,
 				key_types: KeyTypes::Sr25519,
 				size: *size,
 				block_type: *block_type,
+				database_type: *database_type,
 			},
 		(size, db_type) in
 			[
@@ -23,9 +23,9 @@ use node_runtime::{
 	AuthorityDiscoveryConfig, BabeConfig, BalancesConfig, ContractsConfig, CouncilConfig,
 	DemocracyConfig,GrandpaConfig, ImOnlineConfig, SessionConfig, SessionKeys, StakerStatus,
 	StakingConfig, ElectionsConfig, IndicesConfig, SocietyConfig, SudoConfig, SystemConfig,
-	TechnicalCommitteeConfig, WASM_BINARY,
+	TechnicalCommitteeConfig, SystemCall, WASM_BINARY,
 };
-use node_runtime::Block;
+use node_runtime::{Block, Call};
 use node_runtime::constants::currency::*;
 use sc_service::ChainType;
 use hex_literal::hex;
@@ -34,7 +34,9 @@ use grandpa_primitives::{AuthorityId as GrandpaId};
 use sp_consensus_babe::{AuthorityId as BabeId};
 use pallet_im_online::sr25519::{AuthorityId as ImOnlineId};
 use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
-use sp_runtime::{Perbill, traits::{Verify, IdentifyAccount}};
+use sp_runtime::{Perbill, traits::{Verify, IdentifyAccount, BlakeTwo256, Hash}};
+use pallet_quadratic_democracy::{Conviction, VoteThreshold, VoteWeight};
+use codec::Encode;
 
 pub use node_primitives::{AccountId, Balance, Signature};
 pub use node_runtime::GenesisConfig;
@@ -271,7 +273,7 @@ pub fn testnet_genesis(
 			slash_reward_fraction: Perbill::from_percent(10),
 			.. Default::default()
 		}),
-		pallet_democracy: Some(DemocracyConfig::default()),
+		pallet_quadratic_democracy: Some(DemocracyConfig::default()),
 		pallet_elections_phragmen: Some(ElectionsConfig {
 			members: endowed_accounts.iter()
 						.take((num_endowed_accounts + 1) / 2)
@@ -310,6 +312,7 @@ pub fn testnet_genesis(
 		}),
 		pallet_membership_Instance1: Some(Default::default()),
 		pallet_treasury: Some(Default::default()),
+		pallet_identity: Some(Default::default()),
 		pallet_society: Some(SocietyConfig {
 			members: endowed_accounts.iter()
 						.take((num_endowed_accounts + 1) / 2)
@@ -375,6 +378,56 @@ pub fn local_testnet_config() -> ChainSpec {
 	)
 }
 
+/// Helper function to create a democracy GenesisConfig with governance under way: the first of
+/// `accounts` notes a remark and proposes it publicly, a referendum on it is started under each
+/// vote weighting, and the other accounts delegate to the first.
+pub fn governance_genesis(accounts: &[AccountId]) -> DemocracyConfig {
+	let proposer = match accounts.first() {
+		Some(proposer) => proposer.clone(),
+		None => return DemocracyConfig::default(),
+	};
+	let proposal = Call::System(SystemCall::remark(b"governance genesis".to_vec())).encode();
+	let proposal_hash = BlakeTwo256::hash(&proposal);
+
+	DemocracyConfig {
+		preimages: vec![(proposer.clone(), proposal)],
+		proposals: vec![(proposer.clone(), proposal_hash, 100 * DOLLARS, VoteWeight::Quadratic)],
+		referenda: [VoteWeight::Standard, VoteWeight::Quadratic, VoteWeight::Credits].iter()
+			.map(|&weight| (proposal_hash, VoteThreshold::SimpleMajority, weight, 0))
+			.collect(),
+		delegations: accounts.iter()
+			.skip(1)
+			.map(|who| (who.clone(), proposer.clone(), Conviction::Locked1x, 1_000 * DOLLARS))
+			.collect(),
+		linked_sub_accounts: vec![],
+	}
+}
+
+fn governance_development_config_genesis() -> GenesisConfig {
+	let mut genesis = development_config_genesis();
+	genesis.pallet_quadratic_democracy = Some(governance_genesis(&[
+		get_account_id_from_seed::<sr25519::Public>("Alice"),
+		get_account_id_from_seed::<sr25519::Public>("Bob"),
+		get_account_id_from_seed::<sr25519::Public>("Charlie"),
+	]));
+	genesis
+}
+
+/// Development config (single validator Alice) with referenda open for voting
+pub fn governance_development_config() -> ChainSpec {
+	ChainSpec::from_genesis(
+		"Governance Development",
+		"governance_dev",
+		ChainType::Development,
+		governance_development_config_genesis,
+		vec![],
+		None,
+		None,
+		None,
+		Default::default(),
+	)
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
 	use super::*;
@@ -443,6 +496,11 @@ pub(crate) mod tests {
 		local_testnet_config().build_storage().unwrap();
 	}
 
+	#[test]
+	fn test_create_governance_development_chain_spec() {
+		governance_development_config().build_storage().unwrap();
+	}
+
 	#[test]
 	fn test_staging_test_net_chain_spec() {
 		staging_testnet_config().build_storage().unwrap();
@@ -14,6 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::path::PathBuf;
+
 use sc_cli::{ImportParams, RunCmd, SharedParams};
 use structopt::StructOpt;
 
@@ -37,8 +39,8 @@ pub enum Subcommand {
 	/// The custom factory subcommmand for manufacturing transactions.
 	#[structopt(
 		name = "factory",
-		about = "Manufactures num transactions from Alice to random accounts. \
-		Only supported for development or local testnet."
+		about = "Manufactures num transactions from Alice to random accounts, \
+		or the calls of a scenario. Only supported for development or local testnet."
 	)]
 	Factory(FactoryCmd),
 
@@ -66,6 +68,14 @@ pub struct FactoryCmd {
 	#[structopt(long = "transactions", default_value = "8")]
 	pub transactions: u32,
 
+	/// TOML file describing a scenario of calls to make instead of transfers.
+	///
+	/// The blocks funding the accounts of the scenario and making its setup
+	/// calls come first, followed by `--blocks` blocks of its calls. Export the chain with `export-blocks` to
+	/// replay it.
+	#[structopt(long = "scenario", parse(from_os_str))]
+	pub scenario: Option<PathBuf>,
+
 	#[allow(missing_docs)]
 	#[structopt(flatten)]
 	pub shared_params: SharedParams,
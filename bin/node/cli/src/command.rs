@@ -54,6 +54,7 @@ impl SubstrateCli for Cli {
 		Ok(match id {
 			"dev" => Box::new(chain_spec::development_config()),
 			"local" => Box::new(chain_spec::local_testnet_config()),
+			"governance-dev" => Box::new(chain_spec::governance_development_config()),
 			"" | "fir" | "flaming-fir" => Box::new(chain_spec::flaming_fir_config()?),
 			"staging" => Box::new(chain_spec::staging_testnet_config()),
 			path => Box::new(chain_spec::ChainSpec::from_json_file(
@@ -135,11 +136,15 @@ impl FactoryCmd {
 			}
 		}
 
+		let scenario = self.scenario.as_ref()
+			.map(|path| node_transaction_factory::Scenario::from_file(path))
+			.transpose()?;
 		let factory_state = FactoryState::new(self.blocks, self.transactions);
 
 		let service_builder = new_full_start!(config).0;
 		node_transaction_factory::factory(
 			factory_state,
+			scenario.as_ref(),
 			service_builder.client(),
 			service_builder
 				.select_chain()
@@ -18,21 +18,26 @@
 //! using the cli to manufacture transactions and distribute them
 //! to accounts.
 
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
 use rand::{Rng, SeedableRng};
 use rand::rngs::StdRng;
 
 use codec::{Encode, Decode};
 use sp_keyring::sr25519::Keyring;
 use node_runtime::{
-	Call, CheckedExtrinsic, UncheckedExtrinsic, SignedExtra, BalancesCall, ExistentialDeposit,
-	MinimumPeriod
+	Call, CheckedExtrinsic, UncheckedExtrinsic, SignedExtra, BalancesCall, SystemCall, ExistentialDeposit,
+	MinimumPeriod, constants::currency::DOLLARS,
 };
-use node_primitives::Signature;
-use sp_core::{sr25519, crypto::Pair};
+use node_primitives::{AccountId, Balance, Index, Signature};
+use pallet_quadratic_democracy::{AccountVote, Conviction, Vote};
+use pallet_identity::{Data, IdentityInfo, Judgement};
+use sp_core::{sr25519, crypto::Pair, Bytes};
 use sp_runtime::{
 	generic::Era, traits::{Block as BlockT, Header as HeaderT, SignedExtension, Verify, IdentifyAccount}
 };
-use node_transaction_factory::RuntimeAdapter;
+use node_transaction_factory::{RuntimeAdapter, ScenarioCall};
 use sp_inherents::InherentData;
 use sp_timestamp;
 use sp_finality_tracker;
@@ -43,7 +48,7 @@ pub struct FactoryState<N> {
 	blocks: u32,
 	transactions: u32,
 	block_number: N,
-	index: u32,
+	nonces: HashMap<AccountId, Index>,
 }
 
 type Number = <<node_primitives::Block as BlockT>::Header as HeaderT>::Number;
@@ -59,6 +64,14 @@ impl<Number> FactoryState<Number> {
 			pallet_transaction_payment::ChargeTransactionPayment::from(0),
 		)
 	}
+
+	/// The nonce of the next transaction of `who`, which is expected to only
+	/// send transactions manufactured by the factory.
+	fn next_nonce(&mut self, who: &AccountId) -> Index {
+		let nonce = self.nonces.entry(who.clone()).or_insert(0);
+		*nonce += 1;
+		*nonce - 1
+	}
 }
 
 impl RuntimeAdapter for FactoryState<Number> {
@@ -79,7 +92,7 @@ impl RuntimeAdapter for FactoryState<Number> {
 			blocks,
 			transactions,
 			block_number: 0,
-			index: 0,
+			nonces: HashMap::new(),
 		}
 	}
 
@@ -110,8 +123,7 @@ impl RuntimeAdapter for FactoryState<Number> {
 		prior_block_hash: &<Self::Block as BlockT>::Hash,
 	) -> <Self::Block as BlockT>::Extrinsic {
 		let phase = self.block_number() as Self::Phase;
-		let extra = Self::build_extra(self.index, phase);
-		self.index += 1;
+		let extra = Self::build_extra(self.next_nonce(sender), phase);
 
 		sign::<Self>(CheckedExtrinsic {
 			signed: Some((sender.clone(), extra)),
@@ -124,6 +136,29 @@ impl RuntimeAdapter for FactoryState<Number> {
 		}, key, (version, genesis_hash.clone(), prior_block_hash.clone(), (), (), ()))
 	}
 
+	fn scenario_extrinsic(
+		&mut self,
+		call: &ScenarioCall,
+		sender: &Self::AccountId,
+		key: &Self::Secret,
+		target: &Self::AccountId,
+		super_account: Option<&Self::AccountId>,
+		sub_accounts: &[Self::AccountId],
+		seed: u32,
+		version: u32,
+		genesis_hash: &<Self::Block as BlockT>::Hash,
+		prior_block_hash: &<Self::Block as BlockT>::Hash,
+	) -> Result<<Self::Block as BlockT>::Extrinsic, String> {
+		let function = scenario_call(call, sender, target, super_account, sub_accounts, seed)?;
+		let phase = self.block_number() as Self::Phase;
+		let extra = Self::build_extra(self.next_nonce(sender), phase);
+
+		Ok(sign::<Self>(CheckedExtrinsic {
+			signed: Some((sender.clone(), extra)),
+			function,
+		}, key, (version, genesis_hash.clone(), prior_block_hash.clone(), (), (), ())))
+	}
+
 	fn inherent_extrinsics(&self) -> InherentData {
 		let timestamp = (self.block_number as u64 + 1) * MinimumPeriod::get();
 
@@ -160,6 +195,103 @@ impl RuntimeAdapter for FactoryState<Number> {
 	}
 }
 
+/// The runtime call a scenario `call` from `sender` stands for.
+///
+/// Calls given `encoded` are decoded as they are. Votes are split if given an
+/// `aye_balance` or a `nay_balance`, and otherwise alternate between aye and
+/// nay unless told which; quadratic referenda weigh them with the capital of
+/// the sub-accounts linked to the voter. Transfers, delegations and judgements
+/// are made to `target`, and sub-accounts are linked to `super_account`.
+/// Registrars are added through `sudo`, so only by the master account of
+/// development chains.
+fn scenario_call(
+	call: &ScenarioCall,
+	sender: &AccountId,
+	target: &AccountId,
+	super_account: Option<&AccountId>,
+	sub_accounts: &[AccountId],
+	seed: u32,
+) -> Result<Call, String> {
+	if let Some(encoded) = &call.encoded {
+		let encoded = encoded.parse::<Bytes>()
+			.map_err(|e| format!("Invalid encoded {}::{}: {}", call.pallet, call.call, e))?;
+		return Call::decode(&mut &encoded[..])
+			.map_err(|e| format!("Invalid encoded {}::{}: {}", call.pallet, call.call, e.what()));
+	}
+
+	let balance = |default: Balance| -> Result<Balance, String> {
+		Ok(call.arg::<u64>("balance")?.map_or(default, Into::into))
+	};
+	let conviction = || -> Result<Conviction, String> {
+		match call.arg::<u8>("conviction")? {
+			Some(conviction) => Conviction::try_from(conviction)
+				.map_err(|_| format!("Invalid conviction {}", conviction)),
+			None => Ok(Conviction::None),
+		}
+	};
+
+	Ok(match (call.pallet.as_str(), call.call.as_str()) {
+		("system", "remark") => Call::System(SystemCall::remark(
+			vec![0; call.arg("size")?.unwrap_or(0)],
+		)),
+		("balances", "transfer") => Call::Balances(BalancesCall::transfer(
+			pallet_indices::address::Address::Id(target.clone()),
+			balance(ExistentialDeposit::get())?,
+		)),
+		("democracy", "vote") => Call::Democracy(pallet_quadratic_democracy::Call::vote(
+			call.arg("referendum")?.unwrap_or(0),
+			match (call.arg::<u64>("aye_balance")?, call.arg::<u64>("nay_balance")?) {
+				(None, None) => AccountVote::Standard {
+					vote: Vote {
+						aye: call.arg("aye")?.unwrap_or(seed % 2 == 0),
+						conviction: conviction()?,
+					},
+					balance: balance(DOLLARS)?,
+				},
+				(aye, nay) => AccountVote::Split {
+					aye: aye.map_or(0, Into::into),
+					nay: nay.map_or(0, Into::into),
+				},
+			},
+		)),
+		("democracy", "remove_vote") => Call::Democracy(pallet_quadratic_democracy::Call::remove_vote(
+			call.arg("referendum")?.unwrap_or(0),
+		)),
+		("democracy", "delegate") => Call::Democracy(pallet_quadratic_democracy::Call::delegate(
+			target.clone(),
+			conviction()?,
+			balance(DOLLARS)?,
+		)),
+		("democracy", "undelegate") => Call::Democracy(pallet_quadratic_democracy::Call::undelegate()),
+		("democracy", "link_sub_account") => Call::Democracy(pallet_quadratic_democracy::Call::link_sub_account(
+			super_account.ok_or("democracy::link_sub_account is only made from sub-accounts")?.clone(),
+		)),
+		("identity", "set_identity") => Call::Identity(pallet_identity::Call::set_identity(IdentityInfo {
+			additional: vec![],
+			display: Data::Raw(format!("factory-{}", seed).into_bytes()),
+			legal: Data::None,
+			web: Data::None,
+			riot: Data::None,
+			email: Data::None,
+			pgp_fingerprint: None,
+			image: Data::None,
+			twitter: Data::None,
+		})),
+		("identity", "set_subs") => Call::Identity(pallet_identity::Call::set_subs(
+			sub_accounts.iter().map(|sub| (sub.clone(), Data::None)).collect(),
+		)),
+		("identity", "add_registrar") => Call::Sudo(pallet_sudo::Call::sudo(Box::new(
+			Call::Identity(pallet_identity::Call::add_registrar(sender.clone())),
+		))),
+		("identity", "provide_judgement") => Call::Identity(pallet_identity::Call::provide_judgement(
+			call.arg("registrar")?.unwrap_or(0),
+			pallet_indices::address::Address::Id(target.clone()),
+			Judgement::KnownGood,
+		)),
+		(pallet, name) => return Err(format!("Unsupported scenario call {}::{}", pallet, name)),
+	})
+}
+
 fn gen_seed_bytes(seed: u32) -> [u8; 32] {
 	let mut rng: StdRng = SeedableRng::seed_from_u64(seed as u64);
 
@@ -16,9 +16,10 @@
 
 //! Structs to easily compose inspect sub-command for CLI.
 
-use std::fmt::Debug;
+use std::{fmt::Debug, str::FromStr};
 use sc_cli::{ImportParams, SharedParams};
 use structopt::StructOpt;
+use crate::OutputFormat;
 
 /// The `inspect` command used to print decoded chain data.
 #[derive(Debug, StructOpt, Clone)]
@@ -27,6 +28,13 @@ pub struct InspectCmd {
 	#[structopt(flatten)]
 	pub command: InspectSubCmd,
 
+	/// How to print out the details: `debug` for the debug representation,
+	/// or `text` or `json` for data decoded with the runtime metadata.
+	///
+	/// The state and events are always decoded, and printed as text with `debug`.
+	#[structopt(long = "output", value_name = "FORMAT", default_value = "text")]
+	pub output: Output,
+
 	#[allow(missing_docs)]
 	#[structopt(flatten)]
 	pub shared_params: SharedParams,
@@ -39,7 +47,7 @@ pub struct InspectCmd {
 /// A possible inspect sub-commands.
 #[derive(Debug, StructOpt, Clone)]
 pub enum InspectSubCmd {
-	/// Decode block with native version of runtime and print out the details.
+	/// Decode block and the events of its extrinsics, and print out the details.
 	Block {
 		/// Address of the block to print out.
 		///
@@ -49,7 +57,7 @@ pub enum InspectSubCmd {
 		#[structopt(value_name = "HASH or NUMBER or BYTES")]
 		input: String,
 	},
-	/// Decode extrinsic with native version of runtime and print out the details.
+	/// Decode extrinsic and the events it emitted, and print out the details.
 	Extrinsic {
 		/// Address of an extrinsic to print out.
 		///
@@ -59,4 +67,59 @@ pub enum InspectSubCmd {
 		#[structopt(value_name = "BLOCK:INDEX or BYTES")]
 		input: String,
 	},
+	/// Read a storage item at a block and decode it with the runtime metadata.
+	State {
+		/// The block whose resulting state to read.
+		///
+		/// Can be either a block hash (no 0x prefix) or a number.
+		#[structopt(value_name = "HASH or NUMBER")]
+		block: String,
+		/// The storage item to read.
+		///
+		/// Can be either `Pallet::Item`, such as `Democracy::ReferendumInfoOf`,
+		/// or a 0x-prefixed raw storage key, whose value is printed undecoded.
+		#[structopt(value_name = "PALLET::ITEM or KEY")]
+		key: String,
+		/// A key of the map item, given twice for a double map.
+		///
+		/// Can be a number, an SS58 address or a variant name, depending on the
+		/// type of the key, or its 0x-prefixed SCALE encoding.
+		#[structopt(long = "map-key", value_name = "KEY", number_of_values = 1)]
+		map_keys: Vec<String>,
+	},
+	/// Decode the events deposited by a range of blocks.
+	Events {
+		/// The first block of the range.
+		///
+		/// Can be either a block hash (no 0x prefix) or a number.
+		#[structopt(value_name = "FROM")]
+		from: String,
+		/// The last block of the range, the first one by default.
+		#[structopt(value_name = "TO")]
+		to: Option<String>,
+		/// Only print the events of this pallet; can be given several times.
+		#[structopt(long = "pallet", value_name = "PALLET", number_of_values = 1)]
+		pallets: Vec<String>,
+	},
+}
+
+/// How the inspect command prints out the details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Output {
+	/// Debug representation of the native types.
+	Debug,
+	/// Decoded with the runtime metadata.
+	Metadata(OutputFormat),
+}
+
+impl FromStr for Output {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"debug" => Ok(Self::Debug),
+			_ => s.parse().map(Self::Metadata)
+				.map_err(|_| format!("Unknown output {}, expected debug, text or json", s)),
+		}
+	}
 }
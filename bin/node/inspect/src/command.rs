@@ -16,12 +16,13 @@
 
 //! Command ran by the CLI
 
-use crate::cli::{InspectCmd, InspectSubCmd};
-use crate::Inspector;
+use crate::cli::{InspectCmd, InspectSubCmd, Output};
+use crate::{DebugPrinter, Inspector, MetadataPrinter, OutputFormat, PrettyPrinter, StorageQuery};
 use sc_cli::{CliConfiguration, ImportParams, Result, SharedParams};
-use sc_service::{new_full_client, Configuration, NativeExecutionDispatch};
+use sc_service::{new_full_client, Configuration, NativeExecutionDispatch, TFullClient};
+use sp_api::ConstructRuntimeApi;
 use sp_runtime::traits::Block;
-use std::str::FromStr;
+use std::{str::FromStr, sync::Arc};
 
 impl InspectCmd {
 	/// Run the inspect command, passing the inspector.
@@ -29,12 +30,41 @@ impl InspectCmd {
 	where
 		B: Block,
 		B::Hash: FromStr,
-		RA: Send + Sync + 'static,
+		RA: ConstructRuntimeApi<B, TFullClient<B, RA, EX>> + Send + Sync + 'static,
+		RA::RuntimeApi: sp_api::Metadata<B, Error = sp_blockchain::Error>,
 		EX: NativeExecutionDispatch + 'static,
 	{
-		let client = new_full_client::<B, RA, EX>(&config)?;
-		let inspect = Inspector::<B>::new(client);
+		let client = Arc::new(new_full_client::<B, RA, EX>(&config)?);
 
+		match (&self.command, self.output) {
+			(InspectSubCmd::State { block, key, map_keys }, output) => {
+				let printer = MetadataPrinter::new(client, output.format());
+				let query = StorageQuery::parse(key, map_keys.clone())?;
+				let res = printer.state(block.parse()?, &query).map_err(|e| format!("{}", e))?;
+				println!("{}", res);
+				Ok(())
+			}
+			(InspectSubCmd::Events { from, to, pallets }, output) => {
+				let printer = MetadataPrinter::new(client, output.format());
+				let to = to.as_ref().map(|to| to.parse()).transpose()?;
+				let res = printer.events(from.parse()?, to, pallets).map_err(|e| format!("{}", e))?;
+				println!("{}", res);
+				Ok(())
+			}
+			(_, Output::Debug) => self.print(Inspector::from_shared(client, DebugPrinter)),
+			(_, Output::Metadata(format)) => {
+				let printer = MetadataPrinter::new(client.clone(), format);
+				self.print(Inspector::from_shared(client, printer))
+			},
+		}
+	}
+
+	fn print<B, P>(&self, inspect: Inspector<B, P>) -> Result<()>
+	where
+		B: Block,
+		B::Hash: FromStr,
+		P: PrettyPrinter<B>,
+	{
 		match &self.command {
 			InspectSubCmd::Block { input } => {
 				let input = input.parse()?;
@@ -48,6 +78,17 @@ impl InspectCmd {
 				println!("{}", res);
 				Ok(())
 			}
+			InspectSubCmd::State { .. } | InspectSubCmd::Events { .. } =>
+				unreachable!("The state and events are printed by the metadata printer; qed"),
+		}
+	}
+}
+impl Output {
+	/// The format of the data decoded with the metadata.
+	fn format(self) -> OutputFormat {
+		match self {
+			Output::Debug => OutputFormat::Text,
+			Output::Metadata(format) => format,
 		}
 	}
 }
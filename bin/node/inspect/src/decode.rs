@@ -0,0 +1,1204 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Decoding of extrinsics and events using the runtime metadata.
+//!
+//! The metadata only names the types of the call and event arguments, so the
+//! encoding behind each name is looked up in a [`TypeRegistry`]. The default
+//! registry knows the types used by the pallets of this repository; types it
+//! doesn't know make decoding stop with an error naming them.
+//!
+//! [`TypeRegistry`]: struct.TypeRegistry.html
+
+use std::{collections::HashMap, fmt};
+
+use codec::{Compact, Decode, Encode, Input};
+use frame_metadata::{
+	DecodeDifferent, ErrorMetadata, EventMetadata, FunctionMetadata, RuntimeMetadata,
+	RuntimeMetadataPrefixed, RuntimeMetadataV11, StorageEntryModifier, StorageEntryType,
+	StorageHasher,
+};
+use sp_core::{crypto::{AccountId32, Ss58Codec}, hexdisplay::HexDisplay};
+
+/// A decoded value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+	/// The empty tuple.
+	Unit,
+	/// A boolean.
+	Bool(bool),
+	/// An unsigned integer.
+	Unsigned(u128),
+	/// A signed integer.
+	Signed(i128),
+	/// An account, as its SS58 address.
+	Account(String),
+	/// Raw bytes, such as a hash.
+	Bytes(Vec<u8>),
+	/// A sequence of values, such as a vector or a tuple.
+	Sequence(Vec<Value>),
+	/// A struct, with its named fields.
+	Struct(Vec<(String, Value)>),
+	/// A variant of an enum, with its fields.
+	Variant(String, Vec<Value>),
+	/// A call of the runtime.
+	Call(Box<Call>),
+}
+
+/// A decoded call of the runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Call {
+	/// Name of the pallet of the call.
+	pub pallet: String,
+	/// Name of the call.
+	pub name: String,
+	/// The arguments of the call, with their names.
+	pub args: Vec<(String, Value)>,
+}
+
+/// A decoded event.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+	/// Name of the pallet of the event.
+	pub pallet: String,
+	/// Name of the event.
+	pub name: String,
+	/// The arguments of the event.
+	pub args: Vec<Value>,
+}
+
+/// The phase of the block an event was deposited in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+	/// Applying the extrinsic with the given index.
+	ApplyExtrinsic(u32),
+	/// Finalizing the block.
+	Finalization,
+	/// Initializing the block.
+	Initialization,
+}
+
+/// The signature part of a decoded extrinsic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signature {
+	/// The address of the signer.
+	pub signer: Value,
+	/// The signature.
+	pub signature: Value,
+	/// The signed extensions, by name.
+	pub extra: Vec<(String, Value)>,
+}
+
+/// A decoded extrinsic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Extrinsic {
+	/// The signature, if the extrinsic is signed.
+	pub signature: Option<Signature>,
+	/// The call.
+	pub call: Call,
+}
+
+/// A storage item located with the runtime metadata.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StorageEntry {
+	/// The storage key of the item, including the hashed map keys.
+	pub key: Vec<u8>,
+	/// The type of the value.
+	pub ty: String,
+	/// The encoded value of the item when it isn't stored, unless the
+	/// item is optional.
+	pub default: Option<Vec<u8>>,
+}
+
+/// How a named type is encoded.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeDef {
+	/// The same encoding as another type.
+	Alias(String),
+	/// A struct with the given named fields and their types.
+	Struct(Vec<(String, String)>),
+	/// An enum with the given variants and the types of their fields. The
+	/// fields of a variant are either all named or all unnamed (empty names).
+	Enum(Vec<(String, Vec<(String, String)>)>),
+	/// 32 bytes of an account, printed as an SS58 address.
+	AccountId,
+	/// An address of the indices pallet: an account or an account index.
+	IndicesAddress,
+	/// A call of the runtime.
+	Call,
+	/// The era of a transaction.
+	Era,
+	/// A vote of the democracy pallets, packed in one byte.
+	DemocracyVote,
+	/// Data of an identity, whose variant also encodes the length of raw data.
+	IdentityData,
+	/// An error of a dispatch, with its module error named from the metadata.
+	DispatchError,
+}
+
+/// The encodings of the types named in the metadata.
+#[derive(Debug, Clone)]
+pub struct TypeRegistry {
+	types: HashMap<String, TypeDef>,
+	signed_extensions: HashMap<String, String>,
+}
+
+impl TypeRegistry {
+	/// An empty registry, which only knows the primitive types.
+	pub fn empty() -> Self {
+		TypeRegistry { types: HashMap::new(), signed_extensions: HashMap::new() }
+	}
+
+	/// Register the encoding of the type `name`.
+	pub fn register(&mut self, name: &str, def: TypeDef) -> &mut Self {
+		self.types.insert(name.into(), def);
+		self
+	}
+
+	/// Register the type of what the signed extension `name` adds to the
+	/// extrinsic. Unregistered extensions add nothing.
+	pub fn register_signed_extension(&mut self, name: &str, ty: &str) -> &mut Self {
+		self.signed_extensions.insert(name.into(), ty.into());
+		self
+	}
+
+	fn alias(&mut self, name: &str, ty: &str) -> &mut Self {
+		self.register(name, TypeDef::Alias(ty.into()))
+	}
+
+	fn structure(&mut self, name: &str, fields: &[(&str, &str)]) -> &mut Self {
+		let fields = fields.iter().map(|(n, t)| (n.to_string(), t.to_string())).collect();
+		self.register(name, TypeDef::Struct(fields))
+	}
+
+	fn enumeration(&mut self, name: &str, variants: &[(&str, &[(&str, &str)])]) -> &mut Self {
+		let variants = variants.iter()
+			.map(|(v, fields)| (
+				v.to_string(),
+				fields.iter().map(|(n, t)| (n.to_string(), t.to_string())).collect(),
+			))
+			.collect();
+		self.register(name, TypeDef::Enum(variants))
+	}
+
+	fn simple_enum(&mut self, name: &str, variants: &[&str]) -> &mut Self {
+		let variants = variants.iter().map(|v| (v.to_string(), Vec::new())).collect();
+		self.register(name, TypeDef::Enum(variants))
+	}
+}
+
+impl Default for TypeRegistry {
+	/// The types of the pallets of this repository, as used by the node runtime.
+	fn default() -> Self {
+		let mut registry = Self::empty();
+		registry
+			// Primitives of the node.
+			.register("AccountId", TypeDef::AccountId)
+			.register("AccountId32", TypeDef::AccountId)
+			.register("Address", TypeDef::IndicesAddress)
+			.register("LookupSource", TypeDef::IndicesAddress)
+			.register("Call", TypeDef::Call)
+			.register("Proposal", TypeDef::Call)
+			.register("Era", TypeDef::Era)
+			.register("DispatchError", TypeDef::DispatchError)
+			.alias("AccountIndex", "u32")
+			.alias("Balance", "u128")
+			.alias("BalanceOf", "Balance")
+			.alias("NegativeImbalance", "Balance")
+			.alias("BlockNumber", "u32")
+			.alias("Index", "u32")
+			.alias("Moment", "u64")
+			.alias("Hash", "[u8; 32]")
+			.alias("H256", "[u8; 32]")
+			.alias("Weight", "u64")
+			.alias("Perbill", "u32")
+			.alias("Permill", "u32")
+			.alias("Percent", "u8")
+			.alias("Perquintill", "u64")
+			.alias("Bytes", "Vec<u8>")
+			.alias("Key", "Vec<u8>")
+			.alias("KeyValue", "(Vec<u8>, Vec<u8>)")
+			.alias("ChangesTrieConfiguration", "(u32, u32)")
+			.alias("AuthorityId", "[u8; 32]")
+			.alias("AuthorityWeight", "u64")
+			.alias("AuthorityList", "Vec<(AuthorityId, AuthorityWeight)>")
+			.alias("SessionIndex", "u32")
+			.alias("EraIndex", "u32")
+			.alias("Keys", "([u8; 32], [u8; 32], [u8; 32], [u8; 32])")
+			.alias("MemberCount", "u32")
+			.alias("ProposalIndex", "u32")
+			.alias("RegistrarIndex", "u32")
+			.alias("ReferendumIndex", "u32")
+			.alias("PropIndex", "u32")
+			.alias("TaskAddress", "(BlockNumber, u32)")
+			.alias("CodeHash", "Hash")
+			.alias("Kind", "[u8; 16]")
+			.alias("OpaqueTimeSlot", "Vec<u8>")
+			.alias("Schedule", "Vec<u8>")
+			.alias("Timepoint", "(BlockNumber, u32)")
+			.enumeration("MultiSignature", &[
+				("Ed25519", &[("", "[u8; 64]")]),
+				("Sr25519", &[("", "[u8; 64]")]),
+				("Ecdsa", &[("", "[u8; 65]")]),
+			])
+			.alias("Signature", "MultiSignature")
+			// System.
+			.simple_enum("DispatchClass", &["Normal", "Operational", "Mandatory"])
+			.simple_enum("Pays", &["Yes", "No"])
+			.structure("DispatchInfo", &[
+				("weight", "Weight"),
+				("class", "DispatchClass"),
+				("pays_fee", "Pays"),
+			])
+			.enumeration("DispatchResult", &[("Ok", &[]), ("Err", &[("", "DispatchError")])])
+			// Balances.
+			.simple_enum("BalanceStatus", &["Free", "Reserved"])
+			.alias("Status", "BalanceStatus")
+			// Staking.
+			.enumeration("RewardDestination", &[("Staked", &[]), ("Stash", &[]), ("Controller", &[])])
+			.structure("ValidatorPrefs", &[("commission", "Compact<Perbill>")])
+			.simple_enum("ElectionCompute", &["OnChain", "Signed", "Authority"])
+			// Identity.
+			.register("Data", TypeDef::IdentityData)
+			.structure("IdentityInfo", &[
+				("additional", "Vec<(Data, Data)>"),
+				("display", "Data"),
+				("legal", "Data"),
+				("web", "Data"),
+				("riot", "Data"),
+				("email", "Data"),
+				("pgp_fingerprint", "Option<[u8; 20]>"),
+				("image", "Data"),
+				("twitter", "Data"),
+			])
+			.enumeration("Judgement", &[
+				("Unknown", &[]),
+				("FeePaid", &[("", "Balance")]),
+				("Reasonable", &[]),
+				("KnownGood", &[]),
+				("OutOfDate", &[]),
+				("LowQuality", &[]),
+				("Erroneous", &[]),
+			])
+			.alias("IdentityFields", "u64")
+			// Quadratic democracy.
+			.register("Vote", TypeDef::DemocracyVote)
+			.simple_enum("Conviction", &[
+				"None", "Locked1x", "Locked2x", "Locked3x", "Locked4x", "Locked5x", "Locked6x",
+			])
+			.simple_enum("VoteWeight", &["Standard", "Quadratic", "Credits"])
+			.enumeration("VoteThreshold", &[
+				("SuperMajorityApprove", &[]),
+				("SuperMajorityAgainst", &[]),
+				("SimpleMajority", &[]),
+				("Quorum", &[("voters", "u32"), ("turnout", "Perbill")]),
+			])
+			.simple_enum("Rejection", &["Outvoted", "TooFewVoters", "LowTurnout", "NoWinner"])
+			.enumeration("AccountVote", &[
+				("Standard", &[("vote", "Vote"), ("balance", "Balance")]),
+				("Split", &[("aye", "Balance"), ("nay", "Balance")]),
+			])
+			.structure("ConvictionLevel", &[("lock_periods", "u32"), ("multiplier", "u8")])
+			.enumeration("AccountVoteWeight", &[
+				("Standard", &[
+					("vote", "Vote"),
+					("balance", "Balance"),
+					("weighted_balance", "Balance"),
+					("level", "ConvictionLevel"),
+				]),
+				("Split", &[
+					("aye", "Balance"),
+					("nay", "Balance"),
+					("aye_weight", "Balance"),
+					("nay_weight", "Balance"),
+					("level", "ConvictionLevel"),
+				]),
+				("Credits", &[("aye", "bool"), ("votes", "Balance"), ("period", "u32")]),
+			])
+			.structure("Delegations", &[
+				("capital", "Balance"),
+				("votes", "Balance"),
+				("quadratic_votes", "Balance"),
+				("weighted_capital", "Balance"),
+			])
+			.simple_enum("ProposalSource", &["Public", "External"])
+			.structure("DelegationClass", &[
+				("weight", "Option<VoteWeight>"),
+				("source", "Option<ProposalSource>"),
+			])
+			// Signed extensions of the node.
+			.register_signed_extension("CheckEra", "Era")
+			.register_signed_extension("CheckNonce", "Compact<Index>")
+			.register_signed_extension("ChargeTransactionPayment", "Compact<Balance>");
+		registry
+	}
+}
+
+/// The parsed name of a type.
+#[derive(Debug, Clone, PartialEq)]
+enum Type {
+	Named(String),
+	Compact(Box<Type>),
+	Vec(Box<Type>),
+	Option(Box<Type>),
+	Tuple(Vec<Type>),
+	Array(Box<Type>, usize),
+}
+
+impl Type {
+	/// Parse a type name of the metadata, such as `Vec<T::AccountId>` or
+	/// `<T::Lookup as StaticLookup>::Source`.
+	fn parse(name: &str) -> Result<Self, String> {
+		let cleaned: String = name.chars().filter(|c| !c.is_whitespace()).collect();
+		let cleaned = strip_qualified_paths(&cleaned);
+		let mut parser = TypeParser { input: cleaned.as_bytes(), position: 0 };
+		let ty = parser.parse()
+			.ok_or_else(|| format!("Could not parse type name {}", name))?;
+		if parser.position != parser.input.len() {
+			return Err(format!("Could not parse type name {}", name));
+		}
+		Ok(ty)
+	}
+}
+
+/// Replace the qualified paths of a type name, such as `<T as Trait>::Balance`,
+/// by the name of the associated type.
+fn strip_qualified_paths(name: &str) -> String {
+	let mut out = String::new();
+	let bytes = name.as_bytes();
+	let mut i = 0;
+	while i < bytes.len() {
+		let starts_path = bytes[i] == b'<' &&
+			(i == 0 || matches!(bytes[i - 1], b'<' | b',' | b'(' | b'[' | b';'));
+		if !starts_path {
+			out.push(bytes[i] as char);
+			i += 1;
+			continue;
+		}
+
+		// Find the matching `>` and the associated type name after `::`.
+		let mut depth = 0;
+		let mut end = i;
+		for (j, b) in bytes.iter().enumerate().skip(i) {
+			match b {
+				b'<' => depth += 1,
+				b'>' => {
+					depth -= 1;
+					if depth == 0 {
+						end = j;
+						break;
+					}
+				},
+				_ => {},
+			}
+		}
+		let rest = &name[end + 1..];
+		let ident: String = rest.trim_start_matches("::")
+			.chars()
+			.take_while(|c| c.is_alphanumeric() || *c == '_')
+			.collect();
+		let skipped = end + 1 + (rest.len() - rest.trim_start_matches("::").len()) + ident.len();
+		out.push_str(if ident == "Source" { "LookupSource" } else { &ident });
+		i = skipped;
+	}
+	out
+}
+
+struct TypeParser<'a> {
+	input: &'a [u8],
+	position: usize,
+}
+
+impl<'a> TypeParser<'a> {
+	fn peek(&self) -> Option<u8> {
+		self.input.get(self.position).copied()
+	}
+
+	fn eat(&mut self, c: u8) -> bool {
+		if self.peek() == Some(c) {
+			self.position += 1;
+			true
+		} else {
+			false
+		}
+	}
+
+	fn parse(&mut self) -> Option<Type> {
+		if self.eat(b'&') {
+			while self.peek().map_or(false, |c| c == b'\'' || c.is_ascii_alphanumeric()) {
+				self.position += 1;
+			}
+		}
+		if self.eat(b'(') {
+			let mut items = Vec::new();
+			while !self.eat(b')') {
+				items.push(self.parse()?);
+				self.eat(b',');
+			}
+			return Some(Type::Tuple(items));
+		}
+		if self.eat(b'[') {
+			let item = self.parse()?;
+			if self.eat(b']') {
+				return Some(Type::Vec(Box::new(item)));
+			}
+			if !self.eat(b';') {
+				return None;
+			}
+			let start = self.position;
+			while self.peek().map_or(false, |c| c.is_ascii_digit()) {
+				self.position += 1;
+			}
+			let len = std::str::from_utf8(&self.input[start..self.position]).ok()?.parse().ok()?;
+			return if self.eat(b']') { Some(Type::Array(Box::new(item), len)) } else { None };
+		}
+
+		// A path, of which only the last segment matters.
+		let mut name = String::new();
+		loop {
+			let start = self.position;
+			while self.peek().map_or(false, |c| c.is_ascii_alphanumeric() || c == b'_') {
+				self.position += 1;
+			}
+			name = std::str::from_utf8(&self.input[start..self.position]).ok()?.into();
+			if self.input[self.position..].starts_with(b"::") {
+				self.position += 2;
+			} else {
+				break;
+			}
+		}
+		if name.is_empty() {
+			return None;
+		}
+
+		let mut params = Vec::new();
+		if self.eat(b'<') {
+			while !self.eat(b'>') {
+				params.push(self.parse()?);
+				self.eat(b',');
+			}
+		}
+
+		let param = params.get(0).cloned().map(Box::new);
+		Some(match name.as_str() {
+			"Compact" => Type::Compact(param?),
+			"Vec" | "VecDeque" | "BTreeSet" => Type::Vec(param?),
+			"BTreeMap" if params.len() == 2 => Type::Vec(Box::new(Type::Tuple(params))),
+			"Option" => Type::Option(param?),
+			"Box" => *param?,
+			_ => Type::Named(name),
+		})
+	}
+}
+
+/// Decodes extrinsics and events, given the runtime metadata.
+pub struct Decoder<'a> {
+	registry: &'a TypeRegistry,
+	metadata: &'a RuntimeMetadataV11,
+}
+
+fn decoded<B, O>(value: &DecodeDifferent<B, O>) -> Result<&O, String> {
+	match value {
+		DecodeDifferent::Decoded(value) => Ok(value),
+		DecodeDifferent::Encode(_) => Err("Metadata was not decoded".into()),
+	}
+}
+
+impl<'a> Decoder<'a> {
+	/// Create a decoder of the types of `metadata`, which must be of the
+	/// latest version.
+	pub fn new(
+		registry: &'a TypeRegistry,
+		metadata: &'a RuntimeMetadataPrefixed,
+	) -> Result<Self, String> {
+		match &metadata.1 {
+			RuntimeMetadata::V11(metadata) => Ok(Decoder { registry, metadata }),
+			_ => Err("Unsupported metadata version".into()),
+		}
+	}
+
+	/// Decode an encoded extrinsic, prefixed with its length.
+	pub fn extrinsic(&self, input: &mut &[u8]) -> Result<Extrinsic, String> {
+		let _length = <Compact<u32>>::decode(input).map_err(|e| e.what())?;
+		let version = input.read_byte().map_err(|e| e.what())?;
+		let expected = self.metadata.extrinsic.version;
+		if version & 0b0111_1111 != expected {
+			return Err(format!("Unsupported extrinsic version {}, expected {}", version & 0b0111_1111, expected));
+		}
+
+		let signature = if version & 0b1000_0000 != 0 {
+			let signer = self.value("Address", input)?;
+			let signature = self.value("Signature", input)?;
+			let mut extra = Vec::new();
+			for name in &self.metadata.extrinsic.signed_extensions {
+				let name = decoded(name)?;
+				if let Some(ty) = self.registry.signed_extensions.get(name) {
+					extra.push((name.clone(), self.value(ty, input)?));
+				}
+			}
+			Some(Signature { signer, signature, extra })
+		} else {
+			None
+		};
+
+		Ok(Extrinsic { signature, call: self.call(input)? })
+	}
+
+	/// Decode a call of the runtime.
+	pub fn call(&self, input: &mut &[u8]) -> Result<Call, String> {
+		let module_index = input.read_byte().map_err(|e| e.what())?;
+		let call_index = input.read_byte().map_err(|e| e.what())?;
+
+		let (pallet, calls) = self.modules()?
+			.iter()
+			.filter_map(|module| module.calls.as_ref().map(|calls| (&module.name, calls)))
+			.nth(module_index as usize)
+			.ok_or_else(|| format!("No pallet with calls at index {}", module_index))?;
+		let function: &FunctionMetadata = decoded(calls)?
+			.get(call_index as usize)
+			.ok_or_else(|| format!("No call at index {} of {}", call_index, decoded(pallet).unwrap_or(&String::new())))?;
+
+		let mut call = Call {
+			pallet: decoded(pallet)?.clone(),
+			name: decoded(&function.name)?.clone(),
+			args: Vec::new(),
+		};
+		for arg in decoded(&function.arguments)? {
+			let value = self.value(decoded(&arg.ty)?, input)
+				.map_err(|e| format!("{}.{}: {}", call.pallet, call.name, e))?;
+			call.args.push((decoded(&arg.name)?.clone(), value));
+		}
+		Ok(call)
+	}
+
+	/// Decode the records of `System::Events`.
+	///
+	/// Events are decoded in order, up to the first one that can't be. The
+	/// error that stopped decoding is returned along with the events before.
+	pub fn events(&self, input: &mut &[u8]) -> (Vec<(Phase, Event)>, Option<String>) {
+		let mut events = Vec::new();
+		let count = match <Compact<u32>>::decode(input) {
+			Ok(count) => count.0,
+			Err(e) => return (events, Some(e.what().into())),
+		};
+
+		for _ in 0..count {
+			match self.event_record(input) {
+				Ok(record) => events.push(record),
+				Err(e) => return (events, Some(e)),
+			}
+		}
+		(events, None)
+	}
+
+	fn event_record(&self, input: &mut &[u8]) -> Result<(Phase, Event), String> {
+		let phase = match input.read_byte().map_err(|e| e.what())? {
+			0 => Phase::ApplyExtrinsic(u32::decode(input).map_err(|e| e.what())?),
+			1 => Phase::Finalization,
+			2 => Phase::Initialization,
+			_ => return Err("Invalid phase".into()),
+		};
+
+		let module_index = input.read_byte().map_err(|e| e.what())?;
+		let event_index = input.read_byte().map_err(|e| e.what())?;
+		let (pallet, events) = self.modules()?
+			.iter()
+			.filter_map(|module| module.event.as_ref().map(|events| (&module.name, events)))
+			.nth(module_index as usize)
+			.ok_or_else(|| format!("No pallet with events at index {}", module_index))?;
+		let event: &EventMetadata = decoded(events)?
+			.get(event_index as usize)
+			.ok_or_else(|| format!("No event at index {}", event_index))?;
+
+		let mut decoded_event = Event {
+			pallet: decoded(pallet)?.clone(),
+			name: decoded(&event.name)?.clone(),
+			args: Vec::new(),
+		};
+		for ty in decoded(&event.arguments)? {
+			let value = self.value(ty, input)
+				.map_err(|e| format!("{}.{}: {}", decoded_event.pallet, decoded_event.name, e))?;
+			decoded_event.args.push(value);
+		}
+		let _topics = <Vec<[u8; 32]>>::decode(input).map_err(|e| e.what())?;
+
+		Ok((phase, decoded_event))
+	}
+
+	fn modules(&self) -> Result<&Vec<frame_metadata::ModuleMetadata>, String> {
+		decoded(&self.metadata.modules)
+	}
+
+	/// Decode a value of the type `name`, as named by the metadata.
+	pub fn value(&self, name: &str, input: &mut &[u8]) -> Result<Value, String> {
+		self.decode(&Type::parse(name)?, input)
+	}
+
+	fn decode(&self, ty: &Type, input: &mut &[u8]) -> Result<Value, String> {
+		let err = |e: codec::Error| e.what().to_string();
+		Ok(match ty {
+			Type::Compact(inner) => match self.resolve(inner)? {
+				Type::Named(name) => match name.as_str() {
+					"u8" | "u16" | "u32" | "u64" | "u128" =>
+						Value::Unsigned(<Compact<u128>>::decode(input).map_err(err)?.0),
+					"()" => Value::Unit,
+					_ => return Err(format!("Unsupported compact type {}", name)),
+				},
+				Type::Tuple(items) if items.is_empty() => Value::Unit,
+				other => return Err(format!("Unsupported compact type {:?}", other)),
+			},
+			Type::Vec(inner) => {
+				let len = <Compact<u32>>::decode(input).map_err(err)?.0 as usize;
+				if self.resolve(inner)? == Type::Named("u8".into()) {
+					let mut bytes = vec![0; len];
+					input.read(&mut bytes).map_err(err)?;
+					Value::Bytes(bytes)
+				} else {
+					Value::Sequence((0..len).map(|_| self.decode(inner, input)).collect::<Result<_, _>>()?)
+				}
+			},
+			Type::Option(inner) => match input.read_byte().map_err(err)? {
+				0 => Value::Variant("None".into(), Vec::new()),
+				1 => Value::Variant("Some".into(), vec![self.decode(inner, input)?]),
+				_ => return Err("Invalid option".into()),
+			},
+			Type::Tuple(items) if items.is_empty() => Value::Unit,
+			Type::Tuple(items) =>
+				Value::Sequence(items.iter().map(|item| self.decode(item, input)).collect::<Result<_, _>>()?),
+			Type::Array(inner, len) => {
+				if self.resolve(inner)? == Type::Named("u8".into()) {
+					let mut bytes = vec![0; *len];
+					input.read(&mut bytes).map_err(err)?;
+					Value::Bytes(bytes)
+				} else {
+					Value::Sequence((0..*len).map(|_| self.decode(inner, input)).collect::<Result<_, _>>()?)
+				}
+			},
+			Type::Named(name) => self.decode_named(name, input)?,
+		})
+	}
+
+	/// Follow the aliases of `ty`.
+	fn resolve(&self, ty: &Type) -> Result<Type, String> {
+		let mut ty = ty.clone();
+		while let Type::Named(name) = &ty {
+			match self.registry.types.get(name) {
+				Some(TypeDef::Alias(alias)) => ty = Type::parse(alias)?,
+				_ => break,
+			}
+		}
+		Ok(ty)
+	}
+
+	fn decode_named(&self, name: &str, input: &mut &[u8]) -> Result<Value, String> {
+		let err = |e: codec::Error| e.what().to_string();
+		Ok(match name {
+			"bool" => Value::Bool(bool::decode(input).map_err(err)?),
+			"u8" => Value::Unsigned(u8::decode(input).map_err(err)?.into()),
+			"u16" => Value::Unsigned(u16::decode(input).map_err(err)?.into()),
+			"u32" => Value::Unsigned(u32::decode(input).map_err(err)?.into()),
+			"u64" => Value::Unsigned(u64::decode(input).map_err(err)?.into()),
+			"u128" => Value::Unsigned(u128::decode(input).map_err(err)?),
+			"i8" => Value::Signed(i8::decode(input).map_err(err)?.into()),
+			"i16" => Value::Signed(i16::decode(input).map_err(err)?.into()),
+			"i32" => Value::Signed(i32::decode(input).map_err(err)?.into()),
+			"i64" => Value::Signed(i64::decode(input).map_err(err)?.into()),
+			"i128" => Value::Signed(i128::decode(input).map_err(err)?),
+			"str" | "String" => Value::Bytes(<Vec<u8>>::decode(input).map_err(err)?),
+			_ => match self.registry.types.get(name) {
+				None => return Err(format!("Unknown type {}", name)),
+				Some(TypeDef::Alias(alias)) => self.value(alias, input)?,
+				Some(TypeDef::Struct(fields)) => Value::Struct(
+					fields.iter()
+						.map(|(field, ty)| Ok((field.clone(), self.value(ty, input)?)))
+						.collect::<Result<_, String>>()?,
+				),
+				Some(TypeDef::Enum(variants)) => {
+					let index = input.read_byte().map_err(err)?;
+					let (variant, fields) = variants.get(index as usize)
+						.ok_or_else(|| format!("Invalid variant {} of {}", index, name))?;
+					let values = if fields.iter().all(|(field, _)| field.is_empty()) {
+						fields.iter()
+							.map(|(_, ty)| self.value(ty, input))
+							.collect::<Result<_, _>>()?
+					} else {
+						vec![Value::Struct(
+							fields.iter()
+								.map(|(field, ty)| Ok((field.clone(), self.value(ty, input)?)))
+								.collect::<Result<_, String>>()?,
+						)]
+					};
+					Value::Variant(variant.clone(), values)
+				},
+				Some(TypeDef::AccountId) => Value::Account(
+					AccountId32::from(<[u8; 32]>::decode(input).map_err(err)?).to_ss58check(),
+				),
+				Some(TypeDef::IndicesAddress) => match input.read_byte().map_err(err)? {
+					x @ 0x00..=0xef => Value::Variant("Index".into(), vec![Value::Unsigned(x.into())]),
+					0xfc => Value::Variant("Index".into(), vec![self.value("u16", input)?]),
+					0xfd | 0xfe => Value::Variant("Index".into(), vec![self.value("u32", input)?]),
+					0xff => self.decode_named("AccountId", input)?,
+					_ => return Err("Invalid address".into()),
+				},
+				Some(TypeDef::Call) => Value::Call(Box::new(self.call(input)?)),
+				Some(TypeDef::Era) => match input.read_byte().map_err(err)? {
+					0 => Value::Variant("Immortal".into(), Vec::new()),
+					first => {
+						let encoded = first as u64 + ((input.read_byte().map_err(err)? as u64) << 8);
+						let period = 2 << (encoded % (1 << 4));
+						let quantize_factor = (period >> 12).max(1);
+						let phase = (encoded >> 4) * quantize_factor;
+						Value::Variant("Mortal".into(), vec![Value::Struct(vec![
+							("period".into(), Value::Unsigned(period.into())),
+							("phase".into(), Value::Unsigned(phase.into())),
+						])])
+					},
+				},
+				Some(TypeDef::DemocracyVote) => {
+					let byte = input.read_byte().map_err(err)?;
+					let conviction = match self.registry.types.get("Conviction") {
+						Some(TypeDef::Enum(variants)) => variants.get((byte & 0b0111_1111) as usize)
+							.map(|(variant, _)| variant.clone())
+							.ok_or_else(|| "Invalid conviction".to_string())?,
+						_ => return Err("Unknown type Conviction".into()),
+					};
+					Value::Struct(vec![
+						("aye".into(), Value::Bool(byte & 0b1000_0000 != 0)),
+						("conviction".into(), Value::Variant(conviction, Vec::new())),
+					])
+				},
+				Some(TypeDef::IdentityData) => match input.read_byte().map_err(err)? {
+					0 => Value::Variant("None".into(), Vec::new()),
+					len @ 1..=33 => {
+						let mut bytes = vec![0; len as usize - 1];
+						input.read(&mut bytes).map_err(err)?;
+						Value::Variant("Raw".into(), vec![Value::Bytes(bytes)])
+					},
+					hasher @ 34..=37 => {
+						let name = ["BlakeTwo256", "Sha256", "Keccak256", "ShaThree256"][hasher as usize - 34];
+						Value::Variant(name.into(), vec![self.value("[u8; 32]", input)?])
+					},
+					_ => return Err("Invalid identity data".into()),
+				},
+				Some(TypeDef::DispatchError) => match input.read_byte().map_err(err)? {
+					0 => Value::Variant("Other".into(), Vec::new()),
+					1 => Value::Variant("CannotLookup".into(), Vec::new()),
+					2 => Value::Variant("BadOrigin".into(), Vec::new()),
+					3 => {
+						let index = input.read_byte().map_err(err)?;
+						let error = input.read_byte().map_err(err)?;
+						self.module_error(index, error)
+					},
+					_ => return Err("Invalid dispatch error".into()),
+				},
+			},
+		})
+	}
+
+	/// Locate the storage item `item` of `pallet`, with the given map keys.
+	///
+	/// A map key is either the 0x-prefixed SCALE encoding of the key, or a
+	/// number, an SS58 address or the name of a variant without fields,
+	/// depending on the type of the key.
+	pub fn storage_entry(&self, pallet: &str, item: &str, keys: &[String]) -> Result<StorageEntry, String> {
+		let storage = self.modules()?
+			.iter()
+			.filter(|module| decoded(&module.name).map_or(false, |name| name.eq_ignore_ascii_case(pallet)))
+			.find_map(|module| module.storage.as_ref())
+			.ok_or_else(|| format!("No pallet {} with storage", pallet))?;
+		let storage = decoded(storage)?;
+		let entry = decoded(&storage.entries)?
+			.iter()
+			.find(|entry| decoded(&entry.name).map_or(false, |name| name.eq_ignore_ascii_case(item)))
+			.ok_or_else(|| format!("No storage item {} in {}", item, pallet))?;
+
+		let mut key = [
+			sp_core::twox_128(decoded(&storage.prefix)?.as_bytes()),
+			sp_core::twox_128(decoded(&entry.name)?.as_bytes()),
+		].concat();
+		let (hashed_keys, ty) = match &entry.ty {
+			StorageEntryType::Plain(value) => (Vec::new(), value),
+			StorageEntryType::Map { hasher, key, value, .. } => (vec![(hasher, key)], value),
+			StorageEntryType::DoubleMap { hasher, key1, key2, value, key2_hasher } =>
+				(vec![(hasher, key1), (key2_hasher, key2)], value),
+		};
+		if keys.len() != hashed_keys.len() {
+			return Err(format!(
+				"{}::{} takes {} map keys, but {} were given",
+				pallet, item, hashed_keys.len(), keys.len(),
+			));
+		}
+		for ((hasher, ty), input) in hashed_keys.into_iter().zip(keys) {
+			let encoded = self.encode_key(decoded(ty)?, input)?;
+			match hasher {
+				StorageHasher::Blake2_128 => key.extend(&sp_core::blake2_128(&encoded)),
+				StorageHasher::Blake2_256 => key.extend(&sp_core::blake2_256(&encoded)),
+				StorageHasher::Blake2_128Concat => {
+					key.extend(&sp_core::blake2_128(&encoded));
+					key.extend(encoded);
+				},
+				StorageHasher::Twox128 => key.extend(&sp_core::twox_128(&encoded)),
+				StorageHasher::Twox256 => key.extend(&sp_core::twox_256(&encoded)),
+				StorageHasher::Twox64Concat => {
+					key.extend(&sp_core::twox_64(&encoded));
+					key.extend(encoded);
+				},
+				StorageHasher::Identity => key.extend(encoded),
+			}
+		}
+
+		let default = match entry.modifier {
+			StorageEntryModifier::Default => Some(decoded(&entry.default)?.clone()),
+			StorageEntryModifier::Optional => None,
+		};
+		Ok(StorageEntry { key, ty: decoded(ty)?.clone(), default })
+	}
+
+	/// Encode a map key of the type `name`, given as a string.
+	fn encode_key(&self, name: &str, input: &str) -> Result<Vec<u8>, String> {
+		if input.starts_with("0x") {
+			return sp_core::bytes::from_hex(input).map_err(|e| format!("Invalid key {}: {:?}", input, e));
+		}
+
+		let invalid = |e: &dyn fmt::Debug| format!("Invalid key {} of type {}: {:?}", input, name, e);
+		let named = match self.resolve(&Type::parse(name)?)? {
+			Type::Named(named) => named,
+			_ => return Err(format!("Keys of type {} must be given as 0x-prefixed SCALE encoding", name)),
+		};
+		Ok(match named.as_str() {
+			"bool" => input.parse::<bool>().map_err(|e| invalid(&e))?.encode(),
+			"u8" => input.parse::<u8>().map_err(|e| invalid(&e))?.encode(),
+			"u16" => input.parse::<u16>().map_err(|e| invalid(&e))?.encode(),
+			"u32" => input.parse::<u32>().map_err(|e| invalid(&e))?.encode(),
+			"u64" => input.parse::<u64>().map_err(|e| invalid(&e))?.encode(),
+			"u128" => input.parse::<u128>().map_err(|e| invalid(&e))?.encode(),
+			_ => match self.registry.types.get(&named) {
+				Some(TypeDef::AccountId) => AccountId32::from_ss58check(input)
+					.map_err(|e| invalid(&e))?
+					.encode(),
+				Some(TypeDef::Enum(variants)) => {
+					let index = variants.iter()
+						.position(|(variant, fields)| fields.is_empty() && variant.eq_ignore_ascii_case(input))
+						.ok_or_else(|| format!("No variant {} of {} without fields", input, name))?;
+					vec![index as u8]
+				},
+				_ => return Err(format!("Keys of type {} must be given as 0x-prefixed SCALE encoding", name)),
+			},
+		})
+	}
+
+	/// The error `error` of the module at `index`, named if the metadata
+	/// knows it.
+	fn module_error(&self, index: u8, error: u8) -> Value {
+		let named = self.modules().ok()
+			.and_then(|modules| modules.get(index as usize))
+			.and_then(|module| {
+				let errors: &Vec<ErrorMetadata> = decoded(&module.errors).ok()?;
+				let error = errors.get(error as usize)?;
+				Some(format!("{}.{}", decoded(&module.name).ok()?, decoded(&error.name).ok()?))
+			});
+		match named {
+			Some(name) => Value::Variant("Module".into(), vec![Value::Variant(name, Vec::new())]),
+			None => Value::Variant("Module".into(), vec![Value::Struct(vec![
+				("index".into(), Value::Unsigned(index.into())),
+				("error".into(), Value::Unsigned(error.into())),
+			])]),
+		}
+	}
+}
+
+impl fmt::Display for Value {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fn list<T: fmt::Display>(f: &mut fmt::Formatter, items: &[T]) -> fmt::Result {
+			for (i, item) in items.iter().enumerate() {
+				if i > 0 {
+					write!(f, ", ")?;
+				}
+				write!(f, "{}", item)?;
+			}
+			Ok(())
+		}
+
+		match self {
+			Value::Unit => write!(f, "()"),
+			Value::Bool(b) => write!(f, "{}", b),
+			Value::Unsigned(n) => write!(f, "{}", n),
+			Value::Signed(n) => write!(f, "{}", n),
+			Value::Account(address) => write!(f, "{}", address),
+			Value::Bytes(bytes) => match std::str::from_utf8(bytes) {
+				Ok(text) if !text.is_empty() && text.chars().all(|c| !c.is_control()) =>
+					write!(f, "{:?}", text),
+				_ => write!(f, "0x{}", HexDisplay::from(bytes)),
+			},
+			Value::Sequence(items) => {
+				write!(f, "[")?;
+				list(f, items)?;
+				write!(f, "]")
+			},
+			Value::Struct(fields) => {
+				write!(f, "{{ ")?;
+				for (i, (name, value)) in fields.iter().enumerate() {
+					if i > 0 {
+						write!(f, ", ")?;
+					}
+					write!(f, "{}: {}", name, value)?;
+				}
+				write!(f, " }}")
+			},
+			Value::Variant(name, fields) if fields.is_empty() => write!(f, "{}", name),
+			Value::Variant(name, fields) => match &fields[..] {
+				[Value::Struct(_)] => write!(f, "{} {}", name, fields[0]),
+				_ => {
+					write!(f, "{}(", name)?;
+					list(f, fields)?;
+					write!(f, ")")
+				},
+			},
+			Value::Call(call) => write!(f, "{}", call),
+		}
+	}
+}
+
+impl fmt::Display for Call {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}.{}(", self.pallet, self.name)?;
+		for (i, (name, value)) in self.args.iter().enumerate() {
+			if i > 0 {
+				write!(f, ", ")?;
+			}
+			write!(f, "{}: {}", name, value)?;
+		}
+		write!(f, ")")
+	}
+}
+
+impl fmt::Display for Event {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}.{}(", self.pallet, self.name)?;
+		for (i, value) in self.args.iter().enumerate() {
+			if i > 0 {
+				write!(f, ", ")?;
+			}
+			write!(f, "{}", value)?;
+		}
+		write!(f, ")")
+	}
+}
+
+impl Value {
+	/// The value as JSON.
+	///
+	/// Numbers that don't fit in 64 bits are given as strings.
+	pub fn to_json(&self) -> serde_json::Value {
+		use serde_json::Value as Json;
+
+		match self {
+			Value::Unit => Json::Null,
+			Value::Bool(b) => Json::Bool(*b),
+			Value::Unsigned(n) if *n <= u64::max_value() as u128 => Json::from(*n as u64),
+			Value::Signed(n) if *n <= i64::max_value() as i128 && *n >= i64::min_value() as i128 =>
+				Json::from(*n as i64),
+			Value::Unsigned(n) => Json::String(n.to_string()),
+			Value::Signed(n) => Json::String(n.to_string()),
+			Value::Account(address) => Json::String(address.clone()),
+			Value::Bytes(bytes) => Json::String(format!("0x{}", HexDisplay::from(bytes))),
+			Value::Sequence(items) => Json::Array(items.iter().map(Value::to_json).collect()),
+			Value::Struct(fields) => Json::Object(
+				fields.iter().map(|(name, value)| (name.clone(), value.to_json())).collect(),
+			),
+			Value::Variant(name, fields) => {
+				let value = match &fields[..] {
+					[] => return Json::String(name.clone()),
+					[field] => field.to_json(),
+					_ => Json::Array(fields.iter().map(Value::to_json).collect()),
+				};
+				let mut variant = serde_json::Map::new();
+				variant.insert(name.clone(), value);
+				Json::Object(variant)
+			},
+			Value::Call(call) => call.to_json(),
+		}
+	}
+}
+
+impl Call {
+	/// The call as JSON.
+	pub fn to_json(&self) -> serde_json::Value {
+		serde_json::json!({
+			"pallet": self.pallet,
+			"call": self.name,
+			"args": self.args.iter()
+				.map(|(name, value)| (name.clone(), value.to_json()))
+				.collect::<serde_json::Map<_, _>>(),
+		})
+	}
+}
+
+impl Event {
+	/// The event as JSON.
+	pub fn to_json(&self) -> serde_json::Value {
+		serde_json::json!({
+			"pallet": self.pallet,
+			"event": self.name,
+			"args": self.args.iter().map(Value::to_json).collect::<Vec<_>>(),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn named(name: &str) -> Type {
+		Type::Named(name.into())
+	}
+
+	#[test]
+	fn should_parse_metadata_type_names() {
+		assert_eq!(Type::parse("T::AccountId"), Ok(named("AccountId")));
+		assert_eq!(Type::parse("<T::Lookup as StaticLookup>::Source"), Ok(named("LookupSource")));
+		assert_eq!(Type::parse("Compact<BalanceOf<T>>"), Ok(Type::Compact(Box::new(named("BalanceOf")))));
+		assert_eq!(
+			Type::parse("Vec<(<T as frame_system::Trait>::AccountId, Data)>"),
+			Ok(Type::Vec(Box::new(Type::Tuple(vec![named("AccountId"), named("Data")])))),
+		);
+		assert_eq!(Type::parse("Box<<T as Trait>::Call>"), Ok(named("Call")));
+		assert_eq!(Type::parse("Option<[u8; 20]>"), Ok(Type::Option(Box::new(Type::Array(Box::new(named("u8")), 20)))));
+		assert_eq!(Type::parse("AccountVote<BalanceOf<T>>"), Ok(named("AccountVote")));
+		assert_eq!(Type::parse("&[u8]"), Ok(Type::Vec(Box::new(named("u8")))));
+		assert!(Type::parse("Vec<u8").is_err());
+	}
+
+	#[test]
+	fn should_decode_values_of_registered_types() {
+		let registry = TypeRegistry::default();
+		let metadata = RuntimeMetadataPrefixed(
+			frame_metadata::META_RESERVED,
+			RuntimeMetadata::V11(RuntimeMetadataV11 {
+				modules: DecodeDifferent::Decoded(vec![]),
+				extrinsic: frame_metadata::ExtrinsicMetadata { version: 4, signed_extensions: vec![] },
+			}),
+		);
+		let decoder = Decoder::new(&registry, &metadata).unwrap();
+		let decode = |ty: &str, bytes: &[u8]| {
+			let mut input = bytes;
+			let value = decoder.value(ty, &mut input);
+			assert!(input.is_empty());
+			value
+		};
+
+		assert_eq!(decode("Compact<BalanceOf<T>>", &[0x10]), Ok(Value::Unsigned(4)));
+		assert_eq!(
+			decode("AccountVote<BalanceOf<T>>", &[0, 0b1000_0001, 5, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+			Ok(Value::Variant("Standard".into(), vec![Value::Struct(vec![
+				("vote".into(), Value::Struct(vec![
+					("aye".into(), Value::Bool(true)),
+					("conviction".into(), Value::Variant("Locked1x".into(), vec![])),
+				])),
+				("balance".into(), Value::Unsigned(5)),
+			])])),
+		);
+		assert_eq!(
+			decode("Data", &[4, b'b', b'o', b'b']),
+			Ok(Value::Variant("Raw".into(), vec![Value::Bytes(b"bob".to_vec())])),
+		);
+		assert_eq!(decode("<T::Lookup as StaticLookup>::Source", &[7]), Ok(Value::Variant("Index".into(), vec![Value::Unsigned(7)])));
+		assert_eq!(
+			decode("DispatchError", &[3, 1, 2]),
+			Ok(Value::Variant("Module".into(), vec![Value::Struct(vec![
+				("index".into(), Value::Unsigned(1)),
+				("error".into(), Value::Unsigned(2)),
+			])])),
+		);
+		assert_eq!(decode("Unknown", &[]), Err("Unknown type Unknown".into()));
+	}
+
+	#[test]
+	fn should_locate_storage_entries() {
+		use frame_metadata::{ModuleMetadata, StorageEntryMetadata, StorageMetadata};
+
+		let entry = |name: &'static str, ty, modifier| StorageEntryMetadata {
+			name: DecodeDifferent::Decoded(name.into()),
+			modifier,
+			ty,
+			default: DecodeDifferent::Decoded(vec![0; 4]),
+			documentation: DecodeDifferent::Decoded(vec![]),
+		};
+		let registry = TypeRegistry::default();
+		let metadata = RuntimeMetadataPrefixed(
+			frame_metadata::META_RESERVED,
+			RuntimeMetadata::V11(RuntimeMetadataV11 {
+				modules: DecodeDifferent::Decoded(vec![ModuleMetadata {
+					name: DecodeDifferent::Decoded("Democracy".into()),
+					storage: Some(DecodeDifferent::Decoded(StorageMetadata {
+						prefix: DecodeDifferent::Decoded("Democracy".into()),
+						entries: DecodeDifferent::Decoded(vec![
+							entry(
+								"ReferendumCount",
+								StorageEntryType::Plain(DecodeDifferent::Decoded("ReferendumIndex".into())),
+								StorageEntryModifier::Default,
+							),
+							entry(
+								"ReferendumInfoOf",
+								StorageEntryType::Map {
+									hasher: StorageHasher::Twox64Concat,
+									key: DecodeDifferent::Decoded("ReferendumIndex".into()),
+									value: DecodeDifferent::Decoded("ReferendumInfo".into()),
+									unused: false,
+								},
+								StorageEntryModifier::Optional,
+							),
+						]),
+					})),
+					calls: None,
+					event: None,
+					constants: DecodeDifferent::Decoded(vec![]),
+					errors: DecodeDifferent::Decoded(vec![]),
+				}]),
+				extrinsic: frame_metadata::ExtrinsicMetadata { version: 4, signed_extensions: vec![] },
+			}),
+		);
+		let decoder = Decoder::new(&registry, &metadata).unwrap();
+		let prefix = [sp_core::twox_128(b"Democracy"), sp_core::twox_128(b"ReferendumInfoOf")].concat();
+
+		let count = decoder.storage_entry("Democracy", "ReferendumCount", &[]).unwrap();
+		assert_eq!(count.key, [sp_core::twox_128(b"Democracy"), sp_core::twox_128(b"ReferendumCount")].concat());
+		assert_eq!(count.default, Some(vec![0; 4]));
+
+		let info = decoder.storage_entry("democracy", "ReferendumInfoOf", &["12".into()]).unwrap();
+		assert_eq!(info.key, [&prefix[..], &sp_core::twox_64(&12u32.encode()), &12u32.encode()].concat());
+		assert_eq!(info.ty, "ReferendumInfo");
+		assert_eq!(info.default, None);
+		assert_eq!(decoder.storage_entry("Democracy", "ReferendumInfoOf", &["0x0c000000".into()]), Ok(info));
+
+		assert!(decoder.storage_entry("Democracy", "ReferendumInfoOf", &[]).is_err());
+		assert!(decoder.storage_entry("Democracy", "ReferendumInfoOf", &["twelve".into()]).is_err());
+		assert!(decoder.storage_entry("Society", "Members", &[]).is_err());
+	}
+
+	#[test]
+	fn should_print_values() {
+		let value = Value::Variant("Standard".into(), vec![Value::Struct(vec![
+			("balance".into(), Value::Unsigned(u128::max_value())),
+		])]);
+		assert_eq!(format!("{}", value), format!("Standard {{ balance: {} }}", u128::max_value()));
+		assert_eq!(
+			value.to_json(),
+			serde_json::json!({ "Standard": { "balance": u128::max_value().to_string() } }),
+		);
+		assert_eq!(Value::Variant("None".into(), vec![]).to_json(), serde_json::json!("None"));
+	}
+}
@@ -19,22 +19,34 @@
 //!
 //! The blocks and extrinsics can either be retrieved from the database (on-chain),
 //! or a raw SCALE-encoding can be provided.
+//!
+//! Besides the `DebugPrinter`, the `MetadataPrinter` decodes calls and the events
+//! they emitted using the runtime metadata, and prints them as text or JSON. It
+//! also reads decoded storage items and the events of block ranges straight from
+//! the database, without the node running.
 
 #![warn(missing_docs)]
 
 pub mod cli;
 pub mod command;
+pub mod decode;
+mod printer;
+
+pub use printer::{MetadataPrinter, OutputFormat, StorageQuery};
 
 use std::{
 	fmt,
 	fmt::Debug,
 	marker::PhantomData,
 	str::FromStr,
+	sync::Arc,
 };
 use codec::{Encode, Decode};
+use frame_metadata::RuntimeMetadataPrefixed;
 use sc_client_api::BlockBackend;
+use sp_api::{Metadata, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
-use sp_core::hexdisplay::HexDisplay;
+use sp_core::{hexdisplay::HexDisplay, storage::{StorageData, StorageKey}};
 use sp_runtime::{
 	generic::BlockId,
 	traits::{Block, HashFor, NumberFor, Hash}
@@ -52,6 +64,12 @@ pub trait PrettyPrinter<TBlock: Block> {
 	fn fmt_block(&self, fmt: &mut fmt::Formatter, block: &TBlock) -> fmt::Result;
 	/// Nicely format extrinsic.
 	fn fmt_extrinsic(&self, fmt: &mut fmt::Formatter, extrinsic: &TBlock::Extrinsic) -> fmt::Result;
+	/// Nicely format the extrinsic at `index` of `block`.
+	///
+	/// Defaults to formatting the extrinsic alone.
+	fn fmt_block_extrinsic(&self, fmt: &mut fmt::Formatter, block: &TBlock, index: usize) -> fmt::Result {
+		self.fmt_extrinsic(fmt, &block.extrinsics()[index])
+	}
 }
 
 /// Default dummy debug printer.
@@ -86,6 +104,9 @@ pub enum Error {
 	Blockchain(sp_blockchain::Error),
 	/// Given block has not been found.
 	NotFound(String),
+	/// Could not locate or decode data with the runtime metadata.
+	#[from(ignore)]
+	Metadata(String),
 }
 
 impl std::error::Error for Error {
@@ -94,6 +115,7 @@ impl std::error::Error for Error {
 			Self::Codec(ref e) => Some(e),
 			Self::Blockchain(ref e) => Some(e),
 			Self::NotFound(_) => None,
+			Self::Metadata(_) => None,
 		}
 	}
 }
@@ -109,10 +131,34 @@ impl<T, TBlock> ChainAccess<TBlock> for T where
 	T: sp_blockchain::HeaderBackend<TBlock> + sc_client_api::BlockBackend<TBlock>,
 {}
 
+/// A helper trait to access the runtime metadata and storage at a block.
+pub trait StateAccess<TBlock: Block>: ChainAccess<TBlock> {
+	/// Metadata of the runtime at block `id`.
+	fn metadata(&self, id: &BlockId<TBlock>) -> Result<RuntimeMetadataPrefixed, Error>;
+	/// Value of the storage `key` at block `id`.
+	fn storage(&self, id: &BlockId<TBlock>, key: &StorageKey) -> Result<Option<StorageData>, Error>;
+}
+
+impl<TBlock, RA, EX> StateAccess<TBlock> for sc_service::TFullClient<TBlock, RA, EX> where
+	TBlock: Block,
+	EX: sc_service::NativeExecutionDispatch + 'static,
+	RA: sp_api::ConstructRuntimeApi<TBlock, Self> + Send + Sync,
+	RA::RuntimeApi: Metadata<TBlock, Error = sp_blockchain::Error>,
+{
+	fn metadata(&self, id: &BlockId<TBlock>) -> Result<RuntimeMetadataPrefixed, Error> {
+		let metadata = self.runtime_api().metadata(id)?;
+		Ok(RuntimeMetadataPrefixed::decode(&mut &metadata[..])?)
+	}
+
+	fn storage(&self, id: &BlockId<TBlock>, key: &StorageKey) -> Result<Option<StorageData>, Error> {
+		Ok(sc_client_api::StorageProvider::storage(self, id, key)?)
+	}
+}
+
 /// Blockchain inspector.
 pub struct Inspector<TBlock: Block, TPrinter: PrettyPrinter<TBlock> = DebugPrinter> {
 	printer: TPrinter,
-	chain: Box<dyn ChainAccess<TBlock>>,
+	chain: Arc<dyn ChainAccess<TBlock>>,
 	_block: PhantomData<TBlock>,
 }
 
@@ -128,9 +174,17 @@ impl<TBlock: Block, TPrinter: PrettyPrinter<TBlock>> Inspector<TBlock, TPrinter>
 	pub fn with_printer(
 		chain: impl ChainAccess<TBlock> + 'static,
 		printer: TPrinter,
+	) -> Self {
+		Self::from_shared(Arc::new(chain), printer)
+	}
+
+	/// Create an inspector sharing the chain access, e.g. with its printer.
+	pub fn from_shared(
+		chain: Arc<dyn ChainAccess<TBlock>>,
+		printer: TPrinter,
 	) -> Self {
 		Inspector {
-			chain: Box::new(chain) as _,
+			chain,
 			printer,
 			_block: Default::default(),
 		}
@@ -187,22 +241,28 @@ impl<TBlock: Block, TPrinter: PrettyPrinter<TBlock>> Inspector<TBlock, TPrinter>
 			}
 		}
 
-		let ext = match input {
+		struct BlockExtrinsicPrinter<'a, A, B>(A, usize, &'a B);
+		impl<'a, A: Block, B: PrettyPrinter<A>> fmt::Display for BlockExtrinsicPrinter<'a, A, B> {
+			fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+				self.2.fmt_block_extrinsic(fmt, &self.0, self.1)
+			}
+		}
+
+		match input {
 			ExtrinsicAddress::Block(block, index) => {
 				let block = self.get_block(block)?;
-				block.extrinsics()
-					.get(index)
-					.cloned()
-					.ok_or_else(|| Error::NotFound(format!(
+				if index >= block.extrinsics().len() {
+					return Err(Error::NotFound(format!(
 						"Could not find extrinsic {} in block {:?}", index, block
-					)))?
+					)));
+				}
+				Ok(format!("{}", BlockExtrinsicPrinter(block, index, &self.printer)))
 			},
 			ExtrinsicAddress::Bytes(bytes) => {
-				TBlock::Extrinsic::decode(&mut &*bytes)?
+				let ext = TBlock::Extrinsic::decode(&mut &*bytes)?;
+				Ok(format!("{}", ExtrinsicPrinter(ext, &self.printer)))
 			}
-		};
-
-		Ok(format!("{}", ExtrinsicPrinter(ext, &self.printer)))
+		}
 	}
 }
 
@@ -0,0 +1,514 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A printer decoding blocks, extrinsics, events and storage with the runtime
+//! metadata.
+
+use std::{fmt, str::FromStr, sync::Arc};
+
+use codec::Encode;
+use frame_metadata::RuntimeMetadataPrefixed;
+use sp_core::{hexdisplay::HexDisplay, storage::StorageKey, twox_128};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block, Header, NumberFor, One, UniqueSaturatedInto, Zero},
+};
+
+use crate::{
+	BlockAddress, BlockAddressFor, Error, PrettyPrinter, StateAccess,
+	decode::{Decoder, Event, Extrinsic, Phase, TypeRegistry, Value},
+};
+
+/// The format a `MetadataPrinter` prints in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+	/// Human readable text.
+	Text,
+	/// JSON.
+	Json,
+}
+
+impl FromStr for OutputFormat {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"text" => Ok(Self::Text),
+			"json" => Ok(Self::Json),
+			_ => Err(format!("Unknown output format {}, expected text or json", s)),
+		}
+	}
+}
+
+/// What to read from the storage.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StorageQuery {
+	/// A raw storage key, whose value is printed undecoded.
+	Raw(StorageKey),
+	/// A storage item of a pallet, with its map keys.
+	Item {
+		/// Name of the pallet.
+		pallet: String,
+		/// Name of the storage item.
+		item: String,
+		/// The map keys, see `Decoder::storage_entry`.
+		keys: Vec<String>,
+	},
+}
+
+impl StorageQuery {
+	/// Parse a query of either a 0x-prefixed raw storage key, or a
+	/// `Pallet::Item` with the given map keys.
+	pub fn parse(key: &str, keys: Vec<String>) -> Result<Self, String> {
+		if key.starts_with("0x") {
+			if !keys.is_empty() {
+				return Err("Map keys can't be given along with a raw storage key".into());
+			}
+			return sp_core::bytes::from_hex(key)
+				.map(|key| Self::Raw(StorageKey(key)))
+				.map_err(|e| format!("Invalid storage key {}: {:?}", key, e));
+		}
+
+		let mut parts = key.splitn(2, "::");
+		match (parts.next(), parts.next()) {
+			(Some(pallet), Some(item)) if !pallet.is_empty() && !item.is_empty() =>
+				Ok(Self::Item { pallet: pallet.into(), item: item.into(), keys }),
+			_ => Err(format!("Expected a 0x-prefixed storage key or Pallet::Item, got {}", key)),
+		}
+	}
+}
+
+/// A printer decoding the calls of extrinsics and the events they emitted
+/// using the runtime metadata.
+///
+/// Calls are decoded with the metadata of the runtime that executed them, and
+/// the events are read from `System::Events` in the state after the block.
+pub struct MetadataPrinter<TBlock: Block> {
+	state: Arc<dyn StateAccess<TBlock>>,
+	registry: TypeRegistry,
+	format: OutputFormat,
+}
+
+/// What could be decoded of an extrinsic.
+struct ExtrinsicReport {
+	index: Option<usize>,
+	bytes: Vec<u8>,
+	decoded: Result<Extrinsic, String>,
+	events: Vec<Event>,
+}
+
+/// What could be decoded of a block.
+struct BlockReport {
+	number: u64,
+	hash: String,
+	parent_hash: String,
+	extrinsics: Vec<ExtrinsicReport>,
+	/// The events deposited outside of the extrinsics.
+	events: Vec<(Phase, Event)>,
+	/// Why not everything could be decoded.
+	errors: Vec<String>,
+}
+
+fn hex(bytes: &[u8]) -> String {
+	format!("0x{}", HexDisplay::from(&bytes))
+}
+
+/// The storage key of `System::Events`.
+fn events_key() -> StorageKey {
+	StorageKey([twox_128(b"System"), twox_128(b"Events")].concat())
+}
+
+impl<TBlock: Block> MetadataPrinter<TBlock> {
+	/// Create a printer with the types of this repository.
+	pub fn new(state: Arc<dyn StateAccess<TBlock>>, format: OutputFormat) -> Self {
+		Self::with_registry(state, format, Default::default())
+	}
+
+	/// Create a printer knowing the types of `registry`.
+	pub fn with_registry(
+		state: Arc<dyn StateAccess<TBlock>>,
+		format: OutputFormat,
+		registry: TypeRegistry,
+	) -> Self {
+		MetadataPrinter { state, registry, format }
+	}
+
+	/// Decode `block`, or only its extrinsic at `only`.
+	fn block_report(&self, block: &TBlock, only: Option<usize>) -> BlockReport {
+		let header = block.header();
+		let hash = header.hash();
+		let mut report = BlockReport {
+			number: (*header.number()).unique_saturated_into(),
+			hash: hex(hash.as_ref()),
+			parent_hash: hex(header.parent_hash().as_ref()),
+			extrinsics: Vec::new(),
+			events: Vec::new(),
+			errors: Vec::new(),
+		};
+
+		let metadata = self.runtime_metadata(header);
+		let decoder = metadata.as_ref()
+			.map_err(Clone::clone)
+			.and_then(|metadata| Decoder::new(&self.registry, metadata));
+		let events = match &decoder {
+			Ok(decoder) => self.events_at(decoder, hash, &mut report.errors),
+			Err(e) => {
+				report.errors.push(format!("Could not use runtime metadata: {}", e));
+				Vec::new()
+			},
+		};
+
+		for (index, extrinsic) in block.extrinsics().iter().enumerate() {
+			if only.map_or(false, |only| only != index) {
+				continue;
+			}
+			let mut extrinsic = self.extrinsic_report(decoder.as_ref().ok(), extrinsic);
+			extrinsic.index = Some(index);
+			extrinsic.events = events.iter()
+				.filter(|(phase, _)| *phase == Phase::ApplyExtrinsic(index as u32))
+				.map(|(_, event)| event.clone())
+				.collect();
+			report.extrinsics.push(extrinsic);
+		}
+
+		if only.is_none() {
+			report.events = events.into_iter()
+				.filter(|(phase, _)| match phase {
+					Phase::ApplyExtrinsic(_) => false,
+					_ => true,
+				})
+				.collect();
+		}
+
+		report
+	}
+
+	/// Metadata of the runtime that executed the block of `header`.
+	fn runtime_metadata(&self, header: &TBlock::Header) -> Result<RuntimeMetadataPrefixed, String> {
+		// The extrinsics are executed by the runtime of the parent block.
+		let runtime_at = if header.number().is_zero() {
+			BlockId::Hash(header.hash())
+		} else {
+			BlockId::Hash(*header.parent_hash())
+		};
+		self.state.metadata(&runtime_at).map_err(|e| e.to_string())
+	}
+
+	/// The events deposited by the block `hash`, adding why not all of them
+	/// could be decoded to `errors`.
+	fn events_at(&self, decoder: &Decoder, hash: TBlock::Hash, errors: &mut Vec<String>) -> Vec<(Phase, Event)> {
+		match self.state.storage(&BlockId::Hash(hash), &events_key()) {
+			Ok(Some(data)) => {
+				let (events, error) = decoder.events(&mut &data.0[..]);
+				errors.extend(error.map(|e| format!("Could not decode all events: {}", e)));
+				events
+			},
+			Ok(None) => Vec::new(),
+			Err(e) => {
+				errors.push(format!("Could not read events: {}", e));
+				Vec::new()
+			},
+		}
+	}
+
+	/// The header of the block at `address` of the chain.
+	fn header(&self, address: BlockAddressFor<TBlock>) -> Result<TBlock::Header, Error> {
+		let id = match address {
+			BlockAddress::Hash(hash) => BlockId::Hash(hash),
+			BlockAddress::Number(number) => BlockId::Number(number),
+			BlockAddress::Bytes(_) => return Err(Error::NotFound(
+				"The state is only available at blocks of the chain, not at block bytes".into()
+			)),
+		};
+		let not_found = format!("Could not find block {:?}", id);
+		self.state.header(id)?.ok_or_else(|| Error::NotFound(not_found))
+	}
+
+	/// Get the pretty-printed value of `query` in the state after the block
+	/// at `at`.
+	pub fn state(&self, at: BlockAddressFor<TBlock>, query: &StorageQuery) -> Result<String, Error> {
+		let header = self.header(at)?;
+		let id = BlockId::Hash(header.hash());
+
+		let (name, key, value) = match query {
+			StorageQuery::Raw(key) => {
+				let value = self.state.storage(&id, key)?;
+				(hex(&key.0), key.clone(), value.map(|data| Value::Bytes(data.0)))
+			},
+			StorageQuery::Item { pallet, item, keys } => {
+				// The state after the block is the one its own runtime sees.
+				let metadata = self.state.metadata(&id)?;
+				let decoder = Decoder::new(&self.registry, &metadata).map_err(Error::Metadata)?;
+				let entry = decoder.storage_entry(pallet, item, keys).map_err(Error::Metadata)?;
+				let key = StorageKey(entry.key);
+				let data = self.state.storage(&id, &key)?.map(|data| data.0).or(entry.default);
+				let value = data
+					.map(|data| decoder.value(&entry.ty, &mut &data[..]))
+					.transpose()
+					.map_err(Error::Metadata)?;
+				(format!("{}::{}", pallet, item), key, value)
+			},
+		};
+
+		let number: u64 = (*header.number()).unique_saturated_into();
+		Ok(match self.format {
+			OutputFormat::Json => {
+				let json = serde_json::json!({
+					"block": { "number": number, "hash": hex(header.hash().as_ref()) },
+					"key": hex(&key.0),
+					"value": value.as_ref().map(Value::to_json),
+				});
+				serde_json::to_string_pretty(&json).expect("JSON values always serialize; qed")
+			},
+			OutputFormat::Text => format!(
+				"{} at block #{} {}\nKey: {}\nValue: {}",
+				name,
+				number,
+				hex(header.hash().as_ref()),
+				hex(&key.0),
+				value.map_or_else(|| "None".to_string(), |value| value.to_string()),
+			),
+		})
+	}
+
+	/// Get the pretty-printed events of the blocks `from` to `to`, of the
+	/// given pallets or of all of them if none is given.
+	pub fn events(
+		&self,
+		from: BlockAddressFor<TBlock>,
+		to: Option<BlockAddressFor<TBlock>>,
+		pallets: &[String],
+	) -> Result<String, Error> {
+		let first = *self.header(from)?.number();
+		let last = match to {
+			Some(to) => *self.header(to)?.number(),
+			None => first,
+		};
+
+		let mut number: NumberFor<TBlock> = first;
+		let mut blocks = Vec::new();
+		while number <= last {
+			let header = self.header(BlockAddress::Number(number))?;
+			let mut errors = Vec::new();
+			let events = match self.runtime_metadata(&header)
+				.and_then(|metadata| Decoder::new(&self.registry, &metadata)
+					.map(|decoder| self.events_at(&decoder, header.hash(), &mut errors)))
+			{
+				Ok(events) => events,
+				Err(e) => {
+					errors.push(format!("Could not use runtime metadata: {}", e));
+					Vec::new()
+				},
+			};
+			let events = events.into_iter()
+				.filter(|(_, event)| pallets.is_empty() ||
+					pallets.iter().any(|pallet| pallet.eq_ignore_ascii_case(&event.pallet)))
+				.collect::<Vec<_>>();
+			blocks.push((header, events, errors));
+			number += One::one();
+		}
+
+		Ok(match self.format {
+			OutputFormat::Json => {
+				let json = blocks.iter()
+					.map(|(header, events, errors)| {
+						let number: u64 = (*header.number()).unique_saturated_into();
+						serde_json::json!({
+							"number": number,
+							"hash": hex(header.hash().as_ref()),
+							"events": events.iter()
+								.map(|(phase, event)| {
+									let mut json = event.to_json();
+									json["phase"] = serde_json::Value::String(format!("{:?}", phase));
+									json
+								})
+								.collect::<Vec<_>>(),
+							"errors": errors,
+						})
+					})
+					.collect::<Vec<_>>();
+				serde_json::to_string_pretty(&json).expect("JSON values always serialize; qed")
+			},
+			OutputFormat::Text => {
+				let mut out = String::new();
+				for (header, events, errors) in blocks {
+					let number: u64 = (*header.number()).unique_saturated_into();
+					out.push_str(&format!("Block #{} {}\n", number, hex(header.hash().as_ref())));
+					for (phase, event) in events {
+						out.push_str(&format!("  {:?}: {}\n", phase, event));
+					}
+					for error in errors {
+						out.push_str(&format!("  {}\n", error));
+					}
+				}
+				out
+			},
+		})
+	}
+
+	fn extrinsic_report(&self, decoder: Option<&Decoder>, extrinsic: &TBlock::Extrinsic) -> ExtrinsicReport {
+		let bytes = extrinsic.encode();
+		let decoded = match decoder {
+			Some(decoder) => decoder.extrinsic(&mut &bytes[..]),
+			None => Err("No runtime metadata".into()),
+		};
+		ExtrinsicReport { index: None, bytes, decoded, events: Vec::new() }
+	}
+
+	fn fmt_report(&self, fmt: &mut fmt::Formatter, report: &BlockReport, only: Option<usize>) -> fmt::Result {
+		if self.format == OutputFormat::Json {
+			let json = match only {
+				Some(_) => match report.extrinsics.get(0) {
+					Some(extrinsic) => {
+						let mut json = extrinsic.to_json();
+						json["block"] = serde_json::json!({ "number": report.number, "hash": report.hash });
+						json["errors"] = serde_json::json!(report.errors);
+						json
+					},
+					None => serde_json::json!({ "errors": report.errors }),
+				},
+				None => report.to_json(),
+			};
+			return writeln!(fmt, "{}", serde_json::to_string_pretty(&json).map_err(|_| fmt::Error)?);
+		}
+
+		writeln!(fmt, "Block #{} {}", report.number, report.hash)?;
+		if only.is_none() {
+			writeln!(fmt, "Parent: {}", report.parent_hash)?;
+			writeln!(fmt, "Extrinsics ({}):", report.extrinsics.len())?;
+		}
+		for extrinsic in &report.extrinsics {
+			extrinsic.fmt_text(fmt)?;
+		}
+		if !report.events.is_empty() {
+			writeln!(fmt, "Block events:")?;
+			for (phase, event) in &report.events {
+				writeln!(fmt, "  {:?}: {}", phase, event)?;
+			}
+		}
+		for error in &report.errors {
+			writeln!(fmt, "{}", error)?;
+		}
+		Ok(())
+	}
+}
+
+impl ExtrinsicReport {
+	fn fmt_text(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		match self.index {
+			Some(index) => write!(fmt, "- {}: ", index)?,
+			None => write!(fmt, "- ")?,
+		}
+		match &self.decoded {
+			Ok(extrinsic) => {
+				writeln!(fmt, "{}", extrinsic.call)?;
+				if let Some(signature) = &extrinsic.signature {
+					write!(fmt, "    Signer: {}", signature.signer)?;
+					for (name, value) in &signature.extra {
+						write!(fmt, ", {}: {}", name, value)?;
+					}
+					writeln!(fmt)?;
+				}
+			},
+			Err(e) => {
+				writeln!(fmt, "{}", hex(&self.bytes))?;
+				writeln!(fmt, "    Could not decode: {}", e)?;
+			},
+		}
+		if !self.events.is_empty() {
+			writeln!(fmt, "    Events:")?;
+			for event in &self.events {
+				writeln!(fmt, "      {}", event)?;
+			}
+		}
+		Ok(())
+	}
+
+	fn to_json(&self) -> serde_json::Value {
+		let mut json = serde_json::json!({
+			"index": self.index,
+			"bytes": hex(&self.bytes),
+			"events": self.events.iter().map(Event::to_json).collect::<Vec<_>>(),
+		});
+		match &self.decoded {
+			Ok(extrinsic) => {
+				json["call"] = extrinsic.call.to_json();
+				json["signature"] = match &extrinsic.signature {
+					Some(signature) => serde_json::json!({
+						"signer": signature.signer.to_json(),
+						"extra": signature.extra.iter()
+							.map(|(name, value)| (name.clone(), value.to_json()))
+							.collect::<serde_json::Map<_, _>>(),
+					}),
+					None => serde_json::Value::Null,
+				};
+			},
+			Err(e) => json["error"] = serde_json::Value::String(e.clone()),
+		}
+		json
+	}
+}
+
+impl BlockReport {
+	fn to_json(&self) -> serde_json::Value {
+		serde_json::json!({
+			"number": self.number,
+			"hash": self.hash,
+			"parent_hash": self.parent_hash,
+			"extrinsics": self.extrinsics.iter().map(ExtrinsicReport::to_json).collect::<Vec<_>>(),
+			"events": self.events.iter()
+				.map(|(phase, event)| {
+					let mut json = event.to_json();
+					json["phase"] = serde_json::Value::String(format!("{:?}", phase));
+					json
+				})
+				.collect::<Vec<_>>(),
+			"errors": self.errors,
+		})
+	}
+}
+
+impl<TBlock: Block> PrettyPrinter<TBlock> for MetadataPrinter<TBlock> {
+	fn fmt_block(&self, fmt: &mut fmt::Formatter, block: &TBlock) -> fmt::Result {
+		self.fmt_report(fmt, &self.block_report(block, None), None)
+	}
+
+	fn fmt_extrinsic(&self, fmt: &mut fmt::Formatter, extrinsic: &TBlock::Extrinsic) -> fmt::Result {
+		// Not knowing its block, decode the extrinsic with the latest runtime.
+		let best = BlockId::Hash(self.state.info().best_hash);
+		let metadata = self.state.metadata(&best).map_err(|e| e.to_string());
+		let decoder = metadata.as_ref()
+			.map_err(Clone::clone)
+			.and_then(|metadata| Decoder::new(&self.registry, metadata));
+		let report = self.extrinsic_report(decoder.as_ref().ok(), extrinsic);
+
+		match self.format {
+			OutputFormat::Json => {
+				let mut json = report.to_json();
+				if let Err(e) = &decoder {
+					json["errors"] = serde_json::json!([e]);
+				}
+				writeln!(fmt, "{}", serde_json::to_string_pretty(&json).map_err(|_| fmt::Error)?)
+			},
+			OutputFormat::Text => report.fmt_text(fmt),
+		}
+	}
+
+	fn fmt_block_extrinsic(&self, fmt: &mut fmt::Formatter, block: &TBlock, index: usize) -> fmt::Result {
+		self.fmt_report(fmt, &self.block_report(block, Some(index)), Some(index))
+	}
+}
+
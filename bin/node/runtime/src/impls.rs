@@ -17,11 +17,16 @@
 //! Some configurable implementations as associated type for the substrate runtime.
 
 use core::num::NonZeroI128;
-use node_primitives::Balance;
-use sp_runtime::traits::{Convert, Saturating};
-use sp_runtime::{Fixed128, Perquintill};
+use node_primitives::{AccountId, Balance};
+use sp_runtime::traits::{Convert, Saturating, Zero};
+use sp_runtime::{Fixed128, Perquintill, DispatchResult};
 use frame_support::{traits::{OnUnbalanced, Currency, Get}, weights::Weight};
-use crate::{Balances, System, Authorship, MaximumBlockWeight, NegativeImbalance};
+use sp_std::prelude::*;
+use pallet_identity::Judgement;
+use crate::{
+	Balances, System, Authorship, Identity, Treasury, Proxy, ProxyType, MaximumBlockWeight,
+	NegativeImbalance,
+};
 
 pub struct Author;
 impl OnUnbalanced<NegativeImbalance> for Author {
@@ -30,6 +35,92 @@ impl OnUnbalanced<NegativeImbalance> for Author {
 	}
 }
 
+/// Personhood for quadratic democracy backed by `pallet-identity`. An account is a person once a
+/// registrar has judged its identity `Reasonable` or `KnownGood`; its identity sub-accounts count
+/// towards its quadratic votes once they have agreed to through `link_sub_account`, since
+/// `set_subs` doesn't ask them.
+///
+/// The population is the number of identities judged `Reasonable` or `KnownGood`, which
+/// `pallet-identity` counts as judgements are given and lost. It follows who can actually vote as
+/// a person rather than a bound governance would have to keep raising.
+pub struct IdentityPersonhood;
+
+impl pallet_quadratic_democracy::Personhood<AccountId> for IdentityPersonhood {
+	fn is_person(who: &AccountId) -> bool {
+		Identity::identity(who).map_or(false, |registration| registration.is_judged_good())
+	}
+
+	fn super_of(who: &AccountId) -> Option<AccountId> {
+		Identity::super_of(who).map(|(s, _)| s)
+	}
+
+	fn sub_accounts(who: &AccountId) -> Vec<AccountId> {
+		Identity::subs_of(who).1
+	}
+
+	fn population() -> u32 {
+		Identity::judged_good()
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn make_person(who: &AccountId, subs: Vec<AccountId>) {
+		use pallet_identity::{IdentityOf, SuperOf, SubsOf, JudgedGood, Registration, IdentityInfo, Data};
+		use frame_support::{StorageMap, StorageValue};
+		let info = IdentityInfo {
+			additional: Vec::new(),
+			display: Data::None,
+			legal: Data::None,
+			web: Data::None,
+			riot: Data::None,
+			email: Data::None,
+			pgp_fingerprint: None,
+			image: Data::None,
+			twitter: Data::None,
+		};
+		let judgements = vec![(0, Judgement::KnownGood)];
+		if !Identity::identity(who).map_or(false, |registration| registration.is_judged_good()) {
+			JudgedGood::mutate(|n| *n = n.saturating_add(1));
+		}
+		IdentityOf::<crate::Runtime>::insert(who, Registration { judgements, deposit: 0, info });
+		for sub in subs.iter() {
+			SuperOf::<crate::Runtime>::insert(sub, (who.clone(), Data::None));
+		}
+		SubsOf::<crate::Runtime>::insert(who, (0, subs));
+	}
+}
+
+/// Voting proxies for quadratic democracy backed by `pallet-proxy`. An account may vote on behalf
+/// of another once it has been made its `Voting` (or `Governance` or `Any`) proxy without an
+/// announcement delay.
+pub struct GovernanceProxies;
+
+impl pallet_quadratic_democracy::VotingProxies<AccountId> for GovernanceProxies {
+	fn is_voting_proxy(real: &AccountId, delegate: &AccountId) -> bool {
+		Proxy::has_proxy(real, delegate, &ProxyType::Voting)
+	}
+
+	fn add_voting_proxy(real: &AccountId, delegate: &AccountId) -> DispatchResult {
+		// The proxies of the democracy pallet took no deposit, so one the account can't cover is
+		// left to be reserved with its next proxy rather than losing the proxy.
+		Proxy::add_proxy_delegate(real, delegate.clone(), ProxyType::Voting, Zero::zero())
+			.or_else(|_| Proxy::add_proxy_delegate_without_deposit(
+				real,
+				delegate.clone(),
+				ProxyType::Voting,
+				Zero::zero(),
+			))
+	}
+}
+
+/// The treasury's account, out of which quadratic funding matching pools are funded.
+pub struct TreasuryAccount;
+
+impl Get<AccountId> for TreasuryAccount {
+	fn get() -> AccountId {
+		Treasury::account_id()
+	}
+}
+
 /// Struct that handles the conversion of Balance -> `u64`. This is used for staking's election
 /// calculation.
 pub struct CurrencyToVoteHandler;
@@ -32,7 +32,7 @@ use node_primitives::{AccountIndex, Balance, BlockNumber, Hash, Index, Moment};
 use sp_api::impl_runtime_apis;
 use sp_runtime::{
 	Permill, Perbill, Perquintill, Percent, ApplyExtrinsicResult,
-	impl_opaque_keys, generic, create_runtime_str, ModuleId,
+	impl_opaque_keys, generic, create_runtime_str, ModuleId, RuntimeDebug,
 };
 use sp_runtime::curve::PiecewiseLinear;
 use sp_runtime::transaction_validity::{TransactionValidity, TransactionSource, TransactionPriority};
@@ -50,6 +50,10 @@ use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
 use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
 use pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo;
 use pallet_contracts_rpc_runtime_api::ContractExecResult;
+use pallet_quadratic_democracy::{ConvictionLevel, ConvictionSchedule};
+use pallet_quadratic_democracy_rpc_runtime_api::{
+	Conviction, ReferendumIndex, ReferendumTally, VoteWeight, VotingPower,
+};
 use sp_inherents::{InherentData, CheckInherentsResult};
 
 #[cfg(any(feature = "std", test))]
@@ -60,16 +64,22 @@ pub use frame_system::Call as SystemCall;
 pub use pallet_contracts::Gas;
 pub use frame_support::StorageValue;
 pub use pallet_staking::StakerStatus;
-use codec::Encode;
+use codec::{Encode, Decode};
 
 /// Implementations of some helper traits passed into runtime modules as associated types.
 pub mod impls;
-use impls::{CurrencyToVoteHandler, Author, LinearWeightToFee, TargetedFeeAdjustment};
+use impls::{
+	CurrencyToVoteHandler, Author, LinearWeightToFee, TargetedFeeAdjustment, IdentityPersonhood,
+	TreasuryAccount, GovernanceProxies,
+};
 
 /// Constant values used within the runtime.
 pub mod constants;
 use constants::{time::*, currency::*};
 
+/// Weights of the calls of the runtime's pallets, following their benchmarks.
+pub mod weights;
+
 // Make the WASM binary available.
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
@@ -83,10 +93,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// and set impl_version to 0. If only runtime
 	// implementation changes and behavior does not, then leave spec_version as
 	// is and increment impl_version.
-	spec_version: 244,
-	impl_version: 3,
+	spec_version: 245,
+	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 /// Native version.
@@ -175,6 +185,82 @@ impl pallet_utility::Trait for Runtime {
 	type MaxSignatories = MaxSignatories;
 }
 
+parameter_types! {
+	// One storage item; key size 32, value size 8.
+	pub const ProxyDepositBase: Balance = 20 * CENTS;
+	// Additional storage item size of 33 bytes.
+	pub const ProxyDepositFactor: Balance = 5 * CENTS;
+	pub const MaxProxies: u16 = 32;
+	pub const AnnouncementDepositBase: Balance = 20 * CENTS;
+	// Additional storage item size of 68 bytes.
+	pub const AnnouncementDepositFactor: Balance = 10 * CENTS;
+	pub const MaxPending: u32 = 32;
+}
+
+/// The type used to represent the kinds of proxying allowed.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug)]
+pub enum ProxyType {
+	/// May make any call on behalf of the proxied account.
+	Any,
+	/// May vote, propose and otherwise take part in on-chain governance.
+	Governance,
+	/// May manage the proxied account's stake.
+	Staking,
+	/// May only vote in any of the ways democracy allows, remove votes and delegate votes.
+	Voting,
+}
+
+impl Default for ProxyType { fn default() -> Self { Self::Any } }
+
+impl pallet_proxy::InstanceFilter<Call> for ProxyType {
+	fn filter(&self, c: &Call) -> bool {
+		match self {
+			ProxyType::Any => true,
+			ProxyType::Governance => matches!(c,
+				Call::Democracy(..) | Call::Council(..) | Call::TechnicalCommittee(..)
+					| Call::Elections(..) | Call::Treasury(..)
+			),
+			ProxyType::Staking => matches!(c, Call::Staking(..)),
+			ProxyType::Voting => matches!(c,
+				Call::Democracy(pallet_quadratic_democracy::Call::vote(..))
+					| Call::Democracy(pallet_quadratic_democracy::Call::vote_with_credits(..))
+					| Call::Democracy(pallet_quadratic_democracy::Call::vote_options(..))
+					| Call::Democracy(pallet_quadratic_democracy::Call::commit_vote(..))
+					| Call::Democracy(pallet_quadratic_democracy::Call::reveal_vote(..))
+					| Call::Democracy(pallet_quadratic_democracy::Call::proxy_vote(..))
+					| Call::Democracy(pallet_quadratic_democracy::Call::remove_vote(..))
+					| Call::Democracy(pallet_quadratic_democracy::Call::delegate(..))
+					| Call::Democracy(pallet_quadratic_democracy::Call::undelegate(..))
+					| Call::Democracy(pallet_quadratic_democracy::Call::delegate_class(..))
+					| Call::Democracy(pallet_quadratic_democracy::Call::undelegate_class(..))
+			),
+		}
+	}
+
+	fn is_superset(&self, o: &Self) -> bool {
+		match (self, o) {
+			(x, y) if x == y => true,
+			(ProxyType::Any, _) => true,
+			(ProxyType::Governance, ProxyType::Voting) => true,
+			_ => false,
+		}
+	}
+}
+
+impl pallet_proxy::Trait for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ProxyDepositBase;
+	type ProxyDepositFactor = ProxyDepositFactor;
+	type MaxProxies = MaxProxies;
+	type AnnouncementDepositBase = AnnouncementDepositBase;
+	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+	type MaxPending = MaxPending;
+	type WeightInfo = weights::pallet_proxy::WeightInfo;
+}
+
 parameter_types! {
 	pub const MaximumWeight: Weight = 2_000_000;
 }
@@ -353,6 +439,34 @@ parameter_types! {
 	pub const CooloffPeriod: BlockNumber = 28 * 24 * 60 * MINUTES;
 	// One cent: $10,000 / MB
 	pub const PreimageByteDeposit: Balance = 1 * CENTS;
+	pub const DefaultVoteWeight: pallet_quadratic_democracy::VoteWeight =
+		pallet_quadratic_democracy::VoteWeight::Quadratic;
+	pub const DelegationWeighting: pallet_quadratic_democracy::DelegationWeighting =
+		pallet_quadratic_democracy::DelegationWeighting::SumOfRoots;
+	// A hundred votes on a single referendum per period, or ten on each of a hundred.
+	pub const CreditAllowance: Balance = 10_000;
+	pub const CreditPeriod: BlockNumber = 28 * 24 * 60 * MINUTES;
+	pub const MaxOptions: u32 = 16;
+	pub const RevealPeriod: BlockNumber = 7 * 24 * 60 * MINUTES;
+	pub const CommitDeposit: Balance = 1 * DOLLARS;
+	pub const HistoryRetention: BlockNumber = 90 * 24 * 60 * MINUTES;
+	pub const IndexingBatch: u32 = 100;
+	pub const MaxVotes: u32 = 100;
+	// Each sub-account looked at costs a vote five reads, so persons link only their first few.
+	pub const MaxLinkedSubAccounts: u32 = 16;
+	pub const MaxProposals: u32 = 100;
+	pub const MaxSeconds: u32 = 100;
+	// Flatter than the coin-weighted curve, so that under quadratic weighting long locks don't
+	// outweigh breadth of support.
+	pub const QuadraticConvictionSchedule: ConvictionSchedule = ConvictionSchedule(vec![
+		ConvictionLevel { lock_periods: 0, multiplier: 1 },
+		ConvictionLevel { lock_periods: 1, multiplier: 10 },
+		ConvictionLevel { lock_periods: 2, multiplier: 15 },
+		ConvictionLevel { lock_periods: 4, multiplier: 20 },
+		ConvictionLevel { lock_periods: 8, multiplier: 25 },
+		ConvictionLevel { lock_periods: 16, multiplier: 30 },
+		ConvictionLevel { lock_periods: 32, multiplier: 35 },
+	]);
 }
 
 impl pallet_quadratic_democracy::Trait for Runtime {
@@ -363,6 +477,9 @@ impl pallet_quadratic_democracy::Trait for Runtime {
 	type LaunchPeriod = LaunchPeriod;
 	type VotingPeriod = VotingPeriod;
 	type MinimumDeposit = MinimumDeposit;
+	type DefaultVoteWeight = DefaultVoteWeight;
+	type DelegationWeighting = DelegationWeighting;
+	type ConvictionSchedule = QuadraticConvictionSchedule;
 	/// A straight majority of the council can decide what their next motion is.
 	type ExternalOrigin = pallet_collective::EnsureProportionAtLeast<_1, _2, AccountId, CouncilCollective>;
 	/// A super-majority can have the next scheduled referendum be a straight majority-carries vote.
@@ -385,6 +502,20 @@ impl pallet_quadratic_democracy::Trait for Runtime {
 	type PreimageByteDeposit = PreimageByteDeposit;
 	type Slash = Treasury;
 	type Scheduler = Scheduler;
+	type Personhood = IdentityPersonhood;
+	type Proxies = GovernanceProxies;
+	type CreditAllowance = CreditAllowance;
+	type CreditPeriod = CreditPeriod;
+	type MaxOptions = MaxOptions;
+	type RevealPeriod = RevealPeriod;
+	type CommitDeposit = CommitDeposit;
+	type HistoryRetention = HistoryRetention;
+	type IndexingBatch = IndexingBatch;
+	type MaxVotes = MaxVotes;
+	type MaxLinkedSubAccounts = MaxLinkedSubAccounts;
+	type MaxProposals = MaxProposals;
+	type MaxSeconds = MaxSeconds;
+	type WeightInfo = weights::pallet_quadratic_democracy::WeightInfo;
 }
 
 parameter_types! {
@@ -480,6 +611,29 @@ impl pallet_treasury::Trait for Runtime {
 	type ModuleId = TreasuryModuleId;
 }
 
+parameter_types! {
+	pub const QuadraticFundingModuleId: ModuleId = ModuleId(*b"py/qfund");
+	pub const ProjectDeposit: Balance = 10 * DOLLARS;
+	pub const MaxProjects: u32 = 100;
+	pub const MinimumContribution: Balance = 1 * CENTS;
+	pub const FundingLockPeriod: BlockNumber = 7 * DAYS;
+}
+
+impl pallet_quadratic_funding::Trait for Runtime {
+	type Event = Event;
+	type ModuleId = QuadraticFundingModuleId;
+	type Currency = Balances;
+	type Treasury = TreasuryAccount;
+	type RoundOrigin = pallet_collective::EnsureMembers<_4, AccountId, CouncilCollective>;
+	type ProjectDeposit = ProjectDeposit;
+	type MaxProjects = MaxProjects;
+	type MinimumContribution = MinimumContribution;
+	type LockPeriod = FundingLockPeriod;
+	type ConvictionSchedule = QuadraticConvictionSchedule;
+	type Personhood = IdentityPersonhood;
+	type WeightInfo = weights::pallet_quadratic_funding::WeightInfo;
+}
+
 parameter_types! {
 	pub const TombstoneDeposit: Balance = 1 * DOLLARS;
 	pub const RentByteFee: Balance = 1 * DOLLARS;
@@ -705,17 +859,19 @@ construct_runtime!(
 		FinalityTracker: pallet_finality_tracker::{Module, Call, Inherent},
 		Grandpa: pallet_grandpa::{Module, Call, Storage, Config, Event},
 		Treasury: pallet_treasury::{Module, Call, Storage, Config, Event<T>},
+		QuadraticFunding: pallet_quadratic_funding::{Module, Call, Storage, Event<T>},
 		Contracts: pallet_contracts::{Module, Call, Config, Storage, Event<T>},
 		Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
 		ImOnline: pallet_im_online::{Module, Call, Storage, Event<T>, ValidateUnsigned, Config<T>},
 		AuthorityDiscovery: pallet_authority_discovery::{Module, Call, Config},
 		Offences: pallet_offences::{Module, Call, Storage, Event},
 		RandomnessCollectiveFlip: pallet_randomness_collective_flip::{Module, Call, Storage},
-		Identity: pallet_identity::{Module, Call, Storage, Event<T>},
+		Identity: pallet_identity::{Module, Call, Storage, Event<T>, Config<T>},
 		Society: pallet_society::{Module, Call, Storage, Event<T>, Config<T>},
 		Recovery: pallet_recovery::{Module, Call, Storage, Event<T>},
 		Vesting: pallet_vesting::{Module, Call, Storage, Event<T>, Config<T>},
 		Scheduler: pallet_scheduler::{Module, Call, Storage, Event<T>},
+		Proxy: pallet_proxy::{Module, Call, Storage, Event<T>},
 	}
 );
 
@@ -890,6 +1046,44 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_quadratic_democracy_rpc_runtime_api::QuadraticDemocracyApi<
+		Block,
+		AccountId,
+		Balance,
+	> for Runtime {
+		fn referendum_tally(index: ReferendumIndex) -> Option<ReferendumTally<Balance>> {
+			Democracy::referendum_outcome(index).map(|(tally, electorate, weight, threshold, approved)|
+				ReferendumTally {
+					ayes: tally.ayes,
+					nays: tally.nays,
+					turnout: tally.turnout,
+					weighted_turnout: tally.weighted_turnout,
+					electorate,
+					weight,
+					threshold,
+					approved,
+				}
+			)
+		}
+
+		fn voting_power(
+			who: AccountId,
+			weight: VoteWeight,
+			balance: Balance,
+			conviction: Conviction,
+		) -> Option<VotingPower<Balance>> {
+			Democracy::voting_power(&who, weight, balance, conviction).map(|(weighted_balance, direct, delegated)|
+				VotingPower {
+					capital: balance,
+					weighted_balance,
+					votes: direct.votes,
+					delegated_capital: delegated.capital,
+					delegated_votes: delegated.votes,
+				}
+			)
+		}
+	}
+
 	impl sp_session::SessionKeys<Block> for Runtime {
 		fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
 			SessionKeys::generate(seed)
@@ -933,6 +1127,8 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, b"identity", Identity);
 			add_benchmark!(params, batches, b"im-online", ImOnline);
 			add_benchmark!(params, batches, b"offences", OffencesBench::<Runtime>);
+			add_benchmark!(params, batches, b"proxy", Proxy);
+			add_benchmark!(params, batches, b"quadratic-funding", QuadraticFunding);
 			add_benchmark!(params, batches, b"session", SessionBench::<Runtime>);
 			add_benchmark!(params, batches, b"staking", Staking);
 			add_benchmark!(params, batches, b"system", SystemBench::<Runtime>);
@@ -960,4 +1156,31 @@ mod tests {
 
 		is_submit_signed_transaction::<Runtime>();
 	}
+
+	#[test]
+	fn voting_proxies_may_make_every_kind_of_vote_and_delegation() {
+		use pallet_proxy::InstanceFilter;
+		use pallet_quadratic_democracy::{AccountVote, Conviction, DelegationClass, Vote};
+		use pallet_quadratic_democracy::Call as DemocracyCall;
+
+		let who = AccountId::default();
+		let vote = AccountVote::Standard { vote: Vote { aye: true, conviction: Conviction::None }, balance: 1 };
+		let calls = vec![
+			DemocracyCall::vote(0, vote),
+			DemocracyCall::vote_with_credits(0, true, 1),
+			DemocracyCall::vote_options(0, vec![1, 0]),
+			DemocracyCall::commit_vote(0, Default::default()),
+			DemocracyCall::reveal_vote(0, vote, Default::default()),
+			DemocracyCall::proxy_vote(who.clone(), 0, vote),
+			DemocracyCall::remove_vote(0),
+			DemocracyCall::delegate(who.clone(), Conviction::None, 1),
+			DemocracyCall::undelegate(),
+			DemocracyCall::delegate_class(DelegationClass::default(), who, Conviction::None, 1),
+			DemocracyCall::undelegate_class(DelegationClass::default()),
+		];
+		for call in calls {
+			assert!(ProxyType::Voting.filter(&Call::Democracy(call)));
+		}
+		assert!(!ProxyType::Voting.filter(&Call::Democracy(DemocracyCall::clear_public_proposals())));
+	}
 }
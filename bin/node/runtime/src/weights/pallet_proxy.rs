@@ -0,0 +1,86 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for `pallet_proxy`, following the benchmarks of the pallet.
+//!
+//! Each weight is an execution time, linear in the components of the benchmark of the same name,
+//! plus the database reads and writes of its worst case priced with the runtime's `DbWeight`.
+//! The database accesses are counted from the calls, but the execution times are estimates that
+//! have not been measured yet. Replace them with the output of
+//!
+//! ```text
+//! substrate benchmark --chain dev --execution wasm --pallet proxy --extrinsic '*' \
+//!     --steps 50 --repeat 20
+//! ```
+//!
+//! on the validators' reference hardware, and again whenever the pallet or its benchmarks change.
+
+use frame_support::{traits::Get, weights::Weight};
+use crate::DbWeight;
+
+/// The weights of `pallet_proxy`.
+pub struct WeightInfo;
+impl pallet_proxy::WeightInfo for WeightInfo {
+	fn proxy(p: u32) -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add((80_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+	}
+	fn add_proxy(p: u32) -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add((150_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn remove_proxy(p: u32) -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add((150_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn remove_proxies(p: u32) -> Weight {
+		(33_000_000 as Weight)
+			.saturating_add((100_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn announce(a: u32, p: u32) -> Weight {
+		(44_000_000 as Weight)
+			.saturating_add((180_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add((70_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn remove_announcement(a: u32) -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add((170_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn reject_announcement(a: u32) -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add((170_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn proxy_announced(a: u32, p: u32) -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add((180_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add((80_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+}
@@ -0,0 +1,326 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for `pallet_quadratic_democracy`, following the benchmarks of the pallet.
+//!
+//! Each weight is an execution time, linear in the component of the benchmark of the same name,
+//! plus the database reads and writes of its worst case priced with the runtime's `DbWeight`.
+//! The database accesses are counted from the calls, but the execution times are estimates that
+//! have not been measured yet. Replace them with the output of
+//!
+//! ```text
+//! substrate benchmark --chain dev --execution wasm --pallet democracy --extrinsic '*' \
+//!     --steps 50 --repeat 20
+//! ```
+//!
+//! on the validators' reference hardware, and again whenever the pallet or its benchmarks change.
+//! The pallet is benchmarked as `democracy`, the name it is added to the runtime's benchmarks
+//! under.
+
+use frame_support::{traits::Get, weights::Weight};
+use crate::DbWeight;
+
+/// The weights of `pallet_quadratic_democracy`.
+pub struct WeightInfo;
+impl pallet_quadratic_democracy::WeightInfo for WeightInfo {
+	fn propose(p: u32) -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add((290_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn second(s: u32) -> Weight {
+		(44_000_000 as Weight)
+			.saturating_add((220_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn vote_new(r: u32, l: u32) -> Weight {
+		(71_000_000 as Weight)
+			.saturating_add((260_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add((28_000_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(DbWeight::get().reads(11 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+			.saturating_add(DbWeight::get().reads((5 as Weight).saturating_mul(l as Weight)))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(l as Weight)))
+	}
+	fn vote_existing(r: u32, l: u32) -> Weight {
+		(71_000_000 as Weight)
+			.saturating_add((275_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add((28_000_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(DbWeight::get().reads(11 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+			.saturating_add(DbWeight::get().reads((5 as Weight).saturating_mul(l as Weight)))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(l as Weight)))
+	}
+	fn proxy_vote_new(r: u32, l: u32) -> Weight {
+		(76_000_000 as Weight)
+			.saturating_add((260_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add((28_000_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(DbWeight::get().reads(12 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+			.saturating_add(DbWeight::get().reads((5 as Weight).saturating_mul(l as Weight)))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(l as Weight)))
+	}
+	fn proxy_vote_existing(r: u32, l: u32) -> Weight {
+		(76_000_000 as Weight)
+			.saturating_add((275_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add((28_000_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(DbWeight::get().reads(12 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+			.saturating_add(DbWeight::get().reads((5 as Weight).saturating_mul(l as Weight)))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(l as Weight)))
+	}
+	fn emergency_cancel(_r: u32) -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn external_propose(_p: u32) -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn external_propose_majority(_p: u32) -> Weight {
+		(3_500_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn external_propose_default(_p: u32) -> Weight {
+		(3_500_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn external_propose_with_weight(_p: u32) -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn external_propose_majority_with_weight(_p: u32) -> Weight {
+		(3_500_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn external_propose_default_with_weight(_p: u32) -> Weight {
+		(3_500_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn external_propose_quorum(_p: u32) -> Weight {
+		(3_600_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn fast_track(_p: u32) -> Weight {
+		(33_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn veto_external(v: u32) -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add((185_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn cancel_referendum(_r: u32) -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+	}
+	fn cancel_queued(r: u32) -> Weight {
+		(43_000_000 as Weight)
+			.saturating_add((3_300_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn on_initialize_external(r: u32) -> Weight {
+		(72_000_000 as Weight)
+			.saturating_add((10_700_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(r as Weight)))
+	}
+	fn on_initialize_public(r: u32) -> Weight {
+		(70_000_000 as Weight)
+			.saturating_add((10_700_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(6 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(r as Weight)))
+	}
+	fn delegate(r: u32) -> Weight {
+		(82_000_000 as Weight)
+			.saturating_add((7_700_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(8 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+	}
+	fn undelegate(r: u32) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((7_600_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+	}
+	fn delegate_class(r: u32) -> Weight {
+		(88_000_000 as Weight)
+			.saturating_add((7_900_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(9 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+	}
+	fn undelegate_class(r: u32) -> Weight {
+		(44_000_000 as Weight)
+			.saturating_add((7_800_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+	}
+	fn clear_public_proposals(_p: u32) -> Weight {
+		(3_700_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn note_preimage(b: u32) -> Weight {
+		(46_000_000 as Weight)
+			.saturating_add((4_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn note_imminent_preimage(b: u32) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn reap_preimage(b: u32) -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add((1_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn unlock_remove(r: u32) -> Weight {
+		(54_000_000 as Weight)
+			.saturating_add((40_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn unlock_set(r: u32) -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add((215_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn remove_vote(r: u32) -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add((220_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn remove_other_vote(r: u32) -> Weight {
+		(31_200_000 as Weight)
+			.saturating_add((220_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn proxy_delegate(r: u32) -> Weight {
+		(87_000_000 as Weight)
+			.saturating_add((7_700_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(9 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+	}
+	fn proxy_undelegate(r: u32) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((7_600_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+	}
+	fn proxy_remove_vote(r: u32) -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add((220_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(5 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn vote_with_credits(r: u32) -> Weight {
+		(60_000_000 as Weight)
+			.saturating_add((255_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(DbWeight::get().reads(7 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn start_multi_option_referendum(o: u32) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((2_600_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn vote_options(o: u32) -> Weight {
+		(62_000_000 as Weight)
+			.saturating_add((1_100_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add(DbWeight::get().reads(8 as Weight))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+	}
+	fn external_propose_secret(_p: u32) -> Weight {
+		(15_500_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn commit_vote(_r: u32) -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn reveal_vote(r: u32, l: u32) -> Weight {
+		(82_000_000 as Weight)
+			.saturating_add((265_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add((28_000_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(DbWeight::get().reads(13 as Weight))
+			.saturating_add(DbWeight::get().writes(5 as Weight))
+			.saturating_add(DbWeight::get().reads((5 as Weight).saturating_mul(l as Weight)))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(l as Weight)))
+	}
+	fn prune_referendum(_r: u32) -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(2 as Weight))
+	}
+	fn reap_commitments(c: u32) -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add((41_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)))
+	}
+	fn reap_multi_option_votes(v: u32) -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add((9_000_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(v as Weight)))
+	}
+	fn link_sub_account(_r: u32) -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+	fn unlink_sub_account(_r: u32) -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(1 as Weight))
+			.saturating_add(DbWeight::get().writes(1 as Weight))
+	}
+}
@@ -0,0 +1,72 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for `pallet_quadratic_funding`, following the benchmarks of the pallet.
+//!
+//! Each weight is an execution time, linear in the component of the benchmark of the same name,
+//! plus the database reads and writes of its worst case priced with the runtime's `DbWeight`.
+//! The database accesses are counted from the calls, but the execution times are estimates that
+//! have not been measured yet. Replace them with the output of
+//!
+//! ```text
+//! substrate benchmark --chain dev --execution wasm --pallet quadratic-funding --extrinsic '*' \
+//!     --steps 50 --repeat 20
+//! ```
+//!
+//! on the validators' reference hardware, and again whenever the pallet or its benchmarks change.
+
+use frame_support::{traits::Get, weights::Weight};
+use crate::DbWeight;
+
+/// The weights of `pallet_quadratic_funding`.
+pub struct WeightInfo;
+impl pallet_quadratic_funding::WeightInfo for WeightInfo {
+	fn start_round() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn register_project() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+	fn contribute() -> Weight {
+		(90_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(8 as Weight))
+			.saturating_add(DbWeight::get().writes(7 as Weight))
+	}
+	fn finalize_round(p: u32) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((60_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(DbWeight::get().reads(4 as Weight))
+			.saturating_add(DbWeight::get().reads((3 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(DbWeight::get().writes(4 as Weight))
+			.saturating_add(DbWeight::get().writes((3 as Weight).saturating_mul(p as Weight)))
+	}
+	fn reap_contributions(c: u32) -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add((6_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(DbWeight::get().reads(2 as Weight))
+			.saturating_add(DbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn unlock() -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add(DbWeight::get().reads(3 as Weight))
+			.saturating_add(DbWeight::get().writes(3 as Weight))
+	}
+}
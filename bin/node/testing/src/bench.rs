@@ -22,7 +22,7 @@
 
 use std::{sync::Arc, path::Path, collections::BTreeMap};
 
-use node_primitives::Block;
+use node_primitives::{Block, Index};
 use crate::client::{Client, Backend};
 use crate::keyring::*;
 use sc_client_db::PruningMode;
@@ -47,7 +47,13 @@ use node_runtime::{
 	BalancesCall,
 	AccountId,
 	Signature,
+	DemocracyConfig,
+	IdentityConfig,
 };
+use pallet_quadratic_democracy::{
+	AccountVote, Conviction, ReferendumIndex, Vote, VoteThreshold, VoteWeight,
+};
+use pallet_identity::{Data, IdentityInfo, Judgement};
 use sp_core::{ExecutionContext, blake2_256, traits::CloneableSpawn};
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
@@ -94,6 +100,7 @@ impl BenchPair {
 pub struct BenchDb {
 	keyring: BenchKeyring,
 	directory_guard: Guard,
+	database_type: DatabaseType,
 }
 
 impl Clone for BenchDb {
@@ -122,10 +129,31 @@ impl Clone for BenchDb {
 			&fs_extra::dir::CopyOptions::new(),
 		).expect("Copy of seed database is ok");
 
-		BenchDb { keyring, directory_guard: Guard(dir) }
+		BenchDb { keyring, directory_guard: Guard(dir), database_type: self.database_type }
 	}
 }
 
+/// Number of referenda ongoing in the benchmarking genesis.
+///
+/// The even ones are quadratic and the odd ones standard-weighted. Account `n`
+/// of the keyring votes on referendum `n % BENCH_REFERENDA`, so the quadratic
+/// ones are voted on by the persons at even positions, see `BENCH_PERSONS`.
+pub const BENCH_REFERENDA: ReferendumIndex = 16;
+
+/// Number of persons in the benchmarking genesis.
+///
+/// The accounts at even positions among the first `2 * BENCH_PERSONS` of the
+/// keyring (at most half of it) have an identity judged by a registrar, and
+/// the account following each of them is its sub-account, linked to it so
+/// that its capital counts towards the person's quadratic votes.
+pub const BENCH_PERSONS: usize = 2_000;
+
+/// Number of accounts that are delegated to in the benchmarking genesis.
+///
+/// The first accounts of the keyring each receive a delegation from one of
+/// the last, so that their votes carry delegated voting power.
+pub const BENCH_DELEGATED: usize = 1_000;
+
 /// Type of block for generation
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum BlockType {
@@ -135,13 +163,30 @@ pub enum BlockType {
 	RandomTransfersReaping(usize),
 	/// Bunch of "no-op" calls.
 	Noop(usize),
+	/// Bunch of democracy votes on the genesis referenda, many of them
+	/// carrying delegations, and half of them quadratic votes of persons
+	/// counting the capital of their sub-account.
+	Votes(usize),
+	/// One account voting on every genesis referendum, followed by other
+	/// accounts delegating to it and undelegating, each of which changes the
+	/// tally of every referendum.
+	DelegationCascades(usize),
+	/// Bunch of democracy votes on the genesis referenda, each removed by the
+	/// transaction following it.
+	VoteRemovals(usize),
+	/// Bunch of identities being set.
+	IdentitySets(usize),
+	/// Bunch of bids to join the society.
+	SocietyBids(usize),
 }
 
 impl BlockType {
 	/// Number of transactions for this block type.
 	pub fn transactions(&self) -> usize {
 		match self {
-			Self::RandomTransfersKeepAlive(v) | Self::RandomTransfersReaping(v) | Self::Noop(v) => *v,
+			Self::RandomTransfersKeepAlive(v) | Self::RandomTransfersReaping(v) | Self::Noop(v) |
+			Self::Votes(v) | Self::DelegationCascades(v) | Self::VoteRemovals(v) |
+			Self::IdentitySets(v) | Self::SocietyBids(v) => *v,
 		}
 	}
 }
@@ -181,7 +226,7 @@ impl BenchDb {
 	///
 	/// See [`new`] method documentation for more information about the purpose
 	/// of this structure.
-	pub fn with_key_types(keyring_length: usize, key_types: KeyTypes) -> Self {
+	pub fn with_key_types(database_type: DatabaseType, keyring_length: usize, key_types: KeyTypes) -> Self {
 		let keyring = BenchKeyring::new(keyring_length, key_types);
 
 		let dir = tempfile::tempdir().expect("temp dir creation failed");
@@ -190,10 +235,15 @@ impl BenchDb {
 			"Created seed db at {}",
 			dir.path().to_string_lossy(),
 		);
-		let (_client, _backend) = Self::bench_client(dir.path(), Profile::Native, &keyring);
+		let (_client, _backend) = Self::bench_client(
+			database_type,
+			dir.path(),
+			Profile::Native,
+			&keyring,
+		);
 		let directory_guard = Guard(dir);
 
-		BenchDb { keyring, directory_guard }
+		BenchDb { keyring, directory_guard, database_type }
 	}
 
 	/// New immutable benchmarking database.
@@ -204,8 +254,8 @@ impl BenchDb {
 	/// You can `clone` this database or you can `create_context` from it
 	/// (which also does `clone`) to run actual operation against new database
 	/// which will be identical to the original.
-	pub fn new(keyring_length: usize) -> Self {
-		Self::with_key_types(keyring_length, KeyTypes::Sr25519)
+	pub fn new(database_type: DatabaseType, keyring_length: usize) -> Self {
+		Self::with_key_types(database_type, keyring_length, KeyTypes::Sr25519)
 	}
 
 	// This should return client that is doing everything that full node
@@ -213,15 +263,17 @@ impl BenchDb {
 	//
 	// - This client should use best wasm execution method.
 	// - This client should work with real database only.
-	fn bench_client(dir: &std::path::Path, profile: Profile, keyring: &BenchKeyring) -> (Client, std::sync::Arc<Backend>) {
+	fn bench_client(
+		database_type: DatabaseType,
+		dir: &std::path::Path,
+		profile: Profile,
+		keyring: &BenchKeyring,
+	) -> (Client, std::sync::Arc<Backend>) {
 		let db_config = sc_client_db::DatabaseSettings {
 			state_cache_size: 16*1024*1024,
 			state_cache_child_ratio: Some((0, 100)),
 			pruning: PruningMode::ArchiveAll,
-			source: sc_client_db::DatabaseSettingsSrc::RocksDb {
-				path: dir.into(),
-				cache_size: 512,
-			},
+			source: database_type.into_settings(dir.into()),
 		};
 
 		let (client, backend) = sc_service::new_client(
@@ -242,6 +294,7 @@ impl BenchDb {
 	/// Generate new block using this database.
 	pub fn generate_block(&mut self, block_type: BlockType) -> Block {
 		let (client, _backend) = Self::bench_client(
+			self.database_type,
 			self.directory_guard.path(),
 			Profile::Wasm,
 			&self.keyring,
@@ -282,39 +335,12 @@ impl BenchDb {
 		let start = std::time::Instant::now();
 		for _ in 0..block_type.transactions() {
 
-			let sender = self.keyring.at(iteration);
-			let receiver = get_account_id_from_seed::<sr25519::Public>(
-				&format!("random-user//{}", iteration)
-			);
+			let (sender, nonce, function) = self.transaction(block_type, iteration);
 
 			let signed = self.keyring.sign(
 				CheckedExtrinsic {
-					signed: Some((sender, signed_extra(0, node_runtime::ExistentialDeposit::get() + 1))),
-					function: match block_type {
-						BlockType::RandomTransfersKeepAlive(_) => {
-							Call::Balances(
-								BalancesCall::transfer_keep_alive(
-									pallet_indices::address::Address::Id(receiver),
-									node_runtime::ExistentialDeposit::get() + 1,
-								)
-							)
-						},
-						BlockType::RandomTransfersReaping(_) => {
-							Call::Balances(
-								BalancesCall::transfer(
-									pallet_indices::address::Address::Id(receiver),
-									// Transfer so that ending balance would be 1 less than existential deposit
-									// so that we kill the sender account.
-									100*DOLLARS - (node_runtime::ExistentialDeposit::get() - 1),
-								)
-							)
-						},
-						BlockType::Noop(_) => {
-							Call::System(
-								SystemCall::remark(Vec::new())
-							)
-						},
-					},
+					signed: Some((sender, signed_extra(nonce, node_runtime::ExistentialDeposit::get() + 1))),
+					function,
 				},
 				version,
 				genesis_hash,
@@ -347,6 +373,100 @@ impl BenchDb {
 		block
 	}
 
+	/// The sender, nonce and call of transaction `iteration` of a block of
+	/// `block_type`.
+	fn transaction(&self, block_type: BlockType, iteration: usize) -> (AccountId, Index, Call) {
+		let referendum = |n: usize| n as ReferendumIndex % BENCH_REFERENDA;
+		let vote = |n: usize| Call::Democracy(pallet_quadratic_democracy::Call::vote(
+			referendum(n),
+			AccountVote::Standard {
+				vote: Vote { aye: n % 2 == 0, conviction: Conviction::Locked1x },
+				balance: 10 * DOLLARS,
+			},
+		));
+
+		match block_type {
+			BlockType::RandomTransfersKeepAlive(_) => {
+				let receiver = get_account_id_from_seed::<sr25519::Public>(
+					&format!("random-user//{}", iteration)
+				);
+				(self.keyring.at(iteration), 0, Call::Balances(
+					BalancesCall::transfer_keep_alive(
+						pallet_indices::address::Address::Id(receiver),
+						node_runtime::ExistentialDeposit::get() + 1,
+					)
+				))
+			},
+			BlockType::RandomTransfersReaping(_) => {
+				let receiver = get_account_id_from_seed::<sr25519::Public>(
+					&format!("random-user//{}", iteration)
+				);
+				// Persons and their sub-accounts can't be reaped, since they hold
+				// identity deposits.
+				let sender = self.keyring.at(2 * self.keyring.persons() + iteration);
+				(sender, 0, Call::Balances(
+					BalancesCall::transfer(
+						pallet_indices::address::Address::Id(receiver),
+						// Transfer so that ending balance would be 1 less than existential deposit
+						// so that we kill the sender account.
+						100*DOLLARS - (node_runtime::ExistentialDeposit::get() - 1),
+					)
+				))
+			},
+			BlockType::Noop(_) => {
+				(self.keyring.at(iteration), 0, Call::System(
+					SystemCall::remark(Vec::new())
+				))
+			},
+			BlockType::Votes(_) => (self.keyring.at(iteration), 0, vote(iteration)),
+			BlockType::DelegationCascades(_) => {
+				let referenda = BENCH_REFERENDA as usize;
+				if iteration < referenda {
+					return (self.keyring.at(0), iteration as Index, vote(iteration));
+				}
+				let n = iteration - referenda;
+				let sender = self.keyring.at(1 + n / 2);
+				let call = if n % 2 == 0 {
+					pallet_quadratic_democracy::Call::delegate(
+						self.keyring.at(0),
+						Conviction::Locked1x,
+						10 * DOLLARS,
+					)
+				} else {
+					pallet_quadratic_democracy::Call::undelegate()
+				};
+				(sender, (n % 2) as Index, Call::Democracy(call))
+			},
+			BlockType::VoteRemovals(_) => {
+				let sender = self.keyring.at(iteration / 2);
+				if iteration % 2 == 0 {
+					(sender, 0, vote(iteration / 2))
+				} else {
+					let call = pallet_quadratic_democracy::Call::remove_vote(referendum(iteration / 2));
+					(sender, 1, Call::Democracy(call))
+				}
+			},
+			BlockType::IdentitySets(_) => {
+				let info = IdentityInfo {
+					additional: vec![],
+					display: Data::Raw(format!("bench-user-{}", iteration).into_bytes()),
+					legal: Data::None,
+					web: Data::None,
+					riot: Data::None,
+					email: Data::None,
+					pgp_fingerprint: None,
+					image: Data::None,
+					twitter: Data::None,
+				};
+				(self.keyring.at(iteration), 0, Call::Identity(pallet_identity::Call::set_identity(info)))
+			},
+			BlockType::SocietyBids(_) => {
+				let call = pallet_society::Call::bid(DOLLARS + iteration as node_primitives::Balance);
+				(self.keyring.at(iteration), 0, Call::Society(call))
+			},
+		}
+	}
+
 	/// Database path.
 	pub fn path(&self) -> &Path {
 		self.directory_guard.path()
@@ -354,8 +474,8 @@ impl BenchDb {
 
 	/// Clone this database and create context for testing/benchmarking.
 	pub fn create_context(&self, profile: Profile) -> BenchContext {
-		let BenchDb { directory_guard, keyring } = self.clone();
-		let (client, backend) = Self::bench_client(directory_guard.path(), profile, &keyring);
+		let BenchDb { directory_guard, keyring, database_type } = self.clone();
+		let (client, backend) = Self::bench_client(database_type, directory_guard.path(), profile, &keyring);
 
 		BenchContext {
 			client, backend, db_guard: directory_guard,
@@ -408,6 +528,11 @@ impl BenchKeyring {
 		self.accounts.keys().nth(index).expect("Failed to get account").clone()
 	}
 
+	/// Number of persons in the genesis, see `BENCH_PERSONS`.
+	pub fn persons(&self) -> usize {
+		BENCH_PERSONS.min(self.accounts.len() / 4)
+	}
+
 	/// Sign transaction with keypair from this keyring.
 	pub fn sign(&self, xt: CheckedExtrinsic, version: u32, genesis_hash: [u8; 32]) -> UncheckedExtrinsic {
 		match xt.signed {
@@ -434,12 +559,57 @@ impl BenchKeyring {
 	}
 
 	/// Generate genesis with accounts from this keyring endowed with some balance.
+	///
+	/// There are `BENCH_REFERENDA` ongoing referenda, the first
+	/// `BENCH_DELEGATED` accounts (at most half of the keyring) have each been
+	/// delegated to by one of the last, and there are `BENCH_PERSONS` persons
+	/// judged by Alice.
 	pub fn generate_genesis(&self) -> node_runtime::GenesisConfig {
-		crate::genesis::config_endowed(
+		let accounts = self.collect_account_ids();
+		let delegated = BENCH_DELEGATED.min(accounts.len() / 2);
+		let persons: Vec<_> = accounts.chunks_exact(2).take(self.persons()).collect();
+		let democracy = DemocracyConfig {
+			referenda: (0..BENCH_REFERENDA)
+				.map(|index| (
+					blake2_256(&index.encode()).into(),
+					VoteThreshold::SimpleMajority,
+					if index % 2 == 0 { VoteWeight::Quadratic } else { VoteWeight::Standard },
+					0,
+				))
+				.collect(),
+			delegations: accounts.iter()
+				.take(delegated)
+				.zip(accounts.iter().rev())
+				.map(|(target, who)| (who.clone(), target.clone(), Conviction::Locked1x, 10 * DOLLARS))
+				.collect(),
+			linked_sub_accounts: persons.iter()
+				.map(|pair| (pair[1].clone(), pair[0].clone()))
+				.collect(),
+			.. Default::default()
+		};
+		let identity = IdentityConfig {
+			registrars: vec![crate::keyring::alice()],
+			identities: persons.iter()
+				.enumerate()
+				.map(|(n, pair)| (
+					pair[0].clone(),
+					format!("bench-person-{}", n).into_bytes(),
+					vec![pair[1].clone()],
+				))
+				.collect(),
+			judgements: persons.iter()
+				.map(|pair| (0, pair[0].clone(), Judgement::KnownGood))
+				.collect(),
+		};
+
+		let mut genesis = crate::genesis::config_endowed(
 			false,
 			Some(node_runtime::WASM_BINARY),
-			self.collect_account_ids(),
-		)
+			accounts,
+		);
+		genesis.pallet_quadratic_democracy = Some(democracy);
+		genesis.pallet_identity = Some(identity);
+		genesis
 	}
 }
 
@@ -473,6 +643,29 @@ impl Profile {
 	}
 }
 
+/// Database backend the benchmarking client is running on.
+#[derive(Clone, Copy, Debug)]
+pub enum DatabaseType {
+	/// RocksDb backend.
+	RocksDb,
+	/// ParityDb backend.
+	ParityDb,
+}
+
+impl DatabaseType {
+	fn into_settings(self, path: std::path::PathBuf) -> sc_client_db::DatabaseSettingsSrc {
+		match self {
+			Self::RocksDb => sc_client_db::DatabaseSettingsSrc::RocksDb {
+				path,
+				cache_size: 512,
+			},
+			Self::ParityDb => sc_client_db::DatabaseSettingsSrc::ParityDb {
+				path,
+			},
+		}
+	}
+}
+
 struct Guard(tempfile::TempDir);
 
 impl Guard {
@@ -104,13 +104,14 @@ pub fn config_endowed(
 		}),
 		pallet_im_online: Some(Default::default()),
 		pallet_authority_discovery: Some(Default::default()),
-		pallet_democracy: Some(Default::default()),
+		pallet_quadratic_democracy: Some(Default::default()),
 		pallet_collective_Instance1: Some(Default::default()),
 		pallet_collective_Instance2: Some(Default::default()),
 		pallet_membership_Instance1: Some(Default::default()),
 		pallet_elections_phragmen: Some(Default::default()),
 		pallet_sudo: Some(Default::default()),
 		pallet_treasury: Some(Default::default()),
+		pallet_identity: Some(Default::default()),
 		pallet_society: Some(SocietyConfig {
 			members: vec![alice(), bob()],
 			pot: 0,
@@ -17,6 +17,9 @@
 //! Simple transaction factory which distributes tokens from a master
 //! account to a specified number of newly created accounts.
 //!
+//! Given a [`Scenario`](struct.Scenario.html), it instead funds the
+//! accounts of the scenario and then fills blocks with its mix of calls.
+//!
 //! The factory currently only works on an empty database!
 
 use std::collections::HashMap;
@@ -37,10 +40,14 @@ use sp_consensus::block_import::BlockImport;
 use codec::{Decode, Encode};
 use sp_runtime::generic::BlockId;
 use sp_runtime::traits::{
-	Block as BlockT, Header as HeaderT, AtLeast32Bit, One, Zero,
+	Block as BlockT, Header as HeaderT, AtLeast32Bit, One, Zero, UniqueSaturatedFrom,
 };
 use sp_blockchain::HeaderBackend;
 
+mod scenario;
+
+pub use scenario::{Scenario, ScenarioCall, Senders};
+
 pub trait RuntimeAdapter {
 	type AccountId: Display;
 	type Balance: Display + AtLeast32Bit + From<Self::Number>;
@@ -69,6 +76,26 @@ pub trait RuntimeAdapter {
 		prior_block_hash: &<Self::Block as BlockT>::Hash,
 	) -> <Self::Block as BlockT>::Extrinsic;
 
+	/// Creates a signed extrinsic making `call` of a scenario from `sender`,
+	/// towards `target` if the call needs another account.
+	///
+	/// `super_account` is the account `sender` is a sub-account of, if any,
+	/// and `sub_accounts` its own sub-accounts. `seed` is unique to the
+	/// transaction, to vary the arguments not given.
+	fn scenario_extrinsic(
+		&mut self,
+		call: &ScenarioCall,
+		sender: &Self::AccountId,
+		key: &Self::Secret,
+		target: &Self::AccountId,
+		super_account: Option<&Self::AccountId>,
+		sub_accounts: &[Self::AccountId],
+		seed: u32,
+		version: u32,
+		genesis_hash: &<Self::Block as BlockT>::Hash,
+		prior_block_hash: &<Self::Block as BlockT>::Hash,
+	) -> Result<<Self::Block as BlockT>::Extrinsic, String>;
+
 	fn inherent_extrinsics(&self) -> InherentData;
 
 	fn minimum_balance() -> Self::Balance;
@@ -80,8 +107,14 @@ pub trait RuntimeAdapter {
 }
 
 /// Manufactures transactions. The exact amount depends on `num` and `rounds`.
+///
+/// Without a `scenario`, every transaction transfers the minimum balance from
+/// the master account to a new account. With one, the blocks funding its
+/// accounts and making its setup calls are followed by `blocks` blocks of its
+/// calls.
 pub fn factory<Backend, Block, Client, Sc, RA>(
 	mut factory_state: RA,
+	scenario: Option<&Scenario>,
 	client: &Arc<Client>,
 	select_chain: &Sc,
 ) -> sc_cli::Result<()>
@@ -99,28 +132,69 @@ pub fn factory<Backend, Block, Client, Sc, RA>(
 	let best_header: Result<<Block as BlockT>::Header, sc_cli::Error> =
 		select_chain.best_chain().map_err(|e| format!("{:?}", e).into());
 	let mut best_hash = best_header?.hash();
-	let mut best_block_id = BlockId::<Block>::hash(best_hash);
-	let version = client.runtime_version_at(&best_block_id)?.spec_version;
+	let version = client.runtime_version_at(&BlockId::<Block>::hash(best_hash))?.spec_version;
 	let genesis_hash = client.hash(Zero::zero())?
 		.expect("Genesis block always exists; qed").into();
 
-	while factory_state.block_number() < factory_state.blocks() {
-		let from = (RA::master_account_id(), RA::master_account_secret());
-		let amount = RA::minimum_balance();
-
-		let inherents = RA::inherent_extrinsics(&factory_state);
-		let inherents = client.runtime_api().inherent_extrinsics(&best_block_id, inherents)
-			.expect("Failed to create inherent extrinsics");
-
-		let tx_per_block = factory_state.transactions();
+	let scenario = match scenario {
+		Some(scenario) => scenario,
+		None => {
+			while factory_state.block_number() < factory_state.blocks() {
+				let from = (RA::master_account_id(), RA::master_account_secret());
+				let amount = RA::minimum_balance();
+
+				let tx_per_block = factory_state.transactions();
+				let mut transfers = Vec::new();
+
+				for tx_num in 0..tx_per_block {
+					let seed = tx_num * (factory_state.block_number() + 1);
+					let to = RA::gen_random_account_id(seed);
+
+					transfers.push(factory_state.transfer_extrinsic(
+						&from.0,
+						&from.1,
+						&to,
+						&amount,
+						version,
+						&genesis_hash,
+						&best_hash,
+					));
+
+					info!("Pushing transfer {}/{} to {} into block.", tx_num + 1, tx_per_block, to);
+				}
+
+				best_hash = import_block(&mut factory_state, client, best_hash, transfers);
+			}
+
+			return Ok(())
+		},
+	};
+
+	// The master account funds every account, which then gives each of its
+	// sub-accounts an equal share.
+	let share = scenario.endowment / (scenario.sub_accounts as u64 + 1);
+	let mut funding = Vec::new();
+	for index in 0..scenario.accounts {
+		funding.push((None, scenario.account_seed(index), scenario.endowment));
+		for sub in 0..scenario.sub_accounts {
+			funding.push((Some(scenario.account_seed(index)), scenario.sub_account_seed(index, sub), share));
+		}
+	}
 
-		let mut block = client.new_block(Default::default()).expect("Failed to create new block");
+	let tx_per_block = factory_state.transactions().max(1) as usize;
+	for chunk in funding.chunks(tx_per_block) {
+		let mut transfers = Vec::new();
+		for (from, to, amount) in chunk {
+			let from = match from {
+				Some(seed) => (RA::gen_random_account_id(*seed), RA::gen_random_account_secret(*seed)),
+				None => (RA::master_account_id(), RA::master_account_secret()),
+			};
+			let to = RA::gen_random_account_id(*to);
+			let amount = RA::Balance::unique_saturated_from(*amount);
 
-		for tx_num in 0..tx_per_block {
-			let seed = tx_num * (factory_state.block_number() + 1);
-			let to = RA::gen_random_account_id(seed);
+			info!("Pushing funding of {} with {} from {} into block.", to, amount, from.0);
 
-			let transfer = factory_state.transfer_extrinsic(
+			transfers.push(factory_state.transfer_extrinsic(
 				&from.0,
 				&from.1,
 				&to,
@@ -128,40 +202,160 @@ pub fn factory<Backend, Block, Client, Sc, RA>(
 				version,
 				&genesis_hash,
 				&best_hash,
-			);
+			));
+		}
 
-			info!("Pushing transfer {}/{} to {} into block.", tx_num + 1, tx_per_block, to);
+		best_hash = import_block(&mut factory_state, client, best_hash, transfers);
+	}
 
-			block.push(
-				Decode::decode(&mut &transfer.encode()[..])
-					.expect("Failed to decode transfer extrinsic")
-			).expect("Failed to push transfer extrinsic into block");
+	// Extrinsics are applied in order, so setup calls may rely on the ones
+	// before them in the same block.
+	let mut tx_count = 0;
+	for chunk in scenario.setup().chunks(tx_per_block) {
+		let mut extrinsics = Vec::new();
+		for (call, sender, target) in chunk {
+			extrinsics.push(scenario_extrinsic(
+				&mut factory_state,
+				scenario,
+				call,
+				*sender,
+				*target,
+				tx_count,
+				version,
+				&genesis_hash,
+				&best_hash,
+			)?);
+			tx_count += 1;
 		}
 
-		for inherent in inherents {
-			block.push(inherent).expect("Failed ...");
+		best_hash = import_block(&mut factory_state, client, best_hash, extrinsics);
+	}
+
+	let mut call_count = 0;
+	for _ in 0..factory_state.blocks() {
+		let tx_per_block = factory_state.transactions();
+		let mut extrinsics = Vec::new();
+
+		for _ in 0..tx_per_block {
+			let (call, sender, target) = scenario.nth(call_count);
+			extrinsics.push(scenario_extrinsic(
+				&mut factory_state,
+				scenario,
+				call,
+				sender,
+				target,
+				tx_count,
+				version,
+				&genesis_hash,
+				&best_hash,
+			)?);
+			call_count += 1;
+			tx_count += 1;
 		}
 
-		let block = block.build().expect("Failed to bake block").block;
+		best_hash = import_block(&mut factory_state, client, best_hash, extrinsics);
+	}
 
-		factory_state.set_block_number(factory_state.block_number() + 1);
+	Ok(())
+}
 
-		info!(
-			"Created block {} with hash {}.",
-			factory_state.block_number(),
-			best_hash,
-		);
+/// Creates the extrinsic making `call` of `scenario` from the account with
+/// seed `sender`, or the master account, towards the account with seed
+/// `target`.
+fn scenario_extrinsic<RA: RuntimeAdapter>(
+	factory_state: &mut RA,
+	scenario: &Scenario,
+	call: &ScenarioCall,
+	sender: Option<u32>,
+	target: u32,
+	seed: u32,
+	version: u32,
+	genesis_hash: &<RA::Block as BlockT>::Hash,
+	prior_block_hash: &<RA::Block as BlockT>::Hash,
+) -> Result<<RA::Block as BlockT>::Extrinsic, String> {
+	let (from, key) = match sender {
+		Some(seed) => (RA::gen_random_account_id(seed), RA::gen_random_account_secret(seed)),
+		None => (RA::master_account_id(), RA::master_account_secret()),
+	};
+	let super_account = sender
+		.and_then(|seed| scenario.super_account_seed(seed))
+		.map(RA::gen_random_account_id);
+	let sub_accounts: Vec<_> = sender
+		.map_or_else(Vec::new, |seed| scenario.sub_account_seeds(seed))
+		.into_iter()
+		.map(RA::gen_random_account_id)
+		.collect();
+
+	info!("Pushing {}::{} from {} into block.", call.pallet, call.call, from);
+
+	factory_state.scenario_extrinsic(
+		call,
+		&from,
+		&key,
+		&RA::gen_random_account_id(target),
+		super_account.as_ref(),
+		&sub_accounts,
+		seed,
+		version,
+		genesis_hash,
+		prior_block_hash,
+	)
+}
 
-		best_hash = block.header().hash();
-		best_block_id = BlockId::<Block>::hash(best_hash);
+/// Bakes a block on top of `best_hash` out of `extrinsics` and imports it,
+/// returning its hash.
+fn import_block<Backend, Block, Client, RA>(
+	factory_state: &mut RA,
+	client: &Arc<Client>,
+	best_hash: Block::Hash,
+	extrinsics: Vec<Block::Extrinsic>,
+) -> Block::Hash
+	where
+		Backend: sc_client_api::backend::Backend<Block> + Send,
+		Block: BlockT,
+		Client: BlockBuilderProvider<Backend, Block, Client> + CallApiAt<Block, Error = sp_blockchain::Error>
+			+ ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+		Client::Api: BlockBuilder<Block, Error = sp_blockchain::Error> + ApiExt<Block, StateBackend = Backend::State>,
+		RA: RuntimeAdapter<Block = Block>,
+		for<'a> &'a Client: BlockImport<Block, Transaction = TransactionFor<Client, Block>>,
+{
+	let best_block_id = BlockId::<Block>::hash(best_hash);
+
+	let inherents = RA::inherent_extrinsics(factory_state);
+	let inherents = client.runtime_api().inherent_extrinsics(&best_block_id, inherents)
+		.expect("Failed to create inherent extrinsics");
 
-		let mut import = BlockImportParams::new(BlockOrigin::File, block.header().clone());
-		import.body = Some(block.extrinsics().to_vec());
-		import.fork_choice = Some(ForkChoiceStrategy::LongestChain);
-		(&**client).import_block(import, HashMap::new()).expect("Failed to import block");
+	let mut block = client.new_block(Default::default()).expect("Failed to create new block");
 
-		info!("Imported block at {}", factory_state.block_number());
+	for extrinsic in extrinsics {
+		block.push(
+			Decode::decode(&mut &extrinsic.encode()[..])
+				.expect("Failed to decode extrinsic")
+		).expect("Failed to push extrinsic into block");
 	}
 
-	Ok(())
+	for inherent in inherents {
+		block.push(inherent).expect("Failed ...");
+	}
+
+	let block = block.build().expect("Failed to bake block").block;
+
+	factory_state.set_block_number(factory_state.block_number() + 1);
+
+	info!(
+		"Created block {} with hash {}.",
+		factory_state.block_number(),
+		best_hash,
+	);
+
+	let best_hash = block.header().hash();
+
+	let mut import = BlockImportParams::new(BlockOrigin::File, block.header().clone());
+	import.body = Some(block.extrinsics().to_vec());
+	import.fork_choice = Some(ForkChoiceStrategy::LongestChain);
+	(&**client).import_block(import, HashMap::new()).expect("Failed to import block");
+
+	info!("Imported block at {}", factory_state.block_number());
+
+	best_hash
 }
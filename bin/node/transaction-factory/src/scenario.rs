@@ -0,0 +1,421 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Scenarios describing a mix of calls for the factory to manufacture.
+//!
+//! A scenario is read from a TOML file such as:
+//!
+//! ```toml
+//! # Accounts funded by the master account.
+//! accounts = 100
+//! # Sub-accounts each of them splits its endowment with.
+//! sub_accounts = 4
+//! endowment = 1000000000000000
+//!
+//! # Made in order before the mix of calls: every account becomes a judged
+//! # identity whose sub-accounts link their capital to it.
+//! [[setup]]
+//! pallet = "identity"
+//! call = "set_identity"
+//!
+//! [[setup]]
+//! pallet = "identity"
+//! call = "set_subs"
+//!
+//! [[setup]]
+//! pallet = "identity"
+//! call = "add_registrar"
+//! from = "master"
+//!
+//! [[setup]]
+//! pallet = "identity"
+//! call = "provide_judgement"
+//! from = "master"
+//! share = 100
+//!
+//! [[setup]]
+//! pallet = "democracy"
+//! call = "link_sub_account"
+//! from = "sub_accounts"
+//!
+//! [[calls]]
+//! pallet = "democracy"
+//! call = "vote"
+//! share = 3
+//! args = { referendum = 0, balance = 10000000000000, conviction = 1 }
+//!
+//! [[calls]]
+//! pallet = "democracy"
+//! call = "vote"
+//! args = { referendum = 1, aye_balance = 10000000000000, nay_balance = 5000000000000 }
+//!
+//! # A call the runtime adapter doesn't know, given as the hex-encoded
+//! # runtime call.
+//! [[calls]]
+//! pallet = "system"
+//! call = "remark"
+//! encoded = "0x000100"
+//! ```
+//!
+//! The setup calls are made in order, each once by every account it is made
+//! `from`, or `share` times towards each account in turn if made from the
+//! master account. The calls are then made in turn, each `share` times in a
+//! row, by the accounts picked in turn from the ones they are made `from`.
+//! What the pallet, call and arguments stand for is up to the
+//! `RuntimeAdapter`, except for calls given `encoded`, the hex-encoded runtime
+//! call, which are made as they are.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, de::DeserializeOwned};
+
+/// A mix of calls made by a set of funded accounts.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+	/// Number of accounts funded by the master account.
+	pub accounts: u32,
+	/// Number of sub-accounts of each account, which receive an equal share of
+	/// its endowment.
+	#[serde(default)]
+	pub sub_accounts: u32,
+	/// Balance each account is funded with.
+	pub endowment: u64,
+	/// The calls made in order after funding the accounts.
+	#[serde(default)]
+	pub setup: Vec<ScenarioCall>,
+	/// The calls to make.
+	pub calls: Vec<ScenarioCall>,
+}
+
+/// A call made by the accounts of a scenario.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioCall {
+	/// Name of the pallet of the call.
+	pub pallet: String,
+	/// Name of the call.
+	pub call: String,
+	/// The accounts making the call.
+	#[serde(default)]
+	pub from: Senders,
+	/// Number of transactions making the call each time its turn comes. Setup
+	/// calls are only made `share` times when made from the master account.
+	#[serde(default = "one")]
+	pub share: u32,
+	/// Arguments of the call, by name.
+	#[serde(default)]
+	pub args: BTreeMap<String, toml::Value>,
+	/// The hex-encoded runtime call to make instead of building it from the
+	/// pallet, call and arguments.
+	#[serde(default)]
+	pub encoded: Option<String>,
+}
+
+/// The accounts of a scenario that make a call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Senders {
+	/// The accounts funded by the master account.
+	Accounts,
+	/// The sub-accounts of the accounts funded by the master account.
+	SubAccounts,
+	/// The master account.
+	Master,
+}
+
+impl Default for Senders {
+	fn default() -> Self {
+		Senders::Accounts
+	}
+}
+
+fn one() -> u32 {
+	1
+}
+
+impl Scenario {
+	/// Read a scenario from the TOML file at `path`.
+	pub fn from_file(path: &Path) -> Result<Self, String> {
+		let content = std::fs::read_to_string(path)
+			.map_err(|e| format!("Failed to read scenario {}: {}", path.display(), e))?;
+		Self::from_toml(&content)
+	}
+
+	/// Parse a scenario from TOML.
+	pub fn from_toml(content: &str) -> Result<Self, String> {
+		let scenario: Self = toml::from_str(content)
+			.map_err(|e| format!("Invalid scenario: {}", e))?;
+
+		if scenario.accounts == 0 {
+			return Err("Invalid scenario: it needs at least one account".into());
+		}
+		if scenario.calls.iter().all(|call| call.share == 0) {
+			return Err("Invalid scenario: it needs at least one call".into());
+		}
+		if scenario.sub_accounts == 0 &&
+			scenario.setup.iter().chain(scenario.calls.iter()).any(|call| call.from == Senders::SubAccounts)
+		{
+			return Err("Invalid scenario: calls are made from sub-accounts, but there are none".into());
+		}
+
+		Ok(scenario)
+	}
+
+	/// Seed of the `index`th account.
+	pub fn account_seed(&self, index: u32) -> u32 {
+		index
+	}
+
+	/// Seed of the `sub`th sub-account of the `index`th account.
+	pub fn sub_account_seed(&self, index: u32, sub: u32) -> u32 {
+		self.accounts + index * self.sub_accounts + sub
+	}
+
+	/// Seeds of the sub-accounts of the account with seed `seed`, if it is one
+	/// of the accounts funded by the master account.
+	pub fn sub_account_seeds(&self, seed: u32) -> Vec<u32> {
+		if seed < self.accounts {
+			(0..self.sub_accounts).map(|sub| self.sub_account_seed(seed, sub)).collect()
+		} else {
+			Vec::new()
+		}
+	}
+
+	/// Seed of the account that the account with seed `seed` is a sub-account
+	/// of, if any.
+	pub fn super_account_seed(&self, seed: u32) -> Option<u32> {
+		if seed >= self.accounts && seed < self.accounts * (self.sub_accounts + 1) {
+			Some(self.account_seed((seed - self.accounts) / self.sub_accounts))
+		} else {
+			None
+		}
+	}
+
+	/// The setup calls, each along with the seed of its sender, `None` for the
+	/// master account, and of the account it is made towards, in the order
+	/// they are made.
+	///
+	/// Each call is made once by each of its senders, but `share` times by the
+	/// master account.
+	pub fn setup(&self) -> Vec<(&ScenarioCall, Option<u32>, u32)> {
+		self.setup.iter()
+			.flat_map(|call| {
+				let turns = match call.from {
+					Senders::Accounts => self.accounts,
+					Senders::SubAccounts => self.accounts * self.sub_accounts,
+					Senders::Master => call.share,
+				};
+				(0..turns).map(move |turn| {
+					let (sender, target) = self.turn(call, turn);
+					(call, sender, target)
+				})
+			})
+			.collect()
+	}
+
+	/// The call made by the `n`th transaction, along with the seed of its
+	/// sender, `None` for the master account, and of the account it is made
+	/// towards, if any.
+	pub fn nth(&self, n: u32) -> (&ScenarioCall, Option<u32>, u32) {
+		let round: u32 = self.calls.iter().map(|call| call.share).sum();
+		let mut position = n % round;
+		let call = self.calls.iter()
+			.find(|call| if position < call.share {
+				true
+			} else {
+				position -= call.share;
+				false
+			})
+			.expect("position is less than the sum of the shares; qed");
+
+		let (sender, target) = self.turn(call, n / round * call.share + position);
+		(call, sender, target)
+	}
+
+	/// The seeds of the sender of `call` the `turn`th time it is made, and of
+	/// the account it is made towards.
+	///
+	/// The master account makes calls towards each account in turn, and the
+	/// others towards the account following their own account.
+	fn turn(&self, call: &ScenarioCall, turn: u32) -> (Option<u32>, u32) {
+		let (sender, account) = match call.from {
+			Senders::Accounts => {
+				let account = turn % self.accounts;
+				(self.account_seed(account), account)
+			},
+			Senders::SubAccounts => {
+				let sub = turn % (self.accounts * self.sub_accounts);
+				let account = sub / self.sub_accounts;
+				(self.sub_account_seed(account, sub % self.sub_accounts), account)
+			},
+			Senders::Master => return (None, self.account_seed(turn % self.accounts)),
+		};
+		(Some(sender), self.account_seed((account + 1) % self.accounts))
+	}
+}
+
+impl ScenarioCall {
+	/// The argument `name` of the call, if it is given.
+	pub fn arg<T: DeserializeOwned>(&self, name: &str) -> Result<Option<T>, String> {
+		self.args.get(name)
+			.map(|value| value.clone().try_into().map_err(|e| format!(
+				"Invalid argument {} of {}::{}: {}",
+				name,
+				self.pallet,
+				self.call,
+				e,
+			)))
+			.transpose()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const SCENARIO: &str = r#"
+		accounts = 2
+		sub_accounts = 3
+		endowment = 1000
+
+		[[setup]]
+		pallet = "identity"
+		call = "set_subs"
+
+		[[setup]]
+		pallet = "identity"
+		call = "add_registrar"
+		from = "master"
+
+		[[setup]]
+		pallet = "identity"
+		call = "provide_judgement"
+		from = "master"
+		share = 2
+
+		[[setup]]
+		pallet = "democracy"
+		call = "link_sub_account"
+		from = "sub_accounts"
+
+		[[calls]]
+		pallet = "democracy"
+		call = "vote"
+		from = "sub_accounts"
+		share = 2
+		args = { referendum = 1, balance = 10 }
+
+		[[calls]]
+		pallet = "democracy"
+		call = "undelegate"
+
+		[[calls]]
+		pallet = "democracy"
+		call = "cancel_referendum"
+		from = "master"
+		share = 0
+		encoded = "0x0e0b00000000"
+	"#;
+
+	#[test]
+	fn scenario_parses() {
+		let scenario = Scenario::from_toml(SCENARIO).unwrap();
+		assert_eq!(scenario.accounts, 2);
+		assert_eq!(scenario.sub_accounts, 3);
+		assert_eq!(scenario.calls[0].from, Senders::SubAccounts);
+		assert_eq!(scenario.calls[0].arg::<u32>("referendum"), Ok(Some(1)));
+		assert_eq!(scenario.calls[0].arg::<u32>("conviction"), Ok(None));
+		assert!(scenario.calls[0].arg::<bool>("balance").is_err());
+		assert_eq!(scenario.calls[1].from, Senders::Accounts);
+		assert_eq!(scenario.calls[1].share, 1);
+		assert_eq!(scenario.calls[1].encoded, None);
+		assert_eq!(scenario.calls[2].from, Senders::Master);
+		assert_eq!(scenario.calls[2].encoded.as_deref(), Some("0x0e0b00000000"));
+	}
+
+	#[test]
+	fn calls_and_senders_take_turns() {
+		let scenario = Scenario::from_toml(SCENARIO).unwrap();
+		let nth = |n| {
+			let (call, sender, target) = scenario.nth(n);
+			(call.call.as_str(), sender, target)
+		};
+		// Sub-accounts have seeds 2..8, three for each account.
+		assert_eq!(nth(0), ("vote", Some(2), 1));
+		assert_eq!(nth(1), ("vote", Some(3), 1));
+		assert_eq!(nth(2), ("undelegate", Some(0), 1));
+		assert_eq!(nth(3), ("vote", Some(4), 1));
+		assert_eq!(nth(4), ("vote", Some(5), 0));
+		assert_eq!(nth(5), ("undelegate", Some(1), 0));
+		assert_eq!(nth(9), ("vote", Some(2), 1));
+	}
+
+	#[test]
+	fn setup_calls_are_made_by_each_sender_in_order() {
+		let scenario = Scenario::from_toml(SCENARIO).unwrap();
+		let setup: Vec<_> = scenario.setup().into_iter()
+			.map(|(call, sender, target)| (call.call.as_str(), sender, target))
+			.collect();
+		assert_eq!(setup, vec![
+			("set_subs", Some(0), 1),
+			("set_subs", Some(1), 0),
+			("add_registrar", None, 0),
+			("provide_judgement", None, 0),
+			("provide_judgement", None, 1),
+			("link_sub_account", Some(2), 1),
+			("link_sub_account", Some(3), 1),
+			("link_sub_account", Some(4), 1),
+			("link_sub_account", Some(5), 0),
+			("link_sub_account", Some(6), 0),
+			("link_sub_account", Some(7), 0),
+		]);
+	}
+
+	#[test]
+	fn accounts_know_their_sub_accounts() {
+		let scenario = Scenario::from_toml(SCENARIO).unwrap();
+		assert_eq!(scenario.sub_account_seeds(0), vec![2, 3, 4]);
+		assert_eq!(scenario.sub_account_seeds(1), vec![5, 6, 7]);
+		assert_eq!(scenario.sub_account_seeds(2), Vec::<u32>::new());
+		assert_eq!(scenario.super_account_seed(0), None);
+		assert_eq!(scenario.super_account_seed(4), Some(0));
+		assert_eq!(scenario.super_account_seed(5), Some(1));
+		assert_eq!(scenario.super_account_seed(8), None);
+	}
+
+	#[test]
+	fn invalid_scenarios_are_rejected() {
+		assert!(Scenario::from_toml("accounts = 1\nendowment = 1\ncalls = []").is_err());
+		assert!(Scenario::from_toml(r#"
+			accounts = 1
+			endowment = 1
+			[[calls]]
+			pallet = "democracy"
+			call = "vote"
+			from = "sub_accounts"
+		"#).is_err());
+		assert!(Scenario::from_toml(r#"
+			accounts = 1
+			endowment = 1
+			blocks = 1
+			[[calls]]
+			pallet = "system"
+			call = "remark"
+		"#).is_err());
+	}
+}
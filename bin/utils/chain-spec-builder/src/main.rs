@@ -44,6 +44,11 @@ enum ChainSpecBuilder {
 		/// The path where the chain spec should be saved.
 		#[structopt(long, short, default_value = "./chain_spec.json")]
 		chain_spec_path: PathBuf,
+		/// Start the chain with governance under way: the first endowed account
+		/// proposes a remark, referenda on it are open and the other endowed
+		/// accounts delegate to the first.
+		#[structopt(long)]
+		governance: bool,
 	},
 	/// Create a new chain spec with the given number of authorities and endowed
 	/// accounts. Random keys will be generated as required.
@@ -64,6 +69,11 @@ enum ChainSpecBuilder {
 		/// `auth-0`, `auth-1`, etc.
 		#[structopt(long, short)]
 		keystore_path: Option<PathBuf>,
+		/// Start the chain with governance under way: the first endowed account
+		/// proposes a remark, referenda on it are open and the other endowed
+		/// accounts delegate to the first.
+		#[structopt(long)]
+		governance: bool,
 	},
 }
 
@@ -77,12 +87,21 @@ impl ChainSpecBuilder {
 				chain_spec_path.as_path(),
 		}
 	}
+
+	/// Returns whether the chain should start with governance under way.
+	fn governance(&self) -> bool {
+		match self {
+			ChainSpecBuilder::New { governance, .. } => *governance,
+			ChainSpecBuilder::Generate { governance, .. } => *governance,
+		}
+	}
 }
 
 fn genesis_constructor(
 	authority_seeds: &[String],
 	endowed_accounts: &[AccountId],
 	sudo_account: &AccountId,
+	governance: bool,
 ) -> chain_spec::GenesisConfig {
 	let authorities = authority_seeds
 		.iter()
@@ -92,18 +111,23 @@ fn genesis_constructor(
 
 	let enable_println = true;
 
-	chain_spec::testnet_genesis(
+	let mut genesis = chain_spec::testnet_genesis(
 		authorities,
 		sudo_account.clone(),
 		Some(endowed_accounts.to_vec()),
 		enable_println,
-	)
+	);
+	if governance {
+		genesis.pallet_quadratic_democracy = Some(chain_spec::governance_genesis(endowed_accounts));
+	}
+	genesis
 }
 
 fn generate_chain_spec(
 	authority_seeds: Vec<String>,
 	endowed_accounts: Vec<String>,
 	sudo_account: String,
+	governance: bool,
 ) -> Result<String, String> {
 	let parse_account = |address: &String| {
 		AccountId::from_string(address)
@@ -121,7 +145,7 @@ fn generate_chain_spec(
 		"Custom",
 		"custom",
 		sc_chain_spec::ChainType::Live,
-		move || genesis_constructor(&authority_seeds, &endowed_accounts, &sudo_account),
+		move || genesis_constructor(&authority_seeds, &endowed_accounts, &sudo_account, governance),
 		vec![],
 		None,
 		None,
@@ -222,6 +246,7 @@ fn main() -> Result<(), String> {
 
 	let builder = ChainSpecBuilder::from_args();
 	let chain_spec_path = builder.chain_spec_path().to_path_buf();
+	let governance = builder.governance();
 
 	let (authority_seeds, endowed_accounts, sudo_account) = match builder {
 		ChainSpecBuilder::Generate { authorities, endowed, keystore_path, .. } => {
@@ -268,6 +293,7 @@ fn main() -> Result<(), String> {
 		authority_seeds,
 		endowed_accounts,
 		sudo_account,
+		governance,
 	)?;
 
 	fs::write(chain_spec_path, json).map_err(|err| err.to_string())
@@ -0,0 +1,122 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Arguments of the democracy transactions, and the votes the chain credits them.
+
+use std::convert::TryFrom;
+use clap::ArgMatches;
+use frame_support::traits::Get;
+use node_primitives::Balance;
+use node_runtime::{DelegationWeighting, QuadraticConvictionSchedule};
+use pallet_quadratic_democracy::{Calculate, Conviction, ConvictionLevel, Vote, VoteWeight};
+use super::Error;
+
+/// Read the optional `conviction`, either its level from 0 to 6 or its name. It must have a
+/// level in the runtime's conviction schedule.
+pub fn read_conviction(matches: &ArgMatches) -> Result<Conviction, Error> {
+	let conviction = match matches.value_of("conviction") {
+		Some(conviction) => conviction,
+		None => return Ok(Conviction::None),
+	};
+	let level = match conviction.to_lowercase().as_str() {
+		"none" => 0,
+		"locked1x" => 1,
+		"locked2x" => 2,
+		"locked3x" => 3,
+		"locked4x" => 4,
+		"locked5x" => 5,
+		"locked6x" => 6,
+		level => level.parse().map_err(|_| invalid_conviction())?,
+	};
+	let conviction = Conviction::try_from(level).map_err(|_| invalid_conviction())?;
+	match QuadraticConvictionSchedule::get().level(conviction) {
+		Some(_) => Ok(conviction),
+		None => Err(Error::Static("The `conviction' has no level in the runtime's conviction schedule.")),
+	}
+}
+
+/// The level of a `conviction` read by `read_conviction`.
+fn level(conviction: Conviction) -> ConvictionLevel {
+	QuadraticConvictionSchedule::get().level(conviction)
+		.expect("convictions are checked against the schedule as they are read; qed")
+}
+
+fn invalid_conviction() -> Error {
+	Error::Static("Invalid `conviction' parameter; expecting 0 to 6, none or locked1x to locked6x.")
+}
+
+/// Read the required `vote`, `aye` or `nay`, with the optional `conviction`.
+pub fn read_vote(matches: &ArgMatches) -> Result<Vote, Error> {
+	let aye = match matches.value_of("vote").expect("vote is required; qed") {
+		"aye" => true,
+		"nay" => false,
+		_ => return Err(Error::Static("Invalid `vote' parameter; expecting aye or nay.")),
+	};
+	Ok(Vote { aye, conviction: read_conviction(matches)? })
+}
+
+/// Read the vote weight named by the parameter `name`, if it is given.
+pub fn read_vote_weight(matches: &ArgMatches, name: &str) -> Result<Option<VoteWeight>, Error> {
+	matches.value_of(name)
+		.map(|weight| match weight {
+			"standard" => Ok(VoteWeight::Standard),
+			"quadratic" => Ok(VoteWeight::Quadratic),
+			"credits" => Ok(VoteWeight::Credits),
+			_ => Err(Error::Formatted(format!(
+				"Invalid `{}' parameter; expecting standard, quadratic or credits.",
+				name,
+			))),
+		})
+		.transpose()
+}
+
+/// The votes credited to a direct vote of `balance` with `conviction` in a referendum
+/// weighted by `weight`.
+///
+/// Under quadratic weighting, the capital the voter links from its sub-accounts is added
+/// to `balance` by the chain, and isn't accounted for here.
+pub fn vote_votes(weight: VoteWeight, conviction: Conviction, balance: Balance) -> Balance {
+	let weighted_balance = weight.calculate_weight(balance);
+	level(conviction).votes(balance, weighted_balance).votes
+}
+
+/// The votes credited to the delegate of `balance` with `conviction`, in a referendum
+/// weighted by `weight`.
+///
+/// Under quadratic weighting with `DelegationWeighting::RootOfSum`, the credited votes
+/// depend on the other delegations to the same account, and the delegation alone is
+/// accounted for here.
+pub fn delegation_votes(weight: VoteWeight, conviction: Conviction, balance: Balance) -> Balance {
+	let delegations = level(conviction).delegation_votes(balance);
+	weight.delegation(delegations, DelegationWeighting::get()).votes
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn votes_follow_the_runtime_schedule() {
+		// The runtime schedule gives 1.5 votes per unit at `Locked2x`.
+		assert_eq!(vote_votes(VoteWeight::Standard, Conviction::Locked2x, 10_000), 15_000);
+		assert_eq!(vote_votes(VoteWeight::Quadratic, Conviction::Locked2x, 10_000), 150);
+		assert_eq!(vote_votes(VoteWeight::Quadratic, Conviction::None, 10_000), 10);
+		assert_eq!(vote_votes(VoteWeight::Credits, Conviction::Locked6x, 10_000), 0);
+
+		assert_eq!(delegation_votes(VoteWeight::Standard, Conviction::Locked1x, 10_000), 10_000);
+		assert_eq!(delegation_votes(VoteWeight::Quadratic, Conviction::Locked1x, 10_000), 100);
+	}
+}
@@ -21,11 +21,13 @@ extern crate test;
 use bip39::{Language, Mnemonic, MnemonicType};
 use clap::{App, ArgMatches, SubCommand};
 use codec::{Decode, Encode};
+use frame_support::traits::Get;
 use hex_literal::hex;
 use itertools::Itertools;
 use libp2p::identity::{ed25519 as libp2p_ed25519, PublicKey};
 use node_primitives::{Balance, Hash, Index, AccountId, Signature};
-use node_runtime::{BalancesCall, Call, Runtime, SignedPayload, UncheckedExtrinsic, VERSION};
+use node_runtime::{BalancesCall, Call, DefaultVoteWeight, Runtime, SignedPayload, UncheckedExtrinsic, VERSION};
+use pallet_quadratic_democracy::{AccountVote, PropIndex, ReferendumIndex, VoteWeight};
 use serde_json::json;
 use sp_core::{
 	crypto::{set_default_ss58_version, Ss58AddressFormat, Ss58Codec},
@@ -36,6 +38,7 @@ use std::{
 	convert::{TryInto, TryFrom}, io::{stdin, Read}, str::FromStr, path::PathBuf, fs, fmt,
 };
 
+mod democracy;
 mod rpc;
 mod vanity;
 
@@ -293,6 +296,69 @@ fn get_app<'a, 'b>(usage: &'a str) -> App<'a, 'b> {
 					<amount> 'The number of units to transfer.'
 					<index> 'The signing account's transaction index.'
 				"),
+			SubCommand::with_name("vote")
+				.about("Author and sign a Node pallet_quadratic_democracy::vote transaction with a given \
+						(secret) key, and print the votes it is credited to stderr")
+				.args_from_usage("
+					<genesis> -g, --genesis <genesis> 'The genesis hash or a recognized \
+											chain identifier (dev, elm, alex).'
+					[conviction] -c, --conviction <conviction> 'The conviction, from 0 (none, the \
+											default) to 6 (locked6x).'
+					[weight] -w, --weight <weight> 'How the referendum weighs votes, to print the \
+											credited votes. One of standard, quadratic. Default is \
+											the chain default.'
+					<from> 'The signing secret key URI.'
+					<referendum> 'The index of the referendum to vote in.'
+					<vote> 'The vote: aye or nay.'
+					<balance> 'The number of units to vote with.'
+					<index> 'The signing account's transaction index.'
+				"),
+			SubCommand::with_name("delegate")
+				.about("Author and sign a Node pallet_quadratic_democracy::delegate transaction with a \
+						given (secret) key, and print the votes it is credited to stderr")
+				.args_from_usage("
+					<genesis> -g, --genesis <genesis> 'The genesis hash or a recognized \
+											chain identifier (dev, elm, alex).'
+					[conviction] -c, --conviction <conviction> 'The conviction, from 0 (none, the \
+											default) to 6 (locked6x).'
+					<from> 'The signing secret key URI.'
+					<to> 'The public key URI of the account to delegate to.'
+					<balance> 'The number of units to delegate.'
+					<index> 'The signing account's transaction index.'
+				"),
+			SubCommand::with_name("propose")
+				.about("Author and sign a Node pallet_quadratic_democracy::propose transaction with a \
+						given (secret) key")
+				.args_from_usage("
+					<genesis> -g, --genesis <genesis> 'The genesis hash or a recognized \
+											chain identifier (dev, elm, alex).'
+					[weight] -w, --weight <weight> 'How the referendum will weigh votes. One of \
+											standard, quadratic, credits. Default is the chain default.'
+					<from> 'The signing secret key URI.'
+					<proposal-hash> 'The hash of the proposal, hex-encoded.'
+					<value> 'The number of units to deposit.'
+					<index> 'The signing account's transaction index.'
+				"),
+			SubCommand::with_name("note-preimage")
+				.about("Author and sign a Node pallet_quadratic_democracy::note_preimage transaction with \
+						a given (secret) key, and print the hash of the proposal to stderr")
+				.args_from_usage("
+					<genesis> -g, --genesis <genesis> 'The genesis hash or a recognized \
+											chain identifier (dev, elm, alex).'
+					<from> 'The signing secret key URI.'
+					<proposal> 'The proposed call, hex-encoded.'
+					<index> 'The signing account's transaction index.'
+				"),
+			SubCommand::with_name("second")
+				.about("Author and sign a Node pallet_quadratic_democracy::second transaction with a \
+						given (secret) key")
+				.args_from_usage("
+					<genesis> -g, --genesis <genesis> 'The genesis hash or a recognized \
+											chain identifier (dev, elm, alex).'
+					<from> 'The signing secret key URI.'
+					<proposal> 'The index of the proposal to second.'
+					<index> 'The signing account's transaction index.'
+				"),
 			SubCommand::with_name("vanity")
 				.about("Generate a seed that provides a vanity address")
 				.args_from_usage("
@@ -479,6 +545,101 @@ where
 
 			print_extrinsic(extrinsic);
 		}
+		("vote", Some(matches)) => {
+			let signer = read_pair::<C>(matches.value_of("from"), password)?;
+			let index = read_required_parameter::<Index>(matches, "index")?;
+			let genesis_hash = read_genesis_hash(matches)?;
+
+			let referendum = read_required_parameter::<ReferendumIndex>(matches, "referendum")?;
+			let vote = democracy::read_vote(matches)?;
+			let balance = read_required_parameter::<Balance>(matches, "balance")?;
+			let weight = democracy::read_vote_weight(matches, "weight")?
+				.unwrap_or_else(DefaultVoteWeight::get);
+			if weight == VoteWeight::Credits {
+				return static_err("Votes of referenda weighted by credits are bought with credits.");
+			}
+			eprintln!(
+				"Voting {} with {} votes in a {:?} referendum",
+				if vote.aye { "aye" } else { "nay" },
+				democracy::vote_votes(weight, vote.conviction, balance),
+				weight,
+			);
+
+			let function = Call::Democracy(pallet_quadratic_democracy::Call::vote(
+				referendum,
+				AccountVote::Standard { vote, balance },
+			));
+			let extrinsic = create_extrinsic::<C>(function, index, signer, genesis_hash);
+
+			print_extrinsic(extrinsic);
+		}
+		("delegate", Some(matches)) => {
+			let signer = read_pair::<C>(matches.value_of("from"), password)?;
+			let index = read_required_parameter::<Index>(matches, "index")?;
+			let genesis_hash = read_genesis_hash(matches)?;
+
+			let to: AccountId = read_account_id(matches.value_of("to"));
+			let conviction = democracy::read_conviction(matches)?;
+			let balance = read_required_parameter::<Balance>(matches, "balance")?;
+			eprintln!(
+				"Delegating {} votes in Standard referenda and {} votes in Quadratic referenda",
+				democracy::delegation_votes(VoteWeight::Standard, conviction, balance),
+				democracy::delegation_votes(VoteWeight::Quadratic, conviction, balance),
+			);
+
+			let function = Call::Democracy(pallet_quadratic_democracy::Call::delegate(to, conviction, balance));
+			let extrinsic = create_extrinsic::<C>(function, index, signer, genesis_hash);
+
+			print_extrinsic(extrinsic);
+		}
+		("propose", Some(matches)) => {
+			let signer = read_pair::<C>(matches.value_of("from"), password)?;
+			let index = read_required_parameter::<Index>(matches, "index")?;
+			let genesis_hash = read_genesis_hash(matches)?;
+
+			let proposal_hash = matches.value_of("proposal-hash").expect("proposal-hash is required; qed");
+			let proposal_hash: Hash = Decode::decode(&mut &decode_hex(proposal_hash.trim_start_matches("0x"))?[..])
+				.map_err(|_| Error::Static("Invalid `proposal-hash' parameter; expecting 32 bytes."))?;
+			let value = read_required_parameter::<Balance>(matches, "value")?;
+			let weight = democracy::read_vote_weight(matches, "weight")?;
+
+			let function = Call::Democracy(pallet_quadratic_democracy::Call::propose(proposal_hash, value, weight));
+			let extrinsic = create_extrinsic::<C>(function, index, signer, genesis_hash);
+
+			print_extrinsic(extrinsic);
+		}
+		("note-preimage", Some(matches)) => {
+			let signer = read_pair::<C>(matches.value_of("from"), password)?;
+			let index = read_required_parameter::<Index>(matches, "index")?;
+			let genesis_hash = read_genesis_hash(matches)?;
+
+			let proposal = matches.value_of("proposal").expect("proposal is required; qed");
+			let proposal = decode_hex(proposal.trim_start_matches("0x"))?;
+			if Call::decode(&mut &proposal[..]).is_err() {
+				return static_err("Invalid `proposal' parameter; expecting an encoded call.");
+			}
+			eprintln!(
+				"Noting the preimage of proposal hash 0x{}",
+				HexDisplay::from(&sp_core::blake2_256(&proposal)),
+			);
+
+			let function = Call::Democracy(pallet_quadratic_democracy::Call::note_preimage(proposal));
+			let extrinsic = create_extrinsic::<C>(function, index, signer, genesis_hash);
+
+			print_extrinsic(extrinsic);
+		}
+		("second", Some(matches)) => {
+			let signer = read_pair::<C>(matches.value_of("from"), password)?;
+			let index = read_required_parameter::<Index>(matches, "index")?;
+			let genesis_hash = read_genesis_hash(matches)?;
+
+			let proposal = read_required_parameter::<PropIndex>(matches, "proposal")?;
+
+			let function = Call::Democracy(pallet_quadratic_democracy::Call::second(proposal));
+			let extrinsic = create_extrinsic::<C>(function, index, signer, genesis_hash);
+
+			print_extrinsic(extrinsic);
+		}
 		("sign-transaction", Some(matches)) => {
 			let signer = read_pair::<C>(matches.value_of("suri"), password)?;
 			let index = read_required_parameter::<Index>(matches, "nonce")?;
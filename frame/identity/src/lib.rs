@@ -68,6 +68,8 @@
 use sp_std::prelude::*;
 use sp_std::{fmt::Debug, ops::Add, iter::once};
 use enumflags2::BitFlags;
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
 use codec::{Encode, Decode};
 use sp_runtime::{DispatchError, RuntimeDebug};
 use sp_runtime::traits::{StaticLookup, Zero, AppendZerosInput};
@@ -199,6 +201,7 @@ pub type RegistrarIndex = u32;
 /// NOTE: Registrars may pay little attention to some fields. Registrars may want to make clear
 /// which fields their attestation is relevant for by off-chain means.
 #[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Judgement<
 	Balance: Encode + Decode + Copy + Clone + Debug + Eq + PartialEq
 > {
@@ -357,6 +360,11 @@ impl <
 			.map(|(_, ref j)| if let Judgement::FeePaid(fee) = j { *fee } else { Zero::zero() })
 			.fold(Zero::zero(), |a, i| a + i)
 	}
+
+	/// Returns `true` if some registrar has judged the identity `Reasonable` or `KnownGood`.
+	pub fn is_judged_good(&self) -> bool {
+		self.judgements.iter().any(|(_, j)| matches!(j, Judgement::Reasonable | Judgement::KnownGood))
+	}
 }
 
 impl<
@@ -407,6 +415,56 @@ decl_storage! {
 		///
 		/// The index into this can be cast to `RegistrarIndex` to get a valid value.
 		pub Registrars get(fn registrars): Vec<Option<RegistrarInfo<BalanceOf<T>, T::AccountId>>>;
+
+		/// The number of identities that some registrar has judged `Reasonable` or `KnownGood`.
+		pub JudgedGood get(fn judged_good): u32;
+	}
+	add_extra_genesis {
+		/// Registrars, added in order without a fee.
+		config(registrars): Vec<T::AccountId>;
+		/// Identities set by an account, which reserves the usual deposit for them, with their
+		/// display name and sub-accounts.
+		config(identities): Vec<(T::AccountId, Vec<u8>, Vec<T::AccountId>)>;
+		/// Judgements of an identity by a registrar.
+		config(judgements): Vec<(RegistrarIndex, T::AccountId, Judgement<BalanceOf<T>>)>;
+		build(|config: &GenesisConfig<T>| {
+			for registrar in config.registrars.iter() {
+				<Module<T>>::add_registrar(frame_system::RawOrigin::Root.into(), registrar.clone())
+					.expect("Genesis registrar could not be added");
+			}
+			for (who, display, subs) in config.identities.iter() {
+				assert!(display.len() <= 32, "Genesis display names are at most 32 bytes");
+				let info = IdentityInfo {
+					additional: Vec::new(),
+					display: Data::Raw(display.clone()),
+					legal: Data::None,
+					web: Data::None,
+					riot: Data::None,
+					email: Data::None,
+					pgp_fingerprint: None,
+					image: Data::None,
+					twitter: Data::None,
+				};
+				<Module<T>>::set_identity(T::Origin::from(Some(who.clone()).into()), info)
+					.expect("Genesis identity could not be set");
+				if !subs.is_empty() {
+					<Module<T>>::set_subs(
+						T::Origin::from(Some(who.clone()).into()),
+						subs.iter().map(|sub| (sub.clone(), Data::None)).collect(),
+					).expect("Genesis sub-accounts could not be set");
+				}
+			}
+			for &(reg_index, ref target, judgement) in config.judgements.iter() {
+				let registrar = <Registrars<T>>::get().get(reg_index as usize).cloned().flatten()
+					.expect("Genesis judgements are given by genesis registrars");
+				<Module<T>>::provide_judgement(
+					T::Origin::from(Some(registrar.account).into()),
+					reg_index,
+					T::Lookup::unlookup(target.clone()),
+					judgement,
+				).expect("Genesis judgement could not be given");
+			}
+		})
 	}
 }
 
@@ -472,6 +530,7 @@ mod weight_for {
 		extra_fields: impl Into<Weight>
 	) -> Weight {
 		db.reads_writes(1, 1)
+			+ db.reads_writes(1, 1) // `JudgedGood`
 			+ 61_000_000 // constant
 			+ 400_000 * judgements.into() // R
 			+ 1_500_000 * extra_fields.into() // X
@@ -499,6 +558,7 @@ mod weight_for {
 		extra_fields: impl Into<Weight>
 	) -> Weight {
 		db.reads_writes(2, subs.into() + 2) // S + 2 deletions
+			+ db.reads_writes(1, 1) // `JudgedGood`
 			+ 58_000_000 // constant
 			+ 20_000 * judgements.into() // R
 			+ 2_600_000 * subs.into() // S
@@ -512,6 +572,7 @@ mod weight_for {
 		extra_fields: impl Into<Weight>
 	) -> Weight {
 		db.reads_writes(2, 1)
+			+ db.reads_writes(1, 1) // `JudgedGood`
 			+ 60_000_000 // constant
 			+ 510_000 * judgements.into() // R
 			+ 1_700_000 * extra_fields.into() // X
@@ -536,6 +597,7 @@ mod weight_for {
 		extra_fields: impl Into<Weight>
 	) -> Weight {
 		db.reads_writes(2, 1)
+			+ db.reads_writes(1, 1) // `JudgedGood`
 			+ 49_000_000 // constant
 			+ 400_000 * judgements.into() // R
 			+ 1_700_000 * extra_fields.into()// X
@@ -550,6 +612,7 @@ mod weight_for {
 	) -> Weight {
 		db.reads_writes(2, subs.into() + 2) // 2 `take`s + S deletions
 			+ db.reads_writes(1, 1) // balance ops
+			+ db.reads_writes(1, 1) // `JudgedGood`
 			+ 110_000_000 // constant
 			+ 100_000 * judgements.into() // R
 			+ 2_600_000 * subs.into() // S
@@ -652,8 +715,10 @@ decl_module! {
 			ensure!(extra_fields <= T::MaxAdditionalFields::get(), Error::<T>::TooManyFields);
 			let fd = <BalanceOf<T>>::from(extra_fields) * T::FieldDeposit::get();
 
+			let mut was_judged_good = false;
 			let mut id = match <IdentityOf<T>>::get(&sender) {
 				Some(mut id) => {
+					was_judged_good = id.is_judged_good();
 					// Only keep non-positive judgements.
 					id.judgements.retain(|j| j.1.is_sticky());
 					id.info = info;
@@ -672,6 +737,7 @@ decl_module! {
 			}
 
 			let judgements = id.judgements.len() as Weight;
+			Self::note_judgement(was_judged_good, id.is_judged_good());
 			<IdentityOf<T>>::insert(&sender, id);
 			Self::deposit_event(RawEvent::IdentitySet(sender));
 
@@ -786,6 +852,7 @@ decl_module! {
 			}
 
 			let _ = T::Currency::unreserve(&sender, deposit.clone());
+			Self::note_judgement(id.is_judged_good(), false);
 
 			Self::deposit_event(RawEvent::IdentityCleared(sender, deposit));
 
@@ -836,6 +903,7 @@ decl_module! {
 				.ok_or(Error::<T>::EmptyIndex)?;
 			ensure!(max_fee >= registrar.fee, Error::<T>::FeeChanged);
 			let mut id = <IdentityOf<T>>::get(&sender).ok_or(Error::<T>::NoIdentity)?;
+			let was_judged_good = id.is_judged_good();
 
 			let item = (reg_index, Judgement::FeePaid(registrar.fee));
 			match id.judgements.binary_search_by_key(&reg_index, |x| x.0) {
@@ -851,6 +919,7 @@ decl_module! {
 
 			let judgements = id.judgements.len() as Weight;
 			let extra_fields = id.info.additional.len() as Weight;
+			Self::note_judgement(was_judged_good, id.is_judged_good());
 			<IdentityOf<T>>::insert(&sender, id);
 
 			Self::deposit_event(RawEvent::JudgementRequested(sender, reg_index));
@@ -1047,6 +1116,7 @@ decl_module! {
 				.and_then(|r| if r.account == sender { Some(r) } else { None })
 				.ok_or(Error::<T>::InvalidIndex)?;
 			let mut id = <IdentityOf<T>>::get(&target).ok_or(Error::<T>::InvalidTarget)?;
+			let was_judged_good = id.is_judged_good();
 
 			let item = (reg_index, judgement);
 			match id.judgements.binary_search_by_key(&reg_index, |x| x.0) {
@@ -1061,6 +1131,7 @@ decl_module! {
 
 			let judgements = id.judgements.len() as Weight;
 			let extra_fields = id.info.additional.len() as Weight;
+			Self::note_judgement(was_judged_good, id.is_judged_good());
 			<IdentityOf<T>>::insert(&target, id);
 			Self::deposit_event(RawEvent::JudgementGiven(target, reg_index));
 
@@ -1109,6 +1180,7 @@ decl_module! {
 			}
 			// Slash their deposit from them.
 			T::Slashed::on_unbalanced(T::Currency::slash_reserved(&target, deposit).0);
+			Self::note_judgement(id.is_judged_good(), false);
 
 			Self::deposit_event(RawEvent::IdentityKilled(target, deposit));
 
@@ -1130,6 +1202,15 @@ impl<T: Trait> Module<T> {
 			.filter_map(|a| SuperOf::<T>::get(&a).map(|x| (a, x.1)))
 			.collect()
 	}
+
+	/// Keep `JudgedGood` up to date with an identity going from `was` to `is` judged good.
+	fn note_judgement(was: bool, is: bool) {
+		match (was, is) {
+			(false, true) => JudgedGood::mutate(|n| *n = n.saturating_add(1)),
+			(true, false) => JudgedGood::mutate(|n| *n = n.saturating_sub(1)),
+			_ => {}
+		}
+	}
 }
 
 #[cfg(test)]
@@ -1364,6 +1445,66 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn judged_good_identities_are_counted() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(Identity::add_registrar(Origin::signed(1), 3));
+			assert_ok!(Identity::add_registrar(Origin::signed(1), 30));
+			assert_ok!(Identity::set_identity(Origin::signed(10), ten()));
+			assert_ok!(Identity::set_identity(Origin::signed(20), ten()));
+			assert_eq!(Identity::judged_good(), 0);
+
+			// A second good judgement of the same identity doesn't count it twice.
+			assert_ok!(Identity::provide_judgement(Origin::signed(3), 0, 10, Judgement::Reasonable));
+			assert_ok!(Identity::provide_judgement(Origin::signed(30), 1, 10, Judgement::KnownGood));
+			assert_ok!(Identity::provide_judgement(Origin::signed(3), 0, 20, Judgement::KnownGood));
+			assert_eq!(Identity::judged_good(), 2);
+
+			// Only losing every good judgement stops an identity being counted.
+			assert_ok!(Identity::provide_judgement(Origin::signed(3), 0, 10, Judgement::OutOfDate));
+			assert_eq!(Identity::judged_good(), 2);
+			assert_ok!(Identity::provide_judgement(Origin::signed(30), 1, 10, Judgement::LowQuality));
+			assert_eq!(Identity::judged_good(), 1);
+
+			// Changing the identity drops its good judgements.
+			assert_ok!(Identity::set_identity(Origin::signed(20), ten()));
+			assert_eq!(Identity::judged_good(), 0);
+
+			assert_ok!(Identity::provide_judgement(Origin::signed(3), 0, 10, Judgement::Reasonable));
+			assert_ok!(Identity::provide_judgement(Origin::signed(3), 0, 20, Judgement::Reasonable));
+			assert_eq!(Identity::judged_good(), 2);
+			assert_ok!(Identity::clear_identity(Origin::signed(10)));
+			assert_eq!(Identity::judged_good(), 1);
+			assert_ok!(Identity::kill_identity(Origin::signed(2), 20));
+			assert_eq!(Identity::judged_good(), 0);
+		});
+	}
+
+	#[test]
+	fn genesis_seeds_judged_identities() {
+		use sp_runtime::BuildStorage;
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+		pallet_balances::GenesisConfig::<Test> {
+			balances: vec![(3, 10), (10, 100), (20, 100)],
+		}.assimilate_storage(&mut t).unwrap();
+		GenesisConfig::<Test> {
+			registrars: vec![3],
+			identities: vec![(10, b"ten".to_vec(), vec![1, 2]), (20, b"twenty".to_vec(), vec![])],
+			judgements: vec![(0, 10, Judgement::KnownGood), (0, 20, Judgement::OutOfDate)],
+		}.assimilate_storage(&mut t).unwrap();
+		sp_io::TestExternalities::new(t).execute_with(|| {
+			assert_eq!(Identity::registrars(), vec![
+				Some(RegistrarInfo { account: 3, fee: 0, fields: Default::default() })
+			]);
+			assert_eq!(Identity::identity(10).unwrap().info.display, Data::Raw(b"ten".to_vec()));
+			assert_eq!(Identity::identity(10).unwrap().judgements, vec![(0, Judgement::KnownGood)]);
+			assert_eq!(Identity::subs_of(10).1, vec![1, 2]);
+			assert_eq!(Identity::super_of(1), Some((10, Data::None)));
+			assert_eq!(Balances::free_balance(10), 70);
+			assert_eq!(Identity::judged_good(), 1);
+		});
+	}
+
 	#[test]
 	fn setting_subaccounts_should_work() {
 		new_test_ext().execute_with(|| {
@@ -0,0 +1,187 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Proxy pallet benchmarking.
+
+use super::*;
+
+use frame_benchmarking::{benchmarks, account};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+
+use crate::Module as Proxy;
+
+const SEED: u32 = 0;
+
+fn funded_account<T: Trait>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value());
+	who
+}
+
+/// Register `p` proxies without delay for `real`, each of them `account("proxy", i, SEED)`.
+fn add_proxies<T: Trait>(real: &T::AccountId, p: u32) -> Result<(), &'static str> {
+	for i in 0 .. p {
+		let delegate: T::AccountId = account("proxy", i, SEED);
+		Proxy::<T>::add_proxy(
+			RawOrigin::Signed(real.clone()).into(),
+			delegate,
+			T::ProxyType::default(),
+			Zero::zero(),
+		)?;
+	}
+	Ok(())
+}
+
+/// Have `delegate` announce `a` calls on behalf of `real`, none of them the benchmarked one.
+fn add_announcements<T: Trait>(
+	delegate: &T::AccountId,
+	real: &T::AccountId,
+	a: u32,
+) -> Result<(), &'static str> {
+	for i in 0 .. a {
+		Proxy::<T>::announce(RawOrigin::Signed(delegate.clone()).into(), real.clone(), T::Hashing::hash_of(&i))?;
+	}
+	Ok(())
+}
+
+/// The cheapest call to dispatch, so that the benchmarks measure only the proxying.
+fn remark<T: Trait>() -> <T as Trait>::Call {
+	frame_system::Call::<T>::remark(vec![]).into()
+}
+
+benchmarks! {
+	_ { }
+
+	proxy {
+		let p in 1 .. u32::from(T::MaxProxies::get());
+
+		let real = funded_account::<T>("real", 0);
+		add_proxies::<T>(&real, p)?;
+		let caller: T::AccountId = account("proxy", p - 1, SEED);
+	}: _(RawOrigin::Signed(caller), real, None, Box::new(remark::<T>()))
+
+	add_proxy {
+		let p in 1 .. u32::from(T::MaxProxies::get()) - 1;
+
+		let caller = funded_account::<T>("real", 0);
+		add_proxies::<T>(&caller, p)?;
+		let delegate: T::AccountId = account("proxy", p, SEED);
+	}: _(RawOrigin::Signed(caller.clone()), delegate, T::ProxyType::default(), Zero::zero())
+	verify {
+		ensure!(Proxy::<T>::proxies(&caller).0.len() == p as usize + 1, "Proxy not added");
+	}
+
+	remove_proxy {
+		let p in 1 .. u32::from(T::MaxProxies::get());
+
+		let caller = funded_account::<T>("real", 0);
+		add_proxies::<T>(&caller, p)?;
+		let delegate: T::AccountId = account("proxy", 0, SEED);
+	}: _(RawOrigin::Signed(caller.clone()), delegate, T::ProxyType::default(), Zero::zero())
+	verify {
+		ensure!(Proxy::<T>::proxies(&caller).0.len() == p as usize - 1, "Proxy not removed");
+	}
+
+	remove_proxies {
+		let p in 1 .. u32::from(T::MaxProxies::get());
+
+		let caller = funded_account::<T>("real", 0);
+		add_proxies::<T>(&caller, p)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		ensure!(Proxy::<T>::proxies(&caller).0.is_empty(), "Proxies not removed");
+	}
+
+	announce {
+		let a in 0 .. T::MaxPending::get() - 1;
+		let p in 1 .. u32::from(T::MaxProxies::get());
+
+		let real = funded_account::<T>("real", 0);
+		add_proxies::<T>(&real, p)?;
+		let caller = funded_account::<T>("proxy", p - 1);
+		add_announcements::<T>(&caller, &real, a)?;
+		let call_hash = T::Hashing::hash_of(&remark::<T>());
+	}: _(RawOrigin::Signed(caller.clone()), real, call_hash)
+	verify {
+		ensure!(Proxy::<T>::announcements(&caller).0.len() == a as usize + 1, "Call not announced");
+	}
+
+	remove_announcement {
+		let a in 0 .. T::MaxPending::get() - 1;
+
+		let real = funded_account::<T>("real", 0);
+		add_proxies::<T>(&real, 1)?;
+		let caller = funded_account::<T>("proxy", 0);
+		let call_hash = T::Hashing::hash_of(&remark::<T>());
+		Proxy::<T>::announce(RawOrigin::Signed(caller.clone()).into(), real.clone(), call_hash)?;
+		add_announcements::<T>(&caller, &real, a)?;
+	}: _(RawOrigin::Signed(caller.clone()), real, call_hash)
+	verify {
+		ensure!(Proxy::<T>::announcements(&caller).0.len() == a as usize, "Announcement not removed");
+	}
+
+	reject_announcement {
+		let a in 0 .. T::MaxPending::get() - 1;
+
+		let caller = funded_account::<T>("real", 0);
+		add_proxies::<T>(&caller, 1)?;
+		let delegate = funded_account::<T>("proxy", 0);
+		let call_hash = T::Hashing::hash_of(&remark::<T>());
+		Proxy::<T>::announce(RawOrigin::Signed(delegate.clone()).into(), caller.clone(), call_hash)?;
+		add_announcements::<T>(&delegate, &caller, a)?;
+	}: _(RawOrigin::Signed(caller), delegate.clone(), call_hash)
+	verify {
+		ensure!(Proxy::<T>::announcements(&delegate).0.len() == a as usize, "Announcement not rejected");
+	}
+
+	proxy_announced {
+		let a in 0 .. T::MaxPending::get() - 1;
+		let p in 1 .. u32::from(T::MaxProxies::get());
+
+		let real = funded_account::<T>("real", 0);
+		add_proxies::<T>(&real, p)?;
+		let delegate = funded_account::<T>("proxy", p - 1);
+		let call = remark::<T>();
+		Proxy::<T>::announce(RawOrigin::Signed(delegate.clone()).into(), real.clone(), T::Hashing::hash_of(&call))?;
+		add_announcements::<T>(&delegate, &real, a)?;
+		let caller = funded_account::<T>("caller", 0);
+	}: _(RawOrigin::Signed(caller), delegate.clone(), real, None, Box::new(call))
+	verify {
+		ensure!(Proxy::<T>::announcements(&delegate).0.len() == a as usize, "Announcement not dispatched");
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_proxy::<Test>());
+			assert_ok!(test_benchmark_add_proxy::<Test>());
+			assert_ok!(test_benchmark_remove_proxy::<Test>());
+			assert_ok!(test_benchmark_remove_proxies::<Test>());
+			assert_ok!(test_benchmark_announce::<Test>());
+			assert_ok!(test_benchmark_remove_announcement::<Test>());
+			assert_ok!(test_benchmark_reject_announcement::<Test>());
+			assert_ok!(test_benchmark_proxy_announced::<Test>());
+		});
+	}
+}
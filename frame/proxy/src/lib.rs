@@ -0,0 +1,627 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Proxy Module
+//! A module allowing accounts to give permission to other accounts to dispatch types of calls from
+//! their signed origin.
+//!
+//! - [`proxy::Trait`](./trait.Trait.html)
+//! - [`Call`](./enum.Call.html)
+//!
+//! ## Overview
+//!
+//! An account may register several proxies, each of a proxy type defined by the runtime that
+//! restricts the calls the proxy may make on its behalf (e.g. governance-only, staking-only or
+//! any). A deposit is reserved for each proxy.
+//!
+//! A proxy may also be given a delay. A delayed proxy must first announce the hash of a call, and
+//! may only dispatch it once the delay has passed, giving the proxied account time to reject it.
+//! A deposit is reserved from the proxy for each pending announcement.
+//!
+//! Other pallets may consult the registered proxies through `has_proxy`, for instance to let a
+//! governance proxy vote on behalf of an account.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! #### For proxied accounts
+//! * `add_proxy` - Register a proxy of some type and delay for the sender.
+//! * `remove_proxy` - Unregister a proxy of the sender.
+//! * `remove_proxies` - Unregister all proxies of the sender.
+//! * `reject_announcement` - Remove a call announced by a delayed proxy of the sender.
+//!
+//! #### For proxies
+//! * `proxy` - Dispatch a call on behalf of a proxied account, through a proxy without delay.
+//! * `announce` - Announce a call to be dispatched through a delayed proxy.
+//! * `remove_announcement` - Withdraw an announcement of the sender.
+//! * `proxy_announced` - Dispatch an announced call once the delay of its proxy has passed.
+//!
+//! [`Call`]: ./enum.Call.html
+//! [`Trait`]: ./trait.Trait.html
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+use codec::{Encode, Decode};
+use sp_runtime::{
+	DispatchResult, DispatchError, RuntimeDebug,
+	traits::{Dispatchable, Zero, Hash, Member, Saturating},
+};
+use frame_support::{
+	decl_module, decl_event, decl_error, decl_storage, Parameter, ensure, IsSubType,
+	traits::{Get, ReservableCurrency, Currency},
+	weights::GetDispatchInfo,
+	dispatch::PostDispatchInfo,
+};
+use frame_system::{self as system, ensure_signed};
+
+mod tests;
+mod weights;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+
+pub use weights::WeightInfo;
+
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
+/// A filter over the calls of the runtime, implemented by its proxy types.
+pub trait InstanceFilter<Call>: Sized + Send + Sync {
+	/// Returns `true` if a proxy of this type may dispatch `call`.
+	fn filter(&self, call: &Call) -> bool;
+
+	/// Returns `true` if this type allows every call that `other` allows, which includes `other`
+	/// itself. A proxy may only add or remove proxies of types it is a superset of.
+	fn is_superset(&self, other: &Self) -> bool;
+}
+
+/// Every call is allowed.
+impl<Call> InstanceFilter<Call> for () {
+	fn filter(&self, _: &Call) -> bool { true }
+	fn is_superset(&self, _: &()) -> bool { true }
+}
+
+/// Configuration trait.
+pub trait Trait: frame_system::Trait {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The overarching call type.
+	type Call: Parameter + Dispatchable<Origin=Self::Origin, PostInfo=PostDispatchInfo>
+		+ GetDispatchInfo + From<frame_system::Call<Self>> + IsSubType<Module<Self>, Self>;
+
+	/// The currency mechanism.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// The kinds of proxy an account may register, each restricting the calls its proxies may
+	/// make.
+	type ProxyType: Parameter + Member + Ord + PartialOrd + Default
+		+ InstanceFilter<<Self as Trait>::Call>;
+
+	/// The base amount of currency needed to reserve for registering proxies.
+	///
+	/// This is held for an additional storage item whose value size is
+	/// `sizeof(Balance)` bytes plus a `Vec` length prefix.
+	type ProxyDepositBase: Get<BalanceOf<Self>>;
+
+	/// The amount of currency needed to reserve per proxy registered.
+	///
+	/// This is held for adding `sizeof(AccountId) + sizeof(ProxyType) + sizeof(BlockNumber)`
+	/// bytes to a pre-existing storage value.
+	type ProxyDepositFactor: Get<BalanceOf<Self>>;
+
+	/// The maximum number of proxies an account may register.
+	type MaxProxies: Get<u16>;
+
+	/// The base amount of currency needed to reserve for announcing calls.
+	type AnnouncementDepositBase: Get<BalanceOf<Self>>;
+
+	/// The amount of currency needed to reserve per pending announcement.
+	type AnnouncementDepositFactor: Get<BalanceOf<Self>>;
+
+	/// The maximum number of announcements a proxy may have pending.
+	type MaxPending: Get<u32>;
+
+	/// The weights of the calls of this module.
+	type WeightInfo: WeightInfo;
+}
+
+/// A proxy registered by an account.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug)]
+pub struct ProxyDefinition<AccountId, ProxyType, BlockNumber> {
+	/// The account that may dispatch calls on behalf of the proxied account.
+	pub delegate: AccountId,
+	/// The kind of calls the proxy may dispatch.
+	pub proxy_type: ProxyType,
+	/// The number of blocks an announced call must wait before it may be dispatched. Zero if
+	/// calls may be dispatched right away.
+	pub delay: BlockNumber,
+}
+
+/// A call announced by a delayed proxy.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct Announcement<AccountId, Hash, BlockNumber> {
+	/// The account the call will be dispatched on behalf of.
+	pub real: AccountId,
+	/// The hash of the call.
+	pub call_hash: Hash,
+	/// The block at which the call was announced.
+	pub height: BlockNumber,
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as Proxy {
+		/// The proxies registered by each account, sorted, along with the deposit reserved for
+		/// them.
+		pub Proxies get(fn proxies): map hasher(twox_64_concat) T::AccountId
+			=> (Vec<ProxyDefinition<T::AccountId, T::ProxyType, T::BlockNumber>>, BalanceOf<T>);
+
+		/// The pending announcements of each delayed proxy, along with the deposit reserved for
+		/// them.
+		pub Announcements get(fn announcements): map hasher(twox_64_concat) T::AccountId
+			=> (Vec<Announcement<T::AccountId, T::Hash, T::BlockNumber>>, BalanceOf<T>);
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// There are too many proxies registered or too many announcements pending.
+		TooMany,
+		/// Proxy registration or announcement not found.
+		NotFound,
+		/// Sender is not a proxy of the account to be proxied.
+		NotProxy,
+		/// The proxy type doesn't allow the call to be made.
+		Unproxyable,
+		/// The proxy is already registered.
+		Duplicate,
+		/// The proxy type is not allowed to alter the proxies of the account.
+		NoPermission,
+		/// The call wasn't announced, or the delay of its proxy hasn't passed yet.
+		Unannounced,
+		/// An account cannot be its own proxy.
+		NoSelfProxy,
+	}
+}
+
+decl_event! {
+	/// Events type.
+	pub enum Event<T> where
+		AccountId = <T as frame_system::Trait>::AccountId,
+		ProxyType = <T as Trait>::ProxyType,
+		Hash = <T as frame_system::Trait>::Hash,
+		BlockNumber = <T as frame_system::Trait>::BlockNumber,
+	{
+		/// A proxy was executed correctly, with the given result.
+		ProxyExecuted(DispatchResult),
+		/// A proxy was registered: the proxied account, the proxy, its type and delay.
+		ProxyAdded(AccountId, AccountId, ProxyType, BlockNumber),
+		/// A proxy was unregistered: the proxied account, the proxy, its type and delay.
+		ProxyRemoved(AccountId, AccountId, ProxyType, BlockNumber),
+		/// A call was announced by a delayed proxy: the proxied account, the proxy and the hash
+		/// of the call.
+		Announced(AccountId, AccountId, Hash),
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		/// The base amount of currency needed to reserve for registering proxies.
+		const ProxyDepositBase: BalanceOf<T> = T::ProxyDepositBase::get();
+
+		/// The amount of currency needed to reserve per proxy registered.
+		const ProxyDepositFactor: BalanceOf<T> = T::ProxyDepositFactor::get();
+
+		/// The maximum number of proxies an account may register.
+		const MaxProxies: u16 = T::MaxProxies::get();
+
+		/// The base amount of currency needed to reserve for announcing calls.
+		const AnnouncementDepositBase: BalanceOf<T> = T::AnnouncementDepositBase::get();
+
+		/// The amount of currency needed to reserve per pending announcement.
+		const AnnouncementDepositFactor: BalanceOf<T> = T::AnnouncementDepositFactor::get();
+
+		/// The maximum number of announcements a proxy may have pending.
+		const MaxPending: u32 = T::MaxPending::get();
+
+		/// Deposit one of this module's events by using the default implementation.
+		fn deposit_event() = default;
+
+		/// Dispatch the given `call` from an account that the sender is a proxy for.
+		///
+		/// The dispatch origin for this call must be _Signed_, and the sender must be registered
+		/// as a proxy of `real` without delay.
+		///
+		/// - `real`: The account that the proxy will make a call on behalf of.
+		/// - `force_proxy_type`: Specify the exact proxy type to be used and checked for this call.
+		/// - `call`: The call to be made by the `real` account.
+		///
+		/// Emits `ProxyExecuted` with the result of the call.
+		///
+		/// # <weight>
+		/// - `O(P)` where P is the number of proxies registered by `real`.
+		/// - One DB read.
+		/// - Plus the weight of the `call`.
+		/// # </weight>
+		#[weight = {
+			let info = call.get_dispatch_info();
+			(T::WeightInfo::proxy(T::MaxProxies::get().into()).saturating_add(info.weight), info.class)
+		}]
+		fn proxy(origin,
+			real: T::AccountId,
+			force_proxy_type: Option<T::ProxyType>,
+			call: Box<<T as Trait>::Call>,
+		) {
+			let who = ensure_signed(origin)?;
+			let def = Self::find_proxy(&real, &who, force_proxy_type)?;
+			ensure!(def.delay.is_zero(), Error::<T>::Unannounced);
+			Self::do_proxy(def, real, *call)?;
+		}
+
+		/// Register a proxy account for the sender that is able to make calls on its behalf.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Payment: `ProxyDepositBase` plus `ProxyDepositFactor` for each proxy is reserved, and
+		/// returned once the proxies are removed.
+		///
+		/// - `delegate`: The account that the sender would like to make a proxy.
+		/// - `proxy_type`: The permissions allowed for this proxy account.
+		/// - `delay`: The announcement period required of the calls of the proxy. Zero if calls
+		///   may be made right away.
+		///
+		/// Emits `ProxyAdded`.
+		///
+		/// # <weight>
+		/// - `O(P)` where P is the number of proxies registered by the sender.
+		/// - One balance-reserve operation.
+		/// - One DB change.
+		/// # </weight>
+		#[weight = T::WeightInfo::add_proxy(T::MaxProxies::get().into())]
+		fn add_proxy(origin, delegate: T::AccountId, proxy_type: T::ProxyType, delay: T::BlockNumber) {
+			let who = ensure_signed(origin)?;
+			Self::add_proxy_delegate(&who, delegate, proxy_type, delay)?;
+		}
+
+		/// Unregister a proxy account for the sender.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `delegate`: The proxy to remove.
+		/// - `proxy_type`: The type of the proxy to remove.
+		/// - `delay`: The delay of the proxy to remove.
+		///
+		/// Emits `ProxyRemoved`.
+		///
+		/// # <weight>
+		/// - `O(P)` where P is the number of proxies registered by the sender.
+		/// - One balance-unreserve operation.
+		/// - One DB change.
+		/// # </weight>
+		#[weight = T::WeightInfo::remove_proxy(T::MaxProxies::get().into())]
+		fn remove_proxy(origin, delegate: T::AccountId, proxy_type: T::ProxyType, delay: T::BlockNumber) {
+			let who = ensure_signed(origin)?;
+			let (mut proxies, old_deposit) = Proxies::<T>::get(&who);
+			let proxy_def = ProxyDefinition { delegate, proxy_type, delay };
+			let i = proxies.binary_search(&proxy_def).ok().ok_or(Error::<T>::NotFound)?;
+			proxies.remove(i);
+			let new_deposit = Self::proxy_deposit(proxies.len() as u32);
+			Self::rejig_deposit(&who, old_deposit, new_deposit)?;
+			if proxies.is_empty() {
+				Proxies::<T>::remove(&who);
+				system::Module::<T>::dec_ref(&who);
+			} else {
+				Proxies::<T>::insert(&who, (proxies, new_deposit));
+			}
+			let ProxyDefinition { delegate, proxy_type, delay } = proxy_def;
+			Self::deposit_event(RawEvent::ProxyRemoved(who, delegate, proxy_type, delay));
+		}
+
+		/// Unregister all proxy accounts for the sender, returning their deposit.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// WARNING: This may be called on accounts created by other means than `add_proxy`, in
+		/// which case they may lose all access.
+		///
+		/// # <weight>
+		/// - `O(P)` where P is the number of proxies registered by the sender.
+		/// - One balance-unreserve operation.
+		/// - One DB clear.
+		/// # </weight>
+		#[weight = T::WeightInfo::remove_proxies(T::MaxProxies::get().into())]
+		fn remove_proxies(origin) {
+			let who = ensure_signed(origin)?;
+			let (proxies, deposit) = Proxies::<T>::take(&who);
+			if !proxies.is_empty() {
+				T::Currency::unreserve(&who, deposit);
+				system::Module::<T>::dec_ref(&who);
+			}
+		}
+
+		/// Announce a call to be made on behalf of `real` through a delayed proxy of the sender.
+		/// It may be dispatched with `proxy_announced` once the delay of the proxy has passed.
+		///
+		/// The dispatch origin for this call must be _Signed_, and the sender must be registered
+		/// as a proxy of `real`.
+		///
+		/// Payment: `AnnouncementDepositBase` plus `AnnouncementDepositFactor` for each pending
+		/// announcement is reserved, and returned once the announcements are dispatched or
+		/// removed.
+		///
+		/// - `real`: The account that the proxy will make a call on behalf of.
+		/// - `call_hash`: The hash of the call to be made by the `real` account.
+		///
+		/// Emits `Announced`.
+		///
+		/// # <weight>
+		/// - `O(P + A)` where P is the number of proxies registered by `real` and A the number of
+		///   announcements pending for the sender.
+		/// - One balance-reserve operation.
+		/// - One DB read, one DB change.
+		/// # </weight>
+		#[weight = T::WeightInfo::announce(T::MaxPending::get(), T::MaxProxies::get().into())]
+		fn announce(origin, real: T::AccountId, call_hash: T::Hash) {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				Proxies::<T>::get(&real).0.iter().any(|d| d.delegate == who),
+				Error::<T>::NotProxy,
+			);
+			let (mut pending, old_deposit) = Announcements::<T>::get(&who);
+			ensure!(pending.len() < T::MaxPending::get() as usize, Error::<T>::TooMany);
+			pending.push(Announcement {
+				real: real.clone(),
+				call_hash,
+				height: system::Module::<T>::block_number(),
+			});
+			let new_deposit = Self::announcement_deposit(pending.len() as u32);
+			Self::rejig_deposit(&who, old_deposit, new_deposit)?;
+			Announcements::<T>::insert(&who, (pending, new_deposit));
+			Self::deposit_event(RawEvent::Announced(real, who, call_hash));
+		}
+
+		/// Withdraw an announcement of the sender, returning its deposit.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `real`: The account the call was announced on behalf of.
+		/// - `call_hash`: The hash of the announced call.
+		///
+		/// # <weight>
+		/// - `O(A)` where A is the number of announcements pending for the sender.
+		/// - One balance-unreserve operation.
+		/// - One DB change.
+		/// # </weight>
+		#[weight = T::WeightInfo::remove_announcement(T::MaxPending::get())]
+		fn remove_announcement(origin, real: T::AccountId, call_hash: T::Hash) {
+			let who = ensure_signed(origin)?;
+			Self::edit_announcements(&who, |ann| ann.real != real || ann.call_hash != call_hash)?;
+		}
+
+		/// Remove a call announced on behalf of the sender by one of its delayed proxies,
+		/// returning the proxy's deposit.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `delegate`: The proxy that announced the call.
+		/// - `call_hash`: The hash of the announced call.
+		///
+		/// # <weight>
+		/// - `O(A)` where A is the number of announcements pending for `delegate`.
+		/// - One balance-unreserve operation.
+		/// - One DB change.
+		/// # </weight>
+		#[weight = T::WeightInfo::reject_announcement(T::MaxPending::get())]
+		fn reject_announcement(origin, delegate: T::AccountId, call_hash: T::Hash) {
+			let who = ensure_signed(origin)?;
+			Self::edit_announcements(&delegate, |ann| ann.real != who || ann.call_hash != call_hash)?;
+		}
+
+		/// Dispatch a call that `delegate` announced on behalf of `real`, once the delay of the
+		/// proxy has passed.
+		///
+		/// The dispatch origin for this call must be _Signed_. Anyone may dispatch an announced
+		/// call.
+		///
+		/// - `delegate`: The proxy that announced the call.
+		/// - `real`: The account that the proxy will make a call on behalf of.
+		/// - `force_proxy_type`: Specify the exact proxy type to be used and checked for this call.
+		/// - `call`: The call to be made by the `real` account.
+		///
+		/// Emits `ProxyExecuted` with the result of the call.
+		///
+		/// # <weight>
+		/// - `O(P + A)` where P is the number of proxies registered by `real` and A the number of
+		///   announcements pending for `delegate`.
+		/// - One balance-unreserve operation.
+		/// - One DB read, one DB change.
+		/// - Plus the weight of the `call`.
+		/// # </weight>
+		#[weight = {
+			let info = call.get_dispatch_info();
+			let weight = T::WeightInfo::proxy_announced(T::MaxPending::get(), T::MaxProxies::get().into());
+			(weight.saturating_add(info.weight), info.class)
+		}]
+		fn proxy_announced(origin,
+			delegate: T::AccountId,
+			real: T::AccountId,
+			force_proxy_type: Option<T::ProxyType>,
+			call: Box<<T as Trait>::Call>,
+		) {
+			ensure_signed(origin)?;
+			let def = Self::find_proxy(&real, &delegate, force_proxy_type)?;
+			let call_hash = T::Hashing::hash_of(&call);
+			let now = system::Module::<T>::block_number();
+			Self::edit_announcements(&delegate, |ann|
+				ann.real != real || ann.call_hash != call_hash || now.saturating_sub(ann.height) < def.delay
+			).map_err(|_| Error::<T>::Unannounced)?;
+			Self::do_proxy(def, real, *call)?;
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	// exposed immutables.
+
+	/// Returns `true` if `delegate` is registered as a proxy of `real` without delay, with a type
+	/// that allows every call `proxy_type` allows.
+	pub fn has_proxy(real: &T::AccountId, delegate: &T::AccountId, proxy_type: &T::ProxyType) -> bool {
+		Proxies::<T>::get(real).0.iter()
+			.any(|d| &d.delegate == delegate && d.delay.is_zero() && d.proxy_type.is_superset(proxy_type))
+	}
+
+	/// Find the proxy of `real` registered for `delegate`, of type `force_proxy_type` if given.
+	pub fn find_proxy(
+		real: &T::AccountId,
+		delegate: &T::AccountId,
+		force_proxy_type: Option<T::ProxyType>,
+	) -> Result<ProxyDefinition<T::AccountId, T::ProxyType, T::BlockNumber>, DispatchError> {
+		let f = |d: &ProxyDefinition<T::AccountId, T::ProxyType, T::BlockNumber>| -> bool {
+			&d.delegate == delegate && force_proxy_type.as_ref().map_or(true, |t| &d.proxy_type == t)
+		};
+		Ok(Proxies::<T>::get(real).0.into_iter().find(f).ok_or(Error::<T>::NotProxy)?)
+	}
+
+	// Exposed mutables.
+
+	/// Register `delegate` as a proxy of `delegator`, reserving the deposit from `delegator`.
+	pub fn add_proxy_delegate(
+		delegator: &T::AccountId,
+		delegate: T::AccountId,
+		proxy_type: T::ProxyType,
+		delay: T::BlockNumber,
+	) -> DispatchResult {
+		Self::insert_proxy(delegator, delegate, proxy_type, delay, true)
+	}
+
+	/// Register `delegate` as a proxy of `delegator` without reserving any more deposit, for
+	/// proxies carried over from elsewhere that `delegator` never agreed to pay for. The deposit
+	/// still owed is reserved once `delegator` next adds a proxy.
+	pub fn add_proxy_delegate_without_deposit(
+		delegator: &T::AccountId,
+		delegate: T::AccountId,
+		proxy_type: T::ProxyType,
+		delay: T::BlockNumber,
+	) -> DispatchResult {
+		Self::insert_proxy(delegator, delegate, proxy_type, delay, false)
+	}
+
+	// private.
+
+	/// Register `delegate` as a proxy of `delegator`, reserving the deposit this takes if
+	/// `reserve`.
+	fn insert_proxy(
+		delegator: &T::AccountId,
+		delegate: T::AccountId,
+		proxy_type: T::ProxyType,
+		delay: T::BlockNumber,
+		reserve: bool,
+	) -> DispatchResult {
+		ensure!(delegator != &delegate, Error::<T>::NoSelfProxy);
+		let (mut proxies, old_deposit) = Proxies::<T>::get(delegator);
+		ensure!(proxies.len() < T::MaxProxies::get() as usize, Error::<T>::TooMany);
+		let proxy_def = ProxyDefinition { delegate, proxy_type, delay };
+		let i = proxies.binary_search(&proxy_def).err().ok_or(Error::<T>::Duplicate)?;
+		proxies.insert(i, proxy_def.clone());
+		let new_deposit = if reserve {
+			let new_deposit = Self::proxy_deposit(proxies.len() as u32);
+			Self::rejig_deposit(delegator, old_deposit, new_deposit)?;
+			new_deposit
+		} else {
+			old_deposit
+		};
+		if proxies.len() == 1 {
+			system::Module::<T>::inc_ref(delegator);
+		}
+		Proxies::<T>::insert(delegator, (proxies, new_deposit));
+		let ProxyDefinition { delegate, proxy_type, delay } = proxy_def;
+		Self::deposit_event(RawEvent::ProxyAdded(delegator.clone(), delegate, proxy_type, delay));
+		Ok(())
+	}
+
+	/// The deposit reserved for `count` proxies.
+	fn proxy_deposit(count: u32) -> BalanceOf<T> {
+		if count == 0 {
+			Zero::zero()
+		} else {
+			T::ProxyDepositBase::get() + T::ProxyDepositFactor::get() * count.into()
+		}
+	}
+
+	/// The deposit reserved for `count` pending announcements.
+	fn announcement_deposit(count: u32) -> BalanceOf<T> {
+		if count == 0 {
+			Zero::zero()
+		} else {
+			T::AnnouncementDepositBase::get() + T::AnnouncementDepositFactor::get() * count.into()
+		}
+	}
+
+	/// Reserve or unreserve the difference between the `old` and `new` deposit of `who`.
+	fn rejig_deposit(who: &T::AccountId, old: BalanceOf<T>, new: BalanceOf<T>) -> DispatchResult {
+		if new > old {
+			T::Currency::reserve(who, new - old)?;
+		} else if new < old {
+			T::Currency::unreserve(who, old - new);
+		}
+		Ok(())
+	}
+
+	/// Keep only the announcements of `delegate` for which `f` is `true`, adjusting its deposit.
+	/// Fails if none are removed.
+	fn edit_announcements<
+		F: FnMut(&Announcement<T::AccountId, T::Hash, T::BlockNumber>) -> bool
+	>(delegate: &T::AccountId, f: F) -> DispatchResult {
+		let (mut pending, old_deposit) = Announcements::<T>::get(delegate);
+		let orig_len = pending.len();
+		pending.retain(f);
+		ensure!(pending.len() < orig_len, Error::<T>::NotFound);
+		let new_deposit = Self::announcement_deposit(pending.len() as u32);
+		Self::rejig_deposit(delegate, old_deposit, new_deposit)?;
+		if pending.is_empty() {
+			Announcements::<T>::remove(delegate);
+		} else {
+			Announcements::<T>::insert(delegate, (pending, new_deposit));
+		}
+		Ok(())
+	}
+
+	/// Dispatch `call` from `real` through the proxy `def`, if its type allows it.
+	fn do_proxy(
+		def: ProxyDefinition<T::AccountId, T::ProxyType, T::BlockNumber>,
+		real: T::AccountId,
+		call: <T as Trait>::Call,
+	) -> DispatchResult {
+		ensure!(def.proxy_type.filter(&call), Error::<T>::Unproxyable);
+		// A proxy may not alter the proxies of `real` beyond what its own type allows.
+		match call.is_sub_type() {
+			Some(Call::add_proxy(_, ref proxy_type, _)) | Some(Call::remove_proxy(_, ref proxy_type, _)) =>
+				ensure!(def.proxy_type.is_superset(proxy_type), Error::<T>::NoPermission),
+			Some(Call::remove_proxies()) => ensure!(
+				Proxies::<T>::get(&real).0.iter().all(|d| def.proxy_type.is_superset(&d.proxy_type)),
+				Error::<T>::NoPermission,
+			),
+			_ => {}
+		}
+		let e = call.dispatch(frame_system::RawOrigin::Signed(real).into());
+		Self::deposit_event(RawEvent::ProxyExecuted(e.map(|_| ()).map_err(|e| e.error)));
+		Ok(())
+	}
+}
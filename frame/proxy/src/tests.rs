@@ -0,0 +1,334 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+// Tests for Proxy Pallet
+
+#![cfg(test)]
+
+use super::*;
+
+use frame_support::{
+	assert_ok, assert_noop, impl_outer_origin, parameter_types, impl_outer_dispatch,
+	weights::Weight, impl_outer_event
+};
+use sp_core::H256;
+use sp_runtime::{Perbill, traits::{BlakeTwo256, IdentityLookup}, testing::Header};
+use crate as proxy;
+
+impl_outer_origin! {
+	pub enum Origin for Test where system = frame_system {}
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Test {
+		system<T>,
+		pallet_balances<T>,
+		proxy<T>,
+	}
+}
+impl_outer_dispatch! {
+	pub enum Call for Test where origin: Origin {
+		frame_system::System,
+		pallet_balances::Balances,
+		proxy::Proxy,
+	}
+}
+
+// For testing the pallet, we construct most of a mock runtime. This means
+// first constructing a configuration type (`Test`) which `impl`s each of the
+// configuration traits of pallets we want to use.
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+impl frame_system::Trait for Test {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Call = Call;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = TestEvent;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type ModuleToIndex = ();
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+}
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Trait for Test {
+	type Balance = u64;
+	type Event = TestEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+}
+parameter_types! {
+	pub const ProxyDepositBase: u64 = 1;
+	pub const ProxyDepositFactor: u64 = 1;
+	pub const MaxProxies: u16 = 4;
+	pub const AnnouncementDepositBase: u64 = 1;
+	pub const AnnouncementDepositFactor: u64 = 1;
+	pub const MaxPending: u32 = 2;
+}
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, RuntimeDebug)]
+pub enum ProxyType {
+	Any,
+	JustTransfer,
+	NotTransfer,
+}
+impl Default for ProxyType {
+	fn default() -> Self { ProxyType::Any }
+}
+impl InstanceFilter<Call> for ProxyType {
+	fn filter(&self, c: &Call) -> bool {
+		match self {
+			ProxyType::Any => true,
+			ProxyType::JustTransfer => matches!(c, Call::Balances(BalancesCall::transfer(..))),
+			ProxyType::NotTransfer => !matches!(c, Call::Balances(BalancesCall::transfer(..))),
+		}
+	}
+	fn is_superset(&self, o: &Self) -> bool {
+		self == &ProxyType::Any || self == o
+	}
+}
+impl Trait for Test {
+	type Event = TestEvent;
+	type Call = Call;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ProxyDepositBase;
+	type ProxyDepositFactor = ProxyDepositFactor;
+	type MaxProxies = MaxProxies;
+	type AnnouncementDepositBase = AnnouncementDepositBase;
+	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+	type MaxPending = MaxPending;
+	type WeightInfo = ();
+}
+type System = frame_system::Module<Test>;
+type Balances = pallet_balances::Module<Test>;
+type Proxy = Module<Test>;
+
+use pallet_balances::Call as BalancesCall;
+use pallet_balances::Error as BalancesError;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 10), (2, 10), (3, 10), (4, 10), (5, 1)],
+	}.assimilate_storage(&mut t).unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn last_event() -> TestEvent {
+	system::Module::<Test>::events().pop().map(|e| e.event).expect("Event expected")
+}
+
+fn expect_event<E: Into<TestEvent>>(e: E) {
+	assert_eq!(last_event(), e.into());
+}
+
+fn transfer(to: u64, value: u64) -> Box<Call> {
+	Box::new(Call::Balances(BalancesCall::transfer(to, value)))
+}
+
+#[test]
+fn add_remove_proxies_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Proxy::add_proxy(Origin::signed(1), 2, ProxyType::Any, 0));
+		expect_event(RawEvent::ProxyAdded(1, 2, ProxyType::Any, 0));
+		assert_noop!(Proxy::add_proxy(Origin::signed(1), 2, ProxyType::Any, 0), Error::<Test>::Duplicate);
+		assert_eq!(Balances::reserved_balance(1), 2);
+		assert_ok!(Proxy::add_proxy(Origin::signed(1), 2, ProxyType::JustTransfer, 0));
+		assert_eq!(Balances::reserved_balance(1), 3);
+		assert_ok!(Proxy::add_proxy(Origin::signed(1), 3, ProxyType::Any, 0));
+		assert_ok!(Proxy::add_proxy(Origin::signed(1), 4, ProxyType::Any, 2));
+		assert_eq!(Balances::reserved_balance(1), 5);
+		assert_noop!(Proxy::add_proxy(Origin::signed(1), 4, ProxyType::JustTransfer, 0), Error::<Test>::TooMany);
+		assert_noop!(Proxy::add_proxy(Origin::signed(1), 1, ProxyType::Any, 0), Error::<Test>::NoSelfProxy);
+		assert!(!System::allow_death(&1));
+
+		assert_noop!(Proxy::remove_proxy(Origin::signed(1), 3, ProxyType::Any, 2), Error::<Test>::NotFound);
+		assert_ok!(Proxy::remove_proxy(Origin::signed(1), 3, ProxyType::Any, 0));
+		expect_event(RawEvent::ProxyRemoved(1, 3, ProxyType::Any, 0));
+		assert_eq!(Balances::reserved_balance(1), 4);
+		assert_ok!(Proxy::remove_proxy(Origin::signed(1), 2, ProxyType::Any, 0));
+		assert_ok!(Proxy::remove_proxy(Origin::signed(1), 2, ProxyType::JustTransfer, 0));
+		assert_ok!(Proxy::remove_proxy(Origin::signed(1), 4, ProxyType::Any, 2));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Proxy::proxies(1).0, vec![]);
+		assert!(System::allow_death(&1));
+
+		assert_noop!(
+			Proxy::add_proxy(Origin::signed(5), 1, ProxyType::Any, 0),
+			BalancesError::<Test, _>::InsufficientBalance,
+		);
+	});
+}
+
+#[test]
+fn remove_proxies_returns_the_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Proxy::add_proxy(Origin::signed(1), 2, ProxyType::Any, 0));
+		assert_ok!(Proxy::add_proxy(Origin::signed(1), 3, ProxyType::JustTransfer, 0));
+		assert_eq!(Balances::reserved_balance(1), 3);
+		assert_ok!(Proxy::remove_proxies(Origin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert!(System::allow_death(&1));
+	});
+}
+
+#[test]
+fn proxies_added_without_a_deposit_reserve_it_with_the_next() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Proxy::add_proxy_delegate(&5, 2, ProxyType::Any, 0),
+			BalancesError::<Test, _>::InsufficientBalance,
+		);
+		assert_ok!(Proxy::add_proxy_delegate_without_deposit(&5, 2, ProxyType::Any, 0));
+		assert!(Proxy::has_proxy(&5, &2, &ProxyType::Any));
+		assert_eq!(Proxy::proxies(5).1, 0);
+		assert_eq!(Balances::reserved_balance(5), 0);
+
+		let _ = Balances::deposit_creating(&5, 9);
+		assert_ok!(Proxy::add_proxy(Origin::signed(5), 3, ProxyType::JustTransfer, 0));
+		assert_eq!(Proxy::proxies(5).1, 3);
+		assert_eq!(Balances::reserved_balance(5), 3);
+		assert_ok!(Proxy::remove_proxies(Origin::signed(5)));
+		assert_eq!(Balances::reserved_balance(5), 0);
+	});
+}
+
+#[test]
+fn filtering_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Proxy::add_proxy(Origin::signed(1), 2, ProxyType::Any, 0));
+		assert_ok!(Proxy::add_proxy(Origin::signed(1), 3, ProxyType::JustTransfer, 0));
+
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, transfer(6, 1)));
+		expect_event(RawEvent::ProxyExecuted(Ok(())));
+		assert_ok!(Proxy::proxy(Origin::signed(3), 1, None, transfer(6, 1)));
+		expect_event(RawEvent::ProxyExecuted(Ok(())));
+		assert_eq!(Balances::free_balance(6), 2);
+
+		let call = Box::new(Call::System(frame_system::Call::remark(vec![])));
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, call.clone()));
+		assert_noop!(Proxy::proxy(Origin::signed(3), 1, None, call.clone()), Error::<Test>::Unproxyable);
+		assert_noop!(
+			Proxy::proxy(Origin::signed(2), 1, Some(ProxyType::JustTransfer), call),
+			Error::<Test>::NotProxy,
+		);
+		assert_noop!(Proxy::proxy(Origin::signed(4), 1, None, transfer(6, 1)), Error::<Test>::NotProxy);
+	});
+}
+
+#[test]
+fn proxies_cannot_escalate_their_permissions() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Proxy::add_proxy(Origin::signed(1), 2, ProxyType::Any, 0));
+		assert_ok!(Proxy::add_proxy(Origin::signed(1), 3, ProxyType::NotTransfer, 0));
+
+		let call = Box::new(Call::Proxy(proxy::Call::add_proxy(4, ProxyType::Any, 0)));
+		assert_noop!(Proxy::proxy(Origin::signed(3), 1, None, call.clone()), Error::<Test>::NoPermission);
+		let call = Box::new(Call::Proxy(proxy::Call::remove_proxies()));
+		assert_noop!(Proxy::proxy(Origin::signed(3), 1, None, call), Error::<Test>::NoPermission);
+		let call = Box::new(Call::Proxy(proxy::Call::add_proxy(4, ProxyType::NotTransfer, 0)));
+		assert_ok!(Proxy::proxy(Origin::signed(3), 1, None, call));
+
+		let call = Box::new(Call::Proxy(proxy::Call::add_proxy(4, ProxyType::Any, 0)));
+		assert_ok!(Proxy::proxy(Origin::signed(2), 1, None, call));
+		assert!(Proxy::has_proxy(&1, &4, &ProxyType::JustTransfer));
+		assert!(!Proxy::has_proxy(&1, &3, &ProxyType::Any));
+	});
+}
+
+#[test]
+fn delayed_proxies_must_announce() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Proxy::add_proxy(Origin::signed(1), 2, ProxyType::Any, 2));
+		assert!(!Proxy::has_proxy(&1, &2, &ProxyType::Any));
+		let call = transfer(6, 1);
+		let call_hash = BlakeTwo256::hash_of(&call);
+
+		assert_noop!(Proxy::proxy(Origin::signed(2), 1, None, call.clone()), Error::<Test>::Unannounced);
+		assert_noop!(Proxy::announce(Origin::signed(3), 1, call_hash), Error::<Test>::NotProxy);
+		assert_ok!(Proxy::announce(Origin::signed(2), 1, call_hash));
+		expect_event(RawEvent::Announced(1, 2, call_hash));
+		assert_eq!(Balances::reserved_balance(2), 2);
+
+		// Too early.
+		System::set_block_number(2);
+		assert_noop!(
+			Proxy::proxy_announced(Origin::signed(4), 2, 1, None, call.clone()),
+			Error::<Test>::Unannounced,
+		);
+		System::set_block_number(3);
+		assert_ok!(Proxy::proxy_announced(Origin::signed(4), 2, 1, None, call.clone()));
+		expect_event(RawEvent::ProxyExecuted(Ok(())));
+		assert_eq!(Balances::free_balance(6), 1);
+		assert_eq!(Balances::reserved_balance(2), 0);
+
+		// Each announcement is only dispatched once.
+		assert_noop!(
+			Proxy::proxy_announced(Origin::signed(4), 2, 1, None, call),
+			Error::<Test>::Unannounced,
+		);
+	});
+}
+
+#[test]
+fn announcements_can_be_removed_and_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Proxy::add_proxy(Origin::signed(1), 2, ProxyType::Any, 1));
+		let first = BlakeTwo256::hash_of(&transfer(6, 1));
+		let second = BlakeTwo256::hash_of(&transfer(6, 2));
+		assert_ok!(Proxy::announce(Origin::signed(2), 1, first));
+		assert_ok!(Proxy::announce(Origin::signed(2), 1, second));
+		assert_noop!(Proxy::announce(Origin::signed(2), 1, first), Error::<Test>::TooMany);
+		assert_eq!(Balances::reserved_balance(2), 3);
+
+		assert_ok!(Proxy::remove_announcement(Origin::signed(2), 1, first));
+		assert_eq!(Balances::reserved_balance(2), 2);
+		assert_noop!(Proxy::reject_announcement(Origin::signed(3), 2, second), Error::<Test>::NotFound);
+		assert_ok!(Proxy::reject_announcement(Origin::signed(1), 2, second));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Proxy::announcements(2).0, vec![]);
+
+		System::set_block_number(5);
+		assert_noop!(
+			Proxy::proxy_announced(Origin::signed(2), 2, 1, None, transfer(6, 2)),
+			Error::<Test>::Unannounced,
+		);
+	});
+}
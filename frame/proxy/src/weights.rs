@@ -0,0 +1,51 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The weights of the calls of this pallet.
+
+use frame_support::weights::Weight;
+
+/// The weight of each call, as measured by the benchmark of the same name in `benchmarking.rs`.
+/// The weights of `proxy` and `proxy_announced` don't include that of the call they dispatch.
+///
+/// The parameters are the components of the benchmark:
+/// - `p`: the number of proxies registered by the proxied account;
+/// - `a`: the number of announcements pending for the proxy.
+///
+/// The runtime should plug in weights generated with `benchmark` on its own hardware.
+pub trait WeightInfo {
+	fn proxy(p: u32) -> Weight;
+	fn add_proxy(p: u32) -> Weight;
+	fn remove_proxy(p: u32) -> Weight;
+	fn remove_proxies(p: u32) -> Weight;
+	fn announce(a: u32, p: u32) -> Weight;
+	fn remove_announcement(a: u32) -> Weight;
+	fn reject_announcement(a: u32) -> Weight;
+	fn proxy_announced(a: u32, p: u32) -> Weight;
+}
+
+/// A flat weight for every call, for tests and chains that don't care about the weight of
+/// proxies.
+impl WeightInfo for () {
+	fn proxy(_p: u32) -> Weight { 50_000_000 }
+	fn add_proxy(_p: u32) -> Weight { 50_000_000 }
+	fn remove_proxy(_p: u32) -> Weight { 50_000_000 }
+	fn remove_proxies(_p: u32) -> Weight { 50_000_000 }
+	fn announce(_a: u32, _p: u32) -> Weight { 50_000_000 }
+	fn remove_announcement(_a: u32) -> Weight { 50_000_000 }
+	fn reject_announcement(_a: u32) -> Weight { 50_000_000 }
+	fn proxy_announced(_a: u32, _p: u32) -> Weight { 50_000_000 }
+}
@@ -0,0 +1,151 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition required by Quadratic Democracy RPC extensions.
+//!
+//! This API should be imported and implemented by the runtime,
+//! of a node that wants to use the custom RPC extension
+//! adding Quadratic Democracy access methods.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Encode, Codec, Decode};
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+use sp_runtime::traits::{MaybeDisplay, MaybeFromStr};
+pub use pallet_quadratic_democracy::{Conviction, ReferendumIndex, VoteThreshold, VoteWeight};
+
+/// The tally of an ongoing referendum and the outcome it would have if it ended now.
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "std", serde(bound(
+	serialize = "Balance: std::fmt::Display",
+	deserialize = "Balance: std::str::FromStr",
+)))]
+pub struct ReferendumTally<Balance> {
+	/// The aye votes, weighted and post-conviction.
+	#[cfg_attr(feature = "std", serde(with = "as_string"))]
+	pub ayes: Balance,
+	/// The nay votes, weighted and post-conviction.
+	#[cfg_attr(feature = "std", serde(with = "as_string"))]
+	pub nays: Balance,
+	/// The raw capital that has voted.
+	#[cfg_attr(feature = "std", serde(with = "as_string"))]
+	pub turnout: Balance,
+	/// The sum of each voter's weighted capital, pre-conviction.
+	#[cfg_attr(feature = "std", serde(with = "as_string"))]
+	pub weighted_turnout: Balance,
+	/// The weighted capital of the whole electorate, which the weighted turnout is measured
+	/// against.
+	#[cfg_attr(feature = "std", serde(with = "as_string"))]
+	pub electorate: Balance,
+	/// How the votes of this referendum are weighted.
+	pub weight: VoteWeight,
+	/// The threshold this referendum must pass.
+	pub threshold: VoteThreshold,
+	/// Whether the referendum would be approved if it ended now.
+	pub approved: bool,
+}
+
+/// The voting power an account would bring to a referendum.
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "std", serde(bound(
+	serialize = "Balance: std::fmt::Display",
+	deserialize = "Balance: std::str::FromStr",
+)))]
+pub struct VotingPower<Balance> {
+	/// The raw capital of the vote itself.
+	#[cfg_attr(feature = "std", serde(with = "as_string"))]
+	pub capital: Balance,
+	/// The capital after weighting, before conviction.
+	#[cfg_attr(feature = "std", serde(with = "as_string"))]
+	pub weighted_balance: Balance,
+	/// The post-conviction votes of the account's own capital.
+	#[cfg_attr(feature = "std", serde(with = "as_string"))]
+	pub votes: Balance,
+	/// The raw capital delegated to the account.
+	#[cfg_attr(feature = "std", serde(with = "as_string"))]
+	pub delegated_capital: Balance,
+	/// The weighted, post-conviction votes delegated to the account.
+	#[cfg_attr(feature = "std", serde(with = "as_string"))]
+	pub delegated_votes: Balance,
+}
+
+#[cfg(feature = "std")]
+mod as_string {
+	use super::*;
+
+	pub fn serialize<S: Serializer, T: std::fmt::Display>(t: &T, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_str(&t.to_string())
+	}
+
+	pub fn deserialize<'de, D: Deserializer<'de>, T: std::str::FromStr>(deserializer: D) -> Result<T, D::Error> {
+		let s = String::deserialize(deserializer)?;
+		s.parse::<T>().map_err(|_| serde::de::Error::custom("Parse from string failed"))
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// The API to query the derived values of quadratic democracy.
+	pub trait QuadraticDemocracyApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec + MaybeDisplay + MaybeFromStr,
+	{
+		/// The tally of referendum `index` and its projected outcome, or `None` if the referendum
+		/// is not ongoing.
+		fn referendum_tally(index: ReferendumIndex) -> Option<ReferendumTally<Balance>>;
+
+		/// The voting power of `who` in a referendum weighted by `weight` if it voted `balance`
+		/// with `conviction`, including the votes delegated to it. `None` if `who` couldn't vote
+		/// in such a referendum.
+		fn voting_power(
+			who: AccountId,
+			weight: VoteWeight,
+			balance: Balance,
+			conviction: Conviction,
+		) -> Option<VotingPower<Balance>>;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn should_serialize_and_deserialize_properly_with_string() {
+		let tally = ReferendumTally {
+			ayes: 10u64,
+			nays: 5,
+			turnout: 400,
+			weighted_turnout: 20,
+			electorate: 145,
+			weight: VoteWeight::Quadratic,
+			threshold: VoteThreshold::SimpleMajority,
+			approved: true,
+		};
+
+		let json_str = r#"{"ayes":"10","nays":"5","turnout":"400","weightedTurnout":"20","electorate":"145","weight":"Quadratic","threshold":"SimpleMajority","approved":true}"#;
+
+		assert_eq!(serde_json::to_string(&tally).unwrap(), json_str);
+		assert_eq!(serde_json::from_str::<ReferendumTally<u64>>(json_str).unwrap(), tally);
+
+		// should not panic
+		serde_json::to_value(&tally).unwrap();
+	}
+}
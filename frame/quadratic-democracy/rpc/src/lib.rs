@@ -0,0 +1,137 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC interface for the quadratic democracy module.
+
+use std::sync::Arc;
+use std::convert::TryFrom;
+use codec::Codec;
+use sp_blockchain::HeaderBackend;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_runtime::{generic::BlockId, traits::{Block as BlockT, MaybeDisplay, MaybeFromStr}};
+use sp_api::ProvideRuntimeApi;
+use pallet_quadratic_democracy_rpc_runtime_api::{
+	Conviction, ReferendumIndex, ReferendumTally, VoteWeight, VotingPower,
+};
+pub use pallet_quadratic_democracy_rpc_runtime_api::QuadraticDemocracyApi as QuadraticDemocracyRuntimeApi;
+pub use self::gen_client::Client as QuadraticDemocracyClient;
+
+#[rpc]
+pub trait QuadraticDemocracyApi<BlockHash, AccountId, Balance> {
+	/// Returns the weighted tally of an ongoing referendum together with its electorate and the
+	/// outcome it would have if it ended now, or `None` if the referendum is not ongoing.
+	#[rpc(name = "quadraticDemocracy_referendumTally")]
+	fn referendum_tally(
+		&self,
+		index: ReferendumIndex,
+		at: Option<BlockHash>
+	) -> Result<Option<ReferendumTally<Balance>>>;
+
+	/// Returns the votes `who` would cast by voting `balance` with `conviction` (0 to 6) in a
+	/// referendum weighted by `weight`, along with the votes delegated to it, or `None` if `who`
+	/// couldn't vote in such a referendum.
+	#[rpc(name = "quadraticDemocracy_votingPower")]
+	fn voting_power(
+		&self,
+		who: AccountId,
+		weight: VoteWeight,
+		balance: Balance,
+		conviction: u8,
+		at: Option<BlockHash>
+	) -> Result<Option<VotingPower<Balance>>>;
+}
+
+/// A struct that implements the [`QuadraticDemocracyApi`].
+pub struct QuadraticDemocracy<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> QuadraticDemocracy<C, P> {
+	/// Create new `QuadraticDemocracy` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		QuadraticDemocracy { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i64 {
+	fn from(e: Error) -> i64 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, AccountId, Balance> QuadraticDemocracyApi<<Block as BlockT>::Hash, AccountId, Balance>
+	for QuadraticDemocracy<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: QuadraticDemocracyRuntimeApi<Block, AccountId, Balance>,
+	AccountId: Codec,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+{
+	fn referendum_tally(
+		&self,
+		index: ReferendumIndex,
+		at: Option<<Block as BlockT>::Hash>
+	) -> Result<Option<ReferendumTally<Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		api.referendum_tally(&at, index).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query referendum tally.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn voting_power(
+		&self,
+		who: AccountId,
+		weight: VoteWeight,
+		balance: Balance,
+		conviction: u8,
+		at: Option<<Block as BlockT>::Hash>
+	) -> Result<Option<VotingPower<Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash
+		));
+
+		let conviction = Conviction::try_from(conviction).map_err(|_| RpcError {
+			code: ErrorCode::InvalidParams,
+			message: format!("Invalid conviction `{}`, expected 0 to 6.", conviction),
+			data: None,
+		})?;
+		api.voting_power(&at, who, weight, balance, conviction).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query voting power.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+}
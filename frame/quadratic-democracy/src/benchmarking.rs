@@ -26,12 +26,9 @@ use sp_runtime::traits::{Bounded, One};
 use crate::Module as Democracy;
 
 const SEED: u32 = 0;
-const MAX_USERS: u32 = 1000;
-const MAX_REFERENDUMS: u32 = 100;
-const MAX_PROPOSALS: u32 = 100;
-const MAX_SECONDERS: u32 = 100;
-const MAX_VETOERS: u32 = 100;
-const MAX_BYTES: u32 = 16_384;
+const MAX_BYTES: u32 = MAX_PREIMAGE_BYTES;
+const MAX_COMMITMENTS: u32 = 1_000;
+const MAX_MULTI_OPTION_VOTES: u32 = 1_000;
 
 fn assert_last_event<T: Trait>(generic_event: <T as Trait>::Event) {
 	let events = System::<T>::events();
@@ -52,7 +49,7 @@ fn add_proposal<T: Trait>(n: u32) -> Result<T::Hash, &'static str> {
 	let value = T::MinimumDeposit::get();
 	let proposal_hash: T::Hash = T::Hashing::hash_of(&n);
 
-	Democracy::<T>::propose(RawOrigin::Signed(other).into(), proposal_hash, value.into())?;
+	Democracy::<T>::propose(RawOrigin::Signed(other).into(), proposal_hash, value.into(), None)?;
 
 	Ok(proposal_hash)
 }
@@ -80,6 +77,38 @@ fn add_referendum<T: Trait>(n: u32) -> Result<ReferendumIndex, &'static str> {
 	Ok(referendum_index)
 }
 
+fn add_credit_referendum<T: Trait>(n: u32) -> Result<ReferendumIndex, &'static str> {
+	let proposal_hash = add_proposal::<T>(n)?;
+
+	Ok(Democracy::<T>::inject_referendum(
+		T::LaunchPeriod::get(),
+		proposal_hash,
+		VoteThreshold::SimpleMajority,
+		VoteWeight::Credits,
+		0.into(),
+	))
+}
+
+fn add_secret_referendum<T: Trait>(n: u32) -> Result<ReferendumIndex, &'static str> {
+	let proposal_hash = add_proposal::<T>(n)?;
+	// Leave time to commit before the reveal period.
+	let end = System::<T>::block_number() + T::VotingPeriod::get() + T::RevealPeriod::get();
+
+	let referendum_index = Democracy::<T>::inject_referendum(
+		end,
+		proposal_hash,
+		VoteThreshold::SimpleMajority,
+		VoteWeight::Quadratic,
+		0.into(),
+	);
+	Democracy::<T>::open_ballot(referendum_index, Ballot::Secret, end);
+	Ok(referendum_index)
+}
+
+fn option_hashes<T: Trait>(o: u32) -> Vec<T::Hash> {
+	(0 .. o).map(|i| T::Hashing::hash_of(&i)).collect()
+}
+
 fn account_vote<T: Trait>(b: BalanceOf<T>) -> AccountVote<BalanceOf<T>> {
 	let v = Vote {
 		aye: true,
@@ -92,12 +121,25 @@ fn account_vote<T: Trait>(b: BalanceOf<T>) -> AccountVote<BalanceOf<T>> {
 	}
 }
 
-fn open_activate_proxy<T: Trait>(u: u32) -> Result<(T::AccountId, T::AccountId), &'static str> {
+/// Make `who` a person with `l` sub-accounts, each of them linked to it.
+fn link_sub_accounts<T: Trait>(who: &T::AccountId, l: u32) -> Result<(), &'static str> {
+	let subs = (0 .. l).map(|i| {
+		let sub: T::AccountId = account("sub", i, SEED);
+		T::Currency::make_free_balance_be(&sub, T::Currency::minimum_balance() * 100.into());
+		sub
+	}).collect::<Vec<_>>();
+	T::Personhood::make_person(who, subs.clone());
+	for sub in subs.into_iter() {
+		Democracy::<T>::link_sub_account(RawOrigin::Signed(sub).into(), who.clone())?;
+	}
+	Ok(())
+}
+
+fn add_voting_proxy<T: Trait>(u: u32) -> Result<(T::AccountId, T::AccountId), &'static str> {
 	let caller = funded_account::<T>("caller", u);
 	let voter = funded_account::<T>("voter", u);
 
-	Democracy::<T>::open_proxy(RawOrigin::Signed(caller.clone()).into(), voter.clone())?;
-	Democracy::<T>::activate_proxy(RawOrigin::Signed(voter.clone()).into(), caller.clone())?;
+	T::Proxies::add_voting_proxy(&voter, &caller).map_err(|_| "failed to add voting proxy")?;
 
 	Ok((caller, voter))
 }
@@ -106,7 +148,7 @@ benchmarks! {
 	_ { }
 
 	propose {
-		let p in 1 .. MAX_PROPOSALS;
+		let p in 1 .. T::MaxProposals::get() - 1;
 
 		// Add p proposals
 		for i in 0 .. p {
@@ -118,13 +160,13 @@ benchmarks! {
 		let caller = funded_account::<T>("caller", 0);
 		let proposal_hash: T::Hash = T::Hashing::hash_of(&p);
 		let value = T::MinimumDeposit::get();
-	}: _(RawOrigin::Signed(caller), proposal_hash, value.into())
+	}: _(RawOrigin::Signed(caller), proposal_hash, value.into(), None)
 	verify {
 		assert_eq!(Democracy::<T>::public_props().len(), (p + 1) as usize, "Proposals not created.");
 	}
 
 	second {
-		let s in 0 .. MAX_SECONDERS;
+		let s in 0 .. T::MaxSeconds::get() - 1;
 
 		let caller = funded_account::<T>("caller", 0);
 		let proposal_hash = add_proposal::<T>(s)?;
@@ -144,9 +186,11 @@ benchmarks! {
 	}
 
 	vote_new {
-		let r in 1 .. MAX_REFERENDUMS;
+		let r in 1 .. T::MaxVotes::get() - 1;
+		let l in 0 .. T::MaxLinkedSubAccounts::get();
 
 		let caller = funded_account::<T>("caller", 0);
+		link_sub_accounts::<T>(&caller, l)?;
 		let account_vote = account_vote::<T>(100.into());
 
 		// We need to create existing direct votes
@@ -172,9 +216,11 @@ benchmarks! {
 	}
 
 	vote_existing {
-		let r in 1 .. MAX_REFERENDUMS;
+		let r in 1 .. T::MaxVotes::get() - 1;
+		let l in 0 .. T::MaxLinkedSubAccounts::get();
 
 		let caller = funded_account::<T>("caller", 0);
+		link_sub_accounts::<T>(&caller, l)?;
 		let account_vote = account_vote::<T>(100.into());
 
 		// We need to create existing direct votes
@@ -207,14 +253,17 @@ benchmarks! {
 			ReferendumInfo::Ongoing(r) => r.tally,
 			_ => return Err("referendum not ongoing"),
 		};
-		assert_eq!(tally.nays, 1000.into(), "changed vote was not recorded");
+		// Linked capital adds to the weight of the nay.
+		ensure!(tally.ayes.is_zero() && !tally.nays.is_zero(), "changed vote was not recorded");
 	}
 
 	// Basically copy paste of `vote_new`
 	proxy_vote_new {
-		let r in 1 .. MAX_REFERENDUMS;
+		let r in 1 .. T::MaxVotes::get() - 1;
+		let l in 0 .. T::MaxLinkedSubAccounts::get();
 
-		let (caller, voter) = open_activate_proxy::<T>(0)?;
+		let (caller, voter) = add_voting_proxy::<T>(0)?;
+		link_sub_accounts::<T>(&voter, l)?;
 		let account_vote = account_vote::<T>(100.into());
 
 		// Populate existing direct votes for the voter, they can vote on their own behalf
@@ -225,7 +274,7 @@ benchmarks! {
 
 		let referendum_index = add_referendum::<T>(r)?;
 
-	}: proxy_vote(RawOrigin::Signed(caller), referendum_index, account_vote)
+	}: proxy_vote(RawOrigin::Signed(caller), voter.clone(), referendum_index, account_vote)
 	verify {
 		let votes = match VotingOf::<T>::get(&voter) {
 			Voting::Direct { votes, .. } => votes,
@@ -236,9 +285,11 @@ benchmarks! {
 
 	// Basically copy paste of `vote_existing`
 	proxy_vote_existing {
-		let r in 1 .. MAX_REFERENDUMS;
+		let r in 1 .. T::MaxVotes::get() - 1;
+		let l in 0 .. T::MaxLinkedSubAccounts::get();
 
-		let (caller, voter) = open_activate_proxy::<T>(0)?;
+		let (caller, voter) = add_voting_proxy::<T>(0)?;
+		link_sub_accounts::<T>(&voter, l)?;
 		let account_vote = account_vote::<T>(100.into());
 
 		// We need to create existing direct votes
@@ -258,7 +309,7 @@ benchmarks! {
 		let referendum_index = Democracy::<T>::referendum_count() - 1;
 
 		// This tests when a user changes a vote
-	}: proxy_vote(RawOrigin::Signed(caller.clone()), referendum_index, new_vote)
+	}: proxy_vote(RawOrigin::Signed(caller.clone()), voter.clone(), referendum_index, new_vote)
 	verify {
 		let votes = match VotingOf::<T>::get(&voter) {
 			Voting::Direct { votes, .. } => votes,
@@ -271,11 +322,12 @@ benchmarks! {
 			ReferendumInfo::Ongoing(r) => r.tally,
 			_ => return Err("referendum not ongoing"),
 		};
-		assert_eq!(tally.nays, 1000.into(), "changed vote was not recorded");
+		// Linked capital adds to the weight of the nay.
+		ensure!(tally.ayes.is_zero() && !tally.nays.is_zero(), "changed vote was not recorded");
 	}
 
 	emergency_cancel {
-		let r in 1 .. MAX_REFERENDUMS;
+		let r in 1 .. T::MaxVotes::get() - 1;
 		let origin = T::CancellationOrigin::successful_origin();
 
 		// Create and cancel a bunch of referendums
@@ -297,7 +349,7 @@ benchmarks! {
 
 	// Worst case scenario, we external propose a previously blacklisted proposal
 	external_propose {
-		let p in 1 .. MAX_PROPOSALS;
+		let p in 1 .. T::MaxProposals::get() - 1;
 
 		let origin = T::ExternalOrigin::successful_origin();
 		let proposal_hash = T::Hashing::hash_of(&p);
@@ -315,7 +367,7 @@ benchmarks! {
 	}
 
 	external_propose_majority {
-		let p in 1 .. MAX_PROPOSALS;
+		let p in 1 .. T::MaxProposals::get() - 1;
 
 		let origin = T::ExternalMajorityOrigin::successful_origin();
 		let proposal_hash = T::Hashing::hash_of(&p);
@@ -327,7 +379,7 @@ benchmarks! {
 	}
 
 	external_propose_default {
-		let p in 1 .. MAX_PROPOSALS;
+		let p in 1 .. T::MaxProposals::get() - 1;
 
 		let origin = T::ExternalDefaultOrigin::successful_origin();
 		let proposal_hash = T::Hashing::hash_of(&p);
@@ -338,8 +390,64 @@ benchmarks! {
 		ensure!(<NextExternal<T>>::exists(), "External proposal didn't work");
 	}
 
+	// Worst case scenario, we external propose a previously blacklisted proposal
+	external_propose_with_weight {
+		let p in 1 .. T::MaxProposals::get() - 1;
+
+		let origin = T::ExternalOrigin::successful_origin();
+		let proposal_hash = T::Hashing::hash_of(&p);
+		// Add proposal to blacklist with block number 0
+		Blacklist::<T>::insert(
+			proposal_hash,
+			(T::BlockNumber::zero(), vec![T::AccountId::default()])
+		);
+
+		let call = Call::<T>::external_propose_with_weight(proposal_hash, VoteWeight::Standard);
+	}: { call.dispatch(origin)? }
+	verify {
+		// External proposal created
+		ensure!(<NextExternal<T>>::exists(), "External proposal didn't work");
+	}
+
+	external_propose_majority_with_weight {
+		let p in 1 .. T::MaxProposals::get() - 1;
+
+		let origin = T::ExternalMajorityOrigin::successful_origin();
+		let proposal_hash = T::Hashing::hash_of(&p);
+		let call = Call::<T>::external_propose_majority_with_weight(proposal_hash, VoteWeight::Standard);
+	}: { call.dispatch(origin)? }
+	verify {
+		// External proposal created
+		ensure!(<NextExternal<T>>::exists(), "External proposal didn't work");
+	}
+
+	external_propose_default_with_weight {
+		let p in 1 .. T::MaxProposals::get() - 1;
+
+		let origin = T::ExternalDefaultOrigin::successful_origin();
+		let proposal_hash = T::Hashing::hash_of(&p);
+		let call = Call::<T>::external_propose_default_with_weight(proposal_hash, VoteWeight::Standard);
+	}: { call.dispatch(origin)? }
+	verify {
+		// External proposal created
+		ensure!(<NextExternal<T>>::exists(), "External proposal didn't work");
+	}
+
+	external_propose_quorum {
+		let p in 1 .. T::MaxProposals::get() - 1;
+
+		let origin = T::ExternalMajorityOrigin::successful_origin();
+		let proposal_hash = T::Hashing::hash_of(&p);
+		let turnout = Perbill::from_percent(10);
+		let call = Call::<T>::external_propose_quorum(proposal_hash, p, turnout, VoteWeight::Quadratic);
+	}: { call.dispatch(origin)? }
+	verify {
+		// External proposal created
+		ensure!(<NextExternal<T>>::exists(), "External proposal didn't work");
+	}
+
 	fast_track {
-		let p in 1 .. MAX_PROPOSALS;
+		let p in 1 .. T::MaxProposals::get() - 1;
 
 		let origin_propose = T::ExternalDefaultOrigin::successful_origin();
 		let proposal_hash: T::Hash = T::Hashing::hash_of(&p);
@@ -383,7 +491,7 @@ benchmarks! {
 	}
 
 	cancel_referendum {
-		let r in 0 .. MAX_REFERENDUMS;
+		let r in 0 .. T::MaxVotes::get() - 1;
 		// Should have no effect on the execution time.
 		for i in 0..r {
 			add_referendum::<T>(i)?;
@@ -392,7 +500,7 @@ benchmarks! {
 	}: _(RawOrigin::Root, referendum_index)
 
 	cancel_queued {
-		let r in 1 .. MAX_REFERENDUMS;
+		let r in 1 .. T::MaxVotes::get() - 1;
 		// Should have no effect on the execution time.
 		for i in 0..r {
 			add_referendum::<T>(i)?;
@@ -402,7 +510,7 @@ benchmarks! {
 
 	// Note that we have a separate benchmark for `launch_next`
 	on_initialize_external {
-		let r in 0 .. MAX_REFERENDUMS;
+		let r in 0 .. T::MaxVotes::get() - 1;
 
 		for i in 0..r {
 			add_referendum::<T>(i)?;
@@ -440,7 +548,7 @@ benchmarks! {
 	}
 
 	on_initialize_public {
-		let r in 1 .. MAX_REFERENDUMS;
+		let r in 1 .. T::MaxVotes::get() - 1;
 
 		for i in 0..r {
 			add_referendum::<T>(i)?;
@@ -469,35 +577,8 @@ benchmarks! {
 		}
 	}
 
-	activate_proxy {
-		let u in 1 .. MAX_USERS;
-
-		let caller: T::AccountId = funded_account::<T>("caller", u);
-		let proxy: T::AccountId = funded_account::<T>("proxy", u);
-		Democracy::<T>::open_proxy(RawOrigin::Signed(proxy.clone()).into(), caller.clone())?;
-	}: _(RawOrigin::Signed(caller.clone()), proxy.clone())
-	verify {
-		assert_eq!(Democracy::<T>::proxy(proxy), Some(ProxyState::Active(caller)));
-	}
-
-	close_proxy {
-		let u in 1 .. MAX_USERS;
-		let (caller, _) = open_activate_proxy::<T>(u)?;
-	}: _(RawOrigin::Signed(caller.clone()))
-	verify {
-		assert_eq!(Democracy::<T>::proxy(caller), None);
-	}
-
-	deactivate_proxy {
-		let u in 1 .. MAX_USERS;
-		let (caller, voter) = open_activate_proxy::<T>(u)?;
-	}: _(RawOrigin::Signed(voter.clone()), caller.clone())
-	verify {
-		assert_eq!(Democracy::<T>::proxy(caller), Some(ProxyState::Open(voter)));
-	}
-
 	delegate {
-		let r in 1 .. MAX_REFERENDUMS;
+		let r in 1 .. T::MaxVotes::get() - 1;
 
 		let initial_balance: BalanceOf<T> = 100.into();
 		let delegated_balance: BalanceOf<T> = 1000.into();
@@ -546,7 +627,7 @@ benchmarks! {
 	}
 
 	undelegate {
-		let r in 1 .. MAX_REFERENDUMS;
+		let r in 1 .. T::MaxVotes::get() - 1;
 
 		let initial_balance: BalanceOf<T> = 100.into();
 		let delegated_balance: BalanceOf<T> = 1000.into();
@@ -590,8 +671,72 @@ benchmarks! {
 		}
 	}
 
+	delegate_class {
+		let r in 1 .. T::MaxVotes::get() - 1;
+
+		let initial_balance: BalanceOf<T> = 100.into();
+		let delegated_balance: BalanceOf<T> = 1000.into();
+		let class = DelegationClass { weight: Some(VoteWeight::Quadratic), source: None };
+
+		let caller = funded_account::<T>("caller", 0);
+		// Caller will initially delegate to `old_delegate`
+		let old_delegate: T::AccountId = funded_account::<T>("old_delegate", r);
+		Democracy::<T>::delegate_class(
+			RawOrigin::Signed(caller.clone()).into(),
+			class,
+			old_delegate.clone(),
+			Conviction::Locked1x,
+			delegated_balance,
+		)?;
+		// Caller will now switch to `new_delegate`, which has voted in referenda of the class
+		let new_delegate: T::AccountId = funded_account::<T>("new_delegate", r);
+		let account_vote = account_vote::<T>(initial_balance);
+		for i in 0..r {
+			let ref_idx = add_referendum::<T>(i)?;
+			Democracy::<T>::vote(RawOrigin::Signed(new_delegate.clone()).into(), ref_idx, account_vote.clone())?;
+		}
+	}: _(RawOrigin::Signed(caller.clone()), class, new_delegate.clone(), Conviction::Locked1x, delegated_balance)
+	verify {
+		let delegating = Democracy::<T>::class_delegating_of(&caller);
+		assert_eq!(delegating.len(), 1, "delegation was not replaced");
+		assert_eq!(delegating[0].target, new_delegate, "delegation target didn't work");
+		let delegations = Democracy::<T>::class_delegations_of(&new_delegate);
+		assert_eq!(delegations[0].1.capital, delegated_balance, "delegation was not recorded.");
+	}
+
+	undelegate_class {
+		let r in 1 .. T::MaxVotes::get() - 1;
+
+		let initial_balance: BalanceOf<T> = 100.into();
+		let delegated_balance: BalanceOf<T> = 1000.into();
+		let class = DelegationClass { weight: Some(VoteWeight::Quadratic), source: None };
+
+		let caller = funded_account::<T>("caller", 0);
+		let the_delegate: T::AccountId = funded_account::<T>("delegate", r);
+		Democracy::<T>::delegate_class(
+			RawOrigin::Signed(caller.clone()).into(),
+			class,
+			the_delegate.clone(),
+			Conviction::Locked1x,
+			delegated_balance,
+		)?;
+		// We need to create direct votes for the `delegate` in referenda of the class
+		let account_vote = account_vote::<T>(initial_balance);
+		for i in 0..r {
+			let ref_idx = add_referendum::<T>(i)?;
+			Democracy::<T>::vote(
+				RawOrigin::Signed(the_delegate.clone()).into(),
+				ref_idx,
+				account_vote.clone()
+			)?;
+		}
+	}: _(RawOrigin::Signed(caller.clone()), class)
+	verify {
+		assert!(Democracy::<T>::class_delegating_of(&caller).is_empty(), "undelegation failed");
+	}
+
 	clear_public_proposals {
-		let p in 0 .. MAX_PROPOSALS;
+		let p in 0 .. T::MaxProposals::get() - 1;
 
 		for i in 0 .. p {
 			add_proposal::<T>(i)?;
@@ -660,7 +805,7 @@ benchmarks! {
 
 	// Test when unlock will remove locks
 	unlock_remove {
-		let r in 1 .. MAX_REFERENDUMS;
+		let r in 1 .. T::MaxVotes::get() - 1;
 
 		let locker = funded_account::<T>("locker", 0);
 		// Populate votes so things are locked
@@ -683,7 +828,7 @@ benchmarks! {
 
 	// Test when unlock will set a new value
 	unlock_set {
-		let r in 1 .. MAX_REFERENDUMS;
+		let r in 1 .. T::MaxVotes::get() - 1;
 
 		let locker = funded_account::<T>("locker", 0);
 		// Populate votes so things are locked
@@ -724,16 +869,8 @@ benchmarks! {
 		assert_eq!(voting.locked_balance(), base_balance);
 	}
 
-	open_proxy {
-		let u in 1 .. MAX_USERS;
-
-		let caller: T::AccountId = funded_account::<T>("caller", u);
-		let proxy: T::AccountId = funded_account::<T>("proxy", u);
-
-	}: _(RawOrigin::Signed(proxy), caller)
-
 	remove_vote {
-		let r in 1 .. MAX_REFERENDUMS;
+		let r in 1 .. T::MaxVotes::get() - 1;
 
 		let caller = funded_account::<T>("caller", 0);
 		let account_vote = account_vote::<T>(100.into());
@@ -761,7 +898,7 @@ benchmarks! {
 	}
 
 	remove_other_vote {
-		let r in 1 .. MAX_REFERENDUMS;
+		let r in 1 .. T::MaxVotes::get() - 1;
 
 		let other = funded_account::<T>("other", r);
 		let account_vote = account_vote::<T>(100.into());
@@ -780,7 +917,7 @@ benchmarks! {
 		let referendum_index = r - 1;
 		ReferendumInfoOf::<T>::insert(
 			referendum_index,
-			ReferendumInfo::Finished { end: T::BlockNumber::zero(), approved: true }
+			ReferendumInfo::Finished { end: T::BlockNumber::zero(), approved: true, result: None }
 		);
 		let caller = funded_account::<T>("caller", 0);
 
@@ -795,14 +932,14 @@ benchmarks! {
 		assert_eq!(votes.len(), (r - 1) as usize, "Vote was not removed");
 	}
 
-	// This is a copy of delegate benchmark, but with `open_activate_proxy`
+	// This is a copy of delegate benchmark, but with `add_voting_proxy`
 	proxy_delegate {
-		let r in 1 .. MAX_REFERENDUMS;
+		let r in 1 .. T::MaxVotes::get() - 1;
 
 		let initial_balance: BalanceOf<T> = 100.into();
 		let delegated_balance: BalanceOf<T> = 1000.into();
 
-		let (caller, voter) = open_activate_proxy::<T>(0)?;
+		let (caller, voter) = add_voting_proxy::<T>(0)?;
 
 		// Voter will initially delegate to `old_delegate`
 		let old_delegate: T::AccountId = funded_account::<T>("old_delegate", r);
@@ -831,7 +968,7 @@ benchmarks! {
 			_ => return Err("Votes are not direct"),
 		};
 		assert_eq!(votes.len(), r as usize, "Votes were not recorded.");
-	}: _(RawOrigin::Signed(caller.clone()), new_delegate.clone(), Conviction::Locked1x, delegated_balance)
+	}: _(RawOrigin::Signed(caller.clone()), voter.clone(), new_delegate.clone(), Conviction::Locked1x, delegated_balance)
 	verify {
 		let (target, balance) = match VotingOf::<T>::get(&voter) {
 			Voting::Delegating { target, balance, .. } => (target, balance),
@@ -846,14 +983,14 @@ benchmarks! {
 		assert_eq!(delegations.capital, delegated_balance, "delegation was not recorded.");
 	}
 
-	// This is a copy of undelegate benchmark, but with `open_activate_proxy`
+	// This is a copy of undelegate benchmark, but with `add_voting_proxy`
 	proxy_undelegate {
-		let r in 1 .. MAX_REFERENDUMS;
+		let r in 1 .. T::MaxVotes::get() - 1;
 
 		let initial_balance: BalanceOf<T> = 100.into();
 		let delegated_balance: BalanceOf<T> = 1000.into();
 
-		let (caller, voter) = open_activate_proxy::<T>(0)?;
+		let (caller, voter) = add_voting_proxy::<T>(0)?;
 		// Caller will delegate
 		let the_delegate: T::AccountId = funded_account::<T>("delegate", r);
 		Democracy::<T>::delegate(
@@ -883,7 +1020,7 @@ benchmarks! {
 			_ => return Err("Votes are not direct"),
 		};
 		assert_eq!(votes.len(), r as usize, "Votes were not recorded.");
-	}: _(RawOrigin::Signed(caller.clone()))
+	}: _(RawOrigin::Signed(caller.clone()), voter.clone())
 	verify {
 		// Voting should now be direct
 		match VotingOf::<T>::get(&voter) {
@@ -893,9 +1030,9 @@ benchmarks! {
 	}
 
 	proxy_remove_vote {
-		let r in 1 .. MAX_REFERENDUMS;
+		let r in 1 .. T::MaxVotes::get() - 1;
 
-		let (caller, voter) = open_activate_proxy::<T>(0)?;
+		let (caller, voter) = add_voting_proxy::<T>(0)?;
 		let account_vote = account_vote::<T>(100.into());
 
 		for i in 0 .. r {
@@ -911,7 +1048,7 @@ benchmarks! {
 
 		let referendum_index = r - 1;
 
-	}: _(RawOrigin::Signed(caller.clone()), referendum_index)
+	}: _(RawOrigin::Signed(caller.clone()), voter.clone(), referendum_index)
 	verify {
 		let votes = match VotingOf::<T>::get(&voter) {
 			Voting::Direct { votes, .. } => votes,
@@ -920,6 +1057,252 @@ benchmarks! {
 		assert_eq!(votes.len(), (r - 1) as usize, "Vote was not removed");
 	}
 
+	vote_with_credits {
+		let r in 1 .. T::MaxVotes::get() - 1;
+
+		let caller = funded_account::<T>("caller", 0);
+		T::Personhood::make_person(&caller, Vec::new());
+		let account_vote = account_vote::<T>(100.into());
+
+		// We need to create existing direct votes
+		for i in 0 .. r {
+			let ref_idx = add_referendum::<T>(i)?;
+			Democracy::<T>::vote(RawOrigin::Signed(caller.clone()).into(), ref_idx, account_vote.clone())?;
+		}
+
+		let referendum_index = add_credit_referendum::<T>(r)?;
+
+	}: _(RawOrigin::Signed(caller.clone()), referendum_index, true, 1.into())
+	verify {
+		let votes = match VotingOf::<T>::get(&caller) {
+			Voting::Direct { votes, .. } => votes,
+			_ => return Err("Votes are not direct"),
+		};
+		assert_eq!(votes.len(), (r + 1) as usize, "Vote was not recorded.");
+	}
+
+	// Worst case scenario, the options were all blacklisted before
+	start_multi_option_referendum {
+		let o in 2 .. T::MaxOptions::get();
+
+		let origin = T::ExternalMajorityOrigin::successful_origin();
+		let proposal_hashes = option_hashes::<T>(o);
+		for proposal_hash in proposal_hashes.iter() {
+			Blacklist::<T>::insert(
+				proposal_hash,
+				(T::BlockNumber::zero(), vec![T::AccountId::default()])
+			);
+		}
+
+		let call = Call::<T>::start_multi_option_referendum(proposal_hashes);
+	}: { call.dispatch(origin)? }
+	verify {
+		ensure!(Democracy::<T>::multi_option_referendum_info(0).is_some(), "referendum not created");
+	}
+
+	// Worst case scenario, the voter replaces an earlier vote
+	vote_options {
+		let o in 2 .. T::MaxOptions::get();
+
+		let caller = funded_account::<T>("caller", 0);
+		T::Personhood::make_person(&caller, Vec::new());
+		let referendum_index = Democracy::<T>::inject_multi_option_referendum(
+			T::LaunchPeriod::get(),
+			option_hashes::<T>(o),
+			0.into(),
+		);
+		let balances: Vec<BalanceOf<T>> = vec![100.into(); o as usize];
+		Democracy::<T>::vote_options(
+			RawOrigin::Signed(caller.clone()).into(),
+			referendum_index,
+			balances.clone(),
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), referendum_index, balances)
+	verify {
+		let votes = MultiOptionVotesOf::<T>::get(referendum_index, &caller);
+		assert_eq!(votes.len(), o as usize, "Vote was not recorded.");
+	}
+
+	// Worst case scenario, we external propose a previously blacklisted proposal
+	external_propose_secret {
+		let p in 1 .. T::MaxProposals::get() - 1;
+
+		let origin = T::ExternalOrigin::successful_origin();
+		let proposal_hash = T::Hashing::hash_of(&p);
+		// Add proposal to blacklist with block number 0
+		Blacklist::<T>::insert(
+			proposal_hash,
+			(T::BlockNumber::zero(), vec![T::AccountId::default()])
+		);
+
+		let call = Call::<T>::external_propose_secret(
+			proposal_hash,
+			VoteThreshold::SuperMajorityApprove,
+			VoteWeight::Quadratic,
+		);
+	}: { call.dispatch(origin)? }
+	verify {
+		// External proposal created
+		ensure!(<NextExternal<T>>::exists(), "External proposal didn't work");
+	}
+
+	commit_vote {
+		let r in 0 .. T::MaxVotes::get() - 1;
+		// Should have no effect on the execution time.
+		for i in 0..r {
+			add_referendum::<T>(i)?;
+		}
+		let referendum_index = add_secret_referendum::<T>(r)?;
+
+		let caller = funded_account::<T>("caller", 0);
+		let commitment = T::Hashing::hash_of(&r);
+	}: _(RawOrigin::Signed(caller.clone()), referendum_index, commitment)
+	verify {
+		ensure!(Democracy::<T>::commitment_of(referendum_index, &caller).is_some(), "Vote not committed");
+	}
+
+	reveal_vote {
+		let r in 1 .. T::MaxVotes::get() - 1;
+		let l in 0 .. T::MaxLinkedSubAccounts::get();
+
+		let caller = funded_account::<T>("caller", 0);
+		link_sub_accounts::<T>(&caller, l)?;
+		let account_vote = account_vote::<T>(100.into());
+
+		// We need to create existing direct votes
+		for i in 0 .. r {
+			let ref_idx = add_referendum::<T>(i)?;
+			Democracy::<T>::vote(RawOrigin::Signed(caller.clone()).into(), ref_idx, account_vote.clone())?;
+		}
+
+		let referendum_index = add_secret_referendum::<T>(r)?;
+		let salt = T::Hashing::hash_of(&r);
+		let commitment = T::Hashing::hash_of(&(&caller, referendum_index, account_vote.clone(), salt));
+		Democracy::<T>::commit_vote(RawOrigin::Signed(caller.clone()).into(), referendum_index, commitment)?;
+
+		let reveal_from = Democracy::<T>::reveal_from(referendum_index).ok_or("not a secret ballot")?;
+		System::<T>::set_block_number(reveal_from);
+
+	}: _(RawOrigin::Signed(caller.clone()), referendum_index, account_vote, salt)
+	verify {
+		let votes = match VotingOf::<T>::get(&caller) {
+			Voting::Direct { votes, .. } => votes,
+			_ => return Err("Votes are not direct"),
+		};
+		assert_eq!(votes.len(), (r + 1) as usize, "Vote was not recorded.");
+		ensure!(Democracy::<T>::commitment_of(referendum_index, &caller).is_none(), "Vote not revealed");
+	}
+
+	prune_referendum {
+		let r in 0 .. T::MaxVotes::get() - 1;
+		// Should have no effect on the execution time.
+		for i in 0..r {
+			add_referendum::<T>(i)?;
+		}
+		let referendum_index = add_referendum::<T>(r)?;
+		let result = ReferendumResult {
+			tally: Default::default(),
+			threshold: VoteThreshold::SimpleMajority,
+			weight: VoteWeight::Quadratic,
+		};
+		ReferendumInfoOf::<T>::insert(
+			referendum_index,
+			ReferendumInfo::Finished { end: T::BlockNumber::zero(), approved: true, result: Some(result) }
+		);
+		System::<T>::set_block_number(T::HistoryRetention::get());
+
+		let caller = funded_account::<T>("caller", 0);
+	}: _(RawOrigin::Signed(caller), referendum_index)
+	verify {
+		ensure!(Democracy::<T>::referendum_info(referendum_index).is_none(), "Referendum not pruned");
+	}
+
+	reap_commitments {
+		let c in 0 .. MAX_COMMITMENTS;
+
+		let referendum_index = add_secret_referendum::<T>(0)?;
+		for i in 0 .. c {
+			let voter = funded_account::<T>("voter", i);
+			let commitment = T::Hashing::hash_of(&i);
+			Democracy::<T>::commit_vote(RawOrigin::Signed(voter).into(), referendum_index, commitment)?;
+		}
+		// Slashing the commitments of a finished ballot is the heavier path.
+		let result = ReferendumResult {
+			tally: Default::default(),
+			threshold: VoteThreshold::SimpleMajority,
+			weight: VoteWeight::Quadratic,
+		};
+		ReferendumInfoOf::<T>::insert(
+			referendum_index,
+			ReferendumInfo::Finished { end: T::BlockNumber::zero(), approved: true, result: Some(result) }
+		);
+
+		let caller = funded_account::<T>("caller", 0);
+	}: _(RawOrigin::Signed(caller), referendum_index, c)
+	verify {
+		ensure!(
+			CommitmentOf::<T>::iter_prefix_values(referendum_index).next().is_none(),
+			"Commitments not reaped",
+		);
+	}
+
+	reap_multi_option_votes {
+		let v in 0 .. MAX_MULTI_OPTION_VOTES;
+
+		let referendum_index = Democracy::<T>::inject_multi_option_referendum(
+			T::LaunchPeriod::get(),
+			option_hashes::<T>(T::MaxOptions::get()),
+			0.into(),
+		);
+		for i in 0 .. v {
+			let voter = funded_account::<T>("voter", i);
+			T::Personhood::make_person(&voter, Vec::new());
+			let balances: Vec<BalanceOf<T>> = vec![100.into(); T::MaxOptions::get() as usize];
+			Democracy::<T>::vote_options(RawOrigin::Signed(voter).into(), referendum_index, balances)?;
+		}
+		MultiOptionReferendumInfoOf::<T>::insert(
+			referendum_index,
+			MultiOptionInfo::Finished { winner: None, end: T::BlockNumber::zero() },
+		);
+
+		let caller = funded_account::<T>("caller", 0);
+	}: _(RawOrigin::Signed(caller), referendum_index, v)
+	verify {
+		ensure!(
+			MultiOptionVotesOf::<T>::iter_prefix_values(referendum_index).next().is_none(),
+			"Votes not reaped",
+		);
+	}
+
+	link_sub_account {
+		let r in 0 .. T::MaxVotes::get() - 1;
+		// Should have no effect on the execution time.
+		for i in 0..r {
+			add_referendum::<T>(i)?;
+		}
+
+		let caller = funded_account::<T>("caller", 0);
+		let person = funded_account::<T>("person", 0);
+	}: _(RawOrigin::Signed(caller.clone()), person.clone())
+	verify {
+		ensure!(Democracy::<T>::linked_to(&caller) == Some(person), "Sub-account not linked");
+	}
+
+	unlink_sub_account {
+		let r in 0 .. T::MaxVotes::get() - 1;
+		// Should have no effect on the execution time.
+		for i in 0..r {
+			add_referendum::<T>(i)?;
+		}
+
+		let caller = funded_account::<T>("caller", 0);
+		let person = funded_account::<T>("person", 0);
+		Democracy::<T>::link_sub_account(RawOrigin::Signed(caller.clone()).into(), person)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		ensure!(Democracy::<T>::linked_to(&caller).is_none(), "Sub-account not unlinked");
+	}
+
 	enact_proposal_execute {
 		// Num of bytes in encoded proposal
 		let b in 0 .. MAX_BYTES;
@@ -982,18 +1365,20 @@ mod tests {
 			assert_ok!(test_benchmark_external_propose::<Test>());
 			assert_ok!(test_benchmark_external_propose_majority::<Test>());
 			assert_ok!(test_benchmark_external_propose_default::<Test>());
+			assert_ok!(test_benchmark_external_propose_with_weight::<Test>());
+			assert_ok!(test_benchmark_external_propose_majority_with_weight::<Test>());
+			assert_ok!(test_benchmark_external_propose_default_with_weight::<Test>());
+			assert_ok!(test_benchmark_external_propose_quorum::<Test>());
 			assert_ok!(test_benchmark_fast_track::<Test>());
 			assert_ok!(test_benchmark_veto_external::<Test>());
 			assert_ok!(test_benchmark_cancel_referendum::<Test>());
 			assert_ok!(test_benchmark_cancel_queued::<Test>());
 			assert_ok!(test_benchmark_on_initialize_external::<Test>());
 			assert_ok!(test_benchmark_on_initialize_public::<Test>());
-			assert_ok!(test_benchmark_open_proxy::<Test>());
-			assert_ok!(test_benchmark_activate_proxy::<Test>());
-			assert_ok!(test_benchmark_close_proxy::<Test>());
-			assert_ok!(test_benchmark_deactivate_proxy::<Test>());
 			assert_ok!(test_benchmark_delegate::<Test>());
 			assert_ok!(test_benchmark_undelegate::<Test>());
+			assert_ok!(test_benchmark_delegate_class::<Test>());
+			assert_ok!(test_benchmark_undelegate_class::<Test>());
 			assert_ok!(test_benchmark_clear_public_proposals::<Test>());
 			assert_ok!(test_benchmark_note_preimage::<Test>());
 			assert_ok!(test_benchmark_note_imminent_preimage::<Test>());
@@ -1005,6 +1390,17 @@ mod tests {
 			assert_ok!(test_benchmark_proxy_delegate::<Test>());
 			assert_ok!(test_benchmark_proxy_undelegate::<Test>());
 			assert_ok!(test_benchmark_proxy_remove_vote::<Test>());
+			assert_ok!(test_benchmark_vote_with_credits::<Test>());
+			assert_ok!(test_benchmark_start_multi_option_referendum::<Test>());
+			assert_ok!(test_benchmark_vote_options::<Test>());
+			assert_ok!(test_benchmark_external_propose_secret::<Test>());
+			assert_ok!(test_benchmark_commit_vote::<Test>());
+			assert_ok!(test_benchmark_reveal_vote::<Test>());
+			assert_ok!(test_benchmark_prune_referendum::<Test>());
+			assert_ok!(test_benchmark_reap_commitments::<Test>());
+			assert_ok!(test_benchmark_reap_multi_option_votes::<Test>());
+			assert_ok!(test_benchmark_link_sub_account::<Test>());
+			assert_ok!(test_benchmark_unlink_sub_account::<Test>());
 			assert_ok!(test_benchmark_enact_proposal_execute::<Test>());
 			assert_ok!(test_benchmark_enact_proposal_slash::<Test>());
 		});
@@ -16,13 +16,16 @@
 
 //! The conviction datatype.
 
-use sp_std::{result::Result, convert::TryFrom};
-use sp_runtime::{RuntimeDebug, traits::{Zero, Bounded, CheckedMul, CheckedDiv}};
+use sp_std::{prelude::*, result::Result, convert::TryFrom};
+use sp_runtime::{RuntimeDebug, traits::{Zero, Bounded, CheckedMul, CheckedDiv, IntegerSquareRoot}};
 use codec::{Encode, Decode};
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
 use crate::types::Delegations;
 
 /// A value denoting the strength of conviction of a vote.
 #[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Conviction {
 	/// 0.1x votes, unlocked.
 	None,
@@ -76,55 +79,138 @@ impl TryFrom<u8> for Conviction {
 	}
 }
 
-impl Conviction {
-	/// The amount of time (in number of periods) that our conviction implies a successful voter's
-	/// balance should be locked for.
-	pub fn lock_periods(self) -> u32 {
-		match self {
-			Conviction::None => 0,
-			Conviction::Locked1x => 1,
-			Conviction::Locked2x => 2,
-			Conviction::Locked3x => 4,
-			Conviction::Locked4x => 8,
-			Conviction::Locked5x => 16,
-			Conviction::Locked6x => 32,
-		}
+impl Bounded for Conviction {
+	fn min_value() -> Self {
+		Conviction::None
+	}
+	fn max_value() -> Self {
+		Conviction::Locked6x
 	}
+}
+
+/// The lock periods and vote multiplier of a conviction level.
+///
+/// Each vote and delegation keeps the level it was made at, so that it is taken back and unlocked
+/// on the terms it was counted on whatever the `ConvictionSchedule` has become since.
+#[derive(Encode, Decode, Default, Copy, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ConvictionLevel {
+	/// The number of enactment periods a successful voter's balance is locked for.
+	pub lock_periods: u32,
+	/// The votes per unit of (weighted) balance, in tenths of a vote.
+	pub multiplier: u8,
+}
 
-	/// The votes of a voter of the given `balance` with our conviction.
+impl ConvictionLevel {
+	/// The votes of a voter of the given `capital` at this level, `quadratic_capital` being the
+	/// capital as the referendum's vote weighting counts it.
 	pub fn votes<
 		B: From<u8> + Zero + Copy + CheckedMul + CheckedDiv + Bounded
 	>(self, capital: B, quadratic_capital: B) -> Delegations<B> {
-		let votes = match self {
-			Conviction::None => quadratic_capital.checked_div(&10u8.into()).unwrap_or_else(Zero::zero),
-			x => quadratic_capital.checked_mul(&u8::from(x).into()).unwrap_or_else(B::max_value),
-		};
+		let votes = self.apply(quadratic_capital);
 		Delegations {
 			votes,
-			capital
+			capital,
+			quadratic_votes: votes,
+			weighted_capital: quadratic_capital,
 		}
 	}
 
-	/// The votes of a voter of the given `balance` with our conviction.
+	/// The votes delegated by a delegator of the given `capital` at this level. Both the linear
+	/// votes and the votes on the square root of `capital` are recorded, so that either can later
+	/// be summed across delegators.
 	pub fn delegation_votes<
-		B: From<u8> + Zero + Copy + CheckedMul + CheckedDiv + Bounded
+		B: From<u8> + Zero + Copy + CheckedMul + CheckedDiv + Bounded + IntegerSquareRoot
 	>(self, capital: B) -> Delegations<B> {
-		let votes = match self {
-			Conviction::None => capital.checked_div(&10u8.into()).unwrap_or_else(Zero::zero),
-			x => capital.checked_mul(&u8::from(x).into()).unwrap_or_else(B::max_value),
-		};
 		Delegations {
-			votes,
-			capital
+			votes: self.apply(capital),
+			capital,
+			quadratic_votes: self.apply(capital.integer_sqrt()),
+			weighted_capital: capital.integer_sqrt(),
+		}
+	}
+
+	/// Multiply `amount` by our vote multiplier.
+	fn apply<
+		B: From<u8> + Zero + Copy + CheckedMul + CheckedDiv + Bounded
+	>(self, amount: B) -> B {
+		let ten: B = 10u8.into();
+		let multiplier: B = self.multiplier.into();
+		match amount.checked_mul(&multiplier) {
+			Some(tenths) => tenths.checked_div(&ten).unwrap_or_else(Zero::zero),
+			// Lose the precision rather than saturate if the balance is large.
+			None => amount.checked_div(&ten)
+				.and_then(|a| a.checked_mul(&multiplier))
+				.unwrap_or_else(B::max_value),
 		}
 	}
 }
 
-impl Bounded for Conviction {
-	fn min_value() -> Self {
-		Conviction::None
+/// The conviction levels, from that of `Conviction::None` upwards.
+///
+/// A schedule of fewer than seven levels leaves the convictions above its last level unavailable
+/// to votes and delegations; levels past the seventh are never reached, since `Conviction` goes
+/// no higher than `Locked6x`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ConvictionSchedule(pub Vec<ConvictionLevel>);
+
+impl ConvictionSchedule {
+	/// The schedule of the coin-weighted democracy pallet: 0.1x votes unlocked, then 1x to 6x
+	/// votes locked for 1 to 32 enactment periods, doubling at each level.
+	pub fn standard() -> Self {
+		ConvictionSchedule(vec![
+			ConvictionLevel { lock_periods: 0, multiplier: 1 },
+			ConvictionLevel { lock_periods: 1, multiplier: 10 },
+			ConvictionLevel { lock_periods: 2, multiplier: 20 },
+			ConvictionLevel { lock_periods: 4, multiplier: 30 },
+			ConvictionLevel { lock_periods: 8, multiplier: 40 },
+			ConvictionLevel { lock_periods: 16, multiplier: 50 },
+			ConvictionLevel { lock_periods: 32, multiplier: 60 },
+		])
 	}
-	fn max_value() -> Self {
-		Conviction::Locked6x
+
+	/// The level of `conviction`, if the schedule goes that high.
+	pub fn level(&self, conviction: Conviction) -> Option<ConvictionLevel> {
+		self.0.get(u8::from(conviction) as usize).copied()
+	}
+}
+
+impl Default for ConvictionSchedule {
+	fn default() -> Self {
+		Self::standard()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn standard_schedule_matches_the_fixed_curve() {
+		let schedule = ConvictionSchedule::standard();
+		let levels: Vec<ConvictionLevel> = (0u8..=6)
+			.map(|i| schedule.level(Conviction::try_from(i).unwrap()).unwrap())
+			.collect();
+		let periods: Vec<u32> = levels.iter().map(|l| l.lock_periods).collect();
+		assert_eq!(periods, vec![0, 1, 2, 4, 8, 16, 32]);
+		let votes: Vec<u64> = levels.iter().map(|l| l.votes(105u64, 105u64).votes).collect();
+		assert_eq!(votes, vec![10, 105, 210, 315, 420, 525, 630]);
+	}
+
+	#[test]
+	fn shorter_schedule_leaves_higher_convictions_out() {
+		let mut schedule = ConvictionSchedule::standard();
+		schedule.0.truncate(3);
+		assert_eq!(schedule.level(Conviction::Locked2x), Some(ConvictionLevel { lock_periods: 2, multiplier: 20 }));
+		assert_eq!(schedule.level(Conviction::Locked3x), None);
+	}
+
+	#[test]
+	fn large_balances_lose_precision_rather_than_saturate() {
+		let schedule = ConvictionSchedule::standard();
+		let level = |c| schedule.level(c).unwrap();
+		assert_eq!(level(Conviction::Locked1x).votes(0, u64::max_value()).votes, u64::max_value() / 10 * 10);
+		assert_eq!(level(Conviction::Locked6x).votes(0, u64::max_value()).votes, u64::max_value());
 	}
 }
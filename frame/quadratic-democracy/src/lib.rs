@@ -43,18 +43,37 @@
 //! - **Lock Period:** A period of time after proposal enactment that the tokens of _winning_ voters
 //! will be locked.
 //! - **Conviction:** An indication of a voter's strength of belief in their vote. An increase
-//! of one in conviction indicates that a token holder is willing to lock their tokens for twice
-//! as many lock periods after enactment.
+//! of one in conviction indicates that a token holder is willing to lock their tokens for more
+//! lock periods after enactment, in return for a higher vote multiplier. The lock periods and
+//! multiplier of each level are set by the runtime's `ConvictionSchedule`, and each vote and
+//! delegation keeps the level it was made at.
 //! - **Vote:** A value that can either be in approval ("Aye") or rejection ("Nay")
 //!   of a particular referendum.
 //! - **Proposal:** A submission to the chain that represents an action that a proposer (either an
 //! account or an external origin) suggests that the system adopt.
 //! - **Referendum:** A proposal that is in the process of being voted on for
 //!   either acceptance or rejection as a change to the system.
-//! - **Proxy:** An account that has full voting power on behalf of a separate "Stash" account
-//!   that holds the funds.
+//! - **Proxy:** An account that may vote on behalf of a separate "Stash" account that holds the
+//!   funds. Proxies are registered with the runtime's `Proxies`, such as the proxy pallet.
 //! - **Delegation:** The act of granting your voting power to the decisions of another account for
 //!   up to a certain conviction.
+//! - **Delegation Class:** The referenda a delegation applies to, by vote weighting and by whether
+//!   the proposal came from the public queue or an external origin. An account may delegate in
+//!   several classes at once, to different accounts, as long as the classes don't overlap, and
+//!   still vote directly in the referenda none of them covers.
+//! - **Personhood:** The runtime's notion of which accounts belong to unique persons. Only persons
+//!   may vote in quadratic referenda, and the capital of the sub-accounts that have agreed to be
+//!   linked to them is added to their own before the square root of a standard vote is taken.
+//!   Only the first `MaxLinkedSubAccounts` sub-accounts of a person are looked at.
+//! - **Voice Credits:** The allowance each person receives every credit period to buy votes in
+//!   credit referenda, casting `n` votes costing `n²` credits across all of them. Credits are
+//!   refunded when a vote bought in the current period is removed.
+//! - **Multi-Option Referendum:** A referendum choosing between several alternative proposals.
+//!   Voters spread capital across the options and each option counts the square root of the
+//!   capital placed on it; only the option with the most votes is enacted.
+//! - **Secret Ballot:** A referendum whose votes are committed as hashes and only revealed, and
+//!   counted, in the last `RevealPeriod` blocks of voting, or its last half if voting is shorter.
+//!   Commitments that aren't revealed lose their deposit once the referendum ends.
 //!
 //! ### Adaptive Quorum Biasing
 //!
@@ -69,6 +88,27 @@
 //! quorum biasing is that _positive bias_ referendums will be rejected by
 //! default and _negative bias_ referendums get passed by default.
 //!
+//! Turnout and electorate are measured in raw capital, so for a quadratic referendum they are
+//! square-rooted along with the votes before the bias is applied. Credit votes commit no capital,
+//! so credit referenda are always decided by a simple majority.
+//!
+//! A _quorum_ referendum is decided by a simple majority too, but fails whatever the ratio if
+//! fewer than a given number of accounts voted or its weighted turnout is below a given fraction
+//! of the weighted electorate.
+//!
+//! ### Offchain Indexes
+//!
+//! The offchain worker indexes the voters of each referendum and the delegators of each account
+//! into the node's persistent offchain storage, under the keys given by `voters_key` and
+//! `delegators_key`, updating them from the events of each block. They can be read with the
+//! `offchain_localStorageGet` RPC on nodes running offchain workers.
+//!
+//! ### Genesis
+//!
+//! The genesis config may note preimages, make public proposals, start referenda and delegate
+//! voting power, so that a chain can begin with governance under way. Preimages and proposals
+//! reserve their usual deposits, and referenda end `VotingPeriod` blocks after genesis.
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
@@ -79,26 +119,38 @@
 //! a signed extrinsic.
 //!
 //! Basic actions:
-//! - `propose` - Submits a sensitive action, represented as a hash. Requires a deposit.
+//! - `propose` - Submits a sensitive action, represented as a hash, along with the vote weighting
+//!   of its referendum. Requires a deposit.
+//! - `propose_secret` - Submits a sensitive action whose referendum will be voted on by secret
+//!   ballot. Requires a deposit.
 //! - `second` - Signals agreement with a proposal, moves it higher on the proposal queue, and
 //!   requires a matching deposit to the original.
 //! - `vote` - Votes in a referendum, either the vote is "Aye" to enact the proposal or "Nay" to
 //!   keep the status quo.
+//! - `vote_with_credits` - Votes in a credit referendum with votes bought with voice credits.
+//! - `vote_options` - Places capital on the options of a multi-option referendum.
+//! - `commit_vote` - Commits to a vote in a secret ballot. Requires a deposit.
+//! - `reveal_vote` - Reveals a committed vote, counting it and returning the deposit.
 //! - `unvote` - Cancel a previous vote, this must be done by the voter before the vote ends.
 //! - `delegate` - Delegates the voting power (tokens * conviction) to another account.
 //! - `undelegate` - Stops the delegation of voting power to another account.
+//! - `delegate_class` - Delegates the voting power in a class of referenda to another account.
+//! - `undelegate_class` - Stops the delegation of voting power in a class of referenda.
+//! - `link_sub_account` - Agrees to have the capital of a sub-account count, and be locked, through
+//!   its person.
+//! - `unlink_sub_account` - Withdraws a sub-account from its person.
 //!
 //! Administration actions that can be done to any account:
 //! - `reap_vote` - Remove some account's expired votes.
 //! - `unlock` - Redetermine the account's balance lock, potentially making tokens available.
+//! - `prune_referendum` - Remove the record of a referendum that finished more than
+//!   `HistoryRetention` blocks ago and has no votes left.
+//! - `reap_commitments` - Slash the commitments left unrevealed in a finished secret ballot, or
+//!   return those of a cancelled one.
+//! - `reap_multi_option_votes` - Remove the votes of a multi-option referendum that is no longer
+//!   ongoing.
 //!
-//! Proxy administration:
-//! - `activate_proxy` - Activates a proxy that is already open to the sender.
-//! - `close_proxy` - Clears the proxy status, called by the proxy.
-//! - `deactivate_proxy` - Deactivates a proxy back to the open status, called by the stash.
-//! - `open_proxy` - Opens a proxy account on behalf of the sender.
-//!
-//! Proxy actions:
+//! Proxy actions, available to the voting proxies of the stash account:
 //! - `proxy_vote` - Votes in a referendum on behalf of a stash account.
 //! - `proxy_unvote` - Cancel a previous vote, done on behalf of the voter by a proxy.
 //! - `proxy_delegate` - Delegate voting power, done on behalf of the voter by a proxy.
@@ -126,6 +178,10 @@
 //!
 //! - `external_propose` - Schedules a proposal to become a referendum once it is is legal
 //!   for an externally proposed referendum.
+//! - `external_propose_with_weight` - As `external_propose`, with an explicit vote weighting.
+//! - `external_propose_secret` - Schedules a secret ballot with an explicit threshold and vote
+//!   weighting. The external origin of the threshold is required, so this is also available to
+//!   the `ExternalMajorityOrigin` and the `ExternalDefaultOrigin`.
 //!
 //! #### External Majority Origin
 //!
@@ -133,6 +189,11 @@
 //!
 //! - `external_propose_majority` - Schedules a proposal to become a majority-carries
 //!	 referendum once it is legal for an externally proposed referendum.
+//! - `external_propose_majority_with_weight` - As `external_propose_majority`, with an explicit
+//!   vote weighting.
+//! - `external_propose_quorum` - Schedules a majority-carries referendum that fails unless a
+//!   quorum of voters and weighted turnout is met.
+//! - `start_multi_option_referendum` - Starts a referendum choosing between several proposals.
 //!
 //! #### External Default Origin
 //!
@@ -140,6 +201,8 @@
 //!
 //! - `external_propose_default` - Schedules a proposal to become a negative-turnout-bias
 //!   referendum once it is legal for an externally proposed referendum.
+//! - `external_propose_default_with_weight` - As `external_propose_default`, with an explicit
+//!   vote weighting.
 //!
 //! #### Fast Track Origin
 //!
@@ -165,13 +228,18 @@
 
 use sp_std::prelude::*;
 use sp_runtime::{
-	DispatchResult, DispatchError, RuntimeDebug,
-	traits::{Zero, Hash, Dispatchable, Saturating},
+	DispatchResult, DispatchError, RuntimeDebug, Perbill,
+	traits::{
+		Zero, One, Hash, Dispatchable, Saturating, CheckedAdd, CheckedMul, SaturatedConversion,
+		IntegerSquareRoot,
+	},
 };
 use codec::{Ref, Encode, Decode};
 use frame_support::{
 	decl_module, decl_storage, decl_event, decl_error, ensure, Parameter,
+	dispatch::DispatchResultWithPostInfo,
 	weights::{Weight, DispatchClass},
+	storage::IterableStorageDoubleMap,
 	traits::{
 		Currency, ReservableCurrency, LockableCurrency, WithdrawReason, LockIdentifier, Get,
 		OnUnbalanced, BalanceStatus, schedule::Named as ScheduleNamed, EnsureOrigin,
@@ -184,12 +252,25 @@ mod vote_weight;
 mod vote;
 mod conviction;
 mod types;
-
-pub use vote_threshold::{Approved, VoteThreshold};
-pub use vote_weight::{Calculate, VoteWeight};
-pub use vote::{Vote, AccountVote, Voting};
-pub use conviction::Conviction;
-pub use types::{ReferendumInfo, ReferendumStatus, ProxyState, Tally, UnvoteScope, Delegations};
+mod personhood;
+mod proxy;
+mod weights;
+mod migration;
+mod offchain;
+
+pub use vote_threshold::{Approved, VoteThreshold, Rejection};
+pub use vote_weight::{Calculate, VoteWeight, DelegationWeighting};
+pub use vote::{Vote, AccountVote, AccountVoteWeight, Voting};
+pub use conviction::{Conviction, ConvictionLevel, ConvictionSchedule};
+pub use types::{
+	ReferendumInfo, ReferendumStatus, ReferendumResult, Tally, UnvoteScope, Delegations,
+	MultiOptionTally, MultiOptionStatus, MultiOptionInfo, Ballot,
+	ProposalSource, DelegationClass, ClassDelegation,
+};
+pub use personhood::Personhood;
+pub use proxy::VotingProxies;
+pub use weights::WeightInfo;
+pub use offchain::{voters_key, delegators_key, IndexedVote, LAST_INDEXED};
 use crate::vote::AccountVoteWeight;
 
 #[cfg(test)]
@@ -200,6 +281,16 @@ pub mod benchmarking;
 
 const DEMOCRACY_ID: LockIdentifier = *b"democrac";
 
+/// The number of vetoers of an external proposal that calls are weighed for before dispatch.
+///
+/// NOTE: This is not enforced by any logic.
+pub const MAX_VETOERS: u32 = 100;
+
+/// The length of a preimage that calls are weighed for before dispatch.
+///
+/// NOTE: This is not enforced by any logic.
+pub const MAX_PREIMAGE_BYTES: u32 = 16_384;
+
 /// A proposal index.
 pub type PropIndex = u32;
 
@@ -234,6 +325,20 @@ pub trait Trait: frame_system::Trait + Sized {
 	/// The minimum amount to be used as a deposit for a public referendum proposal.
 	type MinimumDeposit: Get<BalanceOf<Self>>;
 
+	/// The vote weighting used for public proposals and external proposals that don't specify
+	/// one.
+	type DefaultVoteWeight: Get<VoteWeight>;
+
+	/// Whether the votes delegated to an account count in a quadratic referendum as the square
+	/// root of their sum or as the sum of each delegator's square root.
+	type DelegationWeighting: Get<DelegationWeighting>;
+
+	/// The lock periods and vote multiplier of each conviction level.
+	///
+	/// Votes and delegations keep the level they were made at and are taken back at it, so the
+	/// schedule may change at any time; only new votes and delegations follow the change.
+	type ConvictionSchedule: Get<ConvictionSchedule>;
+
 	/// Origin from which the next tabled referendum may be forced. This is a normal
 	/// "super-majority-required" referendum.
 	type ExternalOrigin: EnsureOrigin<Self::Origin>;
@@ -281,6 +386,72 @@ pub trait Trait: frame_system::Trait + Sized {
 
 	/// The Scheduler.
 	type Scheduler: ScheduleNamed<Self::BlockNumber, Self::Proposal>;
+
+	/// Provider of personhood, used to restrict quadratic votes to verified persons and to count
+	/// the capital of their linked sub-accounts.
+	type Personhood: Personhood<Self::AccountId>;
+
+	/// The most sub-accounts of a person that are looked at for linked capital when it votes.
+	/// Those the `Personhood` lists after the first `MaxLinkedSubAccounts` don't count.
+	type MaxLinkedSubAccounts: Get<u32>;
+
+	/// The registry of the accounts allowed to vote on behalf of others.
+	type Proxies: VotingProxies<Self::AccountId>;
+
+	/// The voice credits each person may spend on credit referenda per credit period.
+	type CreditAllowance: Get<BalanceOf<Self>>;
+
+	/// The length (in blocks) of a credit period, after which spent credits are replenished.
+	type CreditPeriod: Get<Self::BlockNumber>;
+
+	/// The maximum number of alternative proposals in a multi-option referendum.
+	type MaxOptions: Get<u32>;
+
+	/// The period (in blocks) at the end of a secret ballot in which committed votes are
+	/// revealed. Votes may only be committed before it.
+	type RevealPeriod: Get<Self::BlockNumber>;
+
+	/// The deposit reserved for a vote commitment. It is returned when the vote is revealed and
+	/// slashed if it isn't revealed before the referendum ends.
+	type CommitDeposit: Get<BalanceOf<Self>>;
+
+	/// The period (in blocks) for which the record of a finished referendum, with its final
+	/// tally, is kept. Thereafter anyone may prune it once no votes on it remain.
+	type HistoryRetention: Get<Self::BlockNumber>;
+
+	/// The most accounts the offchain worker re-reads in a block to bring its indexes of voters
+	/// and delegators up to date after blocks it did not run for. Zero disables the indexes.
+	type IndexingBatch: Get<u32>;
+
+	/// The maximum number of referenda an account may have votes in. Votes in finished referenda
+	/// count until they are removed.
+	type MaxVotes: Get<u32>;
+
+	/// The maximum number of public proposals that may be waiting to be tabled.
+	type MaxProposals: Get<u32>;
+
+	/// The maximum number of times a public proposal may be seconded.
+	type MaxSeconds: Get<u32>;
+
+	/// The weights of the calls of this module.
+	type WeightInfo: WeightInfo;
+}
+
+// A value placed in storage that represents the current version of the Democracy storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run
+// storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+enum Releases {
+	/// The layout of the coin-weighted democracy pallet.
+	V1_0_0,
+	/// The layout of this pallet.
+	V2_0_0,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1_0_0
+	}
 }
 
 #[derive(Clone, Encode, Decode, RuntimeDebug)]
@@ -313,8 +484,9 @@ decl_storage! {
 		// https://github.com/paritytech/substrate/issues/5322
 		/// The number of (public) proposals that have been made so far.
 		pub PublicPropCount get(fn public_prop_count) build(|_| 0 as PropIndex) : PropIndex;
-		/// The public proposals. Unsorted. The second item is the proposal's hash.
-		pub PublicProps get(fn public_props): Vec<(PropIndex, T::Hash, T::AccountId)>;
+		/// The public proposals. Unsorted. The second item is the proposal's hash and the last two
+		/// are the vote weighting and ballot its referendum will use.
+		pub PublicProps get(fn public_props): Vec<(PropIndex, T::Hash, T::AccountId, VoteWeight, Ballot)>;
 		/// Those who have locked a deposit.
 		pub DepositOf get(fn deposit_of):
 			map hasher(twox_64_concat) PropIndex => Option<(BalanceOf<T>, Vec<T::AccountId>)>;
@@ -338,15 +510,55 @@ decl_storage! {
 			map hasher(twox_64_concat) ReferendumIndex
 			=> Option<ReferendumInfo<T::BlockNumber, T::Hash, BalanceOf<T>>>;
 
+		/// Information concerning any given multi-option referendum. Multi-option referenda share
+		/// their indices with `ReferendumInfoOf`.
+		pub MultiOptionReferendumInfoOf get(fn multi_option_referendum_info):
+			map hasher(twox_64_concat) ReferendumIndex
+			=> Option<MultiOptionInfo<T::BlockNumber, T::Hash, BalanceOf<T>>>;
+
+		/// The capital each voter placed on each option of an ongoing multi-option referendum.
+		pub MultiOptionVotesOf:
+			double_map hasher(twox_64_concat) ReferendumIndex, hasher(twox_64_concat) T::AccountId
+			=> Vec<BalanceOf<T>>;
+
+		/// The referenda voted on by secret ballot, with the block from which their commitments
+		/// may be revealed.
+		pub SecretBallotOf get(fn reveal_from):
+			map hasher(twox_64_concat) ReferendumIndex => Option<T::BlockNumber>;
+
+		/// The unrevealed vote commitment of each voter in a secret ballot, with the deposit
+		/// reserved for it.
+		pub CommitmentOf get(fn commitment_of):
+			double_map hasher(twox_64_concat) ReferendumIndex, hasher(twox_64_concat) T::AccountId
+			=> Option<(T::Hash, BalanceOf<T>)>;
+
+		/// Where the proposals of ongoing referenda came from. Referenda started by other means, such
+		/// as `internal_start_referendum`, have none.
+		pub ReferendumSourceOf get(fn referendum_source):
+			map hasher(twox_64_concat) ReferendumIndex => Option<ProposalSource>;
+
 		/// All votes for a particular voter. We store the balance for the number of votes that we
 		/// have recorded. The second item is the total amount of delegations, that will be added.
 		pub VotingOf: map hasher(twox_64_concat) T::AccountId => Voting<BalanceOf<T>, T::AccountId, T::BlockNumber>;
 
-		/// Who is able to vote for whom. Value is the fund-holding account, key is the
-		/// vote-transaction-sending account.
-		// TODO: Refactor proxy into its own pallet.
-		// https://github.com/paritytech/substrate/issues/5322
-		pub Proxy get(fn proxy): map hasher(twox_64_concat) T::AccountId => Option<ProxyState<T::AccountId>>;
+		/// The number of accounts whose `VotingOf` holds a vote on each referendum. A finished
+		/// referendum may only be pruned once it has none.
+		pub VoterCount get(fn voter_count): map hasher(twox_64_concat) ReferendumIndex => u32;
+
+		/// The delegations an account makes in classes of referenda. Their classes don't overlap,
+		/// and an account delegating for all referenda through its `VotingOf` has none.
+		pub ClassDelegatingOf get(fn class_delegating_of):
+			map hasher(twox_64_concat) T::AccountId => Vec<ClassDelegation<BalanceOf<T>, T::AccountId>>;
+
+		/// The delegations an account has received in classes of referenda, summed per class.
+		/// Those received for all referenda are kept in its `VotingOf`.
+		pub ClassDelegationsOf get(fn class_delegations_of):
+			map hasher(twox_64_concat) T::AccountId => Vec<(DelegationClass, Delegations<BalanceOf<T>>)>;
+
+		/// The person each account has agreed, through `link_sub_account`, to be linked to as a
+		/// sub-account. The link only holds while the `Personhood` also has the account as a
+		/// sub-account of that person.
+		pub LinkedTo get(fn linked_to): map hasher(twox_64_concat) T::AccountId => Option<T::AccountId>;
 
 		/// Accounts for which there are locks in action which may be removed at some point in the
 		/// future. The value is the block number at which the lock expires and may be removed.
@@ -362,7 +574,7 @@ decl_storage! {
 		/// This happens when a referendum needs to be tabled and one of two conditions are met:
 		/// - `LastTabledWasExternal` is `false`; or
 		/// - `PublicProps` is empty.
-		pub NextExternal: Option<(T::Hash, VoteThreshold, VoteWeight)>;
+		pub NextExternal: Option<(T::Hash, VoteThreshold, VoteWeight, Ballot)>;
 
 		/// A record of who vetoed what. Maps proposal hash to a possible existent block number
 		/// (until when it may not be resubmitted) and who vetoed it.
@@ -371,6 +583,63 @@ decl_storage! {
 
 		/// Record of all proposals that have been subject to emergency cancellation.
 		pub Cancellations: map hasher(identity) T::Hash => bool;
+
+		/// Storage version of the pallet.
+		///
+		/// This is set to v2.0.0 for new networks. A chain upgrading from the coin-weighted
+		/// democracy pallet has none, and its storage is migrated on the runtime upgrade.
+		StorageVersion build(|_| Releases::V2_0_0): Releases;
+
+		/// How far the votes and delegations of the coin-weighted democracy pallet have been
+		/// translated, while they are.
+		Migrating: Option<migration::Cursor>;
+	}
+	add_extra_genesis {
+		/// Preimages noted by their provider, who reserves the usual deposit for them.
+		config(preimages): Vec<(T::AccountId, Vec<u8>)>;
+		/// Public proposals of a preimage hash by an account, with the deposit it reserves and the
+		/// vote weighting of the proposal's referendum.
+		config(proposals): Vec<(T::AccountId, T::Hash, BalanceOf<T>, VoteWeight)>;
+		/// Referenda on a preimage hash with their threshold, vote weighting and enactment delay.
+		/// They end `VotingPeriod` blocks after genesis.
+		config(referenda): Vec<(T::Hash, VoteThreshold, VoteWeight, T::BlockNumber)>;
+		/// Delegations of an account's balance, with some conviction, to a target in all referenda.
+		config(delegations): Vec<(T::AccountId, T::AccountId, Conviction, BalanceOf<T>)>;
+		/// Sub-accounts linked to the person they are a sub-account of.
+		config(linked_sub_accounts): Vec<(T::AccountId, T::AccountId)>;
+		build(|config: &GenesisConfig<T>| {
+			for (who, encoded_proposal) in config.preimages.iter() {
+				<Module<T>>::note_preimage(
+					T::Origin::from(Some(who.clone()).into()),
+					encoded_proposal.clone(),
+				).expect("Genesis preimage could not be noted");
+			}
+			for &(ref who, proposal_hash, value, weight) in config.proposals.iter() {
+				<Module<T>>::propose(
+					T::Origin::from(Some(who.clone()).into()),
+					proposal_hash,
+					value,
+					Some(weight),
+				).expect("Genesis proposal could not be made");
+			}
+			for &(proposal_hash, threshold, weight, delay) in config.referenda.iter() {
+				<Module<T>>::internal_start_referendum(proposal_hash, threshold, weight, delay);
+			}
+			for &(ref who, ref target, conviction, balance) in config.delegations.iter() {
+				<Module<T>>::delegate(
+					T::Origin::from(Some(who.clone()).into()),
+					target.clone(),
+					conviction,
+					balance,
+				).expect("Genesis delegation could not be made");
+			}
+			for (who, person) in config.linked_sub_accounts.iter() {
+				<Module<T>>::link_sub_account(
+					T::Origin::from(Some(who.clone()).into()),
+					person.clone(),
+				).expect("Genesis sub-account could not be linked");
+			}
+		})
 	}
 }
 
@@ -391,16 +660,40 @@ decl_event! {
 		Started(ReferendumIndex, VoteThreshold, VoteWeight),
 		/// A proposal has been approved by referendum.
 		Passed(ReferendumIndex),
-		/// A proposal has been rejected by referendum.
-		NotPassed(ReferendumIndex),
+		/// A proposal has been rejected by referendum, for the given reason.
+		NotPassed(ReferendumIndex, Rejection),
 		/// A referendum has been cancelled.
 		Cancelled(ReferendumIndex),
+		/// The record of a finished referendum has been pruned.
+		ReferendumPruned(ReferendumIndex),
+		/// A multi-option referendum has begun with the given number of options.
+		MultiOptionStarted(ReferendumIndex, u32),
+		/// An option of a multi-option referendum has won and its proposal will be enacted.
+		OptionChosen(ReferendumIndex, u32),
 		/// A proposal has been enacted.
 		Executed(ReferendumIndex, bool),
-		/// An account has delegated their vote to another account.
-		Delegated(AccountId, AccountId),
-		/// An account has cancelled a previous delegation operation.
-		Undelegated(AccountId),
+		/// An account has voted in a referendum. The vote is given with its raw and weighted
+		/// balance, along with its conviction and the weighted delegations it carries (last item).
+		Voted(ReferendumIndex, AccountId, AccountVoteWeight<Balance>, Conviction, Delegations<Balance>),
+		/// An account's vote has been removed from a referendum. The delegations it carried are
+		/// zero if the referendum had already finished.
+		VoteRemoved(ReferendumIndex, AccountId, AccountVoteWeight<Balance>, Conviction, Delegations<Balance>),
+		/// An account has committed to a vote in a secret ballot.
+		VoteCommitted(ReferendumIndex, AccountId),
+		/// The deposit of a vote commitment that wasn't revealed in time has been slashed.
+		CommitmentSlashed(ReferendumIndex, AccountId, Balance),
+		/// The deposit of a vote commitment in a cancelled secret ballot has been returned.
+		CommitmentReturned(ReferendumIndex, AccountId, Balance),
+		/// An account has delegated their vote in a class of referenda to another account, with the
+		/// given conviction and the votes this adds to the delegations of the target (last item).
+		Delegated(AccountId, AccountId, DelegationClass, Conviction, Delegations<Balance>),
+		/// An account has cancelled a previous delegation operation in a class of referenda,
+		/// taking the given votes away from the delegations of its former target.
+		Undelegated(AccountId, AccountId, DelegationClass, Delegations<Balance>),
+		/// An account has agreed to be linked to a person (last item) as a sub-account.
+		SubAccountLinked(AccountId, AccountId),
+		/// An account has withdrawn from being linked to a person (last item) as a sub-account.
+		SubAccountUnlinked(AccountId, AccountId),
 		/// An external proposal has been vetoed.
 		Vetoed(AccountId, Hash, BlockNumber),
 		/// A proposal's preimage was noted, and the deposit taken.
@@ -415,6 +708,12 @@ decl_event! {
 		PreimageReaped(Hash, AccountId, Balance, AccountId),
 		/// An account has been unlocked successfully.
 		Unlocked(AccountId),
+		/// An account has voted in a multi-option referendum, placing the given capital on each
+		/// option.
+		OptionsVoted(ReferendumIndex, AccountId, Vec<Balance>),
+		/// The vote of an account in a multi-option referendum that is no longer ongoing has been
+		/// removed.
+		OptionsVoteReaped(ReferendumIndex, AccountId),
 	}
 }
 
@@ -442,10 +741,6 @@ decl_error! {
 		NoProposal,
 		/// Identity may not veto a proposal twice
 		AlreadyVetoed,
-		/// Already a proxy
-		AlreadyProxy,
-		/// Wrong proxy
-		WrongProxy,
 		/// Not delegated
 		NotDelegated,
 		/// Preimage already noted
@@ -468,12 +763,6 @@ decl_error! {
 		NotLocked,
 		/// The lock on the account to be unlocked has not yet expired.
 		NotExpired,
-		/// A proxy-pairing was attempted to an account that was not open.
-		NotOpen,
-		/// A proxy-pairing was attempted to an account that was open to another account.
-		WrongOpen,
-		/// A proxy-de-pairing was attempted to an account that was not active.
-		NotActive,
 		/// The given account did not vote on the referendum.
 		NotVoter,
 		/// The actor has no permission to conduct the action.
@@ -497,6 +786,56 @@ decl_error! {
 		Nonsense,
 		// @TODO add comments
 		NotQuadraticWeight,
+		/// The account is not recognised as a person and may not cast a quadratic vote.
+		NotPerson,
+		/// The account is a sub-account of a person; its capital counts through that person.
+		SubAccount,
+		/// The account hasn't agreed to be linked to a person as a sub-account.
+		NotLinked,
+		/// Votes in a credit referendum must be bought with credits, and credits only buy votes in
+		/// credit referenda.
+		WrongVoteWeight,
+		/// The account doesn't have enough voice credits left this period.
+		InsufficientCredits,
+		/// A multi-option referendum needs at least two options.
+		TooFewOptions,
+		/// A multi-option referendum may have at most `MaxOptions` options.
+		TooManyOptions,
+		/// The vote doesn't place a balance on each option of the referendum.
+		WrongOptionCount,
+		/// Votes in a secret ballot must be committed and then revealed.
+		SecretBallot,
+		/// The referendum isn't voted on by secret ballot.
+		NotSecretBallot,
+		/// Votes may no longer be committed, as the reveal period has begun.
+		CommitClosed,
+		/// Votes may not be revealed before the reveal period.
+		RevealNotOpen,
+		/// The account has no vote commitment in the referendum.
+		NoCommitment,
+		/// The revealed vote doesn't match the commitment.
+		CommitmentMismatch,
+		/// The delegation class overlaps that of another delegation of the account.
+		OverlappingDelegation,
+		/// The account has votes in `MaxVotes` referenda; some must be removed before voting in
+		/// another.
+		MaxVotesReached,
+		/// `MaxProposals` public proposals are already waiting to be tabled.
+		TooManyProposals,
+		/// The proposal has already been seconded `MaxSeconds` times.
+		TooManySeconds,
+		/// Votes on the referendum remain; they must be removed before it is pruned.
+		VotesRemain,
+		/// The conviction has no level in the `ConvictionSchedule`.
+		UnavailableConviction,
+		/// The voting period is too short for a secret ballot to be committed and revealed.
+		VotingPeriodTooShort,
+		/// The secret ballot is still ongoing, so its commitments may yet be revealed.
+		BallotOngoing,
+		/// Votes and delegations are still being migrated.
+		Migrating,
+		/// The multi-option referendum is still ongoing, so its votes still count.
+		ReferendumOngoing,
 	}
 }
 
@@ -520,6 +859,48 @@ decl_module! {
 		/// The minimum amount to be used as a deposit for a public referendum proposal.
 		const MinimumDeposit: BalanceOf<T> = T::MinimumDeposit::get();
 
+		/// The vote weighting used for proposals that don't specify one.
+		const DefaultVoteWeight: VoteWeight = T::DefaultVoteWeight::get();
+
+		/// How delegated votes are weighted in a quadratic referendum.
+		const DelegationWeighting: DelegationWeighting = T::DelegationWeighting::get();
+
+		/// The lock periods and vote multiplier of each conviction level.
+		const ConvictionSchedule: ConvictionSchedule = T::ConvictionSchedule::get();
+
+		/// The voice credits each person may spend on credit referenda per credit period.
+		const CreditAllowance: BalanceOf<T> = T::CreditAllowance::get();
+
+		/// The length (in blocks) of a credit period.
+		const CreditPeriod: T::BlockNumber = T::CreditPeriod::get();
+
+		/// The maximum number of alternative proposals in a multi-option referendum.
+		const MaxOptions: u32 = T::MaxOptions::get();
+
+		/// The period at the end of a secret ballot in which committed votes are revealed.
+		const RevealPeriod: T::BlockNumber = T::RevealPeriod::get();
+
+		/// The deposit reserved for a vote commitment.
+		const CommitDeposit: BalanceOf<T> = T::CommitDeposit::get();
+
+		/// The period for which the record of a finished referendum is kept.
+		const HistoryRetention: T::BlockNumber = T::HistoryRetention::get();
+
+		/// The most accounts the offchain worker re-reads in a block to catch up its indexes.
+		const IndexingBatch: u32 = T::IndexingBatch::get();
+
+		/// The maximum number of referenda an account may have votes in.
+		const MaxVotes: u32 = T::MaxVotes::get();
+
+		/// The most sub-accounts of a person that are looked at for linked capital.
+		const MaxLinkedSubAccounts: u32 = T::MaxLinkedSubAccounts::get();
+
+		/// The maximum number of public proposals that may be waiting to be tabled.
+		const MaxProposals: u32 = T::MaxProposals::get();
+
+		/// The maximum number of times a public proposal may be seconded.
+		const MaxSeconds: u32 = T::MaxSeconds::get();
+
 		/// Minimum voting period allowed for an emergency referendum.
 		const FastTrackVotingPeriod: T::BlockNumber = T::FastTrackVotingPeriod::get();
 
@@ -538,6 +919,8 @@ decl_module! {
 		///
 		/// - `proposal_hash`: The hash of the proposal preimage.
 		/// - `value`: The amount of deposit (must be at least `MinimumDeposit`).
+		/// - `weight`: The vote weighting of the resulting referendum; `DefaultVoteWeight` if
+		///   `None`.
 		///
 		/// Emits `Proposed`.
 		///
@@ -546,23 +929,44 @@ decl_module! {
 		/// - P is the number proposals in the `PublicProps` vec.
 		/// - Two DB changes, one DB entry.
 		/// # </weight>
-		#[weight = 5_000_000_000]
+		#[weight = T::WeightInfo::propose(T::MaxProposals::get())]
 		fn propose(origin,
 			proposal_hash: T::Hash,
-			#[compact] value: BalanceOf<T>
+			#[compact] value: BalanceOf<T>,
+			weight: Option<VoteWeight>,
 		) {
 			let who = ensure_signed(origin)?;
-			ensure!(value >= T::MinimumDeposit::get(), Error::<T>::ValueLow);
-			T::Currency::reserve(&who, value)?;
-
-			let index = Self::public_prop_count();
-			PublicPropCount::put(index + 1);
-			<DepositOf<T>>::insert(index, (value, &[&who][..]));
-
-			let new_prop = (index, proposal_hash, who);
-			<PublicProps<T>>::append_or_put(&[Ref::from(&new_prop)][..]);
+			let weight = weight.unwrap_or_else(T::DefaultVoteWeight::get);
+			Self::do_propose(who, proposal_hash, value, weight, Ballot::Open)?;
+		}
 
-			Self::deposit_event(RawEvent::Proposed(index, value));
+		/// Propose a sensitive action to be taken, its referendum voted on by secret ballot.
+		///
+		/// The dispatch origin of this call must be _Signed_ and the sender must
+		/// have funds to cover the deposit.
+		///
+		/// - `proposal_hash`: The hash of the proposal preimage.
+		/// - `value`: The amount of deposit (must be at least `MinimumDeposit`).
+		/// - `weight`: The vote weighting of the resulting referendum; `DefaultVoteWeight` if
+		///   `None`. Credit votes can't be committed.
+		///
+		/// Emits `Proposed`.
+		///
+		/// # <weight>
+		/// - `O(P)`
+		/// - P is the number proposals in the `PublicProps` vec.
+		/// - Two DB changes, one DB entry.
+		/// # </weight>
+		#[weight = T::WeightInfo::propose(T::MaxProposals::get())]
+		fn propose_secret(origin,
+			proposal_hash: T::Hash,
+			#[compact] value: BalanceOf<T>,
+			weight: Option<VoteWeight>,
+		) {
+			let who = ensure_signed(origin)?;
+			let weight = weight.unwrap_or_else(T::DefaultVoteWeight::get);
+			ensure!(weight != VoteWeight::Credits, Error::<T>::WrongVoteWeight);
+			Self::do_propose(who, proposal_hash, value, weight, Ballot::Secret)?;
 		}
 
 		/// Signals agreement with a particular proposal.
@@ -577,14 +981,18 @@ decl_module! {
 		/// - S is the number of seconds a proposal already has.
 		/// - One DB entry.
 		/// # </weight>
-		#[weight = 5_000_000_000]
-		fn second(origin, #[compact] proposal: PropIndex) {
+		#[weight = T::WeightInfo::second(T::MaxSeconds::get())]
+		fn second(origin, #[compact] proposal: PropIndex) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 			let mut deposit = Self::deposit_of(proposal)
 				.ok_or(Error::<T>::ProposalMissing)?;
+			// The proposer's own deposit comes first.
+			let seconds = deposit.1.len().saturating_sub(1) as u32;
+			ensure!(seconds < T::MaxSeconds::get(), Error::<T>::TooManySeconds);
 			T::Currency::reserve(&who, deposit.0)?;
 			deposit.1.push(who);
 			<DepositOf<T>>::insert(proposal, deposit);
+			Ok(Some(T::WeightInfo::second(seconds)).into())
 		}
 
 		/// Vote in a referendum. If `vote.is_aye()`, the vote is to enact the proposal;
@@ -596,11 +1004,14 @@ decl_module! {
 		/// - `vote`: The vote configuration.
 		///
 		/// # <weight>
-		/// - `O(R)`.
+		/// - `O(R + L)`.
 		/// - R is the number of referendums the voter has voted on.
-		/// - One DB change, one DB entry.
+		/// - L is the number of sub-accounts looked at for linked capital, at most
+		///   `MaxLinkedSubAccounts`.
+		/// - One DB change, one DB entry, and L DB changes for the linked sub-accounts.
 		/// # </weight>
-		#[weight = 200_000_000]
+		#[weight = T::WeightInfo::vote_new(T::MaxVotes::get(), T::MaxLinkedSubAccounts::get())
+			.max(T::WeightInfo::vote_existing(T::MaxVotes::get(), T::MaxLinkedSubAccounts::get()))]
 		fn vote(origin,
 			#[compact] ref_index: ReferendumIndex,
 			vote: AccountVote<BalanceOf<T>>,
@@ -612,23 +1023,29 @@ decl_module! {
 		/// Vote in a referendum on behalf of a stash. If `vote.is_aye()`, the vote is to enact
 		/// the proposal; otherwise it is a vote to keep the status quo.
 		///
-		/// The dispatch origin of this call must be _Signed_.
+		/// The dispatch origin of this call must be _Signed_ by a voting proxy of `real`.
 		///
+		/// - `real`: The stash account to vote on behalf of.
 		/// - `ref_index`: The index of the referendum to proxy vote for.
 		/// - `vote`: The vote configuration.
 		///
 		/// # <weight>
-		/// - `O(1)`.
-		/// - One DB change, one DB entry.
+		/// - `O(R + L)`.
+		/// - R is the number of referendums the voter has voted on.
+		/// - L is the number of sub-accounts looked at for linked capital, at most
+		///   `MaxLinkedSubAccounts`.
+		/// - One DB change, one DB entry, and L DB changes for the linked sub-accounts.
 		/// # </weight>
-		#[weight = 200_000_000]
+		#[weight = T::WeightInfo::proxy_vote_new(T::MaxVotes::get(), T::MaxLinkedSubAccounts::get())
+			.max(T::WeightInfo::proxy_vote_existing(T::MaxVotes::get(), T::MaxLinkedSubAccounts::get()))]
 		fn proxy_vote(origin,
+			real: T::AccountId,
 			#[compact] ref_index: ReferendumIndex,
 			vote: AccountVote<BalanceOf<T>>,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			let voter = Self::proxy(who).and_then(|a| a.as_active()).ok_or(Error::<T>::NotProxy)?;
-			Self::try_vote(&voter, ref_index, vote)
+			Self::ensure_voting_proxy(&real, &who)?;
+			Self::try_vote(&real, ref_index, vote)
 		}
 
 		/// Schedule an emergency cancellation of a referendum. Cannot happen twice to the same
@@ -641,7 +1058,7 @@ decl_module! {
 		/// # <weight>
 		/// - `O(1)`.
 		/// # </weight>
-		#[weight = (500_000_000, DispatchClass::Operational)]
+		#[weight = (T::WeightInfo::emergency_cancel(T::MaxVotes::get()), DispatchClass::Operational)]
 		fn emergency_cancel(origin, ref_index: ReferendumIndex) {
 			T::CancellationOrigin::ensure_origin(origin)?;
 
@@ -664,17 +1081,10 @@ decl_module! {
 		/// - `O(1)`.
 		/// - One DB change.
 		/// # </weight>
-		#[weight = 5_000_000_000]
+		#[weight = T::WeightInfo::external_propose(T::MaxProposals::get())]
 		fn external_propose(origin, proposal_hash: T::Hash) {
 			T::ExternalOrigin::ensure_origin(origin)?;
-			ensure!(!<NextExternal<T>>::exists(), Error::<T>::DuplicateProposal);
-			if let Some((until, _)) = <Blacklist<T>>::get(proposal_hash) {
-				ensure!(
-					<frame_system::Module<T>>::block_number() >= until,
-					Error::<T>::ProposalBlacklisted,
-				);
-			}
-			<NextExternal<T>>::put((proposal_hash, VoteThreshold::SuperMajorityApprove, VoteWeight::Quadratic));
+			Self::do_external_propose(proposal_hash, T::DefaultVoteWeight::get(), Ballot::Open)?;
 		}
 
 		/// Schedule a majority-carries referendum to be tabled next once it is legal to schedule
@@ -691,10 +1101,15 @@ decl_module! {
 		/// - `O(1)`.
 		/// - One DB change.
 		/// # </weight>
-		#[weight = 5_000_000_000]
+		#[weight = T::WeightInfo::external_propose_majority(T::MaxProposals::get())]
 		fn external_propose_majority(origin, proposal_hash: T::Hash) {
 			T::ExternalMajorityOrigin::ensure_origin(origin)?;
-			<NextExternal<T>>::put((proposal_hash, VoteThreshold::SimpleMajority, VoteWeight::Quadratic));
+			<NextExternal<T>>::put((
+				proposal_hash,
+				VoteThreshold::SimpleMajority,
+				T::DefaultVoteWeight::get(),
+				Ballot::Open,
+			));
 		}
 
 		/// Schedule a negative-tugit prnout-bias referendum to be tabled next once it is legal to
@@ -711,10 +1126,15 @@ decl_module! {
 		/// - `O(1)`.
 		/// - One DB change.
 		/// # </weight>
-		#[weight = 5_000_000_000]
+		#[weight = T::WeightInfo::external_propose_default(T::MaxProposals::get())]
 		fn external_propose_default(origin, proposal_hash: T::Hash) {
 			T::ExternalDefaultOrigin::ensure_origin(origin)?;
-			<NextExternal<T>>::put((proposal_hash, VoteThreshold::SuperMajorityAgainst, VoteWeight::Quadratic));
+			<NextExternal<T>>::put((
+				proposal_hash,
+				VoteThreshold::SuperMajorityAgainst,
+				T::DefaultVoteWeight::get(),
+				Ballot::Open,
+			));
 		}
 
 		/// Schedule the currently externally-proposed majority-carries referendum to be tabled
@@ -725,7 +1145,8 @@ decl_module! {
 		///
 		/// - `proposal_hash`: The hash of the current external proposal.
 		/// - `voting_period`: The period that is allowed for voting on this proposal. Increased to
-		///   `FastTrackVotingPeriod` if too low.
+		///   `FastTrackVotingPeriod` if too low. A secret ballot needs at least two blocks, and
+		///   reveals in the last half of them if that is shorter than `RevealPeriod`.
 		/// - `delay`: The number of block after voting has ended in approval and this should be
 		///   enacted. This doesn't have a minimum amount.
 		///
@@ -736,7 +1157,7 @@ decl_module! {
 		/// - One DB change.
 		/// - One extra DB entry.
 		/// # </weight>
-		#[weight = 200_000_000]
+		#[weight = T::WeightInfo::fast_track(T::MaxProposals::get())]
 		fn fast_track(origin,
 			proposal_hash: T::Hash,
 			voting_period: T::BlockNumber,
@@ -759,7 +1180,7 @@ decl_module! {
 				ensure!(T::InstantAllowed::get(), Error::<T>::InstantNotAllowed);
 			}
 
-			let (e_proposal_hash, threshold, weight) = <NextExternal<T>>::get()
+			let (e_proposal_hash, threshold, weight, ballot) = <NextExternal<T>>::get()
 				.ok_or(Error::<T>::ProposalMissing)?;
 			ensure!(
 				threshold != VoteThreshold::SuperMajorityApprove,
@@ -770,10 +1191,18 @@ decl_module! {
 				Error::<T>::NotQuadraticWeight
 			);
 			ensure!(proposal_hash == e_proposal_hash, Error::<T>::InvalidHash);
+			// A secret ballot needs a block to commit in and another to reveal in.
+			ensure!(
+				ballot == Ballot::Open || voting_period >= 2u32.into(),
+				Error::<T>::VotingPeriodTooShort,
+			);
 
 			<NextExternal<T>>::kill();
 			let now = <frame_system::Module<T>>::block_number();
-			Self::inject_referendum(now + voting_period, proposal_hash, threshold, weight, delay);
+			let end = now + voting_period;
+			let ref_index = Self::inject_referendum(end, proposal_hash, threshold, weight, delay);
+			ReferendumSourceOf::insert(ref_index, ProposalSource::External);
+			Self::open_ballot(ref_index, ballot, end);
 		}
 
 		/// Veto and blacklist the external proposal hash.
@@ -791,11 +1220,11 @@ decl_module! {
 		///   be very large.
 		/// - O(log v), v is number of `existing_vetoers`
 		/// # </weight>
-		#[weight = 200_000_000]
-		fn veto_external(origin, proposal_hash: T::Hash) {
+		#[weight = T::WeightInfo::veto_external(MAX_VETOERS)]
+		fn veto_external(origin, proposal_hash: T::Hash) -> DispatchResultWithPostInfo {
 			let who = T::VetoOrigin::ensure_origin(origin)?;
 
-			if let Some((e_proposal_hash, _, _)) = <NextExternal<T>>::get() {
+			if let Some((e_proposal_hash, ..)) = <NextExternal<T>>::get() {
 				ensure!(proposal_hash == e_proposal_hash, Error::<T>::ProposalMissing);
 			} else {
 				Err(Error::<T>::NoProposal)?;
@@ -806,6 +1235,7 @@ decl_module! {
 				.unwrap_or_else(Vec::new);
 			let insert_position = existing_vetoers.binary_search(&who)
 				.err().ok_or(Error::<T>::AlreadyVetoed)?;
+			let vetoers = existing_vetoers.len() as u32;
 
 			existing_vetoers.insert(insert_position, who.clone());
 			let until = <frame_system::Module<T>>::block_number() + T::CooloffPeriod::get();
@@ -813,6 +1243,7 @@ decl_module! {
 
 			Self::deposit_event(RawEvent::Vetoed(who, proposal_hash, until));
 			<NextExternal<T>>::kill();
+			Ok(Some(T::WeightInfo::veto_external(vetoers)).into())
 		}
 
 		/// Remove a referendum.
@@ -824,7 +1255,7 @@ decl_module! {
 		/// # <weight>
 		/// - `O(1)`.
 		/// # </weight>
-		#[weight = (0, DispatchClass::Operational)]
+		#[weight = (T::WeightInfo::cancel_referendum(T::MaxVotes::get()), DispatchClass::Operational)]
 		fn cancel_referendum(origin, #[compact] ref_index: ReferendumIndex) {
 			ensure_root(origin)?;
 			Self::internal_cancel_referendum(ref_index);
@@ -840,103 +1271,94 @@ decl_module! {
 		/// - One DB change.
 		/// - O(d) where d is the items in the dispatch queue.
 		/// # </weight>
-		#[weight = (0, DispatchClass::Operational)]
+		#[weight = (T::WeightInfo::cancel_queued(T::MaxVotes::get()), DispatchClass::Operational)]
 		fn cancel_queued(origin, which: ReferendumIndex) {
 			ensure_root(origin)?;
 			T::Scheduler::cancel_named((DEMOCRACY_ID, which))
 				.map_err(|_| Error::<T>::ProposalMissing)?;
 		}
 
+		fn on_runtime_upgrade() -> Weight {
+			migration::migrate::<T>()
+		}
+
 		fn on_initialize(n: T::BlockNumber) -> Weight {
+			// Votes are counted before any referendum is baked, using up to a quarter of the block.
+			let migrated = migration::migrate_voting::<T>(T::MaximumBlockWeight::get() / 4);
+			// Every referendum from the lowest unbaked one is looked at.
+			let r = Self::referendum_count().saturating_sub(Self::lowest_unbaked());
 			if let Err(e) = Self::begin_block(n) {
 				sp_runtime::print(e);
 			}
 
-			0
+			T::WeightInfo::on_initialize_external(r).max(T::WeightInfo::on_initialize_public(r))
+				.saturating_add(migrated)
 		}
 
-		/// Specify a proxy that is already open to us. Called by the stash.
-		///
-		/// NOTE: Used to be called `set_proxy`.
-		///
-		/// The dispatch origin of this call must be _Signed_.
-		///
-		/// - `proxy`: The account that will be activated as proxy.
-		///
-		/// # <weight>
-		/// - One extra DB entry.
-		/// # </weight>
-		#[weight = 100_000_000]
-		fn activate_proxy(origin, proxy: T::AccountId) {
-			let who = ensure_signed(origin)?;
-			Proxy::<T>::try_mutate(&proxy, |a| match a.take() {
-				None => Err(Error::<T>::NotOpen),
-				Some(ProxyState::Active(_)) => Err(Error::<T>::AlreadyProxy),
-				Some(ProxyState::Open(x)) if &x == &who => {
-					*a = Some(ProxyState::Active(who));
-					Ok(())
-				}
-				Some(ProxyState::Open(_)) => Err(Error::<T>::WrongOpen),
-			})?;
+		fn offchain_worker(now: T::BlockNumber) {
+			offchain::index::<T>(now);
 		}
 
-		/// Clear the proxy. Called by the proxy.
+		/// Delegate the voting power (with some given conviction) of the sending account.
 		///
-		/// NOTE: Used to be called `resign_proxy`.
+		/// The balance delegated is locked for as long as it's delegated, and thereafter for the
+		/// time appropriate for the conviction's lock period.
 		///
-		/// The dispatch origin of this call must be _Signed_.
+		/// The dispatch origin of this call must be _Signed_, and the signing account must either:
+		///   - be delegating already; or
+		///   - have no voting activity (if there is, then it will need to be removed/consolidated
+		///     through `reap_vote` or `unvote`).
+		///
+		/// - `to`: The account whose voting the `target` account's voting power will follow.
+		/// - `conviction`: The conviction that will be attached to the delegated votes. When the
+		///   account is undelegated, the funds will be locked for the corresponding period.
+		/// - `balance`: The amount of the account's balance to be used in delegating. This must
+		///   not be more than the account's current balance.
+		///
+		/// Emits `Delegated`.
 		///
 		/// # <weight>
-		/// - One DB clear.
 		/// # </weight>
-		#[weight = 100_000_000]
-		fn close_proxy(origin) {
+		#[weight = T::WeightInfo::delegate(T::MaxVotes::get())]
+		pub fn delegate(origin, to: T::AccountId, conviction: Conviction, balance: BalanceOf<T>) {
 			let who = ensure_signed(origin)?;
-			Proxy::<T>::mutate(&who, |a| {
-				if a.is_some() {
-					system::Module::<T>::dec_ref(&who);
-				}
-				*a = None;
-			});
+			Self::try_delegate(who, to, DelegationClass::default(), conviction, balance)?;
 		}
 
-		/// Deactivate the proxy, but leave open to this account. Called by the stash.
-		///
-		/// The proxy must already be active.
+		/// Undelegate the voting power of the sending account.
 		///
-		/// NOTE: Used to be called `remove_proxy`.
+		/// Tokens may be unlocked following once an amount of time consistent with the lock period
+		/// of the conviction with which the delegation was issued.
 		///
-		/// The dispatch origin of this call must be _Signed_.
+		/// The dispatch origin of this call must be _Signed_ and the signing account must be
+		/// currently delegating.
 		///
-		/// - `proxy`: The account that will be deactivated as proxy.
+		/// Emits `Undelegated`.
 		///
 		/// # <weight>
-		/// - One DB clear.
+		/// - O(1).
 		/// # </weight>
-		#[weight = 100_000_000]
-		fn deactivate_proxy(origin, proxy: T::AccountId) {
+		#[weight = T::WeightInfo::undelegate(T::MaxVotes::get())]
+		fn undelegate(origin) {
 			let who = ensure_signed(origin)?;
-			Proxy::<T>::try_mutate(&proxy, |a| match a.take() {
-				None | Some(ProxyState::Open(_)) => Err(Error::<T>::NotActive),
-				Some(ProxyState::Active(x)) if &x == &who => {
-					*a = Some(ProxyState::Open(who));
-					Ok(())
-				}
-				Some(ProxyState::Active(_)) => Err(Error::<T>::WrongProxy),
-			})?;
+			Self::try_undelegate(who, DelegationClass::default())?;
 		}
 
-		/// Delegate the voting power (with some given conviction) of the sending account.
+		/// Delegate the voting power (with some given conviction) of the sending account in the
+		/// referenda of a class.
 		///
 		/// The balance delegated is locked for as long as it's delegated, and thereafter for the
-		/// time appropriate for the conviction's lock period.
+		/// time appropriate for the conviction's lock period. Delegating in the class that covers
+		/// all referenda is the same as `delegate`.
 		///
-		/// The dispatch origin of this call must be _Signed_, and the signing account must either:
-		///   - be delegating already; or
-		///   - have no voting activity (if there is, then it will need to be removed/consolidated
-		///     through `reap_vote` or `unvote`).
+		/// The dispatch origin of this call must be _Signed_, and the signing account must:
+		///   - not be delegating in a different class that overlaps `class`; and
+		///   - have no votes in ongoing referenda that `class` covers (if there are, then they will
+		///     need to be removed through `unvote`).
 		///
-		/// - `to`: The account whose voting the `target` account's voting power will follow.
+		/// - `class`: The referenda that the delegation applies to.
+		/// - `to`: The account whose voting the sending account's voting power will follow in
+		///   those referenda.
 		/// - `conviction`: The conviction that will be attached to the delegated votes. When the
 		///   account is undelegated, the funds will be locked for the corresponding period.
 		/// - `balance`: The amount of the account's balance to be used in delegating. This must
@@ -945,30 +1367,40 @@ decl_module! {
 		/// Emits `Delegated`.
 		///
 		/// # <weight>
+		/// - `O(R + D)` where R is the number of referenda the sending account and `to` have voted
+		///   on and D the number of classes they delegate and are delegated in.
 		/// # </weight>
-		#[weight = 500_000_000]
-		pub fn delegate(origin, to: T::AccountId, conviction: Conviction, balance: BalanceOf<T>) {
+		#[weight = T::WeightInfo::delegate_class(T::MaxVotes::get())]
+		fn delegate_class(origin,
+			class: DelegationClass,
+			to: T::AccountId,
+			conviction: Conviction,
+			balance: BalanceOf<T>,
+		) {
 			let who = ensure_signed(origin)?;
-			Self::try_delegate(who, to, conviction, balance)?;
+			Self::try_delegate(who, to, class, conviction, balance)?;
 		}
 
-		/// Undelegate the voting power of the sending account.
+		/// Undelegate the voting power of the sending account in the referenda of a class.
 		///
 		/// Tokens may be unlocked following once an amount of time consistent with the lock period
 		/// of the conviction with which the delegation was issued.
 		///
 		/// The dispatch origin of this call must be _Signed_ and the signing account must be
-		/// currently delegating.
+		/// currently delegating in `class`.
+		///
+		/// - `class`: The class of the delegation to end.
 		///
 		/// Emits `Undelegated`.
 		///
 		/// # <weight>
-		/// - O(1).
+		/// - `O(R + D)` where R is the number of referenda the former target has voted on and D
+		///   the number of classes it is delegated in.
 		/// # </weight>
-		#[weight = 500_000_000]
-		fn undelegate(origin) {
+		#[weight = T::WeightInfo::undelegate_class(T::MaxVotes::get())]
+		fn undelegate_class(origin, class: DelegationClass) {
 			let who = ensure_signed(origin)?;
-			Self::try_undelegate(who)?;
+			Self::try_undelegate(who, class)?;
 		}
 
 		/// Clears all public proposals.
@@ -979,7 +1411,7 @@ decl_module! {
 		/// - `O(1)`.
 		/// - One DB clear.
 		/// # </weight>
-		#[weight = 0]
+		#[weight = T::WeightInfo::clear_public_proposals(T::MaxProposals::get())]
 		fn clear_public_proposals(origin) {
 			ensure_root(origin)?;
 
@@ -999,7 +1431,7 @@ decl_module! {
 		/// - Dependent on the size of `encoded_proposal` but protected by a
 		///   required deposit.
 		/// # </weight>
-		#[weight = 100_000_000]
+		#[weight = T::WeightInfo::note_preimage(encoded_proposal.len() as u32)]
 		fn note_preimage(origin, encoded_proposal: Vec<u8>) {
 			let who = ensure_signed(origin)?;
 			let proposal_hash = T::Hashing::hash(&encoded_proposal[..]);
@@ -1034,7 +1466,7 @@ decl_module! {
 		/// # <weight>
 		/// - Dependent on the size of `encoded_proposal` and length of dispatch queue.
 		/// # </weight>
-		#[weight = 100_000_000]
+		#[weight = T::WeightInfo::note_imminent_preimage(encoded_proposal.len() as u32)]
 		fn note_imminent_preimage(origin, encoded_proposal: Vec<u8>) {
 			let who = ensure_signed(origin)?;
 			let proposal_hash = T::Hashing::hash(&encoded_proposal[..]);
@@ -1070,13 +1502,13 @@ decl_module! {
 		/// # <weight>
 		/// - One DB clear.
 		/// # </weight>
-		#[weight = 0]
-		fn reap_preimage(origin, proposal_hash: T::Hash) {
+		#[weight = T::WeightInfo::reap_preimage(MAX_PREIMAGE_BYTES)]
+		fn reap_preimage(origin, proposal_hash: T::Hash) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			let (provider, deposit, since, expiry) = <Preimages<T>>::get(&proposal_hash)
+			let (bytes, provider, deposit, since, expiry) = <Preimages<T>>::get(&proposal_hash)
 				.and_then(|m| match m {
-					PreimageStatus::Available { provider, deposit, since, expiry, .. }
-						=> Some((provider, deposit, since, expiry)),
+					PreimageStatus::Available { data, provider, deposit, since, expiry }
+						=> Some((data.len() as u32, provider, deposit, since, expiry)),
 					_ => None,
 				}).ok_or(Error::<T>::PreimageMissing)?;
 
@@ -1089,6 +1521,7 @@ decl_module! {
 			let _ = T::Currency::repatriate_reserved(&provider, &who, deposit, BalanceStatus::Free);
 			<Preimages<T>>::remove(&proposal_hash);
 			Self::deposit_event(RawEvent::PreimageReaped(proposal_hash, provider, deposit, who));
+			Ok(Some(T::WeightInfo::reap_preimage(bytes)).into())
 		}
 
 		/// Unlock tokens that have an expired lock.
@@ -1100,36 +1533,14 @@ decl_module! {
 		/// # <weight>
 		/// - `O(1)`.
 		/// # </weight>
-		#[weight = 0]
+		#[weight = T::WeightInfo::unlock_set(T::MaxVotes::get())
+			.max(T::WeightInfo::unlock_remove(T::MaxVotes::get()))]
 		fn unlock(origin, target: T::AccountId) {
 			ensure_signed(origin)?;
+			Self::ensure_migrated()?;
 			Self::update_lock(&target);
 		}
 
-		/// Become a proxy.
-		///
-		/// This must be called prior to a later `activate_proxy`.
-		///
-		/// Origin must be a Signed.
-		///
-		/// - `target`: The account whose votes will later be proxied.
-		///
-		/// `close_proxy` must be called before the account can be destroyed.
-		///
-		/// # <weight>
-		/// - One extra DB entry.
-		/// # </weight>
-		#[weight = 100_000_000]
-		fn open_proxy(origin, target: T::AccountId) {
-			let who = ensure_signed(origin)?;
-			Proxy::<T>::mutate(&who, |a| {
-				if a.is_none() {
-					system::Module::<T>::inc_ref(&who);
-				}
-				*a = Some(ProxyState::Open(target));
-			});
-		}
-
 		/// Remove a vote for a referendum.
 		///
 		/// If:
@@ -1158,7 +1569,7 @@ decl_module! {
 		/// # <weight>
 		/// - `O(R + log R)` where R is the number of referenda that `target` has voted on.
 		/// # </weight>
-		#[weight = 0]
+		#[weight = T::WeightInfo::remove_vote(T::MaxVotes::get())]
 		fn remove_vote(origin, index: ReferendumIndex) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			Self::try_remove_vote(&who, index, UnvoteScope::Any)
@@ -1180,7 +1591,7 @@ decl_module! {
 		/// # <weight>
 		/// - `O(R + log R)` where R is the number of referenda that `target` has voted on.
 		/// # </weight>
-		#[weight = 0]
+		#[weight = T::WeightInfo::remove_other_vote(T::MaxVotes::get())]
 		fn remove_other_vote(origin, target: T::AccountId, index: ReferendumIndex) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			let scope = if target == who { UnvoteScope::Any } else { UnvoteScope::OnlyExpired };
@@ -1188,20 +1599,61 @@ decl_module! {
 			Ok(())
 		}
 
+		/// Remove the record of a finished referendum, along with its final tally.
+		///
+		/// The dispatch origin of this call must be _Signed_. No deposit is needed.
+		///
+		/// - `index`: The index of a referendum that finished at least `HistoryRetention` blocks
+		///   ago. Every vote on it must have been removed, e.g. through `remove_other_vote`, and
+		///   every multi-option vote and secret ballot commitment that uses its index must be gone.
+		///
+		/// Emits `ReferendumPruned`.
+		///
+		/// # <weight>
+		/// - `O(1)`. The benchmark is parameterised over the referenda voted on by `MaxVotes`
+		///   accounts only to confirm that they don't matter.
+		/// - Five DB reads, two DB changes.
+		/// # </weight>
+		#[weight = T::WeightInfo::prune_referendum(T::MaxVotes::get())]
+		fn prune_referendum(origin, #[compact] index: ReferendumIndex) {
+			ensure_signed(origin)?;
+			let end = match (Self::referendum_info(index), Self::multi_option_referendum_info(index)) {
+				(Some(ReferendumInfo::Finished { end, .. }), _) => end,
+				(_, Some(MultiOptionInfo::Finished { end, .. })) => end,
+				_ => Err(Error::<T>::ReferendumInvalid)?,
+			};
+			let now = <frame_system::Module<T>>::block_number();
+			ensure!(now >= end.saturating_add(T::HistoryRetention::get()), Error::<T>::TooEarly);
+			Self::ensure_migrated()?;
+			ensure!(Self::voter_count(index) == 0, Error::<T>::VotesRemain);
+			// Neither multi-option votes nor commitments are counted among the voters.
+			ensure!(
+				MultiOptionVotesOf::<T>::iter_prefix_values(index).next().is_none(),
+				Error::<T>::VotesRemain,
+			);
+			ensure!(
+				<CommitmentOf<T>>::iter_prefix_values(index).next().is_none(),
+				Error::<T>::VotesRemain,
+			);
+
+			ReferendumInfoOf::<T>::remove(index);
+			MultiOptionReferendumInfoOf::<T>::remove(index);
+			Self::deposit_event(RawEvent::ReferendumPruned(index));
+		}
+
 		/// Delegate the voting power (with some given conviction) of a proxied account.
 		///
 		/// The balance delegated is locked for as long as it's delegated, and thereafter for the
 		/// time appropriate for the conviction's lock period.
 		///
-		/// The dispatch origin of this call must be _Signed_, and the signing account must have
-		/// been set as the proxy account for `target`.
+		/// The dispatch origin of this call must be _Signed_ by a voting proxy of `real`.
 		///
-		/// - `target`: The account whole voting power shall be delegated and whose balance locked.
+		/// - `real`: The account whole voting power shall be delegated and whose balance locked.
 		///   This account must either:
 		///   - be delegating already; or
 		///   - have no voting activity (if there is, then it will need to be removed/consolidated
 		///     through `reap_vote` or `unvote`).
-		/// - `to`: The account whose voting the `target` account's voting power will follow.
+		/// - `to`: The account whose voting the `real` account's voting power will follow.
 		/// - `conviction`: The conviction that will be attached to the delegated votes. When the
 		///   account is undelegated, the funds will be locked for the corresponding period.
 		/// - `balance`: The amount of the account's balance to be used in delegating. This must
@@ -1211,15 +1663,16 @@ decl_module! {
 		///
 		/// # <weight>
 		/// # </weight>
-		#[weight = 500_000_000]
+		#[weight = T::WeightInfo::proxy_delegate(T::MaxVotes::get())]
 		pub fn proxy_delegate(origin,
+			real: T::AccountId,
 			to: T::AccountId,
 			conviction: Conviction,
 			balance: BalanceOf<T>,
 		) {
 			let who = ensure_signed(origin)?;
-			let target = Self::proxy(who).and_then(|a| a.as_active()).ok_or(Error::<T>::NotProxy)?;
-			Self::try_delegate(target, to, conviction, balance)?;
+			Self::ensure_voting_proxy(&real, &who)?;
+			Self::try_delegate(real, to, DelegationClass::default(), conviction, balance)?;
 		}
 
 		/// Undelegate the voting power of a proxied account.
@@ -1227,19 +1680,20 @@ decl_module! {
 		/// Tokens may be unlocked following once an amount of time consistent with the lock period
 		/// of the conviction with which the delegation was issued.
 		///
-		/// The dispatch origin of this call must be _Signed_ and the signing account must be a
-		/// proxy for some other account which is currently delegating.
+		/// The dispatch origin of this call must be _Signed_ by a voting proxy of `real`.
+		///
+		/// - `real`: The account to undelegate, which must be currently delegating.
 		///
 		/// Emits `Undelegated`.
 		///
 		/// # <weight>
 		/// - O(1).
 		/// # </weight>
-		#[weight = 500_000_000]
-		fn proxy_undelegate(origin) {
+		#[weight = T::WeightInfo::proxy_undelegate(T::MaxVotes::get())]
+		fn proxy_undelegate(origin, real: T::AccountId) {
 			let who = ensure_signed(origin)?;
-			let target = Self::proxy(who).and_then(|a| a.as_active()).ok_or(Error::<T>::NotProxy)?;
-			Self::try_undelegate(target)?;
+			Self::ensure_voting_proxy(&real, &who)?;
+			Self::try_undelegate(real, DelegationClass::default())?;
 		}
 
 		/// Remove a proxied vote for a referendum.
@@ -1247,19 +1701,20 @@ decl_module! {
 		/// Exactly equivalent to `remove_vote` except that it operates on the account that the
 		/// sender is a proxy for.
 		///
-		/// The dispatch origin of this call must be _Signed_ and the signing account must be a
-		/// proxy for some other account which has a registered vote for the referendum of `index`.
+		/// The dispatch origin of this call must be _Signed_ by a voting proxy of `real`.
 		///
+		/// - `real`: The account of the vote to be removed; this account must have voted for
+		///   referendum `index`.
 		/// - `index`: The index of referendum of the vote to be removed.
 		///
 		/// # <weight>
-		/// - `O(R + log R)` where R is the number of referenda that `target` has voted on.
+		/// - `O(R + log R)` where R is the number of referenda that `real` has voted on.
 		/// # </weight>
-		#[weight = 0]
-		fn proxy_remove_vote(origin, index: ReferendumIndex) -> DispatchResult {
+		#[weight = T::WeightInfo::proxy_remove_vote(T::MaxVotes::get())]
+		fn proxy_remove_vote(origin, real: T::AccountId, index: ReferendumIndex) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			let target = Self::proxy(who).and_then(|a| a.as_active()).ok_or(Error::<T>::NotProxy)?;
-			Self::try_remove_vote(&target, index, UnvoteScope::Any)
+			Self::ensure_voting_proxy(&real, &who)?;
+			Self::try_remove_vote(&real, index, UnvoteScope::Any)
 		}
 
 		/// Enact a proposal from a referendum. For now we just make the weight be the maximum.
@@ -1268,49 +1723,506 @@ decl_module! {
 			ensure_root(origin)?;
 			Self::do_enact_proposal(proposal_hash, index)
 		}
-	}
-}
-
-impl<T: Trait> Module<T> {
-	// exposed immutables.
-
-	/// Get the amount locked in support of `proposal`; `None` if proposal isn't a valid proposal
-	/// index.
-	pub fn backing_for(proposal: PropIndex) -> Option<BalanceOf<T>> {
-		Self::deposit_of(proposal).map(|(d, l)| d * (l.len() as u32).into())
-	}
-
-	/// Get all referenda ready for tally at block `n`.
-	pub fn maturing_referenda_at(
-		n: T::BlockNumber
-	) -> Vec<(ReferendumIndex, ReferendumStatus<T::BlockNumber, T::Hash, BalanceOf<T>>)> {
-		let next = Self::lowest_unbaked();
-		let last = Self::referendum_count();
-		(next..last).into_iter()
-			.map(|i| (i, Self::referendum_info(i)))
-			.filter_map(|(i, maybe_info)| match maybe_info {
-				Some(ReferendumInfo::Ongoing(status)) => Some((i, status)),
-				_ => None,
-			})
-			.filter(|(_, status)| status.end == n)
-			.collect()
-	}
 
-	// Exposed mutables.
+		/// Schedule a referendum with the given vote weighting to be tabled once it is legal to
+		/// schedule an external referendum.
+		///
+		/// The dispatch origin of this call must be `ExternalOrigin`.
+		///
+		/// - `proposal_hash`: The preimage hash of the proposal.
+		/// - `weight`: The vote weighting of the resulting referendum.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - One DB change.
+		/// # </weight>
+		#[weight = T::WeightInfo::external_propose_with_weight(T::MaxProposals::get())]
+		fn external_propose_with_weight(origin, proposal_hash: T::Hash, weight: VoteWeight) {
+			T::ExternalOrigin::ensure_origin(origin)?;
+			Self::do_external_propose(proposal_hash, weight, Ballot::Open)?;
+		}
 
-	#[cfg(feature = "std")]
-	pub fn force_proxy(stash: T::AccountId, proxy: T::AccountId) {
-		Proxy::<T>::mutate(&proxy, |o| {
-			if o.is_none() {
-				system::Module::<T>::inc_ref(&proxy);
-			}
-			*o = Some(ProxyState::Active(stash))
-		})
-	}
+		/// Schedule a majority-carries referendum with the given vote weighting to be tabled next
+		/// once it is legal to schedule an external referendum.
+		///
+		/// The dispatch of this call must be `ExternalMajorityOrigin`.
+		///
+		/// - `proposal_hash`: The preimage hash of the proposal.
+		/// - `weight`: The vote weighting of the resulting referendum.
+		///
+		/// Unlike `external_propose`, blacklisting has no effect on this and it may replace a
+		/// pre-scheduled `external_propose` call.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - One DB change.
+		/// # </weight>
+		#[weight = T::WeightInfo::external_propose_majority_with_weight(T::MaxProposals::get())]
+		fn external_propose_majority_with_weight(origin, proposal_hash: T::Hash, weight: VoteWeight) {
+			T::ExternalMajorityOrigin::ensure_origin(origin)?;
+			<NextExternal<T>>::put((proposal_hash, VoteThreshold::SimpleMajority, weight, Ballot::Open));
+		}
 
-	/// Start a referendum.
-	pub fn internal_start_referendum(
-		proposal_hash: T::Hash,
+		/// Schedule a negative-turnout-bias referendum with the given vote weighting to be tabled
+		/// next once it is legal to schedule an external referendum.
+		///
+		/// The dispatch of this call must be `ExternalDefaultOrigin`.
+		///
+		/// - `proposal_hash`: The preimage hash of the proposal.
+		/// - `weight`: The vote weighting of the resulting referendum.
+		///
+		/// Unlike `external_propose`, blacklisting has no effect on this and it may replace a
+		/// pre-scheduled `external_propose` call.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - One DB change.
+		/// # </weight>
+		#[weight = T::WeightInfo::external_propose_default_with_weight(T::MaxProposals::get())]
+		fn external_propose_default_with_weight(origin, proposal_hash: T::Hash, weight: VoteWeight) {
+			T::ExternalDefaultOrigin::ensure_origin(origin)?;
+			<NextExternal<T>>::put((proposal_hash, VoteThreshold::SuperMajorityAgainst, weight, Ballot::Open));
+		}
+
+		/// Schedule a majority-carries referendum with a quorum to be tabled next once it is legal
+		/// to schedule an external referendum.
+		///
+		/// The dispatch of this call must be `ExternalMajorityOrigin`.
+		///
+		/// - `proposal_hash`: The preimage hash of the proposal.
+		/// - `voters`: The number of accounts that must vote for the referendum to pass.
+		/// - `turnout`: The fraction of the weighted electorate that the weighted turnout, the sum
+		///   of each voter's weighted balance, must reach for the referendum to pass.
+		/// - `weight`: The vote weighting of the resulting referendum.
+		///
+		/// Unlike `external_propose`, blacklisting has no effect on this and it may replace a
+		/// pre-scheduled `external_propose` call.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - One DB change.
+		/// # </weight>
+		#[weight = T::WeightInfo::external_propose_quorum(T::MaxProposals::get())]
+		fn external_propose_quorum(origin,
+			proposal_hash: T::Hash,
+			voters: u32,
+			turnout: Perbill,
+			weight: VoteWeight,
+		) {
+			T::ExternalMajorityOrigin::ensure_origin(origin)?;
+			let threshold = VoteThreshold::Quorum { voters, turnout };
+			<NextExternal<T>>::put((proposal_hash, threshold, weight, Ballot::Open));
+		}
+
+		/// Vote in a credit referendum with `votes` bought with voice credits, at a cost of
+		/// `votes²` credits out of the sender's allowance for the current credit period. Replacing
+		/// an earlier vote refunds its credits if they were spent in the current period.
+		///
+		/// The dispatch origin of this call must be _Signed_ by a person that is not a
+		/// sub-account.
+		///
+		/// - `ref_index`: The index of the credit referendum to vote for.
+		/// - `aye`: Whether the vote is to enact the proposal.
+		/// - `votes`: The number of votes to cast.
+		///
+		/// # <weight>
+		/// - `O(R)`.
+		/// - R is the number of referendums the voter has voted on.
+		/// - One DB change, one DB entry.
+		/// # </weight>
+		#[weight = T::WeightInfo::vote_with_credits(T::MaxVotes::get())]
+		fn vote_with_credits(origin,
+			#[compact] ref_index: ReferendumIndex,
+			aye: bool,
+			#[compact] votes: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::try_vote_with_credits(&who, ref_index, aye, votes)
+		}
+
+		/// Start a referendum choosing between the alternative proposals `proposal_hashes`. Only
+		/// the winning proposal is enacted, `EnactmentPeriod` after the referendum ends.
+		///
+		/// The dispatch of this call must be `ExternalMajorityOrigin`.
+		///
+		/// - `proposal_hashes`: The preimage hashes of the options, between two and `MaxOptions`
+		///   of them, none of them blacklisted.
+		///
+		/// Emits `MultiOptionStarted`.
+		///
+		/// # <weight>
+		/// - `O(P)`.
+		/// - P is the number of options.
+		/// - Two DB changes.
+		/// # </weight>
+		#[weight = T::WeightInfo::start_multi_option_referendum(proposal_hashes.len() as u32)]
+		fn start_multi_option_referendum(origin, proposal_hashes: Vec<T::Hash>) {
+			T::ExternalMajorityOrigin::ensure_origin(origin)?;
+			ensure!(proposal_hashes.len() >= 2, Error::<T>::TooFewOptions);
+			ensure!(proposal_hashes.len() <= T::MaxOptions::get() as usize, Error::<T>::TooManyOptions);
+			let now = <frame_system::Module<T>>::block_number();
+			for proposal_hash in proposal_hashes.iter() {
+				if let Some((until, _)) = <Blacklist<T>>::get(proposal_hash) {
+					ensure!(now >= until, Error::<T>::ProposalBlacklisted);
+				}
+			}
+			Self::inject_multi_option_referendum(
+				now + T::VotingPeriod::get(),
+				proposal_hashes,
+				T::EnactmentPeriod::get(),
+			);
+		}
+
+		/// Vote in a multi-option referendum by placing `balances[i]` of capital on option `i`,
+		/// replacing any earlier vote. Each option counts the square root of the capital placed
+		/// on it. The capital stays locked until the referendum ends, even if the vote is changed.
+		///
+		/// The dispatch origin of this call must be _Signed_ by a person that is not a
+		/// sub-account and is not delegating quadratic referenda.
+		///
+		/// - `ref_index`: The index of the multi-option referendum to vote for.
+		/// - `balances`: The capital to place on each option.
+		///
+		/// Emits `OptionsVoted`.
+		///
+		/// # <weight>
+		/// - `O(P)`.
+		/// - P is the number of options.
+		/// - Three DB changes, one DB entry.
+		/// # </weight>
+		#[weight = T::WeightInfo::vote_options(balances.len() as u32)]
+		fn vote_options(origin,
+			#[compact] ref_index: ReferendumIndex,
+			balances: Vec<BalanceOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::try_vote_options(&who, ref_index, balances)
+		}
+
+		/// Schedule a referendum voted on by secret ballot to be tabled once it is legal to
+		/// schedule an external referendum.
+		///
+		/// The dispatch origin of this call must be the external origin of `threshold`:
+		/// `ExternalOrigin` for `SuperMajorityApprove`, `ExternalMajorityOrigin` for
+		/// `SimpleMajority` and `Quorum`, and `ExternalDefaultOrigin` for `SuperMajorityAgainst`. These replace a
+		/// pre-scheduled external proposal and ignore the blacklist on the same terms as
+		/// `external_propose`, `external_propose_majority` and `external_propose_default`.
+		///
+		/// - `proposal_hash`: The preimage hash of the proposal.
+		/// - `threshold`: The thresholding mechanism of the resulting referendum.
+		/// - `weight`: The vote weighting of the resulting referendum; credit votes can't be
+		///   committed.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - One DB change.
+		/// # </weight>
+		#[weight = T::WeightInfo::external_propose_secret(T::MaxProposals::get())]
+		fn external_propose_secret(origin,
+			proposal_hash: T::Hash,
+			threshold: VoteThreshold,
+			weight: VoteWeight,
+		) {
+			match threshold {
+				VoteThreshold::SuperMajorityApprove => { T::ExternalOrigin::ensure_origin(origin)?; }
+				VoteThreshold::SimpleMajority | VoteThreshold::Quorum { .. } => {
+					T::ExternalMajorityOrigin::ensure_origin(origin)?;
+				}
+				VoteThreshold::SuperMajorityAgainst => { T::ExternalDefaultOrigin::ensure_origin(origin)?; }
+			}
+			ensure!(weight != VoteWeight::Credits, Error::<T>::WrongVoteWeight);
+			match threshold {
+				VoteThreshold::SuperMajorityApprove =>
+					Self::do_external_propose(proposal_hash, weight, Ballot::Secret)?,
+				_ => <NextExternal<T>>::put((proposal_hash, threshold, weight, Ballot::Secret)),
+			}
+		}
+
+		/// Commit to a vote in a secret ballot, replacing any earlier commitment. A deposit of
+		/// `CommitDeposit` is reserved for the first commitment.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `ref_index`: The index of the secret ballot to vote in.
+		/// - `commitment`: The hash of the `(voter, ref_index, vote, salt)` to be revealed.
+		///
+		/// Emits `VoteCommitted`.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - One DB change, two DB entries.
+		/// # </weight>
+		#[weight = T::WeightInfo::commit_vote(T::MaxVotes::get())]
+		fn commit_vote(origin, #[compact] ref_index: ReferendumIndex, commitment: T::Hash) {
+			let who = ensure_signed(origin)?;
+			Self::referendum_status(ref_index)?;
+			let reveal_from = Self::reveal_from(ref_index).ok_or(Error::<T>::NotSecretBallot)?;
+			let now = <frame_system::Module<T>>::block_number();
+			ensure!(now < reveal_from, Error::<T>::CommitClosed);
+
+			let deposit = match Self::commitment_of(ref_index, &who) {
+				Some((_, deposit)) => deposit,
+				None => {
+					let deposit = T::CommitDeposit::get();
+					T::Currency::reserve(&who, deposit)?;
+					deposit
+				}
+			};
+			<CommitmentOf<T>>::insert(ref_index, &who, (commitment, deposit));
+			Self::deposit_event(RawEvent::VoteCommitted(ref_index, who));
+		}
+
+		/// Reveal a committed vote in a secret ballot, counting it and returning the commitment
+		/// deposit. The vote is then held like any other, so it may be removed or unlocked as
+		/// usual.
+		///
+		/// The dispatch origin of this call must be _Signed_ by the account that committed.
+		///
+		/// - `ref_index`: The index of the secret ballot.
+		/// - `vote`: The vote committed to.
+		/// - `salt`: The salt committed to.
+		///
+		/// Emits `Voted`.
+		///
+		/// # <weight>
+		/// - `O(R + L)`.
+		/// - R is the number of referendums the voter has voted on.
+		/// - L is the number of sub-accounts looked at for linked capital, at most
+		///   `MaxLinkedSubAccounts`.
+		/// - Three DB changes, two DB entries, and L DB changes for the linked sub-accounts.
+		/// # </weight>
+		#[weight = T::WeightInfo::reveal_vote(T::MaxVotes::get(), T::MaxLinkedSubAccounts::get())]
+		fn reveal_vote(origin,
+			#[compact] ref_index: ReferendumIndex,
+			vote: AccountVote<BalanceOf<T>>,
+			salt: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let reveal_from = Self::reveal_from(ref_index).ok_or(Error::<T>::NotSecretBallot)?;
+			let now = <frame_system::Module<T>>::block_number();
+			ensure!(now >= reveal_from, Error::<T>::RevealNotOpen);
+			let (commitment, deposit) = Self::commitment_of(ref_index, &who)
+				.ok_or(Error::<T>::NoCommitment)?;
+			ensure!(
+				T::Hashing::hash_of(&(&who, ref_index, vote, salt)) == commitment,
+				Error::<T>::CommitmentMismatch,
+			);
+
+			Self::record_vote(&who, ref_index, vote)?;
+			<CommitmentOf<T>>::remove(ref_index, &who);
+			T::Currency::unreserve(&who, deposit);
+			Ok(())
+		}
+
+		/// Settle up to `count` of the commitments left unrevealed in a secret ballot that is no
+		/// longer ongoing. Their deposits are slashed if the referendum ended, and returned if it
+		/// was cancelled.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `ref_index`: The index of the secret ballot.
+		/// - `count`: The most commitments to settle.
+		///
+		/// Emits `CommitmentSlashed` or `CommitmentReturned` for each commitment settled.
+		///
+		/// # <weight>
+		/// - `O(C)`.
+		/// - C is `count`.
+		/// - One DB entry, C DB changes.
+		/// # </weight>
+		#[weight = T::WeightInfo::reap_commitments(*count)]
+		fn reap_commitments(origin, #[compact] ref_index: ReferendumIndex, #[compact] count: u32) {
+			ensure_signed(origin)?;
+			// Finished referenda keep their record until pruned, which they can't be while
+			// commitments remain, so a referendum without one was cancelled.
+			let slash = match Self::referendum_info(ref_index) {
+				Some(ReferendumInfo::Ongoing(_)) => Err(Error::<T>::BallotOngoing)?,
+				Some(ReferendumInfo::Finished { .. }) => true,
+				None => false,
+			};
+			let reaped = <CommitmentOf<T>>::iter_prefix(ref_index).take(count as usize).collect::<Vec<_>>();
+			for (who, (_, deposit)) in reaped {
+				<CommitmentOf<T>>::remove(ref_index, &who);
+				if slash {
+					T::Slash::on_unbalanced(T::Currency::slash_reserved(&who, deposit).0);
+					Self::deposit_event(RawEvent::CommitmentSlashed(ref_index, who, deposit));
+				} else {
+					// Commitments weren't revealed through no fault of the voters.
+					T::Currency::unreserve(&who, deposit);
+					Self::deposit_event(RawEvent::CommitmentReturned(ref_index, who, deposit));
+				}
+			}
+		}
+
+		/// Remove up to `count` of the votes of a multi-option referendum that is no longer
+		/// ongoing. Their capital stays locked through the voters' prior locks, so this only frees
+		/// the storage.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `ref_index`: The index of the multi-option referendum.
+		/// - `count`: The most votes to remove.
+		///
+		/// Emits `OptionsVoteReaped` for each vote removed.
+		///
+		/// # <weight>
+		/// - `O(V)`.
+		/// - V is `count`.
+		/// - One DB entry, V DB changes.
+		/// # </weight>
+		#[weight = T::WeightInfo::reap_multi_option_votes(*count)]
+		fn reap_multi_option_votes(origin, #[compact] ref_index: ReferendumIndex, #[compact] count: u32) {
+			ensure_signed(origin)?;
+			if let Some(MultiOptionInfo::Ongoing(_)) = Self::multi_option_referendum_info(ref_index) {
+				Err(Error::<T>::ReferendumOngoing)?
+			}
+			let reaped = MultiOptionVotesOf::<T>::iter_prefix(ref_index)
+				.take(count as usize)
+				.map(|(who, _)| who)
+				.collect::<Vec<_>>();
+			for who in reaped {
+				MultiOptionVotesOf::<T>::remove(ref_index, &who);
+				Self::deposit_event(RawEvent::OptionsVoteReaped(ref_index, who));
+			}
+		}
+
+		/// Agree to be linked to `person` as a sub-account, so that the sender's capital is added
+		/// to theirs in quadratic votes, and locked along with them. The sender may then no longer
+		/// vote or delegate itself. The link only takes effect while the `Personhood` also has the
+		/// sender as a sub-account of `person`.
+		///
+		/// The dispatch origin of this call must be _Signed_ by the sub-account.
+		///
+		/// - `person`: The account to be linked to.
+		///
+		/// Emits `SubAccountLinked`.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - One DB change.
+		/// # </weight>
+		#[weight = T::WeightInfo::link_sub_account(T::MaxVotes::get())]
+		fn link_sub_account(origin, person: T::AccountId) {
+			let who = ensure_signed(origin)?;
+			ensure!(who != person, Error::<T>::Nonsense);
+			<LinkedTo<T>>::insert(&who, &person);
+			Self::deposit_event(RawEvent::SubAccountLinked(who, person));
+		}
+
+		/// Withdraw from being linked to a person as a sub-account. Capital already locked along
+		/// with their votes stays locked until those locks expire.
+		///
+		/// The dispatch origin of this call must be _Signed_ by the sub-account.
+		///
+		/// Emits `SubAccountUnlinked`.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - One DB change.
+		/// # </weight>
+		#[weight = T::WeightInfo::unlink_sub_account(T::MaxVotes::get())]
+		fn unlink_sub_account(origin) {
+			let who = ensure_signed(origin)?;
+			let person = <LinkedTo<T>>::take(&who).ok_or(Error::<T>::NotLinked)?;
+			Self::deposit_event(RawEvent::SubAccountUnlinked(who, person));
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	// exposed immutables.
+
+	/// Get the amount locked in support of `proposal`; `None` if proposal isn't a valid proposal
+	/// index.
+	pub fn backing_for(proposal: PropIndex) -> Option<BalanceOf<T>> {
+		Self::deposit_of(proposal).map(|(d, l)| d * (l.len() as u32).into())
+	}
+
+	/// Get all referenda ready for tally at block `n`.
+	pub fn maturing_referenda_at(
+		n: T::BlockNumber
+	) -> Vec<(ReferendumIndex, ReferendumStatus<T::BlockNumber, T::Hash, BalanceOf<T>>)> {
+		let next = Self::lowest_unbaked();
+		let last = Self::referendum_count();
+		(next..last).into_iter()
+			.map(|i| (i, Self::referendum_info(i)))
+			.filter_map(|(i, maybe_info)| match maybe_info {
+				Some(ReferendumInfo::Ongoing(status)) => Some((i, status)),
+				_ => None,
+			})
+			.filter(|(_, status)| status.end == n)
+			.collect()
+	}
+
+	/// Get all multi-option referenda ready for tally at block `n`.
+	pub fn maturing_multi_option_referenda_at(
+		n: T::BlockNumber
+	) -> Vec<(ReferendumIndex, MultiOptionStatus<T::BlockNumber, T::Hash, BalanceOf<T>>)> {
+		let next = Self::lowest_unbaked();
+		let last = Self::referendum_count();
+		(next..last).into_iter()
+			.map(|i| (i, Self::multi_option_referendum_info(i)))
+			.filter_map(|(i, maybe_info)| match maybe_info {
+				Some(MultiOptionInfo::Ongoing(status)) => Some((i, status)),
+				_ => None,
+			})
+			.filter(|(_, status)| status.end == n)
+			.collect()
+	}
+
+	/// Get the tally of referendum `ref_index`, the weighted electorate its weighted turnout is
+	/// measured against and whether it would be approved if it ended now; `None` if the
+	/// referendum isn't ongoing.
+	pub fn referendum_outcome(ref_index: ReferendumIndex)
+		-> Option<(Tally<BalanceOf<T>>, BalanceOf<T>, VoteWeight, VoteThreshold, bool)>
+	{
+		let status = Self::referendum_status(ref_index).ok()?;
+		let electorate = Self::weighted_electorate(status.weight);
+		let approved = status.threshold.approved(status.tally.clone(), electorate, status.weight);
+		Some((status.tally, electorate, status.weight, status.threshold, approved))
+	}
+
+	/// Get the weighted balance and votes `who` would cast by voting `balance` with `conviction`
+	/// in a referendum weighted by `weight`, along with the weighted votes delegated to it; `None`
+	/// if `who` couldn't vote in such a referendum. Delegations in classes tied to a proposal
+	/// source aren't counted, since the referendum's source isn't known.
+	pub fn voting_power(
+		who: &T::AccountId,
+		weight: VoteWeight,
+		balance: BalanceOf<T>,
+		conviction: Conviction,
+	) -> Option<(BalanceOf<T>, Delegations<BalanceOf<T>>, Delegations<BalanceOf<T>>)> {
+		let delegations = match VotingOf::<T>::get(who) {
+			Voting::Direct { delegations, .. } => delegations,
+			Voting::Delegating { .. } => return None,
+		};
+		let delegations = Self::received_delegations(
+			delegations,
+			&Self::class_delegations_of(who),
+			weight,
+			None,
+		);
+		let linked = match weight {
+			VoteWeight::Quadratic => Self::linked_capital(who, None).ok()?,
+			VoteWeight::Standard => Vec::new(),
+			VoteWeight::Credits => return None,
+		};
+		let linked_total = linked.iter()
+			.fold(Zero::zero(), |a: BalanceOf<T>, (_, b)| a.saturating_add(*b));
+		let level = T::ConvictionSchedule::get().level(conviction)?;
+		let weighted_balance = weight.calculate_weight(balance.saturating_add(linked_total));
+		Some((
+			weighted_balance,
+			level.votes(balance, weighted_balance),
+			weight.delegation(delegations, T::DelegationWeighting::get()),
+		))
+	}
+
+	// Exposed mutables.
+
+	/// Start a referendum.
+	pub fn internal_start_referendum(
+		proposal_hash: T::Hash,
 		threshold: VoteThreshold,
 		weight: VoteWeight,
 		delay: T::BlockNumber,
@@ -1324,14 +2236,75 @@ impl<T: Trait> Module<T> {
 		)
 	}
 
+	/// Queue a public proposal from `who`, reserving its deposit of `value`.
+	fn do_propose(
+		who: T::AccountId,
+		proposal_hash: T::Hash,
+		value: BalanceOf<T>,
+		weight: VoteWeight,
+		ballot: Ballot,
+	) -> DispatchResult {
+		ensure!(value >= T::MinimumDeposit::get(), Error::<T>::ValueLow);
+		let proposals = <PublicProps<T>>::decode_len().unwrap_or(0);
+		ensure!(proposals < T::MaxProposals::get() as usize, Error::<T>::TooManyProposals);
+		T::Currency::reserve(&who, value)?;
+
+		let index = Self::public_prop_count();
+		PublicPropCount::put(index + 1);
+		<DepositOf<T>>::insert(index, (value, &[&who][..]));
+
+		let new_prop = (index, proposal_hash, who, weight, ballot);
+		<PublicProps<T>>::append_or_put(&[Ref::from(&new_prop)][..]);
+
+		Self::deposit_event(RawEvent::Proposed(index, value));
+		Ok(())
+	}
+
+	/// Schedule a super-majority-approve external proposal with the given vote weighting and
+	/// ballot, unless one is already waiting or the proposal is still blacklisted.
+	fn do_external_propose(proposal_hash: T::Hash, weight: VoteWeight, ballot: Ballot) -> DispatchResult {
+		ensure!(!<NextExternal<T>>::exists(), Error::<T>::DuplicateProposal);
+		if let Some((until, _)) = <Blacklist<T>>::get(proposal_hash) {
+			ensure!(
+				<frame_system::Module<T>>::block_number() >= until,
+				Error::<T>::ProposalBlacklisted,
+			);
+		}
+		<NextExternal<T>>::put((proposal_hash, VoteThreshold::SuperMajorityApprove, weight, ballot));
+		Ok(())
+	}
+
 	/// Remove a referendum.
 	pub fn internal_cancel_referendum(ref_index: ReferendumIndex) {
 		Self::deposit_event(RawEvent::Cancelled(ref_index));
 		ReferendumInfoOf::<T>::remove(ref_index);
+		ReferendumSourceOf::remove(ref_index);
+		MultiOptionReferendumInfoOf::<T>::remove(ref_index);
+		// The multi-option votes are removed through `reap_multi_option_votes` and the
+		// commitments returned through `reap_commitments`.
+		<SecretBallotOf<T>>::remove(ref_index);
 	}
 
 	// private.
 
+	/// The level `conviction` is counted at under the current `ConvictionSchedule`.
+	fn conviction_level(conviction: Conviction) -> Result<ConvictionLevel, DispatchError> {
+		T::ConvictionSchedule::get().level(conviction).ok_or_else(|| Error::<T>::UnavailableConviction.into())
+	}
+
+	/// Ok if `delegate` may vote on behalf of `real`, Err otherwise.
+	fn ensure_voting_proxy(real: &T::AccountId, delegate: &T::AccountId) -> DispatchResult {
+		ensure!(T::Proxies::is_voting_proxy(real, delegate), Error::<T>::NotProxy);
+		Ok(())
+	}
+
+	/// Ok if the votes and delegations of `VotingOf` are in our layout and counted, Err while
+	/// they are being migrated.
+	fn ensure_migrated() -> DispatchResult {
+		ensure!(!Migrating::exists(), Error::<T>::Migrating);
+		Ok(())
+	}
+
 	/// Ok if the given referendum is active, Err otherwise
 	fn ensure_ongoing(r: ReferendumInfo<T::BlockNumber, T::Hash, BalanceOf<T>>)
 					  -> Result<ReferendumStatus<T::BlockNumber, T::Hash, BalanceOf<T>>, DispatchError>
@@ -1352,35 +2325,71 @@ impl<T: Trait> Module<T> {
 
 	/// Actually enact a vote, if legit.
 	fn try_vote(who: &T::AccountId, ref_index: ReferendumIndex, vote: AccountVote<BalanceOf<T>>) -> DispatchResult {
+		ensure!(!<SecretBallotOf<T>>::contains_key(ref_index), Error::<T>::SecretBallot);
+		Self::record_vote(who, ref_index, vote)
+	}
+
+	/// Count a vote that is open or has been revealed.
+	fn record_vote(who: &T::AccountId, ref_index: ReferendumIndex, vote: AccountVote<BalanceOf<T>>) -> DispatchResult {
+		Self::ensure_migrated()?;
 		let mut status = Self::referendum_status(ref_index)?;
+		ensure!(status.weight != VoteWeight::Credits, Error::<T>::WrongVoteWeight);
 		ensure!(vote.balance() <= T::Currency::free_balance(who), Error::<T>::InsufficientFunds);
+		let source = Self::referendum_source(ref_index);
+		Self::ensure_not_delegating_in(who, status.weight, source)?;
+		let level = Self::conviction_level(match vote {
+			AccountVote::Standard { vote, .. } => vote.conviction,
+			AccountVote::Split { .. } => Conviction::None,
+		})?;
 
-		let weighted_vote = status.weight.calculate(vote);
+		let linked = match status.weight {
+			VoteWeight::Quadratic => Self::linked_capital(who, source)?,
+			_ => Vec::new(),
+		};
+		// Only a standard vote counts linked capital, so only it locks any.
+		let linked = match vote {
+			AccountVote::Standard { .. } => linked,
+			AccountVote::Split { .. } => Vec::new(),
+		};
+		let linked_total = linked.iter()
+			.fold(Zero::zero(), |a: BalanceOf<T>, (_, b)| a.saturating_add(*b));
+		let weighted_vote = status.weight.calculate_linked(vote, linked_total, level);
+		let classes = Self::class_delegations_of(who);
+		let mut delegated = Delegations::default();
+		let mut new_voter = false;
 		VotingOf::<T>::try_mutate(who, |voting| -> DispatchResult {
 			if let Voting::Direct { ref mut votes, delegations, .. } = voting {
+				let delegations = Self::received_delegations(*delegations, &classes, status.weight, source);
 				match votes.binary_search_by_key(&ref_index, |i| i.0) {
 					Ok(i) => {
 						// Shouldn't be possible to fail, but we handle it gracefully.
 						status.tally.remove(votes[i].1).ok_or(Error::<T>::Underflow)?;
 						if let Some(approve) = votes[i].1.as_standard() {
 							let delegation = status.weight.delegation(
-								*delegations
+								delegations,
+								T::DelegationWeighting::get(),
 							);
 
 							status.tally.reduce(approve, delegation);
 						}
 						votes[i].1 = weighted_vote;
 					}
-					Err(i) => votes.insert(i, (ref_index, weighted_vote)),
+					Err(i) => {
+						ensure!(votes.len() < T::MaxVotes::get() as usize, Error::<T>::MaxVotesReached);
+						votes.insert(i, (ref_index, weighted_vote));
+						new_voter = true;
+					}
 				}
 				// Shouldn't be possible to fail, but we handle it gracefully.
 				status.tally.add(weighted_vote).ok_or(Error::<T>::Overflow)?;
 				if let Some(approve) = vote.as_standard() {
 					let delegation = status.weight.delegation(
-						*delegations
+						delegations,
+						T::DelegationWeighting::get(),
 					);
 
 					status.tally.increase(approve, delegation);
+					delegated = delegation;
 				}
 				Ok(())
 			} else {
@@ -1395,10 +2404,164 @@ impl<T: Trait> Module<T> {
 			vote.balance(),
 			WithdrawReason::Transfer.into(),
 		);
+		// Linked capital stays locked at least until the conviction of this vote would expire.
+		let until = status.end + T::EnactmentPeriod::get() * level.lock_periods.into();
+		for (sub, amount) in linked.into_iter() {
+			VotingOf::<T>::mutate(&sub, |voting| if let Voting::Direct { prior, .. } = voting {
+				prior.accumulate(until, amount);
+			});
+			T::Currency::extend_lock(DEMOCRACY_ID, &sub, amount, WithdrawReason::Transfer.into());
+		}
+		if new_voter {
+			VoterCount::mutate(ref_index, |count| *count = count.saturating_add(1));
+		}
+		ReferendumInfoOf::<T>::insert(ref_index, ReferendumInfo::Ongoing(status));
+		Self::deposit_event(RawEvent::Voted(
+			ref_index,
+			who.clone(),
+			weighted_vote,
+			weighted_vote.conviction(),
+			delegated,
+		));
+		Ok(())
+	}
+
+	/// Actually enact a vote bought with credits, if legit.
+	fn try_vote_with_credits(
+		who: &T::AccountId,
+		ref_index: ReferendumIndex,
+		aye: bool,
+		votes: BalanceOf<T>,
+	) -> DispatchResult {
+		Self::ensure_migrated()?;
+		let mut status = Self::referendum_status(ref_index)?;
+		ensure!(status.weight == VoteWeight::Credits, Error::<T>::WrongVoteWeight);
+		ensure!(Self::linked_person(who).is_none(), Error::<T>::SubAccount);
+		ensure!(T::Personhood::is_person(who), Error::<T>::NotPerson);
+		Self::ensure_not_delegating_in(who, status.weight, Self::referendum_source(ref_index))?;
+
+		let period = Self::credit_period();
+		let cost = votes.checked_mul(&votes).ok_or(Error::<T>::Overflow)?;
+		let credit_vote = AccountVoteWeight::Credits { aye, votes, period };
+		let mut new_voter = false;
+		VotingOf::<T>::try_mutate(who, |voting| -> DispatchResult {
+			if let Voting::Direct { votes: ref mut account_votes, ref mut credits, .. } = voting {
+				credits.rejig(period);
+				match account_votes.binary_search_by_key(&ref_index, |i| i.0) {
+					Ok(i) => {
+						// Shouldn't be possible to fail, but we handle it gracefully.
+						status.tally.remove(account_votes[i].1).ok_or(Error::<T>::Underflow)?;
+						credits.refund(account_votes[i].1);
+						account_votes[i].1 = credit_vote;
+					}
+					Err(i) => {
+						ensure!(
+							account_votes.len() < T::MaxVotes::get() as usize,
+							Error::<T>::MaxVotesReached,
+						);
+						account_votes.insert(i, (ref_index, credit_vote));
+						new_voter = true;
+					}
+				}
+				credits.spend(cost, T::CreditAllowance::get()).ok_or(Error::<T>::InsufficientCredits)?;
+				// Shouldn't be possible to fail, but we handle it gracefully.
+				status.tally.add(credit_vote).ok_or(Error::<T>::Overflow)?;
+				Ok(())
+			} else {
+				Err(Error::<T>::AlreadyDelegating.into())
+			}
+		})?;
+		if new_voter {
+			VoterCount::mutate(ref_index, |count| *count = count.saturating_add(1));
+		}
 		ReferendumInfoOf::<T>::insert(ref_index, ReferendumInfo::Ongoing(status));
+		Self::deposit_event(RawEvent::Voted(
+			ref_index,
+			who.clone(),
+			credit_vote,
+			Conviction::None,
+			Default::default(),
+		));
+		Ok(())
+	}
+
+	/// The index of the current credit period.
+	fn credit_period() -> u32 {
+		(system::Module::<T>::block_number() / T::CreditPeriod::get()).saturated_into()
+	}
+
+	/// Actually enact a vote in a multi-option referendum, if legit.
+	fn try_vote_options(
+		who: &T::AccountId,
+		ref_index: ReferendumIndex,
+		balances: Vec<BalanceOf<T>>,
+	) -> DispatchResult {
+		Self::ensure_migrated()?;
+		let mut status = match Self::multi_option_referendum_info(ref_index) {
+			Some(MultiOptionInfo::Ongoing(status)) => status,
+			_ => Err(Error::<T>::ReferendumInvalid)?,
+		};
+		ensure!(balances.len() == status.proposal_hashes.len(), Error::<T>::WrongOptionCount);
+		ensure!(Self::linked_person(who).is_none(), Error::<T>::SubAccount);
+		ensure!(T::Personhood::is_person(who), Error::<T>::NotPerson);
+		// Multi-option referenda are tallied quadratically.
+		Self::ensure_not_delegating_in(who, VoteWeight::Quadratic, Self::referendum_source(ref_index))?;
+		let total = balances.iter()
+			.try_fold(Zero::zero(), |a: BalanceOf<T>, b| a.checked_add(b))
+			.ok_or(Error::<T>::Overflow)?;
+		ensure!(total <= T::Currency::free_balance(who), Error::<T>::InsufficientFunds);
+
+		let old = MultiOptionVotesOf::<T>::get(ref_index, who);
+		if !old.is_empty() {
+			// Shouldn't be possible to fail, but we handle it gracefully.
+			status.tally.remove(&old).ok_or(Error::<T>::Underflow)?;
+		}
+		// Shouldn't be possible to fail, but we handle it gracefully.
+		status.tally.add(&balances).ok_or(Error::<T>::Overflow)?;
+		VotingOf::<T>::try_mutate(who, |voting| -> DispatchResult {
+			if let Voting::Direct { ref mut prior, .. } = voting {
+				// The capital stays locked until the referendum ends, when all votes are dropped.
+				prior.accumulate(status.end, total);
+				Ok(())
+			} else {
+				Err(Error::<T>::AlreadyDelegating.into())
+			}
+		})?;
+		T::Currency::extend_lock(DEMOCRACY_ID, who, total, WithdrawReason::Transfer.into());
+		MultiOptionVotesOf::<T>::insert(ref_index, who, &balances);
+		MultiOptionReferendumInfoOf::<T>::insert(ref_index, MultiOptionInfo::Ongoing(status));
+		Self::deposit_event(RawEvent::OptionsVoted(ref_index, who.clone(), balances));
 		Ok(())
 	}
 
+	/// The person `who` is linked to as a sub-account, if both `who` and the `Personhood` agree
+	/// that it is one.
+	fn linked_person(who: &T::AccountId) -> Option<T::AccountId> {
+		T::Personhood::super_of(who).filter(|person| Self::linked_to(who).as_ref() == Some(person))
+	}
+
+	/// Ensure `who` may cast a quadratic vote in a referendum whose proposal came from `source`
+	/// and return the free capital of each of its linked sub-accounts among the first
+	/// `MaxLinkedSubAccounts`. Sub-accounts that are delegating in such a referendum are skipped
+	/// since their capital already counts elsewhere.
+	fn linked_capital(
+		who: &T::AccountId,
+		source: Option<ProposalSource>,
+	) -> Result<Vec<(T::AccountId, BalanceOf<T>)>, DispatchError> {
+		ensure!(Self::linked_person(who).is_none(), Error::<T>::SubAccount);
+		ensure!(T::Personhood::is_person(who), Error::<T>::NotPerson);
+		Ok(T::Personhood::sub_accounts(who).into_iter()
+			.take(T::MaxLinkedSubAccounts::get() as usize)
+			.filter(|sub| Self::linked_person(sub).as_ref() == Some(who))
+			.filter(|sub| matches!(VotingOf::<T>::get(sub), Voting::Direct { .. }))
+			.filter(|sub| Self::ensure_not_delegating_in(sub, VoteWeight::Quadratic, source).is_ok())
+			.map(|sub| {
+				let free = T::Currency::free_balance(&sub);
+				(sub, free)
+			})
+			.collect())
+	}
+
 	/// Remove the account's vote for the given referendum if possible. This is possible when:
 	/// - The referendum has not finished.
 	/// - The referendum has finished and the voter lost their direction.
@@ -1406,9 +2569,13 @@ impl<T: Trait> Module<T> {
 	///
 	/// This will generally be combined with a call to `unlock`.
 	fn try_remove_vote(who: &T::AccountId, ref_index: ReferendumIndex, scope: UnvoteScope) -> DispatchResult {
+		Self::ensure_migrated()?;
 		let info = ReferendumInfoOf::<T>::get(ref_index);
-		VotingOf::<T>::try_mutate(who, |voting| -> DispatchResult {
-			if let Voting::Direct { ref mut votes, delegations, ref mut prior } = voting {
+		let source = Self::referendum_source(ref_index);
+		let classes = Self::class_delegations_of(who);
+		let removed = VotingOf::<T>::try_mutate(who, |voting| -> Result<_, DispatchError> {
+			let mut delegated = Delegations::default();
+			if let Voting::Direct { ref mut votes, delegations, ref mut prior, ref mut credits } = voting {
 				let i = votes.binary_search_by_key(&ref_index, |i| i.0).map_err(|_| Error::<T>::NotVoter)?;
 				match info {
 					Some(ReferendumInfo::Ongoing(mut status)) => {
@@ -1417,14 +2584,20 @@ impl<T: Trait> Module<T> {
 						status.tally.remove(votes[i].1).ok_or(Error::<T>::Underflow)?;
 						if let Some(approve) = votes[i].1.as_standard() {
 							let delegation = status.weight.delegation(
-								*delegations
+								Self::received_delegations(*delegations, &classes, status.weight, source),
+								T::DelegationWeighting::get(),
 							);
 
 							status.tally.reduce(approve, delegation);
+							delegated = delegation;
 						}
 						ReferendumInfoOf::<T>::insert(ref_index, ReferendumInfo::Ongoing(status));
+						// Credits are only given back while they may still be spent on this
+						// referendum; those of a finished or cancelled one stay spent.
+						credits.rejig(Self::credit_period());
+						credits.refund(votes[i].1);
 					}
-					Some(ReferendumInfo::Finished { end, approved }) =>
+					Some(ReferendumInfo::Finished { end, approved, .. }) =>
 						if let Some((lock_periods, balance)) = votes[i].1.locked_if(approved) {
 							let unlock_at = end + T::EnactmentPeriod::get() * lock_periods.into();
 							let now = system::Module::<T>::block_number();
@@ -1435,137 +2608,336 @@ impl<T: Trait> Module<T> {
 						},
 					None => {}  // Referendum was cancelled.
 				}
-				votes.remove(i);
+				let (_, vote) = votes.remove(i);
+				Ok(Some((vote, delegated)))
+			} else {
+				Ok(None)
 			}
-			Ok(())
 		})?;
+		if let Some((vote, delegated)) = removed {
+			VoterCount::mutate_exists(ref_index, |count|
+				*count = count.and_then(|c| c.checked_sub(1)).filter(|c| !c.is_zero())
+			);
+			Self::deposit_event(RawEvent::VoteRemoved(
+				ref_index,
+				who.clone(),
+				vote,
+				vote.conviction(),
+				delegated,
+			));
+		}
 		Ok(())
 	}
 
-	fn increase_upstream_delegation(who: &T::AccountId, amount: BalanceOf<T>, conviction: Conviction) {
+	fn increase_upstream_delegation(
+		who: &T::AccountId,
+		amount: BalanceOf<T>,
+		level: ConvictionLevel,
+	) -> Delegations<BalanceOf<T>> {
+		let moved = level.delegation_votes(amount);
 		VotingOf::<T>::mutate(who, |voting| match voting {
 			Voting::Delegating { delegations, .. } => {
 				// We don't support second level delegating, so we don't need to do anything more.
-				*delegations = delegations.saturating_add(conviction.delegation_votes(amount))
+				*delegations = delegations.saturating_add(moved)
 			}
 			Voting::Direct { votes, delegations, .. } => {
-				*delegations = delegations.saturating_add(conviction.delegation_votes(amount));
-				for &(ref_index, account_vote) in votes.iter() {
-					// if target of delegation is already voted in referendum we need to increase tally
-					if let AccountVoteWeight::Standard { vote, .. } = account_vote {
-						ReferendumInfoOf::<T>::mutate(ref_index, |maybe_info|
-							if let Some(ReferendumInfo::Ongoing(ref mut status)) = maybe_info {
-								let delegation = status.weight.delegation(
-									conviction.delegation_votes(amount)
-								);
-
-								status.tally.increase(vote.aye, delegation);
-							},
-						);
-					}
-				}
+				let old = *delegations;
+				*delegations = delegations.saturating_add(moved);
+				let classes = Self::class_delegations_of(who);
+				Self::update_delegations_in_tallies(votes, (old, &classes), (*delegations, &classes));
 			}
-		})
+		});
+		moved
 	}
 
-	fn reduce_upstream_delegation(who: &T::AccountId, amount: BalanceOf<T>, conviction: Conviction) {
+	fn reduce_upstream_delegation(
+		who: &T::AccountId,
+		amount: BalanceOf<T>,
+		level: ConvictionLevel,
+	) -> Delegations<BalanceOf<T>> {
+		let moved = level.delegation_votes(amount);
 		VotingOf::<T>::mutate(who, |voting| match voting {
 			Voting::Delegating { delegations, .. } =>
 			// We don't support second level delegating, so we don't need to do anything more.
-				*delegations = delegations.saturating_sub(conviction.delegation_votes(amount)),
+				*delegations = delegations.saturating_sub(moved),
 			Voting::Direct { votes, delegations, .. } => {
-				*delegations = delegations.saturating_sub(conviction.delegation_votes(amount));
-				for &(ref_index, account_vote) in votes.iter() {
-					// if target already voted for referendum we need to decrease tally accordingly
-					if let AccountVoteWeight::Standard { vote, .. } = account_vote {
-						ReferendumInfoOf::<T>::mutate(ref_index, |maybe_info|
-							if let Some(ReferendumInfo::Ongoing(ref mut status)) = maybe_info {
-								let delegation = status.weight.delegation(
-									conviction.delegation_votes(amount)
-								);
-
-								status.tally.reduce(vote.aye, delegation);
-							},
-						);
-					}
-				}
+				let old = *delegations;
+				*delegations = delegations.saturating_sub(moved);
+				let classes = Self::class_delegations_of(who);
+				Self::update_delegations_in_tallies(votes, (old, &classes), (*delegations, &classes));
 			}
-		})
+		});
+		moved
 	}
 
-	/// Attempt to delegate `balance` times `conviction` of voting power from `who` to `target`.
+	fn increase_upstream_class_delegation(
+		who: &T::AccountId,
+		class: DelegationClass,
+		amount: BalanceOf<T>,
+		level: ConvictionLevel,
+	) -> Delegations<BalanceOf<T>> {
+		let moved = level.delegation_votes(amount);
+		Self::update_class_delegations(who, class, |d| d.saturating_add(moved));
+		moved
+	}
+
+	fn reduce_upstream_class_delegation(
+		who: &T::AccountId,
+		class: DelegationClass,
+		amount: BalanceOf<T>,
+		level: ConvictionLevel,
+	) -> Delegations<BalanceOf<T>> {
+		let moved = level.delegation_votes(amount);
+		Self::update_class_delegations(who, class, |d| d.saturating_sub(moved));
+		moved
+	}
+
+	/// Apply `f` to the delegations `who` has received in `class`, updating the tallies of the
+	/// referenda it has voted on accordingly.
+	fn update_class_delegations(
+		who: &T::AccountId,
+		class: DelegationClass,
+		f: impl FnOnce(Delegations<BalanceOf<T>>) -> Delegations<BalanceOf<T>>,
+	) {
+		let old = Self::class_delegations_of(who);
+		let mut new = old.clone();
+		match new.iter().position(|(c, _)| *c == class) {
+			Some(i) => new[i].1 = f(new[i].1),
+			None => new.push((class, f(Default::default()))),
+		}
+		new.retain(|(_, d)| *d != Delegations::default());
+		// We don't support second level delegating, so only the tallies of direct voters change.
+		if let Voting::Direct { votes, delegations, .. } = VotingOf::<T>::get(who) {
+			Self::update_delegations_in_tallies(&votes, (delegations, &old), (delegations, &new));
+		}
+		if new.is_empty() {
+			ClassDelegationsOf::<T>::remove(who);
+		} else {
+			ClassDelegationsOf::<T>::insert(who, new);
+		}
+	}
+
+	/// The delegations received by an account that count in a referendum weighted by `weight`
+	/// whose proposal came from `source`: those received for all referenda, `all`, and those
+	/// received in each of the `classes` that covers the referendum.
+	fn received_delegations(
+		all: Delegations<BalanceOf<T>>,
+		classes: &[(DelegationClass, Delegations<BalanceOf<T>>)],
+		weight: VoteWeight,
+		source: Option<ProposalSource>,
+	) -> Delegations<BalanceOf<T>> {
+		classes.iter()
+			.filter(|(class, _)| class.covers(weight, source))
+			.fold(all, |a, (_, d)| a.saturating_add(*d))
+	}
+
+	/// Replace the weighted `old` delegations of a direct voter with the `new` ones in the tally
+	/// of every ongoing referendum it has voted on. Each is given as the delegations received for
+	/// all referenda and those received per class. The whole aggregate is re-weighted rather than
+	/// just the difference, so the tally always reflects exactly the current delegations.
+	fn update_delegations_in_tallies(
+		votes: &[(ReferendumIndex, AccountVoteWeight<BalanceOf<T>>)],
+		old: (Delegations<BalanceOf<T>>, &[(DelegationClass, Delegations<BalanceOf<T>>)]),
+		new: (Delegations<BalanceOf<T>>, &[(DelegationClass, Delegations<BalanceOf<T>>)]),
+	) {
+		let policy = T::DelegationWeighting::get();
+		for &(ref_index, account_vote) in votes.iter() {
+			// if target of delegation is already voted in referendum we need to update the tally
+			if let AccountVoteWeight::Standard { vote, .. } = account_vote {
+				let source = Self::referendum_source(ref_index);
+				ReferendumInfoOf::<T>::mutate(ref_index, |maybe_info|
+					if let Some(ReferendumInfo::Ongoing(ref mut status)) = maybe_info {
+						let old = Self::received_delegations(old.0, old.1, status.weight, source);
+						let new = Self::received_delegations(new.0, new.1, status.weight, source);
+						status.tally.reduce(vote.aye, status.weight.delegation(old, policy));
+						status.tally.increase(vote.aye, status.weight.delegation(new, policy));
+					},
+				);
+			}
+		}
+	}
+
+	/// Ok if `who` isn't delegating in a class that covers a referendum weighted by `weight` whose
+	/// proposal came from `source`, Err otherwise.
+	fn ensure_not_delegating_in(
+		who: &T::AccountId,
+		weight: VoteWeight,
+		source: Option<ProposalSource>,
+	) -> DispatchResult {
+		ensure!(
+			!Self::class_delegating_of(who).iter().any(|d| d.class.covers(weight, source)),
+			Error::<T>::AlreadyDelegating,
+		);
+		Ok(())
+	}
+
+	/// Attempt to delegate `balance` times `conviction` of voting power from `who` to `target` in
+	/// the referenda covered by `class`.
 	fn try_delegate(
 		who: T::AccountId,
 		target: T::AccountId,
+		class: DelegationClass,
 		conviction: Conviction,
 		balance: BalanceOf<T>,
 	) -> DispatchResult {
+		Self::ensure_migrated()?;
 		ensure!(who != target, Error::<T>::Nonsense);
+		ensure!(Self::linked_person(&who).is_none(), Error::<T>::SubAccount);
 		ensure!(balance <= T::Currency::free_balance(&who), Error::<T>::InsufficientFunds);
-		VotingOf::<T>::try_mutate(&who, |voting| -> DispatchResult {
+		let level = Self::conviction_level(conviction)?;
+		let moved = if class.is_all() {
+			Self::delegate_all(&who, &target, conviction, level, balance)?
+		} else {
+			Self::delegate_in_class(&who, &target, class, conviction, level, balance)?
+		};
+		// Extend the lock to `balance` (rather than setting it) since we don't know what other
+		// votes are in place.
+		T::Currency::extend_lock(
+			DEMOCRACY_ID,
+			&who,
+			balance,
+			WithdrawReason::Transfer.into(),
+		);
+		Self::deposit_event(RawEvent::Delegated(who, target, class, conviction, moved));
+		Ok(())
+	}
+
+	/// Delegate the voting power of `who` for all referenda, replacing any such delegation.
+	fn delegate_all(
+		who: &T::AccountId,
+		target: &T::AccountId,
+		conviction: Conviction,
+		level: ConvictionLevel,
+		balance: BalanceOf<T>,
+	) -> Result<Delegations<BalanceOf<T>>, DispatchError> {
+		ensure!(!ClassDelegatingOf::<T>::contains_key(who), Error::<T>::OverlappingDelegation);
+		VotingOf::<T>::try_mutate(who, |voting| -> Result<_, DispatchError> {
 			let mut old = Voting::Delegating {
 				balance,
 				target: target.clone(),
 				conviction,
+				level,
 				delegations: Default::default(),
 				prior: Default::default(),
 			};
 			sp_std::mem::swap(&mut old, voting);
 			match old {
-				Voting::Delegating { balance, target, conviction, delegations, prior, .. } => {
+				Voting::Delegating { balance, target, level, delegations, prior, .. } => {
 					// remove any delegation votes to our current target.
-					Self::reduce_upstream_delegation(&target, balance, conviction);
+					Self::reduce_upstream_delegation(&target, balance, level);
 					voting.set_common(delegations, prior);
 				}
-				Voting::Direct { votes, delegations, prior } => {
+				Voting::Direct { votes, delegations, prior, .. } => {
 					// here we just ensure that we're currently idling with no votes recorded.
 					ensure!(votes.is_empty(), Error::<T>::VotesExist);
 					voting.set_common(delegations, prior);
 				}
 			}
-			Self::increase_upstream_delegation(&target, balance, conviction);
-			// Extend the lock to `balance` (rather than setting it) since we don't know what other
-			// votes are in place.
-			T::Currency::extend_lock(
-				DEMOCRACY_ID,
-				&who,
-				balance,
-				WithdrawReason::Transfer.into(),
-			);
-			Ok(())
-		})?;
-		Self::deposit_event(Event::<T>::Delegated(who, target));
+			Ok(Self::increase_upstream_delegation(target, balance, level))
+		})
+	}
+
+	/// Delegate the voting power of `who` in the referenda covered by `class`, replacing any
+	/// delegation in that same class.
+	fn delegate_in_class(
+		who: &T::AccountId,
+		target: &T::AccountId,
+		class: DelegationClass,
+		conviction: Conviction,
+		level: ConvictionLevel,
+		balance: BalanceOf<T>,
+	) -> Result<Delegations<BalanceOf<T>>, DispatchError> {
+		let votes = match VotingOf::<T>::get(who) {
+			Voting::Direct { votes, .. } => votes,
+			Voting::Delegating { .. } => Err(Error::<T>::OverlappingDelegation)?,
+		};
+		// Votes in the referenda that the class covers would count alongside the delegation.
+		ensure!(
+			!votes.iter().any(|&(ref_index, _)| match Self::referendum_info(ref_index) {
+				Some(ReferendumInfo::Ongoing(status)) =>
+					class.covers(status.weight, Self::referendum_source(ref_index)),
+				_ => false,
+			}),
+			Error::<T>::VotesExist,
+		);
+		let mut delegating = Self::class_delegating_of(who);
+		ensure!(
+			!delegating.iter().any(|d| d.class != class && d.class.overlaps(&class)),
+			Error::<T>::OverlappingDelegation,
+		);
+		if let Some(i) = delegating.iter().position(|d| d.class == class) {
+			let old = delegating.remove(i);
+			// remove any delegation votes to our current target.
+			Self::reduce_upstream_class_delegation(&old.target, class, old.balance, old.level);
+		}
+		let moved = Self::increase_upstream_class_delegation(target, class, balance, level);
+		delegating.push(ClassDelegation { class, target: target.clone(), conviction, level, balance });
+		ClassDelegatingOf::<T>::insert(who, delegating);
+		Ok(moved)
+	}
+
+	/// Attempt to end the current delegation of `who` in `class`.
+	fn try_undelegate(who: T::AccountId, class: DelegationClass) -> DispatchResult {
+		Self::ensure_migrated()?;
+		let (target, moved) = if class.is_all() {
+			Self::undelegate_all(&who)?
+		} else {
+			Self::undelegate_in_class(&who, class)?
+		};
+		Self::deposit_event(RawEvent::Undelegated(who, target, class, moved));
 		Ok(())
 	}
 
-	/// Attempt to end the current delegation.
-	fn try_undelegate(who: T::AccountId) -> DispatchResult {
-		VotingOf::<T>::try_mutate(&who, |voting| -> DispatchResult {
+	/// End the delegation of `who` for all referenda.
+	fn undelegate_all(who: &T::AccountId) -> Result<(T::AccountId, Delegations<BalanceOf<T>>), DispatchError> {
+		VotingOf::<T>::try_mutate(who, |voting| -> Result<_, DispatchError> {
 			let mut old = Voting::default();
 			sp_std::mem::swap(&mut old, voting);
 			match old {
 				Voting::Delegating {
 					balance,
 					target,
-					conviction,
+					level,
 					delegations,
 					mut prior,
+					..
 				} => {
 					// remove any delegation votes to our current target.
-					Self::reduce_upstream_delegation(&target, balance, conviction);
+					let moved = Self::reduce_upstream_delegation(&target, balance, level);
 					let now = system::Module::<T>::block_number();
-					let lock_periods = conviction.lock_periods().into();
+					let lock_periods = level.lock_periods.into();
 					prior.accumulate(now + T::EnactmentPeriod::get() * lock_periods, balance);
 					voting.set_common(delegations, prior);
+					Ok((target, moved))
 				}
 				Voting::Direct { .. } => {
-					return Err(Error::<T>::NotDelegating.into());
+					Err(Error::<T>::NotDelegating.into())
 				}
 			}
-			Ok(())
-		})?;
-		Self::deposit_event(Event::<T>::Undelegated(who));
-		Ok(())
+		})
+	}
+
+	/// End the delegation of `who` in `class`.
+	fn undelegate_in_class(
+		who: &T::AccountId,
+		class: DelegationClass,
+	) -> Result<(T::AccountId, Delegations<BalanceOf<T>>), DispatchError> {
+		let mut delegating = Self::class_delegating_of(who);
+		let i = delegating.iter().position(|d| d.class == class).ok_or(Error::<T>::NotDelegating)?;
+		let ClassDelegation { target, level, balance, .. } = delegating.remove(i);
+		// remove any delegation votes to our current target.
+		let moved = Self::reduce_upstream_class_delegation(&target, class, balance, level);
+		let now = system::Module::<T>::block_number();
+		let lock_periods = level.lock_periods.into();
+		VotingOf::<T>::mutate(who, |voting| if let Voting::Direct { prior, .. } = voting {
+			prior.accumulate(now + T::EnactmentPeriod::get() * lock_periods, balance);
+		});
+		if delegating.is_empty() {
+			ClassDelegatingOf::<T>::remove(who);
+		} else {
+			ClassDelegatingOf::<T>::insert(who, delegating);
+		}
+		Ok((target, moved))
 	}
 
 	/// Rejig the lock on an account. It will never get more stringent (since that would indicate
@@ -1575,6 +2947,9 @@ impl<T: Trait> Module<T> {
 			voting.rejig(system::Module::<T>::block_number());
 			voting.locked_balance()
 		});
+		// The balance delegated in classes of referenda stays locked as long as it's delegated.
+		let lock_needed = Self::class_delegating_of(who).iter()
+			.fold(lock_needed, |a, d| a.max(d.balance));
 		if lock_needed.is_zero() {
 			T::Currency::remove_lock(DEMOCRACY_ID, who);
 		} else {
@@ -1599,6 +2974,41 @@ impl<T: Trait> Module<T> {
 		ref_index
 	}
 
+	/// Put referendum `ref_index`, ending at `end`, to a secret ballot if `ballot` says so.
+	/// Commitments are revealed in the last `RevealPeriod` blocks, or the last half of voting if
+	/// that is shorter, so that there is always time to commit. Voting shorter than two blocks
+	/// leaves no time for both, so such a referendum is voted on openly.
+	fn open_ballot(ref_index: ReferendumIndex, ballot: Ballot, end: T::BlockNumber) {
+		if ballot == Ballot::Secret {
+			let now = <frame_system::Module<T>>::block_number();
+			let reveal_period = T::RevealPeriod::get().min(end.saturating_sub(now) / 2u32.into());
+			if !reveal_period.is_zero() {
+				<SecretBallotOf<T>>::insert(ref_index, end - reveal_period);
+			}
+		}
+	}
+
+	/// Stop revealing the votes of referendum `index` as it ends. The commitments left unrevealed
+	/// are slashed afterwards, through `reap_commitments`.
+	fn close_ballot(index: ReferendumIndex) {
+		<SecretBallotOf<T>>::remove(index);
+	}
+
+	/// Start a multi-option referendum
+	fn inject_multi_option_referendum(
+		end: T::BlockNumber,
+		proposal_hashes: Vec<T::Hash>,
+		delay: T::BlockNumber,
+	) -> ReferendumIndex {
+		let ref_index = Self::referendum_count();
+		ReferendumCount::put(ref_index + 1);
+		let count = proposal_hashes.len();
+		let status = MultiOptionStatus { end, proposal_hashes, delay, tally: MultiOptionTally::new(count) };
+		<MultiOptionReferendumInfoOf<T>>::insert(ref_index, MultiOptionInfo::Ongoing(status));
+		Self::deposit_event(RawEvent::MultiOptionStarted(ref_index, count as u32));
+		ref_index
+	}
+
 	/// Table the next waiting proposal for a vote.
 	fn launch_next(now: T::BlockNumber) -> DispatchResult {
 		if LastTabledWasExternal::take() {
@@ -1610,16 +3020,19 @@ impl<T: Trait> Module<T> {
 
 	/// Table the waiting external proposal for a vote, if there is one.
 	fn launch_external(now: T::BlockNumber) -> DispatchResult {
-		if let Some((proposal, threshold, weight)) = <NextExternal<T>>::take() {
+		if let Some((proposal, threshold, weight, ballot)) = <NextExternal<T>>::take() {
 			LastTabledWasExternal::put(true);
 			Self::deposit_event(RawEvent::ExternalTabled);
-			Self::inject_referendum(
-				now + T::VotingPeriod::get(),
+			let end = now + T::VotingPeriod::get();
+			let ref_index = Self::inject_referendum(
+				end,
 				proposal,
 				threshold,
 				weight,
 				T::EnactmentPeriod::get(),
 			);
+			ReferendumSourceOf::insert(ref_index, ProposalSource::External);
+			Self::open_ballot(ref_index, ballot, end);
 			Ok(())
 		} else {
 			Err(Error::<T>::NoneWaiting)?
@@ -1634,7 +3047,7 @@ impl<T: Trait> Module<T> {
 			.max_by_key(|x| Self::backing_for((x.1).0).unwrap_or_else(Zero::zero)
 						/* ^^ defensive only: All current public proposals have an amount locked*/)
 		{
-			let (prop_index, proposal, _) = public_props.swap_remove(winner_index);
+			let (prop_index, proposal, _, weight, ballot) = public_props.swap_remove(winner_index);
 			<PublicProps<T>>::put(public_props);
 
 			if let Some((deposit, depositors)) = <DepositOf<T>>::take(prop_index) {
@@ -1643,13 +3056,16 @@ impl<T: Trait> Module<T> {
 					T::Currency::unreserve(d, deposit);
 				}
 				Self::deposit_event(RawEvent::Tabled(prop_index, deposit, depositors));
-				Self::inject_referendum(
-					now + T::VotingPeriod::get(),
+				let end = now + T::VotingPeriod::get();
+				let ref_index = Self::inject_referendum(
+					end,
 					proposal,
 					VoteThreshold::SuperMajorityApprove,
-					VoteWeight::Quadratic,
+					weight,
 					T::EnactmentPeriod::get(),
 				);
+				ReferendumSourceOf::insert(ref_index, ProposalSource::Public);
+				Self::open_ballot(ref_index, ballot, end);
 			}
 			Ok(())
 		} else {
@@ -1679,41 +3095,87 @@ impl<T: Trait> Module<T> {
 		}
 	}
 
+	/// The weighted capital of the whole electorate of a referendum weighted by `weight`, which
+	/// its weighted turnout is measured against. This is the total issuance in standard referenda,
+	/// and in quadratic ones the square root of the total issuance times the population: the sum
+	/// of the square roots of each person's capital if all of them shared the issuance equally.
+	fn weighted_electorate(weight: VoteWeight) -> BalanceOf<T> {
+		let total_issuance = T::Currency::total_issuance();
+		match weight {
+			VoteWeight::Standard => total_issuance,
+			VoteWeight::Quadratic => total_issuance
+				.saturating_mul(T::Personhood::population().into())
+				.integer_sqrt(),
+			VoteWeight::Credits => Zero::zero(),
+		}
+	}
+
 	fn bake_referendum(
 		now: T::BlockNumber,
 		index: ReferendumIndex,
 		status: ReferendumStatus<T::BlockNumber, T::Hash, BalanceOf<T>>,
 	) -> Result<bool, DispatchError> {
-		let total_issuance = T::Currency::total_issuance();
-		let approved = status.threshold.approved(status.tally, total_issuance);
+		let electorate = Self::weighted_electorate(status.weight);
+		let rejection = status.threshold.rejection(status.tally, electorate, status.weight);
 
-		if approved {
+		if let Some(reason) = rejection {
+			Self::deposit_event(RawEvent::NotPassed(index, reason));
+		} else {
 			Self::deposit_event(RawEvent::Passed(index));
-			if status.delay.is_zero() {
-				let _ = Self::do_enact_proposal(status.proposal_hash, index);
-			} else {
-				let when = now + status.delay;
-				// Note that we need the preimage now.
-				Preimages::<T>::mutate_exists(&status.proposal_hash, |maybe_pre| match *maybe_pre {
-					Some(PreimageStatus::Available { ref mut expiry, .. }) => *expiry = Some(when),
-					ref mut a => *a = Some(PreimageStatus::Missing(when)),
-				});
-
-				if T::Scheduler::schedule_named(
-					(DEMOCRACY_ID, index),
-					when,
-					None,
-					63,
-					Call::enact_proposal(status.proposal_hash, index).into(),
-				).is_err() {
-					frame_support::print("LOGIC ERROR: bake_referendum/schedule_named failed");
-				}
-			}
+			Self::schedule_enactment(now, index, status.proposal_hash, status.delay);
+		}
+		Self::close_ballot(index);
+
+		Ok(rejection.is_none())
+	}
+
+	fn bake_multi_option_referendum(
+		now: T::BlockNumber,
+		index: ReferendumIndex,
+		status: MultiOptionStatus<T::BlockNumber, T::Hash, BalanceOf<T>>,
+	) -> Option<u32> {
+		let winner = status.tally.winner();
+
+		if let Some(option) = winner {
+			Self::deposit_event(RawEvent::OptionChosen(index, option));
+			let proposal_hash = status.proposal_hashes[option as usize];
+			Self::schedule_enactment(now, index, proposal_hash, status.delay);
 		} else {
-			Self::deposit_event(RawEvent::NotPassed(index));
+			Self::deposit_event(RawEvent::NotPassed(index, Rejection::NoWinner));
 		}
+		// Votes lock their capital until `now` through the voters' prior locks, and are removed
+		// through `reap_multi_option_votes`.
 
-		Ok(approved)
+		winner
+	}
+
+	/// Enact the proposal of referendum `index` after `delay`, or right away if there is none.
+	fn schedule_enactment(
+		now: T::BlockNumber,
+		index: ReferendumIndex,
+		proposal_hash: T::Hash,
+		delay: T::BlockNumber,
+	) {
+		if delay.is_zero() {
+			let _ = Self::do_enact_proposal(proposal_hash, index);
+		} else {
+			let when = now + delay;
+			// Note that we need the preimage now.
+			Preimages::<T>::mutate_exists(&proposal_hash, |maybe_pre| match *maybe_pre {
+				Some(PreimageStatus::Available { ref mut expiry, .. }) => *expiry = Some(when),
+				ref mut a => *a = Some(PreimageStatus::Missing(when)),
+			});
+
+			if T::Scheduler::schedule_named(
+				(DEMOCRACY_ID, index),
+				when,
+				None,
+				63,
+				Call::enact_proposal(proposal_hash, index).into(),
+			).is_err() {
+				frame_support::print("LOGIC ERROR: bake_referendum/schedule_named failed");
+			}
+		}
 	}
 
 	/// Current era is ending; we should finish up any proposals.
@@ -1726,10 +3188,40 @@ impl<T: Trait> Module<T> {
 		}
 
 		// tally up votes for any expiring referenda.
-		for (index, info) in Self::maturing_referenda_at(now).into_iter() {
-			let approved = Self::bake_referendum(now, index, info)?;
-			ReferendumInfoOf::<T>::insert(index, ReferendumInfo::Finished { end: now, approved });
+		for (index, mut status) in Self::maturing_referenda_at(now).into_iter() {
+			if Migrating::exists() {
+				// Its votes are still being counted, so it is given another block.
+				status.end = now.saturating_add(One::one());
+				ReferendumInfoOf::<T>::insert(index, ReferendumInfo::Ongoing(status));
+				continue
+			}
+			let result = ReferendumResult {
+				tally: status.tally.clone(),
+				threshold: status.threshold,
+				weight: status.weight,
+			};
+			let approved = Self::bake_referendum(now, index, status)?;
+			let info = ReferendumInfo::Finished { end: now, approved, result: Some(result) };
+			ReferendumInfoOf::<T>::insert(index, info);
+			ReferendumSourceOf::remove(index);
+		}
+		for (index, status) in Self::maturing_multi_option_referenda_at(now).into_iter() {
+			let winner = Self::bake_multi_option_referendum(now, index, status);
+			MultiOptionReferendumInfoOf::<T>::insert(index, MultiOptionInfo::Finished { end: now, winner });
 		}
+
+		// Referenda don't end in the order they started, so the lowest unbaked index only moves
+		// past those that have finished, been cancelled or been pruned. The referenda it moves
+		// past were all read by the scans above.
+		let last = Self::referendum_count();
+		LowestUnbaked::mutate(|lowest| {
+			while *lowest < last
+				&& !matches!(Self::referendum_info(*lowest), Some(ReferendumInfo::Ongoing(_)))
+				&& !matches!(Self::multi_option_referendum_info(*lowest), Some(MultiOptionInfo::Ongoing(_)))
+			{
+				*lowest += 1;
+			}
+		});
 		Ok(())
 	}
 }
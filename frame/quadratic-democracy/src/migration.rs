@@ -0,0 +1,275 @@
+// Copyright 2017-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Migration from the storage of the coin-weighted democracy pallet.
+//!
+//! Both democracy pallets store their items under the `Democracy` prefix, and preimages, proposal
+//! deposits, blacklists and the `democrac` balance locks are laid out alike. The proposal queues
+//! and referenda are translated, and the voting proxies handed over to the runtime's `Proxies`, on
+//! the runtime upgrade. The votes and delegations of `VotingOf` may be too many for one block, so
+//! they are translated and counted a few at a time in the blocks that follow, while the calls
+//! that use them wait.
+
+use super::*;
+use frame_support::{
+	Twox64Concat,
+	storage::{StorageValue, StoragePrefixedMap, unhashed, migration::StorageKeyIterator},
+};
+
+/// The storage layout of the coin-weighted democracy pallet, where it differs from ours.
+mod deprecated {
+	use sp_std::prelude::*;
+	use codec::Decode;
+	use crate::{AccountVote, Conviction, ReferendumIndex, VoteThreshold, vote::PriorLock};
+
+	/// The `ayes`, `nays` and `turnout` of a referendum.
+	pub type Tally<Balance> = (Balance, Balance, Balance);
+
+	/// State of a proxy voting account, keyed by the proxy.
+	#[derive(Decode)]
+	pub enum ProxyState<AccountId> {
+		/// The proxy is open to the account but not yet activated by it.
+		Open(AccountId),
+		/// The proxy is voting on behalf of the account.
+		Active(AccountId),
+	}
+
+	/// The `end`, `proposal_hash`, `threshold`, `delay` and `tally` of an ongoing referendum.
+	pub type ReferendumStatus<BlockNumber, Hash, Balance> =
+		(BlockNumber, Hash, VoteThreshold, BlockNumber, Tally<Balance>);
+
+	/// The `votes` and `capital` delegated to an account.
+	pub type Delegations<Balance> = (Balance, Balance);
+
+	#[derive(Decode)]
+	pub enum ReferendumInfo<BlockNumber, Hash, Balance> {
+		Ongoing(ReferendumStatus<BlockNumber, Hash, Balance>),
+		Finished { approved: bool, end: BlockNumber },
+	}
+
+	#[derive(Decode)]
+	pub enum Voting<Balance, AccountId, BlockNumber> {
+		/// The `votes`, `delegations` and `prior` lock of an account voting directly.
+		Direct(
+			Vec<(ReferendumIndex, AccountVote<Balance>)>,
+			Delegations<Balance>,
+			PriorLock<BlockNumber, Balance>,
+		),
+		/// The `balance`, `target`, `conviction`, `delegations` and `prior` lock of a delegating
+		/// account.
+		Delegating(
+			Balance,
+			AccountId,
+			Conviction,
+			Delegations<Balance>,
+			PriorLock<BlockNumber, Balance>,
+		),
+	}
+}
+
+/// How far the translation of `VotingOf` has got: the key of the last account dealt with, or the
+/// prefix of the map before the first.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum Cursor {
+	/// Votes are being translated and counted in the tallies of ongoing referenda.
+	Votes(Vec<u8>),
+	/// Delegations are being counted on top of the votes they follow.
+	Delegations(Vec<u8>),
+}
+
+/// Translate the storage of the coin-weighted democracy pallet, if it hasn't been already, and
+/// set the translation of `VotingOf` going.
+///
+/// Ongoing referenda keep standard weighting, since their votes were cast on it, while queued
+/// proposals are given the `DefaultVoteWeight`. The tallies of ongoing referenda are counted
+/// afresh from the translated votes and delegations, each at the level its conviction has in the
+/// `ConvictionSchedule`, so they start out empty.
+pub fn migrate<T: Trait>() -> Weight {
+	if StorageVersion::get() != Releases::V1_0_0 {
+		return 0
+	}
+	let weight = T::DefaultVoteWeight::get();
+	if PublicProps::<T>::translate::<Vec<(PropIndex, T::Hash, T::AccountId)>, _>(|props|
+		props.map(|props| props.into_iter().map(|(i, h, who)| (i, h, who, weight, Ballot::Open)).collect())
+	).is_err() {
+		frame_support::print("Failed to translate the public proposals of the democracy pallet");
+	}
+	if NextExternal::<T>::translate::<(T::Hash, VoteThreshold), _>(|next|
+		next.map(|(h, threshold)| (h, threshold, weight, Ballot::Open))
+	).is_err() {
+		frame_support::print("Failed to translate the next external proposal of the democracy pallet");
+	}
+
+	// Referenda are never pruned by the coin-weighted pallet.
+	let referenda = ReferendumCount::get() as Weight;
+	ReferendumInfoOf::<T>::translate::<deprecated::ReferendumInfo<T::BlockNumber, T::Hash, BalanceOf<T>>, _>(
+		|_, info| Some(match info {
+			deprecated::ReferendumInfo::Ongoing((end, proposal_hash, threshold, delay, _)) =>
+				ReferendumInfo::Ongoing(ReferendumStatus {
+					end,
+					proposal_hash,
+					threshold,
+					weight: VoteWeight::Standard,
+					delay,
+					tally: Default::default(),
+				}),
+			// Their tallies are gone, and their votes may have been removed since.
+			deprecated::ReferendumInfo::Finished { approved, end } =>
+				ReferendumInfo::Finished { approved, end, result: None },
+		})
+	);
+	let proxies = migrate_proxies::<T>();
+
+	Migrating::put(Cursor::Votes(VotingOf::<T>::final_prefix().to_vec()));
+	StorageVersion::put(Releases::V2_0_0);
+	T::DbWeight::get().reads_writes(referenda + proxies + 4, referenda + 2 * proxies + 5)
+}
+
+/// Translate and count the votes and delegations of `VotingOf` from where the last block left
+/// off, until about `budget` is used, and return the weight used.
+pub fn migrate_voting<T: Trait>(budget: Weight) -> Weight {
+	let cursor = match Migrating::get() {
+		Some(cursor) => cursor,
+		None => return 0,
+	};
+	let db = T::DbWeight::get();
+	let prefix = VotingOf::<T>::final_prefix();
+	let mut used = db.reads_writes(1, 1);
+	let (mut counting_votes, mut key) = match cursor {
+		Cursor::Votes(key) => (true, key),
+		Cursor::Delegations(key) => (false, key),
+	};
+	while used < budget {
+		match sp_io::storage::next_key(&key).filter(|k| k.starts_with(&prefix[..])) {
+			Some(next) => {
+				used = used.saturating_add(if counting_votes {
+					translate_votes::<T>(&next)
+				} else {
+					count_delegation::<T>(&next)
+				});
+				key = next;
+			}
+			None if counting_votes => {
+				counting_votes = false;
+				key = prefix.to_vec();
+			}
+			None => {
+				Migrating::kill();
+				return used
+			}
+		}
+	}
+	Migrating::put(if counting_votes { Cursor::Votes(key) } else { Cursor::Delegations(key) });
+	used
+}
+
+/// Translate the voting of the account stored under `key`, counting its direct votes in the
+/// tallies of ongoing referenda and among the voters of each referendum.
+fn translate_votes<T: Trait>(key: &[u8]) -> Weight {
+	let db = T::DbWeight::get();
+	let schedule = T::ConvictionSchedule::get();
+	let voting = match unhashed::get::<deprecated::Voting<BalanceOf<T>, T::AccountId, T::BlockNumber>>(key) {
+		Some(voting) => voting,
+		None => {
+			frame_support::print("Failed to translate the votes of an account of the democracy pallet");
+			unhashed::kill(key);
+			return db.reads_writes(1, 1)
+		}
+	};
+	let voting = match voting {
+		deprecated::Voting::Direct(votes, _, prior) => Voting::Direct {
+			votes: votes.into_iter()
+				.map(|(i, vote)| {
+					let conviction = match vote {
+						AccountVote::Standard { vote, .. } => vote.conviction,
+						AccountVote::Split { .. } => Conviction::None,
+					};
+					(i, VoteWeight::Standard.calculate(vote, level_of(&schedule, conviction)))
+				})
+				.collect(),
+			delegations: Default::default(),
+			prior,
+			credits: Default::default(),
+		},
+		deprecated::Voting::Delegating(balance, target, conviction, _, prior) => Voting::Delegating {
+			balance,
+			target,
+			conviction,
+			level: level_of(&schedule, conviction),
+			delegations: Default::default(),
+			prior,
+		},
+	};
+	let mut weight = db.reads_writes(2, 1);
+	if let Voting::Direct { ref votes, .. } = voting {
+		for &(ref_index, vote) in votes.iter() {
+			VoterCount::mutate(ref_index, |count| *count = count.saturating_add(1));
+			ReferendumInfoOf::<T>::mutate(ref_index, |maybe_info|
+				if let Some(ReferendumInfo::Ongoing(ref mut status)) = maybe_info {
+					if status.tally.add(vote).is_none() {
+						frame_support::print("Tally overflow while migrating the democracy pallet");
+					}
+				}
+			);
+			weight = weight.saturating_add(db.reads_writes(2, 2));
+		}
+	}
+	unhashed::put(key, &voting);
+	weight
+}
+
+/// Count the delegation of the account stored under `key`, if it is delegating, in the
+/// delegations of its target and the tallies it votes in.
+fn count_delegation<T: Trait>(key: &[u8]) -> Weight {
+	let db = T::DbWeight::get();
+	match unhashed::get::<Voting<BalanceOf<T>, T::AccountId, T::BlockNumber>>(key) {
+		Some(Voting::Delegating { balance, target, level, .. }) => {
+			Module::<T>::increase_upstream_delegation(&target, balance, level);
+			// The target's voting, class delegations and the tallies of its votes.
+			let votes = T::MaxVotes::get() as Weight;
+			db.reads_writes(3 + votes, 1 + votes)
+		}
+		_ => db.reads(1),
+	}
+}
+
+/// The level of a legacy `conviction` under `schedule`. Convictions above its last level, which
+/// can no longer be chosen, are counted at that level.
+fn level_of(schedule: &ConvictionSchedule, conviction: Conviction) -> ConvictionLevel {
+	schedule.level(conviction)
+		.or_else(|| schedule.0.last().copied())
+		.unwrap_or_default()
+}
+
+/// Register the active voting proxies with the `Proxies` and clear the `Proxy` map, returning how
+/// many there were. Proxies that were only opened are dropped, and every proxy account gives up
+/// the reference it held.
+fn migrate_proxies<T: Trait>() -> Weight {
+	let proxies = StorageKeyIterator::<T::AccountId, deprecated::ProxyState<T::AccountId>, Twox64Concat>
+		::new(b"Democracy", b"Proxy")
+		.drain();
+	let mut count = 0;
+	for (proxy, state) in proxies {
+		count += 1;
+		system::Module::<T>::dec_ref(&proxy);
+		if let deprecated::ProxyState::Active(stash) = state {
+			if T::Proxies::add_voting_proxy(&stash, &proxy).is_err() {
+				frame_support::print("Failed to register a voting proxy of the democracy pallet");
+			}
+		}
+	}
+	count
+}
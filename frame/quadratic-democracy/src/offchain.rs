@@ -0,0 +1,277 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Indexes of the voters of each referendum and of the delegators of each account, kept by the
+//! offchain worker in the node's persistent offchain storage.
+//!
+//! `VotingOf`, `ClassDelegatingOf`, `CommitmentOf` and `MultiOptionVotesOf` are keyed by the
+//! voting account, so finding who voted on a referendum or who delegates to an account means
+//! iterating them. Instead, the offchain worker looks at the events this pallet deposited in each
+//! block and re-reads the voting, delegations, commitments and multi-option votes of the accounts
+//! they name. What it finds is written under
+//! `voters_key` and `delegators_key`, where the `offchain_localStorageGet` RPC can read it with the
+//! `PERSISTENT` storage kind. The referenda and targets each account is indexed under are kept
+//! too, so that it can be taken out of the entries it has left.
+//!
+//! The indexes reflect the state of the last block indexed, which is kept under `LAST_INDEXED`.
+//! Blocks the worker did not run for, as while the node is syncing, are made up for by re-reading
+//! the accounts of `VotingOf` and `ClassDelegatingOf`, at most `IndexingBatch` of them per block.
+//! Commitments and multi-option votes made during those blocks are only found if the account is
+//! already indexed under their referendum. The entries of referenda and accounts left with no
+//! voters or delegators are set to an empty list.
+
+use sp_std::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
+use sp_runtime::offchain::storage::StorageValueRef;
+use frame_support::storage::StoragePrefixedMap;
+use super::*;
+
+const VOTERS_PREFIX: &[u8] = b"quadratic-democracy::voters::";
+const DELEGATORS_PREFIX: &[u8] = b"quadratic-democracy::delegators::";
+const INDEXED_PREFIX: &[u8] = b"quadratic-democracy::indexed::";
+
+/// The key of the number of the block last indexed.
+pub const LAST_INDEXED: &[u8] = b"quadratic-democracy::last-indexed";
+
+/// The key of the `Sweep` under way, if any.
+const SWEEP: &[u8] = b"quadratic-democracy::sweep";
+
+/// The vote of a voter in the index of a referendum.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum IndexedVote<Balance> {
+	/// A vote in a standard, quadratic or credit referendum.
+	Vote(AccountVoteWeight<Balance>),
+	/// A commitment to a vote in a secret ballot that hasn't been revealed yet.
+	Committed,
+	/// The capital placed on each option of a multi-option referendum.
+	Options(Vec<Balance>),
+}
+
+/// The voters of a referendum, each with their vote.
+type Voters<T> = Vec<(<T as frame_system::Trait>::AccountId, IndexedVote<BalanceOf<T>>)>;
+
+/// The delegators of an account, each with the class, conviction and balance of the delegation.
+type Delegators<T> = Vec<(<T as frame_system::Trait>::AccountId, DelegationClass, Conviction, BalanceOf<T>)>;
+
+/// The referenda and targets under which an account is indexed.
+type Indexed<T> = (Vec<ReferendumIndex>, Vec<<T as frame_system::Trait>::AccountId>);
+
+/// How far the re-reading of every account has got: the key of the last account re-read, or the
+/// prefix of the map before the first.
+#[derive(Encode, Decode)]
+enum Sweep {
+	/// The accounts of `VotingOf` are being re-read.
+	Voting(Vec<u8>),
+	/// The accounts of `ClassDelegatingOf` are being re-read.
+	ClassDelegating(Vec<u8>),
+}
+
+/// The key of the voters of referendum `index`: the prefix followed by the encoded index.
+///
+/// The value is a `Vec<(AccountId, IndexedVote<Balance>)>`.
+pub fn voters_key(index: ReferendumIndex) -> Vec<u8> {
+	let mut key = VOTERS_PREFIX.to_vec();
+	index.encode_to(&mut key);
+	key
+}
+
+/// The key of the delegators of `target`: the prefix followed by the encoded account.
+///
+/// The value is a `Vec<(AccountId, DelegationClass, Conviction, Balance)>`. Delegations for all
+/// referenda have the default class.
+pub fn delegators_key<AccountId: Encode>(target: &AccountId) -> Vec<u8> {
+	let mut key = DELEGATORS_PREFIX.to_vec();
+	target.encode_to(&mut key);
+	key
+}
+
+/// The key of the referenda and targets under which `who` is indexed.
+fn indexed_key<AccountId: Encode>(who: &AccountId) -> Vec<u8> {
+	let mut key = INDEXED_PREFIX.to_vec();
+	who.encode_to(&mut key);
+	key
+}
+
+/// Bring the indexes up to date with the state of block `now`, unless no other worker has
+/// claimed it.
+pub fn index<T: Trait>(now: T::BlockNumber) {
+	let batch = T::IndexingBatch::get();
+	if batch == 0 {
+		return;
+	}
+	// Votes are in two layouts while they are being migrated.
+	if Migrating::exists() {
+		return;
+	}
+	// Offchain workers may run concurrently; only the one that records the block indexes it.
+	let mut missed = true;
+	let last_indexed = StorageValueRef::persistent(LAST_INDEXED);
+	let claimed = last_indexed.mutate(|last: Option<Option<T::BlockNumber>>| match last {
+		Some(Some(block)) if block == now => Err(()),
+		Some(Some(block)) => {
+			missed = block + One::one() != now;
+			Ok(now)
+		}
+		_ => Ok(now),
+	});
+	if !matches!(claimed, Ok(Ok(_))) {
+		return;
+	}
+	let sweep = StorageValueRef::persistent(SWEEP);
+	if missed {
+		sweep.set(&Some(Sweep::Voting(VotingOf::<T>::final_prefix().to_vec())));
+	}
+
+	// The accounts named by the events of the block, with the referenda they name.
+	let mut touched = BTreeMap::<T::AccountId, Vec<ReferendumIndex>>::new();
+	for event in deposited_events::<T>() {
+		match event {
+			RawEvent::Voted(index, who, ..) |
+			RawEvent::VoteRemoved(index, who, ..) |
+			RawEvent::VoteCommitted(index, who) |
+			RawEvent::CommitmentSlashed(index, who, _) |
+			RawEvent::CommitmentReturned(index, who, _) |
+			RawEvent::OptionsVoted(index, who, _) |
+			RawEvent::OptionsVoteReaped(index, who) =>
+				touched.entry(who).or_insert_with(Vec::new).push(index),
+			RawEvent::Delegated(who, ..) | RawEvent::Undelegated(who, ..) => {
+				touched.entry(who).or_insert_with(Vec::new);
+			}
+			_ => {}
+		}
+	}
+	for (who, referenda) in touched.iter() {
+		reindex::<T>(who, referenda);
+	}
+
+	if let Some(Some(cursor)) = sweep.get::<Option<Sweep>>() {
+		sweep.set(&continue_sweep::<T>(cursor, batch));
+	}
+}
+
+/// The events of this pallet deposited in the current block.
+fn deposited_events<T: Trait>() -> Vec<Event<T>> {
+	// The runtime's events are encoded as the index of the pallet's variant followed by the
+	// pallet's event, so the index is found from the encoding of any event of ours.
+	let ours: Event<T> = RawEvent::ExternalTabled;
+	let ours: <T as frame_system::Trait>::Event = <T as Trait>::Event::from(ours).into();
+	let variant = ours.encode()[0];
+	system::Module::<T>::events()
+		.into_iter()
+		.filter_map(|record| {
+			let encoded = record.event.encode();
+			match encoded.split_first() {
+				Some((&index, mut event)) if index == variant => Event::<T>::decode(&mut event).ok(),
+				_ => None,
+			}
+		})
+		.collect()
+}
+
+/// Re-read up to `batch` accounts from `cursor` on, and return where to carry on from, if
+/// anywhere.
+fn continue_sweep<T: Trait>(cursor: Sweep, batch: u32) -> Option<Sweep> {
+	let (mut voting, mut key) = match cursor {
+		Sweep::Voting(key) => (true, key),
+		Sweep::ClassDelegating(key) => (false, key),
+	};
+	let mut read = 0;
+	while read < batch {
+		let prefix = if voting {
+			VotingOf::<T>::final_prefix()
+		} else {
+			ClassDelegatingOf::<T>::final_prefix()
+		};
+		match sp_io::storage::next_key(&key).filter(|k| k.starts_with(&prefix[..])) {
+			Some(next) => {
+				// Both maps are keyed by `twox_64_concat` of the account.
+				let account = next.get(prefix.len() + 8..)
+					.and_then(|mut raw| T::AccountId::decode(&mut raw).ok());
+				if let Some(who) = account {
+					reindex::<T>(&who, &[]);
+				}
+				read += 1;
+				key = next;
+			}
+			None if voting => {
+				voting = false;
+				key = ClassDelegatingOf::<T>::final_prefix().to_vec();
+			}
+			None => return None,
+		}
+	}
+	Some(if voting { Sweep::Voting(key) } else { Sweep::ClassDelegating(key) })
+}
+
+/// Re-read the voting, delegations, commitments and multi-option votes of `who` and update the
+/// entries it is indexed under, or is to be. Commitments and multi-option votes can only be found
+/// through their referendum, so only those in `referenda` or in referenda `who` is already
+/// indexed under are seen.
+fn reindex<T: Trait>(who: &T::AccountId, referenda: &[ReferendumIndex]) {
+	let indexed_key = indexed_key(who);
+	let indexed = StorageValueRef::persistent(&indexed_key);
+	let (old_referenda, old_targets) = indexed.get::<Indexed<T>>().flatten().unwrap_or_default();
+
+	let mut votes = BTreeMap::new();
+	let mut delegations = Vec::new();
+	match VotingOf::<T>::get(who) {
+		Voting::Direct { votes: direct, .. } =>
+			votes.extend(direct.into_iter().map(|(index, vote)| (index, IndexedVote::Vote(vote)))),
+		Voting::Delegating { balance, target, conviction, .. } =>
+			delegations.push((target, DelegationClass::default(), conviction, balance)),
+	}
+	for d in ClassDelegatingOf::<T>::get(who).into_iter() {
+		delegations.push((d.target, d.class, d.conviction, d.balance));
+	}
+	for &index in old_referenda.iter().chain(referenda.iter()) {
+		if votes.contains_key(&index) {
+			continue;
+		}
+		if CommitmentOf::<T>::contains_key(index, who) {
+			votes.insert(index, IndexedVote::Committed);
+		} else {
+			let options = MultiOptionVotesOf::<T>::get(index, who);
+			if !options.is_empty() {
+				votes.insert(index, IndexedVote::Options(options));
+			}
+		}
+	}
+
+	let affected = old_referenda.into_iter().chain(votes.keys().copied()).collect::<BTreeSet<_>>();
+	for index in affected.into_iter() {
+		let key = voters_key(index);
+		let entry = StorageValueRef::persistent(&key);
+		let mut voters = entry.get::<Voters<T>>().flatten().unwrap_or_default();
+		voters.retain(|(voter, _)| voter != who);
+		if let Some(vote) = votes.get(&index) {
+			voters.push((who.clone(), vote.clone()));
+		}
+		entry.set(&voters);
+	}
+	let targets = delegations.iter().map(|d| d.0.clone()).collect::<BTreeSet<_>>();
+	let old_targets = old_targets.into_iter().collect::<BTreeSet<_>>();
+	for target in old_targets.union(&targets) {
+		let key = delegators_key(target);
+		let entry = StorageValueRef::persistent(&key);
+		let mut delegators = entry.get::<Delegators<T>>().flatten().unwrap_or_default();
+		delegators.retain(|d| &d.0 != who);
+		delegators.extend(delegations.iter()
+			.filter(|d| &d.0 == target)
+			.map(|&(_, class, conviction, balance)| (who.clone(), class, conviction, balance))
+		);
+		entry.set(&delegators);
+	}
+	indexed.set(&(votes.keys().copied().collect::<Vec<_>>(), targets.into_iter().collect::<Vec<_>>()));
+}
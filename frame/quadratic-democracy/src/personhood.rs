@@ -0,0 +1,56 @@
+// Copyright 2017-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Proof of personhood, used for sybil resistance of quadratic referenda.
+
+use sp_std::prelude::*;
+
+/// A source of truth over which accounts belong to unique, verified persons.
+///
+/// Quadratic vote weighting is only meaningful if one person cannot split their capital over many
+/// accounts. The runtime plugs in whatever it trusts for that, e.g. `pallet-identity` judgements,
+/// `pallet-society` membership or a `pallet-membership` instance.
+pub trait Personhood<AccountId> {
+	/// Returns `true` if `who` is recognised as a person and may cast a quadratic vote.
+	fn is_person(who: &AccountId) -> bool;
+
+	/// The account that `who` is a sub-account of, if any.
+	fn super_of(who: &AccountId) -> Option<AccountId>;
+
+	/// The sub-accounts of `who`. The capital of those that have agreed to be linked to `who`,
+	/// through `link_sub_account`, is added to `who`'s own before the square root of a quadratic
+	/// vote is taken.
+	fn sub_accounts(who: &AccountId) -> Vec<AccountId>;
+
+	/// The number of persons, or an upper bound of it. The weighted electorate of a quadratic
+	/// referendum is the most weighted turnout this many persons could make, which they do by
+	/// sharing the total issuance equally.
+	fn population() -> u32;
+
+	/// Make `who` a person with `subs` as its sub-accounts, so that the benchmarks of votes can
+	/// link capital to it.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn make_person(_who: &AccountId, _subs: Vec<AccountId>) {}
+}
+
+/// Every account is a distinct person with no linked sub-accounts, and there is no telling how
+/// many there are.
+impl<AccountId> Personhood<AccountId> for () {
+	fn is_person(_: &AccountId) -> bool { true }
+	fn super_of(_: &AccountId) -> Option<AccountId> { None }
+	fn sub_accounts(_: &AccountId) -> Vec<AccountId> { Vec::new() }
+	fn population() -> u32 { u32::max_value() }
+}
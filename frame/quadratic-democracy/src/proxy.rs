@@ -0,0 +1,41 @@
+// Copyright 2017-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Proxies voting on behalf of other accounts.
+
+use sp_runtime::{DispatchResult, DispatchError};
+
+/// A registry of the accounts allowed to vote on behalf of others.
+///
+/// The runtime plugs in its proxy subsystem, e.g. the governance proxies of `pallet-proxy`.
+pub trait VotingProxies<AccountId> {
+	/// Returns `true` if `delegate` may vote, delegate and remove votes on behalf of `real` right
+	/// away.
+	fn is_voting_proxy(real: &AccountId, delegate: &AccountId) -> bool;
+
+	/// Register `delegate` as a voting proxy of `real`, able to do no more than vote, delegate and
+	/// remove votes. Used to migrate the proxies this pallet used to keep itself, which took no
+	/// deposit, so it shouldn't fail for want of one.
+	fn add_voting_proxy(real: &AccountId, delegate: &AccountId) -> DispatchResult;
+}
+
+/// No account may vote on behalf of another.
+impl<AccountId> VotingProxies<AccountId> for () {
+	fn is_voting_proxy(_: &AccountId, _: &AccountId) -> bool { false }
+	fn add_voting_proxy(_: &AccountId, _: &AccountId) -> DispatchResult {
+		Err(DispatchError::Other("voting proxies are not supported"))
+	}
+}
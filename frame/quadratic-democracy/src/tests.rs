@@ -21,7 +21,8 @@ use std::cell::RefCell;
 use codec::Encode;
 use frame_support::{
 	impl_outer_origin, impl_outer_dispatch, assert_noop, assert_ok, parameter_types,
-	impl_outer_event, ord_parameter_types, traits::{Contains, OnInitialize}, weights::Weight,
+	impl_outer_event, ord_parameter_types, traits::{Contains, OnInitialize},
+	weights::{Weight, RuntimeDbWeight},
 };
 use sp_core::H256;
 use sp_runtime::{
@@ -32,14 +33,23 @@ use pallet_balances::{BalanceLock, Error as BalancesError};
 use frame_system::EnsureSignedBy;
 
 mod cancellation;
+mod credits;
 mod delegation;
 mod external_proposing;
 mod fast_tracking;
+mod genesis;
+mod history;
 mod lock_voting;
+mod migration;
+mod multi_option;
+mod offchain;
+mod personhood;
 mod preimage;
 mod proxying;
 mod public_proposals;
+mod quorum;
 mod scheduling;
+mod secret_ballot;
 mod voting;
 
 const AYE: Vote = Vote { aye: true, conviction: Conviction::None };
@@ -56,6 +66,7 @@ impl_outer_dispatch! {
 		frame_system::System,
 		pallet_balances::Balances,
 		democracy::Democracy,
+		pallet_democracy::LegacyDemocracy,
 	}
 }
 
@@ -69,6 +80,7 @@ impl_outer_event! {
 		pallet_balances<T>,
 		pallet_scheduler<T>,
 		democracy<T>,
+		pallet_democracy<T>,
 	}
 }
 
@@ -78,6 +90,7 @@ pub struct Test;
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub const MaximumBlockWeight: Weight = 1024;
+	pub const DbWeight: RuntimeDbWeight = RuntimeDbWeight { read: 1, write: 1 };
 	pub const MaximumBlockLength: u32 = 2 * 1024;
 	pub const AvailableBlockRatio: Perbill = Perbill::one();
 }
@@ -94,7 +107,7 @@ impl frame_system::Trait for Test {
 	type Event = Event;
 	type BlockHashCount = BlockHashCount;
 	type MaximumBlockWeight = MaximumBlockWeight;
-	type DbWeight = ();
+	type DbWeight = DbWeight;
 	type BlockExecutionWeight = ();
 	type ExtrinsicBaseWeight = ();
 	type MaximumBlockLength = MaximumBlockLength;
@@ -129,6 +142,17 @@ parameter_types! {
 	pub const MinimumDeposit: u64 = 1;
 	pub const EnactmentPeriod: u64 = 2;
 	pub const CooloffPeriod: u64 = 2;
+	pub const DefaultVoteWeight: VoteWeight = VoteWeight::Quadratic;
+	pub const CreditAllowance: u64 = 100;
+	pub const CreditPeriod: u64 = 10;
+	pub const MaxOptions: u32 = 4;
+	pub const CommitDeposit: u64 = 10;
+	pub const HistoryRetention: u64 = 4;
+	pub const IndexingBatch: u32 = 2;
+	pub const MaxVotes: u32 = 10;
+	pub const MaxLinkedSubAccounts: u32 = 2;
+	pub const MaxProposals: u32 = 10;
+	pub const MaxSeconds: u32 = 10;
 }
 ord_parameter_types! {
 	pub const One: u64 = 1;
@@ -149,6 +173,12 @@ impl Contains<u64> for OneToFive {
 thread_local! {
 	static PREIMAGE_BYTE_DEPOSIT: RefCell<u64> = RefCell::new(0);
 	static INSTANT_ALLOWED: RefCell<bool> = RefCell::new(false);
+	static REVEAL_PERIOD: RefCell<u64> = RefCell::new(1);
+	static NOT_PERSONS: RefCell<Vec<u64>> = RefCell::new(vec![]);
+	static SUB_ACCOUNTS: RefCell<Vec<(u64, u64)>> = RefCell::new(vec![]);
+	static VOTING_PROXIES: RefCell<Vec<(u64, u64)>> = RefCell::new(vec![]);
+	static DELEGATION_WEIGHTING: RefCell<DelegationWeighting> = RefCell::new(DelegationWeighting::RootOfSum);
+	static CONVICTION_SCHEDULE: RefCell<ConvictionSchedule> = RefCell::new(ConvictionSchedule::standard());
 }
 pub struct PreimageByteDeposit;
 impl Get<u64> for PreimageByteDeposit {
@@ -158,7 +188,87 @@ pub struct InstantAllowed;
 impl Get<bool> for InstantAllowed {
 	fn get() -> bool { INSTANT_ALLOWED.with(|v| *v.borrow()) }
 }
+pub struct RevealPeriod;
+impl Get<u64> for RevealPeriod {
+	fn get() -> u64 { REVEAL_PERIOD.with(|v| *v.borrow()) }
+}
+pub struct DelegationWeightingPolicy;
+impl Get<DelegationWeighting> for DelegationWeightingPolicy {
+	fn get() -> DelegationWeighting { DELEGATION_WEIGHTING.with(|v| *v.borrow()) }
+}
+pub struct Convictions;
+impl Get<ConvictionSchedule> for Convictions {
+	fn get() -> ConvictionSchedule { CONVICTION_SCHEDULE.with(|v| v.borrow().clone()) }
+}
+pub struct TestPersonhood;
+impl Personhood<u64> for TestPersonhood {
+	fn is_person(who: &u64) -> bool {
+		NOT_PERSONS.with(|v| !v.borrow().contains(who))
+	}
+	fn super_of(who: &u64) -> Option<u64> {
+		SUB_ACCOUNTS.with(|v| v.borrow().iter().find(|(sub, _)| sub == who).map(|(_, s)| *s))
+	}
+	fn sub_accounts(who: &u64) -> Vec<u64> {
+		SUB_ACCOUNTS.with(|v| v.borrow().iter().filter(|(_, s)| s == who).map(|(sub, _)| *sub).collect())
+	}
+	fn population() -> u32 { 6 }
+	#[cfg(feature = "runtime-benchmarks")]
+	fn make_person(who: &u64, subs: Vec<u64>) {
+		NOT_PERSONS.with(|v| v.borrow_mut().retain(|p| p != who));
+		SUB_ACCOUNTS.with(|v| v.borrow_mut().extend(subs.into_iter().map(|sub| (sub, *who))));
+	}
+}
+pub struct TestProxies;
+impl VotingProxies<u64> for TestProxies {
+	fn is_voting_proxy(real: &u64, delegate: &u64) -> bool {
+		VOTING_PROXIES.with(|v| v.borrow().contains(&(*real, *delegate)))
+	}
+	fn add_voting_proxy(real: &u64, delegate: &u64) -> DispatchResult {
+		VOTING_PROXIES.with(|v| v.borrow_mut().push((*real, *delegate)));
+		Ok(())
+	}
+}
 impl super::Trait for Test {
+	type Proposal = Call;
+	type Event = Event;
+	type Currency = pallet_balances::Module<Self>;
+	type EnactmentPeriod = EnactmentPeriod;
+	type LaunchPeriod = LaunchPeriod;
+	type VotingPeriod = VotingPeriod;
+	type FastTrackVotingPeriod = FastTrackVotingPeriod;
+	type MinimumDeposit = MinimumDeposit;
+	type DefaultVoteWeight = DefaultVoteWeight;
+	type DelegationWeighting = DelegationWeightingPolicy;
+	type ConvictionSchedule = Convictions;
+	type ExternalOrigin = EnsureSignedBy<Two, u64>;
+	type ExternalMajorityOrigin = EnsureSignedBy<Three, u64>;
+	type ExternalDefaultOrigin = EnsureSignedBy<One, u64>;
+	type FastTrackOrigin = EnsureSignedBy<Five, u64>;
+	type CancellationOrigin = EnsureSignedBy<Four, u64>;
+	type VetoOrigin = EnsureSignedBy<OneToFive, u64>;
+	type CooloffPeriod = CooloffPeriod;
+	type PreimageByteDeposit = PreimageByteDeposit;
+	type Slash = ();
+	type InstantOrigin = EnsureSignedBy<Six, u64>;
+	type InstantAllowed = InstantAllowed;
+	type Scheduler = Scheduler;
+	type Personhood = TestPersonhood;
+	type Proxies = TestProxies;
+	type CreditAllowance = CreditAllowance;
+	type CreditPeriod = CreditPeriod;
+	type MaxOptions = MaxOptions;
+	type RevealPeriod = RevealPeriod;
+	type CommitDeposit = CommitDeposit;
+	type HistoryRetention = HistoryRetention;
+	type IndexingBatch = IndexingBatch;
+	type MaxVotes = MaxVotes;
+	type MaxLinkedSubAccounts = MaxLinkedSubAccounts;
+	type MaxProposals = MaxProposals;
+	type MaxSeconds = MaxSeconds;
+	type WeightInfo = ();
+}
+/// The coin-weighted democracy pallet, whose storage is migrated.
+impl pallet_democracy::Trait for Test {
 	type Proposal = Call;
 	type Event = Event;
 	type Currency = pallet_balances::Module<Self>;
@@ -193,7 +303,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 			(6, 600)
 		],
 	}.assimilate_storage(&mut t).unwrap();
-	GenesisConfig::default().assimilate_storage(&mut t).unwrap();
+	GenesisConfig::<Test>::default().assimilate_storage(&mut t).unwrap();
 	let mut ext = sp_io::TestExternalities::new(t);
 	ext.execute_with(|| System::set_block_number(1));
 	ext
@@ -203,6 +313,7 @@ type System = frame_system::Module<Test>;
 type Balances = pallet_balances::Module<Test>;
 type Scheduler = pallet_scheduler::Module<Test>;
 type Democracy = Module<Test>;
+type LegacyDemocracy = pallet_democracy::Module<Test>;
 
 #[test]
 fn params_should_work() {
@@ -236,7 +347,8 @@ fn propose_set_balance(who: u64, value: u64, delay: u64) -> DispatchResult {
 	Democracy::propose(
 		Origin::signed(who),
 		set_balance_proposal_hash(value),
-		delay
+		delay,
+		None,
 	)
 }
 
@@ -244,7 +356,8 @@ fn propose_set_balance_and_note(who: u64, value: u64, delay: u64) -> DispatchRes
 	Democracy::propose(
 		Origin::signed(who),
 		set_balance_proposal_hash_and_note(value),
-		delay
+		delay,
+		None,
 	)
 }
 
@@ -283,6 +396,18 @@ fn big_nay(who: u64) -> AccountVote<u64> {
 	AccountVote::Standard { vote: BIG_NAY, balance: Balances::free_balance(&who) }
 }
 
+/// The level of `conviction` in the standard schedule.
+fn level(conviction: Conviction) -> ConvictionLevel {
+	ConvictionSchedule::standard().level(conviction).unwrap()
+}
+
 fn tally(r: ReferendumIndex) -> Tally<u64> {
 	Democracy::referendum_status(r).unwrap().tally
 }
+
+fn last_event() -> RawEvent<u64, u64, H256, u64> {
+	System::events().into_iter()
+		.filter_map(|r| if let Event::democracy(e) = r.event { Some(e) } else { None })
+		.last()
+		.expect("a democracy event was deposited")
+}
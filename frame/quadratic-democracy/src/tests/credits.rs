@@ -0,0 +1,195 @@
+// Copyright 2017-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The tests for voting with voice credits.
+
+use super::*;
+
+fn start_credit_referendum(end: u64) -> ReferendumIndex {
+	Democracy::inject_referendum(
+		end,
+		set_balance_proposal_hash_and_note(2),
+		VoteThreshold::SuperMajorityApprove,
+		VoteWeight::Credits,
+		0,
+	)
+}
+
+fn credits_spent(who: u64) -> u64 {
+	match VotingOf::<Test>::get(who) {
+		Voting::Direct { credits, .. } => credits.spent(),
+		Voting::Delegating { .. } => 0,
+	}
+}
+
+#[test]
+fn votes_cost_their_square_across_referenda() {
+	new_test_ext().execute_with(|| {
+		let r1 = start_credit_referendum(20);
+		let r2 = start_credit_referendum(20);
+		let r3 = start_credit_referendum(20);
+
+		assert_ok!(Democracy::vote_with_credits(Origin::signed(1), r1, true, 6));
+		assert_ok!(Democracy::vote_with_credits(Origin::signed(2), r1, false, 5));
+		assert_eq!(tally(r1), Tally { ayes: 6, nays: 5, turnout: 0, weighted_turnout: 0, voters: 2 });
+		assert_eq!(credits_spent(1), 36);
+
+		assert_ok!(Democracy::vote_with_credits(Origin::signed(1), r2, true, 8));
+		assert_eq!(credits_spent(1), 100);
+		assert_noop!(
+			Democracy::vote_with_credits(Origin::signed(1), r3, true, 1),
+			Error::<Test>::InsufficientCredits,
+		);
+	});
+}
+
+#[test]
+fn removing_or_replacing_a_vote_refunds_its_credits() {
+	new_test_ext().execute_with(|| {
+		let r = start_credit_referendum(20);
+		assert_ok!(Democracy::vote_with_credits(Origin::signed(1), r, true, 10));
+		assert_eq!(credits_spent(1), 100);
+
+		assert_ok!(Democracy::vote_with_credits(Origin::signed(1), r, false, 5));
+		assert_eq!(tally(r), Tally { ayes: 0, nays: 5, turnout: 0, weighted_turnout: 0, voters: 1 });
+		assert_eq!(credits_spent(1), 25);
+
+		assert_ok!(Democracy::remove_vote(Origin::signed(1), r));
+		assert_eq!(tally(r), Tally { ayes: 0, nays: 0, turnout: 0, weighted_turnout: 0, voters: 0 });
+		assert_eq!(credits_spent(1), 0);
+	});
+}
+
+#[test]
+fn removing_a_vote_after_the_referendum_ends_refunds_nothing() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let r1 = start_credit_referendum(2);
+		let r2 = start_credit_referendum(8);
+		assert_ok!(Democracy::vote_with_credits(Origin::signed(1), r1, true, 10));
+		assert_eq!(credits_spent(1), 100);
+
+		fast_forward_to(2);
+		assert_ok!(Democracy::remove_vote(Origin::signed(1), r1));
+		assert_eq!(credits_spent(1), 100);
+		assert_noop!(
+			Democracy::vote_with_credits(Origin::signed(1), r2, true, 1),
+			Error::<Test>::InsufficientCredits,
+		);
+	});
+}
+
+#[test]
+fn credits_replenish_every_period() {
+	new_test_ext().execute_with(|| {
+		let r1 = start_credit_referendum(30);
+		let r2 = start_credit_referendum(30);
+		assert_ok!(Democracy::vote_with_credits(Origin::signed(1), r1, true, 10));
+
+		System::set_block_number(10);
+		assert_ok!(Democracy::vote_with_credits(Origin::signed(1), r2, true, 10));
+		assert_eq!(credits_spent(1), 100);
+
+		// Credits of the previous period have expired and are not refunded.
+		assert_ok!(Democracy::remove_vote(Origin::signed(1), r1));
+		assert_eq!(credits_spent(1), 100);
+		assert_eq!(tally(r1), Tally { ayes: 0, nays: 0, turnout: 0, weighted_turnout: 0, voters: 0 });
+	});
+}
+
+#[test]
+fn credit_votes_lock_nothing() {
+	new_test_ext().execute_with(|| {
+		let r = start_credit_referendum(20);
+		assert_ok!(Democracy::vote_with_credits(Origin::signed(1), r, true, 10));
+		assert_eq!(Balances::locks(1), vec![]);
+		assert_eq!(VotingOf::<Test>::get(1).locked_balance(), 0);
+	});
+}
+
+#[test]
+fn credits_and_balance_votes_dont_mix() {
+	new_test_ext().execute_with(|| {
+		let r = start_credit_referendum(20);
+		assert_noop!(Democracy::vote(Origin::signed(1), r, aye(1)), Error::<Test>::WrongVoteWeight);
+
+		let q = Democracy::inject_referendum(
+			20,
+			set_balance_proposal_hash_and_note(2),
+			VoteThreshold::SuperMajorityApprove,
+			VoteWeight::Quadratic,
+			0,
+		);
+		assert_noop!(
+			Democracy::vote_with_credits(Origin::signed(1), q, true, 1),
+			Error::<Test>::WrongVoteWeight,
+		);
+	});
+}
+
+#[test]
+fn only_persons_may_vote_with_credits() {
+	new_test_ext().execute_with(|| {
+		let r = start_credit_referendum(20);
+		NOT_PERSONS.with(|v| *v.borrow_mut() = vec![1]);
+		SUB_ACCOUNTS.with(|v| *v.borrow_mut() = vec![(3, 2)]);
+		assert_ok!(Democracy::link_sub_account(Origin::signed(3), 2));
+		assert_noop!(
+			Democracy::vote_with_credits(Origin::signed(1), r, true, 1),
+			Error::<Test>::NotPerson,
+		);
+		assert_noop!(
+			Democracy::vote_with_credits(Origin::signed(3), r, true, 1),
+			Error::<Test>::SubAccount,
+		);
+
+		assert_ok!(Democracy::delegate(Origin::signed(4), 2, Conviction::None, 10));
+		assert_noop!(
+			Democracy::vote_with_credits(Origin::signed(4), r, true, 1),
+			Error::<Test>::AlreadyDelegating,
+		);
+	});
+}
+
+#[test]
+fn credit_referendum_passes_by_simple_majority() {
+	new_test_ext().execute_with(|| {
+		let r = start_credit_referendum(2);
+		assert_ok!(Democracy::vote_with_credits(Origin::signed(1), r, true, 3));
+		assert_ok!(Democracy::vote_with_credits(Origin::signed(2), r, false, 2));
+
+		next_block();
+		next_block();
+		assert_eq!(Balances::free_balance(42), 2);
+	});
+}
+
+#[test]
+fn credit_votes_are_capped_at_max_votes() {
+	new_test_ext().execute_with(|| {
+		let max = MaxVotes::get();
+		for _ in 0 ..= max {
+			start_credit_referendum(2);
+		}
+		for r in 0 .. max {
+			assert_ok!(Democracy::vote_with_credits(Origin::signed(1), r, true, 1));
+		}
+		assert_noop!(
+			Democracy::vote_with_credits(Origin::signed(1), max, true, 1),
+			Error::<Test>::MaxVotesReached
+		);
+	});
+}
@@ -31,31 +31,31 @@ fn single_proposal_should_work_with_delegation() {
 		assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::None, 20));
 		let r = 0;
 		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
-		assert_eq!(tally(r), Tally { ayes: 2, nays: 0, turnout: 120});
+		assert_eq!(tally(r), Tally { ayes: 2, nays: 0, turnout: 120, weighted_turnout: 14, voters: 1 });
 
 		// Delegate a second vote.
 		assert_ok!(Democracy::delegate(Origin::signed(3), 1, Conviction::None, 30));
-		assert_eq!(tally(r), Tally { ayes: 3, nays: 0, turnout: 150 });
+		assert_eq!(tally(r), Tally { ayes: 3, nays: 0, turnout: 150, weighted_turnout: 17, voters: 1 });
 
 		// Reduce first vote.
 		assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::None, 10));
-		assert_eq!(tally(r), Tally { ayes: 3, nays: 0, turnout: 140 });
+		assert_eq!(tally(r), Tally { ayes: 3, nays: 0, turnout: 140, weighted_turnout: 16, voters: 1 });
 
 		// Second vote delegates to first; we don't do tiered delegation, so it doesn't get used.
 		assert_ok!(Democracy::delegate(Origin::signed(3), 2, Conviction::None, 30));
-		assert_eq!(tally(r), Tally { ayes: 2, nays: 0, turnout: 110 });
+		assert_eq!(tally(r), Tally { ayes: 2, nays: 0, turnout: 110, weighted_turnout: 13, voters: 1 });
 
 		// Main voter cancels their vote
 		assert_ok!(Democracy::remove_vote(Origin::signed(1), r));
-		assert_eq!(tally(r), Tally { ayes: 0, nays: 0, turnout: 0 });
+		assert_eq!(tally(r), Tally { ayes: 0, nays: 0, turnout: 0, weighted_turnout: 0, weighted_turnout: 0, voters: 0 });
 
 		// First delegator delegates half funds with conviction; nothing changes yet.
 		assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::Locked1x, 10));
-		assert_eq!(tally(r), Tally { ayes: 0, nays: 0, turnout: 0 });
+		assert_eq!(tally(r), Tally { ayes: 0, nays: 0, turnout: 0, weighted_turnout: 0, weighted_turnout: 0, voters: 0 });
 
 		// Main voter reinstates their vote
 		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
-		assert_eq!(tally(r), Tally { ayes: 4, nays: 0, turnout: 110 });
+		assert_eq!(tally(r), Tally { ayes: 4, nays: 0, turnout: 110, weighted_turnout: 13, voters: 1 });
 	});
 }
 
@@ -89,7 +89,7 @@ fn cyclic_delegation_should_unwind() {
 		assert_ok!(Democracy::vote(Origin::signed(1), r, nay(1))); // nay 1
 
 		// Delegated vote is counted.
-		assert_eq!(tally(r), Tally { ayes: 2, nays: 2, turnout: 60 });
+		assert_eq!(tally(r), Tally { ayes: 2, nays: 2, turnout: 60, weighted_turnout: 34, voters: 2 });
 	});
 }
 
@@ -106,13 +106,13 @@ fn single_proposal_should_work_with_vote_and_delegation() {
 		let r = 0;
 		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
 		assert_ok!(Democracy::vote(Origin::signed(2), r, nay(2)));
-		assert_eq!(tally(r), Tally { ayes: 1, nays: 1, turnout: 300 });
+		assert_eq!(tally(r), Tally { ayes: 1, nays: 1, turnout: 300, weighted_turnout: 24, voters: 2 });
 
 		// Delegate vote.
 		assert_ok!(Democracy::remove_vote(Origin::signed(2), r));
 		assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::None, 20));
 		// Delegated vote replaces the explicit vote.
-		assert_eq!(tally(r), Tally { ayes: 2, nays: 0, turnout: 120 });
+		assert_eq!(tally(r), Tally { ayes: 2, nays: 0, turnout: 120, weighted_turnout: 14, voters: 1 });
 	});
 }
 
@@ -132,7 +132,7 @@ fn single_proposal_should_work_with_undelegation() {
 		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
 
 		// Delegated vote is not counted.
-		assert_eq!(tally(r), Tally { ayes: 1, nays: 0, turnout: 100 });
+		assert_eq!(tally(r), Tally { ayes: 1, nays: 0, turnout: 100, weighted_turnout: 10, voters: 1 });
 	});
 }
 
@@ -144,11 +144,11 @@ fn single_proposal_should_work_with_delegation_and_vote() {
 		// Delegate, undelegate and vote.
 		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
 		assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::None, 20));
-		assert_eq!(tally(r), Tally { ayes: 2, nays: 0, turnout: 120 });
+		assert_eq!(tally(r), Tally { ayes: 2, nays: 0, turnout: 120, weighted_turnout: 14, voters: 1 });
 		assert_ok!(Democracy::undelegate(Origin::signed(2)));
 		assert_ok!(Democracy::vote(Origin::signed(2), r, aye(2)));
 		// Delegated vote is not counted.
-		assert_eq!(tally(r), Tally { ayes: 2, nays: 0, turnout: 300 });
+		assert_eq!(tally(r), Tally { ayes: 2, nays: 0, turnout: 300, weighted_turnout: 24, voters: 2 });
 	});
 }
 
@@ -161,7 +161,36 @@ fn conviction_should_be_honored_in_delegation() {
 		assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::Locked6x, 20));
 		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
 		// Delegated vote is huge.
-		assert_eq!(tally(r), Tally { ayes: 11, nays: 0, turnout: 120 });
+		assert_eq!(tally(r), Tally { ayes: 11, nays: 0, turnout: 120, weighted_turnout: 14, voters: 1 });
+	});
+}
+
+#[test]
+fn undelegating_takes_back_what_was_delegated_whatever_the_schedule_has_become() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(0);
+		let r = Democracy::inject_referendum(
+			2,
+			set_balance_proposal_hash_and_note(2),
+			VoteThreshold::SuperMajorityApprove,
+			VoteWeight::Standard,
+			0,
+		);
+		assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::Locked2x, 20));
+		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+		assert_eq!(tally(r), Tally { ayes: 50, nays: 0, turnout: 120, weighted_turnout: 120, voters: 1 });
+
+		CONVICTION_SCHEDULE.with(|v| v.borrow_mut().0[2] = ConvictionLevel { lock_periods: 0, multiplier: 50 });
+		assert_ok!(Democracy::undelegate(Origin::signed(2)));
+		assert_eq!(tally(r), Tally { ayes: 10, nays: 0, turnout: 100, weighted_turnout: 100, voters: 1 });
+
+		// The delegated balance stays locked for the two periods it was delegated for.
+		fast_forward_to(3);
+		assert_ok!(Democracy::unlock(Origin::signed(2), 2));
+		assert_eq!(Balances::locks(2)[0].amount, 20);
+		fast_forward_to(4);
+		assert_ok!(Democracy::unlock(Origin::signed(2), 2));
+		assert!(Balances::locks(2).is_empty());
 	});
 }
 
@@ -173,6 +202,212 @@ fn split_vote_delegation_should_be_ignored() {
 		assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::Locked6x, 20));
 		assert_ok!(Democracy::vote(Origin::signed(1), r, AccountVote::Split { aye: 100, nay: 0 }));
 		// Delegated vote is huge.
-		assert_eq!(tally(r), Tally { ayes: 1, nays: 0, turnout: 100 });
+		assert_eq!(tally(r), Tally { ayes: 1, nays: 0, turnout: 100, weighted_turnout: 10, voters: 1 });
+	});
+}
+
+#[test]
+fn sum_of_roots_delegation_counts_like_direct_votes() {
+	new_test_ext().execute_with(|| {
+		DELEGATION_WEIGHTING.with(|v| *v.borrow_mut() = DelegationWeighting::SumOfRoots);
+		let r = begin_referendum();
+		assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::Locked1x, 200));
+		assert_ok!(Democracy::delegate(Origin::signed(3), 1, Conviction::Locked1x, 300));
+		assert_ok!(Democracy::vote(Origin::signed(1), r, big_aye(1)));
+		// sqrt(100) + sqrt(200) + sqrt(300)
+		assert_eq!(tally(r), Tally { ayes: 41, nays: 0, turnout: 600, weighted_turnout: 41, voters: 1 });
+	});
+}
+
+#[test]
+fn root_of_sum_delegation_counts_the_aggregate() {
+	new_test_ext().execute_with(|| {
+		let r = begin_referendum();
+		assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::Locked1x, 200));
+		assert_ok!(Democracy::delegate(Origin::signed(3), 1, Conviction::Locked1x, 300));
+		assert_ok!(Democracy::vote(Origin::signed(1), r, big_aye(1)));
+		// sqrt(100) + sqrt(200 + 300)
+		assert_eq!(tally(r), Tally { ayes: 32, nays: 0, turnout: 600, weighted_turnout: 32, voters: 1 });
+	});
+}
+
+#[test]
+fn upstream_delegation_changes_are_exact_and_reversible() {
+	for &policy in [DelegationWeighting::RootOfSum, DelegationWeighting::SumOfRoots].iter() {
+		new_test_ext().execute_with(|| {
+			DELEGATION_WEIGHTING.with(|v| *v.borrow_mut() = policy);
+			let r = begin_referendum();
+			assert_ok!(Democracy::vote(Origin::signed(1), r, big_aye(1)));
+			assert_eq!(tally(r), Tally { ayes: 10, nays: 0, turnout: 100, weighted_turnout: 10, voters: 1 });
+
+			// Delegations arriving after the vote land in the tally exactly as if they'd been
+			// there before it.
+			assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::Locked1x, 200));
+			assert_ok!(Democracy::delegate(Origin::signed(3), 1, Conviction::Locked1x, 300));
+			let expected = match policy {
+				DelegationWeighting::RootOfSum => 32,
+				DelegationWeighting::SumOfRoots => 41,
+			};
+			assert_eq!(
+				tally(r),
+				Tally { ayes: expected, nays: 0, turnout: 600, weighted_turnout: expected, voters: 1 },
+			);
+
+			// Removing them in a different order returns to the original tally.
+			assert_ok!(Democracy::undelegate(Origin::signed(2)));
+			assert_eq!(tally(r), Tally { ayes: 27, nays: 0, turnout: 400, weighted_turnout: 27, voters: 1 });
+			assert_ok!(Democracy::undelegate(Origin::signed(3)));
+			assert_eq!(tally(r), Tally { ayes: 10, nays: 0, turnout: 100, weighted_turnout: 10, voters: 1 });
+		});
+	}
+}
+
+#[test]
+fn delegating_and_undelegating_should_report_moved_votes() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::Locked2x, 100));
+		let moved = Delegations { capital: 100, votes: 200, quadratic_votes: 20, weighted_capital: 10 };
+		let all = DelegationClass::default();
+		assert_eq!(last_event(), RawEvent::Delegated(2, 1, all, Conviction::Locked2x, moved));
+
+		assert_ok!(Democracy::undelegate(Origin::signed(2)));
+		assert_eq!(last_event(), RawEvent::Undelegated(2, 1, all, moved));
+	});
+}
+
+fn start_referendum(weight: VoteWeight) -> ReferendumIndex {
+	Democracy::inject_referendum(
+		2,
+		set_balance_proposal_hash_and_note(2),
+		VoteThreshold::SuperMajorityApprove,
+		weight,
+		0,
+	)
+}
+
+fn class(weight: Option<VoteWeight>, source: Option<ProposalSource>) -> DelegationClass {
+	DelegationClass { weight, source }
+}
+
+#[test]
+fn class_delegations_only_count_in_covered_referenda() {
+	new_test_ext().execute_with(|| {
+		let standard = class(Some(VoteWeight::Standard), None);
+		let quadratic = class(Some(VoteWeight::Quadratic), None);
+		let s = start_referendum(VoteWeight::Standard);
+		let q = start_referendum(VoteWeight::Quadratic);
+		assert_ok!(Democracy::vote(Origin::signed(1), s, big_aye(1)));
+		assert_ok!(Democracy::vote(Origin::signed(1), q, big_aye(1)));
+		assert_eq!(tally(s), Tally { ayes: 100, nays: 0, turnout: 100, weighted_turnout: 100, voters: 1 });
+		assert_eq!(tally(q), Tally { ayes: 10, nays: 0, turnout: 100, weighted_turnout: 10, voters: 1 });
+
+		// 2 follows 1 in standard referenda, and votes itself in quadratic ones.
+		assert_ok!(Democracy::delegate_class(Origin::signed(2), standard, 1, Conviction::Locked1x, 200));
+		assert_eq!(tally(s), Tally { ayes: 300, nays: 0, turnout: 300, weighted_turnout: 300, voters: 1 });
+		assert_eq!(tally(q), Tally { ayes: 10, nays: 0, turnout: 100, weighted_turnout: 10, voters: 1 });
+		assert_noop!(
+			Democracy::vote(Origin::signed(2), s, big_aye(2)),
+			Error::<Test>::AlreadyDelegating,
+		);
+		assert_ok!(Democracy::vote(Origin::signed(2), q, big_nay(2)));
+		assert_eq!(tally(q), Tally { ayes: 10, nays: 14, turnout: 300, weighted_turnout: 24, voters: 2 });
+
+		// Its vote stands in the way of delegating in quadratic referenda too.
+		assert_noop!(
+			Democracy::delegate_class(Origin::signed(2), quadratic, 1, Conviction::Locked1x, 200),
+			Error::<Test>::VotesExist,
+		);
+
+		assert_ok!(Democracy::undelegate_class(Origin::signed(2), standard));
+		assert_eq!(tally(s), Tally { ayes: 100, nays: 0, turnout: 100, weighted_turnout: 100, voters: 1 });
+		assert_noop!(
+			Democracy::undelegate_class(Origin::signed(2), standard),
+			Error::<Test>::NotDelegating,
+		);
+	});
+}
+
+#[test]
+fn class_delegations_follow_the_proposal_source() {
+	new_test_ext().execute_with(|| {
+		let r = begin_referendum();
+		assert_eq!(Democracy::referendum_source(r), Some(ProposalSource::Public));
+		assert_ok!(Democracy::vote(Origin::signed(1), r, big_aye(1)));
+		assert_eq!(tally(r), Tally { ayes: 10, nays: 0, turnout: 100, weighted_turnout: 10, voters: 1 });
+
+		let external = class(None, Some(ProposalSource::External));
+		assert_ok!(Democracy::delegate_class(Origin::signed(2), external, 1, Conviction::Locked1x, 200));
+		assert_eq!(tally(r), Tally { ayes: 10, nays: 0, turnout: 100, weighted_turnout: 10, voters: 1 });
+
+		let public = class(None, Some(ProposalSource::Public));
+		assert_ok!(Democracy::delegate_class(Origin::signed(3), public, 1, Conviction::Locked1x, 300));
+		assert_eq!(tally(r), Tally { ayes: 27, nays: 0, turnout: 400, weighted_turnout: 27, voters: 1 });
+	});
+}
+
+#[test]
+fn overlapping_class_delegations_are_refused() {
+	new_test_ext().execute_with(|| {
+		let standard = class(Some(VoteWeight::Standard), None);
+		let external = class(None, Some(ProposalSource::External));
+		let quadratic_external = class(Some(VoteWeight::Quadratic), Some(ProposalSource::External));
+		assert_ok!(Democracy::delegate_class(Origin::signed(2), standard, 1, Conviction::None, 10));
+		assert_noop!(
+			Democracy::delegate_class(Origin::signed(2), external, 3, Conviction::None, 10),
+			Error::<Test>::OverlappingDelegation,
+		);
+		assert_noop!(
+			Democracy::delegate(Origin::signed(2), 3, Conviction::None, 10),
+			Error::<Test>::OverlappingDelegation,
+		);
+
+		// Classes that don't overlap may be delegated to different accounts, and delegating again
+		// in the same class replaces the delegation.
+		assert_ok!(Democracy::delegate_class(Origin::signed(2), quadratic_external, 3, Conviction::None, 10));
+		assert_ok!(Democracy::delegate_class(Origin::signed(2), standard, 4, Conviction::None, 20));
+		assert_eq!(Democracy::class_delegating_of(2), vec![
+			ClassDelegation {
+				class: quadratic_external,
+				target: 3,
+				conviction: Conviction::None,
+				level: level(Conviction::None),
+				balance: 10,
+			},
+			ClassDelegation {
+				class: standard,
+				target: 4,
+				conviction: Conviction::None,
+				level: level(Conviction::None),
+				balance: 20,
+			},
+		]);
+		assert!(Democracy::class_delegations_of(1).is_empty());
+
+		// An account delegating for all referenda can't also delegate in a class.
+		assert_ok!(Democracy::delegate(Origin::signed(5), 1, Conviction::None, 10));
+		assert_noop!(
+			Democracy::delegate_class(Origin::signed(5), standard, 3, Conviction::None, 10),
+			Error::<Test>::OverlappingDelegation,
+		);
+	});
+}
+
+#[test]
+fn class_delegation_keeps_balance_locked_for_its_conviction() {
+	new_test_ext().execute_with(|| {
+		let the_lock = |amount| BalanceLock { id: DEMOCRACY_ID, amount, reasons: pallet_balances::Reasons::Misc };
+		let standard = class(Some(VoteWeight::Standard), None);
+		assert_ok!(Democracy::delegate_class(Origin::signed(2), standard, 1, Conviction::Locked1x, 150));
+		assert_ok!(Democracy::unlock(Origin::signed(2), 2));
+		assert_eq!(Balances::locks(2), vec![the_lock(150)]);
+
+		// Locked for one enactment period once undelegated.
+		assert_ok!(Democracy::undelegate_class(Origin::signed(2), standard));
+		fast_forward_to(2);
+		assert_ok!(Democracy::unlock(Origin::signed(2), 2));
+		assert_eq!(Balances::locks(2), vec![the_lock(150)]);
+		fast_forward_to(3);
+		assert_ok!(Democracy::unlock(Origin::signed(2), 2));
+		assert_eq!(Balances::locks(2), vec![]);
 	});
 }
@@ -106,7 +106,7 @@ fn external_referendum_works() {
 				threshold: VoteThreshold::SuperMajorityApprove,
 				weight: VoteWeight::Quadratic,
 				delay: 2,
-				tally: Tally { ayes: 0, nays: 0, turnout: 0 },
+				tally: Tally { ayes: 0, nays: 0, turnout: 0, weighted_turnout: 0, voters: 0 },
 			})
 		);
 	});
@@ -136,7 +136,7 @@ fn external_majority_referendum_works() {
 				threshold: VoteThreshold::SimpleMajority,
 				weight: VoteWeight::Quadratic,
 				delay: 2,
-				tally: Tally { ayes: 0, nays: 0, turnout: 0 },
+				tally: Tally { ayes: 0, nays: 0, turnout: 0, weighted_turnout: 0, voters: 0 },
 			})
 		);
 	});
@@ -166,7 +166,7 @@ fn external_default_referendum_works() {
 				threshold: VoteThreshold::SuperMajorityAgainst,
 				weight: VoteWeight::Quadratic,
 				delay: 2,
-				tally: Tally { ayes: 0, nays: 0, turnout: 0 },
+				tally: Tally { ayes: 0, nays: 0, turnout: 0, weighted_turnout: 0, voters: 0 },
 			})
 		);
 	});
@@ -193,7 +193,7 @@ fn external_and_public_interleaving_works() {
 				threshold: VoteThreshold::SuperMajorityApprove,
 				weight: VoteWeight::Quadratic,
 				delay: 2,
-				tally: Tally { ayes: 0, nays: 0, turnout: 0 },
+				tally: Tally { ayes: 0, nays: 0, turnout: 0, weighted_turnout: 0, voters: 0 },
 			})
 		);
 		// replenish external
@@ -213,7 +213,7 @@ fn external_and_public_interleaving_works() {
 				threshold: VoteThreshold::SuperMajorityApprove,
 				weight: VoteWeight::Quadratic,
 				delay: 2,
-				tally: Tally { ayes: 0, nays: 0, turnout: 0 },
+				tally: Tally { ayes: 0, nays: 0, turnout: 0, weighted_turnout: 0, voters: 0 },
 			})
 		);
 		// don't replenish public
@@ -229,7 +229,7 @@ fn external_and_public_interleaving_works() {
 				threshold: VoteThreshold::SuperMajorityApprove,
 				weight: VoteWeight::Quadratic,
 				delay: 2,
-				tally: Tally { ayes: 0, nays: 0, turnout: 0 },
+				tally: Tally { ayes: 0, nays: 0, turnout: 0, weighted_turnout: 0, voters: 0 },
 			})
 		);
 		// replenish external
@@ -249,7 +249,7 @@ fn external_and_public_interleaving_works() {
 				threshold: VoteThreshold::SuperMajorityApprove,
 				weight: VoteWeight::Quadratic,
 				delay: 2,
-				tally: Tally { ayes: 0, nays: 0, turnout: 0 },
+				tally: Tally { ayes: 0, nays: 0, turnout: 0, weighted_turnout: 0, voters: 0 },
 			})
 		);
 		// replenish both
@@ -270,7 +270,7 @@ fn external_and_public_interleaving_works() {
 				threshold: VoteThreshold::SuperMajorityApprove,
 				weight: VoteWeight::Quadratic,
 				delay: 2,
-				tally: Tally { ayes: 0, nays: 0, turnout: 0 },
+				tally: Tally { ayes: 0, nays: 0, turnout: 0, weighted_turnout: 0, voters: 0 },
 			})
 		);
 		// replenish public again
@@ -290,8 +290,70 @@ fn external_and_public_interleaving_works() {
 				threshold: VoteThreshold::SuperMajorityApprove,
 				weight: VoteWeight::Quadratic,
 				delay: 2,
-				tally: Tally { ayes: 0, nays: 0, turnout: 0 },
+				tally: Tally { ayes: 0, nays: 0, turnout: 0, weighted_turnout: 0, voters: 0 },
 			})
 		);
 	});
 }
+
+#[test]
+fn external_propose_with_weight_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(0);
+		assert_noop!(
+			Democracy::external_propose_with_weight(
+				Origin::signed(1),
+				set_balance_proposal_hash(2),
+				VoteWeight::Standard,
+			),
+			BadOrigin,
+		);
+		assert_ok!(Democracy::external_propose_with_weight(
+			Origin::signed(2),
+			set_balance_proposal_hash_and_note(2),
+			VoteWeight::Standard,
+		));
+		assert_noop!(Democracy::external_propose_with_weight(
+			Origin::signed(2),
+			set_balance_proposal_hash(1),
+			VoteWeight::Standard,
+		), Error::<Test>::DuplicateProposal);
+		fast_forward_to(2);
+		let status = Democracy::referendum_status(0).unwrap();
+		assert_eq!(status.threshold, VoteThreshold::SuperMajorityApprove);
+		assert_eq!(status.weight, VoteWeight::Standard);
+	});
+}
+
+#[test]
+fn external_majority_and_default_with_weight_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(0);
+		assert_ok!(Democracy::external_propose_majority_with_weight(
+			Origin::signed(3),
+			set_balance_proposal_hash_and_note(2),
+			VoteWeight::Standard,
+		));
+		assert_eq!(
+			<NextExternal<Test>>::get(),
+			Some((set_balance_proposal_hash(2), VoteThreshold::SimpleMajority, VoteWeight::Standard, Ballot::Open)),
+		);
+		assert_noop!(
+			Democracy::external_propose_default_with_weight(
+				Origin::signed(3),
+				set_balance_proposal_hash(2),
+				VoteWeight::Standard,
+			),
+			BadOrigin,
+		);
+		assert_ok!(Democracy::external_propose_default_with_weight(
+			Origin::signed(1),
+			set_balance_proposal_hash_and_note(2),
+			VoteWeight::Standard,
+		));
+		assert_eq!(
+			<NextExternal<Test>>::get(),
+			Some((set_balance_proposal_hash(2), VoteThreshold::SuperMajorityAgainst, VoteWeight::Standard, Ballot::Open)),
+		);
+	});
+}
@@ -38,7 +38,7 @@ fn fast_track_referendum_works() {
 				threshold: VoteThreshold::SimpleMajority,
 				weight: VoteWeight::Quadratic,
 				delay: 0,
-				tally: Tally { ayes: 0, nays: 0, turnout: 0 },
+				tally: Tally { ayes: 0, nays: 0, turnout: 0, weighted_turnout: 0, voters: 0 },
 			})
 		);
 	});
@@ -67,7 +67,7 @@ fn instant_referendum_works() {
 				threshold: VoteThreshold::SimpleMajority,
 				weight: VoteWeight::Quadratic,
 				delay: 0,
-				tally: Tally { ayes: 0, nays: 0, turnout: 0 },
+				tally: Tally { ayes: 0, nays: 0, turnout: 0, weighted_turnout: 0, voters: 0 },
 			})
 		);
 	});
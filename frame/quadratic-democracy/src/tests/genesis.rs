@@ -0,0 +1,78 @@
+// Copyright 2017-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The tests for governance seeded at genesis.
+
+use super::*;
+
+fn genesis_ext(config: GenesisConfig<Test>) -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test>{
+		balances: vec![(1, 100), (2, 200), (3, 300)],
+	}.assimilate_storage(&mut t).unwrap();
+	config.assimilate_storage(&mut t).unwrap();
+	sp_io::TestExternalities::new(t)
+}
+
+#[test]
+fn genesis_seeds_governance() {
+	let h = set_balance_proposal_hash(2);
+	genesis_ext(GenesisConfig {
+		preimages: vec![(1, set_balance_proposal(2))],
+		proposals: vec![(1, h, 2, VoteWeight::Standard)],
+		referenda: vec![(h, VoteThreshold::SimpleMajority, VoteWeight::Quadratic, 1)],
+		delegations: vec![(3, 2, Conviction::Locked1x, 300)],
+		linked_sub_accounts: vec![],
+	}).execute_with(|| {
+		assert!(Preimages::<Test>::contains_key(h));
+		assert_eq!(Democracy::public_props(), vec![(0, h, 1, VoteWeight::Standard, Ballot::Open)]);
+		assert_eq!(Balances::reserved_balance(1), 2);
+		assert_eq!(Democracy::referendum_count(), 1);
+		let status = Democracy::referendum_status(0).unwrap();
+		assert_eq!(status.end, 2);
+		assert_eq!(status.weight, VoteWeight::Quadratic);
+		assert_eq!(Balances::locks(3).len(), 1);
+
+		System::set_block_number(1);
+		assert_ok!(Democracy::vote(Origin::signed(2), 0, aye(2)));
+		assert_eq!(tally(0).turnout, 500);
+		assert_eq!(tally(0).voters, 1);
+
+		fast_forward_to(3);
+		assert_eq!(Balances::free_balance(42), 2);
+	});
+}
+
+#[test]
+fn genesis_links_sub_accounts() {
+	genesis_ext(GenesisConfig {
+		linked_sub_accounts: vec![(2, 1), (3, 1)],
+		.. Default::default()
+	}).execute_with(|| {
+		assert_eq!(Democracy::linked_to(2), Some(1));
+		assert_eq!(Democracy::linked_to(3), Some(1));
+		assert_eq!(Democracy::linked_to(1), None);
+	});
+}
+
+#[test]
+#[should_panic(expected = "Genesis proposal could not be made")]
+fn genesis_proposals_need_their_deposit() {
+	genesis_ext(GenesisConfig {
+		proposals: vec![(1, set_balance_proposal_hash(2), 101, VoteWeight::Standard)],
+		.. Default::default()
+	});
+}
@@ -0,0 +1,131 @@
+// Copyright 2017-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The tests for the results of finished referenda and their pruning.
+
+use super::*;
+
+#[test]
+fn finished_referenda_keep_their_result() {
+	new_test_ext().execute_with(|| {
+		let r = begin_referendum();
+		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+		assert_ok!(Democracy::vote(Origin::signed(2), r, nay(2)));
+		let final_tally = tally(r);
+
+		fast_forward_to(4);
+		match Democracy::referendum_info(r) {
+			Some(ReferendumInfo::Finished { end: 4, result: Some(result), .. }) => assert_eq!(
+				result,
+				ReferendumResult {
+					tally: final_tally,
+					threshold: VoteThreshold::SuperMajorityApprove,
+					weight: VoteWeight::Quadratic,
+				},
+			),
+			_ => panic!("referendum is finished with a result"),
+		}
+	});
+}
+
+#[test]
+fn voters_are_counted_per_referendum() {
+	new_test_ext().execute_with(|| {
+		let r = begin_referendum();
+		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+		assert_ok!(Democracy::vote(Origin::signed(2), r, nay(2)));
+		// Changing a vote doesn't count the voter again.
+		assert_ok!(Democracy::vote(Origin::signed(2), r, aye(2)));
+		assert_eq!(Democracy::voter_count(r), 2);
+
+		assert_ok!(Democracy::remove_vote(Origin::signed(1), r));
+		assert_eq!(Democracy::voter_count(r), 1);
+		assert_noop!(Democracy::remove_vote(Origin::signed(1), r), Error::<Test>::NotVoter);
+		assert_ok!(Democracy::remove_vote(Origin::signed(2), r));
+		assert!(!VoterCount::contains_key(r));
+	});
+}
+
+#[test]
+fn pruning_needs_retention_to_pass_and_votes_to_be_removed() {
+	new_test_ext().execute_with(|| {
+		let r = begin_referendum();
+		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+		assert_noop!(Democracy::prune_referendum(Origin::signed(6), r), Error::<Test>::ReferendumInvalid);
+
+		fast_forward_to(4);
+		assert_noop!(Democracy::prune_referendum(Origin::signed(6), r), Error::<Test>::TooEarly);
+
+		fast_forward_to(8);
+		assert_noop!(Democracy::prune_referendum(Origin::signed(6), r), Error::<Test>::VotesRemain);
+
+		assert_ok!(Democracy::remove_other_vote(Origin::signed(6), 1, r));
+		assert_ok!(Democracy::prune_referendum(Origin::signed(6), r));
+		assert_eq!(last_event(), RawEvent::ReferendumPruned(r));
+		assert_eq!(Democracy::referendum_info(r), None);
+		assert_noop!(Democracy::prune_referendum(Origin::signed(6), r), Error::<Test>::ReferendumInvalid);
+	});
+}
+
+#[test]
+fn finished_multi_option_referenda_can_be_pruned() {
+	new_test_ext().execute_with(|| {
+		let hashes = vec![set_balance_proposal_hash_and_note(2), set_balance_proposal_hash_and_note(3)];
+		assert_ok!(Democracy::start_multi_option_referendum(Origin::signed(3), hashes));
+		let r = Democracy::referendum_count() - 1;
+		assert_ok!(Democracy::vote_options(Origin::signed(1), r, vec![0, 10]));
+
+		fast_forward_to(3);
+		assert_noop!(Democracy::prune_referendum(Origin::signed(6), r), Error::<Test>::TooEarly);
+
+		fast_forward_to(7);
+		assert_noop!(Democracy::prune_referendum(Origin::signed(6), r), Error::<Test>::VotesRemain);
+		assert_ok!(Democracy::reap_multi_option_votes(Origin::signed(6), r, 1));
+		assert_ok!(Democracy::prune_referendum(Origin::signed(6), r));
+		assert_eq!(Democracy::multi_option_referendum_info(r), None);
+	});
+}
+
+#[test]
+fn pruning_needs_uncounted_votes_to_be_gone() {
+	new_test_ext().execute_with(|| {
+		let r = begin_referendum();
+		fast_forward_to(8);
+
+		MultiOptionVotesOf::<Test>::insert(r, 1, vec![0, 10]);
+		assert_noop!(Democracy::prune_referendum(Origin::signed(6), r), Error::<Test>::VotesRemain);
+		MultiOptionVotesOf::<Test>::remove(r, 1);
+
+		CommitmentOf::<Test>::insert(r, 2, (H256::zero(), 1));
+		assert_noop!(Democracy::prune_referendum(Origin::signed(6), r), Error::<Test>::VotesRemain);
+		CommitmentOf::<Test>::remove(r, 2);
+
+		assert_ok!(Democracy::prune_referendum(Origin::signed(6), r));
+	});
+}
+
+#[test]
+fn votes_on_cancelled_referenda_are_uncounted_on_removal() {
+	new_test_ext().execute_with(|| {
+		let r = begin_referendum();
+		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+		assert_ok!(Democracy::cancel_referendum(Origin::ROOT, r.into()));
+		assert_eq!(Democracy::voter_count(r), 1);
+
+		assert_ok!(Democracy::remove_other_vote(Origin::signed(6), 1, r));
+		assert!(!VoterCount::contains_key(r));
+	});
+}
@@ -57,7 +57,7 @@ fn lock_voting_should_work() {
 		assert_ok!(Democracy::vote(Origin::signed(3), r, aye(3, 300)));
 		assert_ok!(Democracy::vote(Origin::signed(4), r, aye(2, 400)));
 		assert_ok!(Democracy::vote(Origin::signed(5), r, nay(1, 500)));
-		assert_eq!(tally(r), Tally { ayes: 147, nays: 72, turnout: 1500 });
+		assert_eq!(tally(r), Tally { ayes: 147, nays: 72, turnout: 1500, weighted_turnout: 83, voters: 5 });
 
 		// All balances are currently locked.
 		for i in 1..=5 {
@@ -155,7 +155,7 @@ fn lock_voting_should_work_with_delegation() {
 		assert_ok!(Democracy::delegate(Origin::signed(4), 2, Conviction::Locked2x, 400));
 		assert_ok!(Democracy::vote(Origin::signed(5), r, nay(1, 500)));
 
-		assert_eq!(tally(r), Tally { ayes: 135, nays: 72, turnout: 1500 });
+		assert_eq!(tally(r), Tally { ayes: 135, nays: 72, turnout: 1500, weighted_turnout: 83, voters: 4 });
 
 		next_block();
 		next_block();
@@ -368,3 +368,93 @@ fn locks_should_persist_from_delegation_to_voting() {
 		assert_eq!(Balances::locks(5), vec![]);
 	});
 }
+
+#[test]
+fn conviction_schedule_is_configurable() {
+	new_test_ext().execute_with(|| {
+		let mut levels = ConvictionSchedule::standard().0;
+		levels[6] = ConvictionLevel { lock_periods: 3, multiplier: 20 };
+		CONVICTION_SCHEDULE.with(|v| *v.borrow_mut() = ConvictionSchedule(levels));
+
+		System::set_block_number(0);
+		let r = Democracy::inject_referendum(
+			2,
+			set_balance_proposal_hash_and_note(2),
+			VoteThreshold::SuperMajorityApprove,
+			VoteWeight::Quadratic,
+			0
+		);
+		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(6, 100)));
+		assert_eq!(tally(r), Tally { ayes: 20, nays: 0, turnout: 100, weighted_turnout: 10, voters: 1 });
+
+		fast_forward_to(2);
+		assert_ok!(Democracy::remove_vote(Origin::signed(1), r));
+
+		// Locked for three enactment periods after the referendum ended.
+		fast_forward_to(7);
+		assert_ok!(Democracy::unlock(Origin::signed(1), 1));
+		assert_eq!(Balances::locks(1), vec![the_lock(100)]);
+		fast_forward_to(8);
+		assert_ok!(Democracy::unlock(Origin::signed(1), 1));
+		assert_eq!(Balances::locks(1), vec![]);
+	});
+}
+
+#[test]
+fn schedule_changes_leave_votes_already_counted_alone() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(0);
+		let r = Democracy::inject_referendum(
+			2,
+			set_balance_proposal_hash_and_note(2),
+			VoteThreshold::SuperMajorityApprove,
+			VoteWeight::Standard,
+			0
+		);
+		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(2, 100)));
+		assert_ok!(Democracy::vote(Origin::signed(2), r, aye(2, 200)));
+
+		let mut levels = ConvictionSchedule::standard().0;
+		levels[2] = ConvictionLevel { lock_periods: 0, multiplier: 50 };
+		CONVICTION_SCHEDULE.with(|v| *v.borrow_mut() = ConvictionSchedule(levels));
+
+		// New votes are counted at the new level, while removing a vote takes back what the old
+		// one counted.
+		assert_ok!(Democracy::vote(Origin::signed(3), r, aye(2, 300)));
+		assert_eq!(tally(r), Tally { ayes: 2100, nays: 0, turnout: 600, weighted_turnout: 600, voters: 3 });
+		assert_ok!(Democracy::remove_vote(Origin::signed(2), r));
+		assert_eq!(tally(r), Tally { ayes: 1700, nays: 0, turnout: 400, weighted_turnout: 400, voters: 2 });
+
+		// The winning vote made before the change stays locked for the two periods it was made
+		// for, the one made after it not at all.
+		fast_forward_to(2);
+		assert_ok!(Democracy::remove_vote(Origin::signed(1), r));
+		assert_ok!(Democracy::remove_vote(Origin::signed(3), r));
+		fast_forward_to(5);
+		assert_ok!(Democracy::unlock(Origin::signed(1), 1));
+		assert_eq!(Balances::locks(1), vec![the_lock(100)]);
+		assert_ok!(Democracy::unlock(Origin::signed(3), 3));
+		assert_eq!(Balances::locks(3), vec![]);
+		fast_forward_to(6);
+		assert_ok!(Democracy::unlock(Origin::signed(1), 1));
+		assert_eq!(Balances::locks(1), vec![]);
+	});
+}
+
+#[test]
+fn convictions_beyond_the_schedule_are_unavailable() {
+	new_test_ext().execute_with(|| {
+		let mut levels = ConvictionSchedule::standard().0;
+		levels.truncate(2);
+		CONVICTION_SCHEDULE.with(|v| *v.borrow_mut() = ConvictionSchedule(levels));
+
+		let r = begin_referendum();
+		assert_noop!(Democracy::vote(Origin::signed(2), r, aye(2, 200)), Error::<Test>::UnavailableConviction);
+		assert_noop!(
+			Democracy::delegate(Origin::signed(2), 3, Conviction::Locked2x, 200),
+			Error::<Test>::UnavailableConviction,
+		);
+		assert_ok!(Democracy::vote(Origin::signed(2), r, aye(1, 200)));
+		assert_eq!(Democracy::voting_power(&2, VoteWeight::Standard, 200, Conviction::Locked2x), None);
+	});
+}
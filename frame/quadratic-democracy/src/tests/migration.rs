@@ -0,0 +1,186 @@
+// Copyright 2017-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The tests for the migration from the coin-weighted democracy pallet.
+
+use super::*;
+use frame_support::traits::OnRuntimeUpgrade;
+use crate::migration::{migrate_voting, Cursor};
+
+fn legacy_vote(aye: bool, conviction: pallet_democracy::Conviction, balance: u64)
+	-> pallet_democracy::AccountVote<u64>
+{
+	pallet_democracy::AccountVote::Standard { vote: pallet_democracy::Vote { aye, conviction }, balance }
+}
+
+/// Run the coin-weighted democracy pallet up to block 3: referendum 0 has passed with a vote
+/// from 1, and referendum 1, ending at block 5, has two direct voters and two delegators to
+/// account 2. A proposal waits in each queue, 10 is an active proxy of 1 and 11 an open one of 2.
+fn legacy_ext() -> sp_io::TestExternalities {
+	use pallet_democracy::{Conviction, VoteThreshold};
+
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		StorageVersion::kill();
+		assert_ok!(LegacyDemocracy::note_preimage(Origin::signed(6), set_balance_proposal(2)));
+		let h = set_balance_proposal_hash(2);
+
+		let r = LegacyDemocracy::internal_start_referendum(h, VoteThreshold::SimpleMajority, 2);
+		assert_ok!(LegacyDemocracy::vote(Origin::signed(1), r, legacy_vote(true, Conviction::None, 100)));
+		System::set_block_number(3);
+		LegacyDemocracy::on_initialize(3);
+
+		let r = LegacyDemocracy::internal_start_referendum(h, VoteThreshold::SuperMajorityApprove, 2);
+		assert_ok!(LegacyDemocracy::vote(Origin::signed(2), r, legacy_vote(true, Conviction::Locked2x, 200)));
+		assert_ok!(LegacyDemocracy::vote(
+			Origin::signed(3),
+			r,
+			pallet_democracy::AccountVote::Split { aye: 10, nay: 20 },
+		));
+		assert_ok!(LegacyDemocracy::delegate(Origin::signed(4), 2, Conviction::Locked1x, 400));
+		assert_ok!(LegacyDemocracy::delegate(Origin::signed(5), 2, Conviction::None, 500));
+
+		assert_ok!(LegacyDemocracy::propose(Origin::signed(6), h, 1));
+		assert_ok!(LegacyDemocracy::external_propose_majority(Origin::signed(3), h));
+
+		assert_ok!(LegacyDemocracy::open_proxy(Origin::signed(10), 1));
+		assert_ok!(LegacyDemocracy::activate_proxy(Origin::signed(1), 10));
+		assert_ok!(LegacyDemocracy::open_proxy(Origin::signed(11), 2));
+	});
+	ext
+}
+
+#[test]
+fn migration_translates_coin_weighted_storage() {
+	legacy_ext().execute_with(|| {
+		let h = set_balance_proposal_hash(2);
+		let weight = <Democracy as OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert!(weight > 0 && weight < MaximumBlockWeight::get());
+		assert_eq!(StorageVersion::get(), Releases::V2_0_0);
+
+		assert_eq!(
+			Democracy::referendum_info(0),
+			Some(ReferendumInfo::Finished { approved: true, end: 3, result: None }),
+		);
+		let status = Democracy::referendum_status(1).unwrap();
+		assert_eq!(status.end, 5);
+		assert_eq!(status.proposal_hash, h);
+		assert_eq!(status.weight, VoteWeight::Standard);
+		assert_eq!(Democracy::public_props(), vec![(0, h, 6, VoteWeight::Quadratic, Ballot::Open)]);
+		assert_eq!(
+			NextExternal::<Test>::get(),
+			Some((h, VoteThreshold::SimpleMajority, VoteWeight::Quadratic, Ballot::Open)),
+		);
+
+		assert!(TestProxies::is_voting_proxy(&1, &10));
+		assert!(!TestProxies::is_voting_proxy(&2, &11));
+		assert!(System::allow_death(&10));
+		assert!(System::allow_death(&11));
+
+		// The votes are counted in the blocks that follow.
+		assert_eq!(tally(1), Tally::default());
+		System::set_block_number(4);
+		Democracy::on_initialize(4);
+		assert!(!Migrating::exists());
+		// 400 from 2's Locked2x vote, 1 and 2 from 3's split, and 450 delegated to 2.
+		assert_eq!(tally(1), Tally { ayes: 851, nays: 2, turnout: 1130, weighted_turnout: 1130, voters: 2 });
+		assert_eq!(Democracy::voter_count(0), 1);
+		assert_eq!(Democracy::voter_count(1), 2);
+		match VotingOf::<Test>::get(2) {
+			Voting::Direct { delegations, .. } =>
+				assert_eq!(delegations, Delegations { capital: 900, votes: 450, quadratic_votes: 22, weighted_capital: 42 }),
+			_ => panic!("account 2 is voting directly"),
+		}
+
+		// Unvoting and undelegating take back exactly what was counted.
+		assert_ok!(Democracy::remove_vote(Origin::signed(3), 1));
+		assert_ok!(Democracy::undelegate(Origin::signed(4)));
+		assert_eq!(tally(1), Tally { ayes: 450, nays: 0, turnout: 700, weighted_turnout: 700, voters: 1 });
+		assert_ok!(Democracy::proxy_vote(Origin::signed(10), 1, 1, aye(1)));
+
+		// Running the upgrade again does nothing.
+		assert_eq!(<Democracy as OnRuntimeUpgrade>::on_runtime_upgrade(), 0);
+		assert_eq!(Democracy::voter_count(1), 2);
+	});
+}
+
+#[test]
+fn migration_recounts_under_the_conviction_schedule() {
+	legacy_ext().execute_with(|| {
+		CONVICTION_SCHEDULE.with(|v| v.borrow_mut().0[2].multiplier = 15);
+		<Democracy as OnRuntimeUpgrade>::on_runtime_upgrade();
+		Democracy::on_initialize(4);
+		assert_eq!(tally(1), Tally { ayes: 751, nays: 2, turnout: 1130, weighted_turnout: 1130, voters: 2 });
+		CONVICTION_SCHEDULE.with(|v| *v.borrow_mut() = ConvictionSchedule::standard());
+	});
+}
+
+#[test]
+fn new_chains_are_not_migrated() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(StorageVersion::get(), Releases::V2_0_0);
+		assert_eq!(<Democracy as OnRuntimeUpgrade>::on_runtime_upgrade(), 0);
+		assert!(!Migrating::exists());
+	});
+}
+
+#[test]
+fn votes_are_migrated_a_few_at_a_time() {
+	legacy_ext().execute_with(|| {
+		<Democracy as OnRuntimeUpgrade>::on_runtime_upgrade();
+		// Enough for one account at a time.
+		assert!(migrate_voting::<Test>(3) > 0);
+		assert!(matches!(Migrating::get(), Some(Cursor::Votes(_))));
+
+		let mut blocks = 1;
+		while Migrating::exists() {
+			assert!(migrate_voting::<Test>(3) > 0);
+			blocks += 1;
+		}
+		// Five accounts are translated, then looked at again for their delegations.
+		assert!(blocks >= 10);
+		assert_eq!(tally(1), Tally { ayes: 851, nays: 2, turnout: 1130, weighted_turnout: 1130, voters: 2 });
+		assert_eq!(migrate_voting::<Test>(3), 0);
+	});
+}
+
+#[test]
+fn voting_waits_for_the_migration() {
+	legacy_ext().execute_with(|| {
+		<Democracy as OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_noop!(Democracy::vote(Origin::signed(6), 1, aye(6)), Error::<Test>::Migrating);
+		assert_noop!(Democracy::remove_vote(Origin::signed(3), 1), Error::<Test>::Migrating);
+		assert_noop!(
+			Democracy::delegate(Origin::signed(6), 2, Conviction::None, 10),
+			Error::<Test>::Migrating,
+		);
+		assert_noop!(Democracy::undelegate(Origin::signed(4)), Error::<Test>::Migrating);
+		assert_noop!(Democracy::unlock(Origin::signed(4), 4), Error::<Test>::Migrating);
+
+		// Referendum 1 is not baked on an incomplete tally.
+		System::set_block_number(5);
+		assert_ok!(Democracy::begin_block(5));
+		assert_eq!(Democracy::referendum_status(1).unwrap().end, 6);
+
+		System::set_block_number(6);
+		Democracy::on_initialize(6);
+		assert!(matches!(
+			Democracy::referendum_info(1),
+			Some(ReferendumInfo::Finished { approved: true, end: 6, result: Some(_) })
+		));
+		assert_ok!(Democracy::delegate(Origin::signed(6), 2, Conviction::None, 10));
+	});
+}
@@ -0,0 +1,209 @@
+// Copyright 2017-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The tests for multi-option referenda.
+
+use super::*;
+
+fn start_options(values: &[u64]) -> ReferendumIndex {
+	let hashes = values.iter().map(|v| set_balance_proposal_hash_and_note(*v)).collect();
+	assert_ok!(Democracy::start_multi_option_referendum(Origin::signed(3), hashes));
+	Democracy::referendum_count() - 1
+}
+
+fn option_tally(r: ReferendumIndex) -> MultiOptionTally<u64> {
+	match Democracy::multi_option_referendum_info(r) {
+		Some(MultiOptionInfo::Ongoing(status)) => status.tally,
+		_ => panic!("referendum is not ongoing"),
+	}
+}
+
+#[test]
+fn winning_option_is_enacted() {
+	new_test_ext().execute_with(|| {
+		let r = start_options(&[2, 3, 4]);
+		assert_ok!(Democracy::vote_options(Origin::signed(1), r, vec![0, 100, 0]));
+		assert_ok!(Democracy::vote_options(Origin::signed(2), r, vec![100, 100, 0]));
+		assert_ok!(Democracy::vote_options(Origin::signed(3), r, vec![0, 0, 300]));
+		// Two smaller voters outweigh a bigger one with more capital.
+		assert_eq!(option_tally(r), MultiOptionTally { options: vec![10, 20, 17], turnout: 600 });
+
+		fast_forward_to(3);
+		assert_eq!(
+			Democracy::multi_option_referendum_info(r),
+			Some(MultiOptionInfo::Finished { winner: Some(1), end: 3 }),
+		);
+		assert_eq!(Balances::free_balance(42), 0);
+
+		fast_forward_to(5);
+		assert_eq!(Balances::free_balance(42), 3);
+	});
+}
+
+#[test]
+fn tied_options_enact_nothing() {
+	new_test_ext().execute_with(|| {
+		let r = start_options(&[2, 3]);
+		assert_ok!(Democracy::vote_options(Origin::signed(1), r, vec![100, 0]));
+		assert_ok!(Democracy::vote_options(Origin::signed(2), r, vec![0, 100]));
+
+		fast_forward_to(5);
+		assert_eq!(
+			Democracy::multi_option_referendum_info(r),
+			Some(MultiOptionInfo::Finished { winner: None, end: 3 }),
+		);
+		assert_eq!(Balances::free_balance(42), 0);
+	});
+}
+
+#[test]
+fn changing_a_vote_replaces_it() {
+	new_test_ext().execute_with(|| {
+		let r = start_options(&[2, 3]);
+		assert_ok!(Democracy::vote_options(Origin::signed(1), r, vec![100, 0]));
+		assert_ok!(Democracy::vote_options(Origin::signed(1), r, vec![16, 9]));
+		assert_eq!(option_tally(r), MultiOptionTally { options: vec![4, 3], turnout: 25 });
+		assert_eq!(MultiOptionVotesOf::<Test>::get(r, 1), vec![16, 9]);
+	});
+}
+
+#[test]
+fn capital_is_locked_until_the_referendum_ends() {
+	new_test_ext().execute_with(|| {
+		let r = start_options(&[2, 3]);
+		assert_ok!(Democracy::vote_options(Origin::signed(1), r, vec![50, 50]));
+		// Moving to a smaller vote doesn't release the capital.
+		assert_ok!(Democracy::vote_options(Origin::signed(1), r, vec![10, 0]));
+		let lock = BalanceLock { id: DEMOCRACY_ID, amount: 100, reasons: pallet_balances::Reasons::Misc };
+		assert_eq!(Balances::locks(1), vec![lock]);
+
+		fast_forward_to(3);
+		assert_ok!(Democracy::reap_multi_option_votes(Origin::signed(2), r, 1));
+		assert_eq!(MultiOptionVotesOf::<Test>::get(r, 1), Vec::<u64>::new());
+		assert_ok!(Democracy::unlock(Origin::signed(1), 1));
+		assert_eq!(Balances::locks(1), vec![]);
+	});
+}
+
+#[test]
+fn starting_should_check_options() {
+	new_test_ext().execute_with(|| {
+		let h = set_balance_proposal_hash(2);
+		assert_noop!(Democracy::start_multi_option_referendum(Origin::signed(1), vec![h, h]), BadOrigin);
+		assert_noop!(
+			Democracy::start_multi_option_referendum(Origin::signed(3), vec![h]),
+			Error::<Test>::TooFewOptions,
+		);
+		assert_noop!(
+			Democracy::start_multi_option_referendum(Origin::signed(3), vec![h; 5]),
+			Error::<Test>::TooManyOptions,
+		);
+
+		assert_ok!(Democracy::external_propose(Origin::signed(2), h));
+		assert_ok!(Democracy::veto_external(Origin::signed(3), h));
+		assert_noop!(
+			Democracy::start_multi_option_referendum(Origin::signed(3), vec![set_balance_proposal_hash(3), h]),
+			Error::<Test>::ProposalBlacklisted,
+		);
+	});
+}
+
+#[test]
+fn voting_should_check_votes() {
+	new_test_ext().execute_with(|| {
+		let r = start_options(&[2, 3]);
+		assert_noop!(
+			Democracy::vote_options(Origin::signed(1), r, vec![1, 1, 1]),
+			Error::<Test>::WrongOptionCount,
+		);
+		assert_noop!(
+			Democracy::vote_options(Origin::signed(1), r, vec![60, 60]),
+			Error::<Test>::InsufficientFunds,
+		);
+		assert_noop!(Democracy::vote(Origin::signed(1), r, aye(1)), Error::<Test>::ReferendumInvalid);
+		assert_noop!(
+			Democracy::vote_options(Origin::signed(1), r + 1, vec![1, 1]),
+			Error::<Test>::ReferendumInvalid,
+		);
+
+		NOT_PERSONS.with(|v| *v.borrow_mut() = vec![1]);
+		assert_noop!(
+			Democracy::vote_options(Origin::signed(1), r, vec![1, 1]),
+			Error::<Test>::NotPerson,
+		);
+
+		assert_ok!(Democracy::delegate(Origin::signed(4), 2, Conviction::None, 10));
+		assert_noop!(
+			Democracy::vote_options(Origin::signed(4), r, vec![1, 1]),
+			Error::<Test>::AlreadyDelegating,
+		);
+	});
+}
+
+#[test]
+fn cancelled_multi_option_referendum_enacts_nothing() {
+	new_test_ext().execute_with(|| {
+		let r = start_options(&[2, 3]);
+		assert_ok!(Democracy::vote_options(Origin::signed(1), r, vec![100, 0]));
+		assert_ok!(Democracy::cancel_referendum(Origin::ROOT, r.into()));
+		assert_eq!(Democracy::multi_option_referendum_info(r), None);
+		assert_ok!(Democracy::reap_multi_option_votes(Origin::signed(2), r, 1));
+		assert_eq!(MultiOptionVotesOf::<Test>::get(r, 1), Vec::<u64>::new());
+
+		fast_forward_to(5);
+		assert_eq!(Balances::free_balance(42), 0);
+	});
+}
+
+#[test]
+fn votes_are_reaped_in_bounded_calls_once_the_referendum_ends() {
+	new_test_ext().execute_with(|| {
+		let r = start_options(&[2, 3]);
+		assert_ok!(Democracy::vote_options(Origin::signed(1), r, vec![10, 0]));
+		assert_ok!(Democracy::vote_options(Origin::signed(2), r, vec![0, 10]));
+		assert_ok!(Democracy::vote_options(Origin::signed(3), r, vec![10, 10]));
+		assert_noop!(
+			Democracy::reap_multi_option_votes(Origin::signed(4), r, 3),
+			Error::<Test>::ReferendumOngoing,
+		);
+
+		fast_forward_to(3);
+		assert_eq!(MultiOptionVotesOf::<Test>::iter_prefix_values(r).count(), 3);
+		assert_ok!(Democracy::reap_multi_option_votes(Origin::signed(4), r, 2));
+		assert_eq!(MultiOptionVotesOf::<Test>::iter_prefix_values(r).count(), 1);
+		assert_ok!(Democracy::reap_multi_option_votes(Origin::signed(4), r, 2));
+		assert_eq!(MultiOptionVotesOf::<Test>::iter_prefix_values(r).count(), 0);
+	});
+}
+
+#[test]
+fn accounts_delegating_quadratic_referenda_cannot_vote_options() {
+	new_test_ext().execute_with(|| {
+		let r = start_options(&[2, 3]);
+		let quadratic = DelegationClass { weight: Some(VoteWeight::Quadratic), source: None };
+		let standard = DelegationClass { weight: Some(VoteWeight::Standard), source: None };
+
+		assert_ok!(Democracy::delegate_class(Origin::signed(2), quadratic, 1, Conviction::Locked1x, 10));
+		assert_noop!(
+			Democracy::vote_options(Origin::signed(2), r, vec![1, 1]),
+			Error::<Test>::AlreadyDelegating,
+		);
+
+		// Delegating referenda that aren't tallied quadratically doesn't get in the way.
+		assert_ok!(Democracy::delegate_class(Origin::signed(3), standard, 1, Conviction::Locked1x, 10));
+		assert_ok!(Democracy::vote_options(Origin::signed(3), r, vec![1, 1]));
+	});
+}
@@ -0,0 +1,221 @@
+// Copyright 2017-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The tests for the offchain indexes of voters and delegators.
+
+use super::*;
+use frame_support::traits::OffchainWorker;
+use sp_core::offchain::{OffchainExt, testing::TestOffchainExt};
+use sp_runtime::offchain::storage::StorageValueRef;
+
+fn offchain_ext() -> sp_io::TestExternalities {
+	let mut ext = new_test_ext();
+	let (offchain, _state) = TestOffchainExt::new();
+	ext.register_extension(OffchainExt::new(offchain));
+	ext
+}
+
+fn votes(r: ReferendumIndex) -> Option<Vec<(u64, IndexedVote<u64>)>> {
+	StorageValueRef::persistent(&voters_key(r))
+		.get::<Vec<(u64, IndexedVote<u64>)>>()
+		.map(|v| {
+			let mut v = v.expect("voters decode");
+			v.sort_by_key(|&(who, _)| who);
+			v
+		})
+}
+
+fn voters(r: ReferendumIndex) -> Option<Vec<u64>> {
+	votes(r).map(|v| v.into_iter().map(|(who, _)| who).collect())
+}
+
+fn delegators(target: u64) -> Option<Vec<(u64, DelegationClass, Conviction, u64)>> {
+	StorageValueRef::persistent(&delegators_key(&target))
+		.get::<Vec<(u64, DelegationClass, Conviction, u64)>>()
+		.map(|v| {
+			let mut v = v.expect("delegators decode");
+			v.sort_by_key(|d| d.0);
+			v
+		})
+}
+
+fn last_indexed() -> Option<u64> {
+	StorageValueRef::persistent(LAST_INDEXED).get().flatten()
+}
+
+/// Clear the events deposited so far, as a new block would.
+fn clear_events() {
+	System::initialize(
+		&System::block_number(),
+		&Default::default(),
+		&Default::default(),
+		&Default::default(),
+		frame_system::InitKind::Full,
+	);
+}
+
+#[test]
+fn voters_and_delegators_are_indexed() {
+	offchain_ext().execute_with(|| {
+		let r = begin_referendum();
+		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+		assert_ok!(Democracy::vote(Origin::signed(2), r, nay(2)));
+		assert_ok!(Democracy::delegate(Origin::signed(3), 1, Conviction::None, 30));
+		let quadratic = DelegationClass { weight: Some(VoteWeight::Quadratic), source: None };
+		assert_ok!(Democracy::delegate_class(Origin::signed(4), quadratic, 1, Conviction::Locked1x, 40));
+
+		Democracy::offchain_worker(2);
+		assert_eq!(last_indexed(), Some(2));
+		assert_eq!(voters(r), Some(vec![1, 2]));
+		assert_eq!(delegators(1), Some(vec![
+			(3, DelegationClass::default(), Conviction::None, 30),
+			(4, quadratic, Conviction::Locked1x, 40),
+		]));
+		assert_eq!(delegators(2), None);
+
+		// Redelegating moves the delegator from one target to the other.
+		clear_events();
+		System::set_block_number(3);
+		assert_ok!(Democracy::delegate(Origin::signed(3), 2, Conviction::Locked2x, 20));
+		Democracy::offchain_worker(3);
+		assert_eq!(delegators(1), Some(vec![(4, quadratic, Conviction::Locked1x, 40)]));
+		assert_eq!(delegators(2), Some(vec![(3, DelegationClass::default(), Conviction::Locked2x, 20)]));
+	});
+}
+
+#[test]
+fn indexes_follow_the_events_of_each_block() {
+	offchain_ext().execute_with(|| {
+		let r = begin_referendum();
+		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+		Democracy::offchain_worker(2);
+		assert_eq!(voters(r), Some(vec![1]));
+		// A block is only indexed once.
+		assert_ok!(Democracy::vote(Origin::signed(2), r, aye(2)));
+		Democracy::offchain_worker(2);
+		assert_eq!(voters(r), Some(vec![1]));
+
+		// Unlike any vote cast through the pallet, this one deposits no event.
+		let vote = match VotingOf::<Test>::get(1) {
+			Voting::Direct { votes, .. } => votes[0].1,
+			_ => panic!("account 1 is voting directly"),
+		};
+		VotingOf::<Test>::mutate(5, |voting| if let Voting::Direct { votes, .. } = voting {
+			votes.push((r, vote));
+		});
+		clear_events();
+		System::set_block_number(3);
+		assert_ok!(Democracy::vote(Origin::signed(3), r, aye(3)));
+		Democracy::offchain_worker(3);
+		assert_eq!(voters(r), Some(vec![1, 3]));
+
+		// Having missed block 4, every account is re-read, two per block.
+		clear_events();
+		for n in 5..=7 {
+			System::set_block_number(n);
+			Democracy::offchain_worker(n);
+		}
+		assert_eq!(last_indexed(), Some(7));
+		assert_eq!(voters(r), Some(vec![1, 2, 3, 5]));
+	});
+}
+
+#[test]
+fn secret_ballot_voters_are_indexed() {
+	offchain_ext().execute_with(|| {
+		System::set_block_number(0);
+		assert_ok!(Democracy::external_propose_secret(
+			Origin::signed(3),
+			set_balance_proposal_hash_and_note(2),
+			VoteThreshold::SimpleMajority,
+			VoteWeight::Quadratic,
+		));
+		fast_forward_to(2);
+		let r = 0;
+		let v = AccountVote::Standard { vote: BIG_AYE, balance: 10 };
+		let salt = H256::repeat_byte(1);
+		for who in 1..=2 {
+			let commitment = BlakeTwo256::hash_of(&(who, r, v, salt));
+			assert_ok!(Democracy::commit_vote(Origin::signed(who), r, commitment));
+		}
+		Democracy::offchain_worker(2);
+		assert_eq!(votes(r), Some(vec![(1, IndexedVote::Committed), (2, IndexedVote::Committed)]));
+
+		clear_events();
+		fast_forward_to(3);
+		assert_ok!(Democracy::reveal_vote(Origin::signed(1), r, v, salt));
+		Democracy::offchain_worker(3);
+		let revealed = match VotingOf::<Test>::get(1) {
+			Voting::Direct { votes, .. } => votes[0].1,
+			_ => panic!("account 1 is voting directly"),
+		};
+		assert_eq!(votes(r), Some(vec![(1, IndexedVote::Vote(revealed)), (2, IndexedVote::Committed)]));
+
+		// The unrevealed commitment is slashed once the ballot has ended.
+		clear_events();
+		fast_forward_to(4);
+		assert_ok!(Democracy::reap_commitments(Origin::signed(3), r, 10));
+		Democracy::offchain_worker(4);
+		assert_eq!(votes(r), Some(vec![(1, IndexedVote::Vote(revealed))]));
+	});
+}
+
+#[test]
+fn multi_option_voters_are_indexed() {
+	offchain_ext().execute_with(|| {
+		let hashes = vec![set_balance_proposal_hash_and_note(2), set_balance_proposal_hash_and_note(3)];
+		assert_ok!(Democracy::start_multi_option_referendum(Origin::signed(3), hashes));
+		let r = Democracy::referendum_count() - 1;
+		assert_ok!(Democracy::vote_options(Origin::signed(1), r, vec![10, 0]));
+		assert_ok!(Democracy::vote_options(Origin::signed(2), r, vec![4, 9]));
+		Democracy::offchain_worker(1);
+		assert_eq!(votes(r), Some(vec![
+			(1, IndexedVote::Options(vec![10, 0])),
+			(2, IndexedVote::Options(vec![4, 9])),
+		]));
+
+		// Votes leave the index as they are reaped.
+		clear_events();
+		fast_forward_to(3);
+		assert_ok!(Democracy::reap_multi_option_votes(Origin::signed(3), r, 1));
+		Democracy::offchain_worker(3);
+		assert_eq!(voters(r).map(|v| v.len()), Some(1));
+		clear_events();
+		System::set_block_number(4);
+		assert_ok!(Democracy::reap_multi_option_votes(Origin::signed(3), r, 1));
+		Democracy::offchain_worker(4);
+		assert_eq!(voters(r), Some(vec![]));
+	});
+}
+
+#[test]
+fn entries_left_empty_are_cleared() {
+	offchain_ext().execute_with(|| {
+		let r = begin_referendum();
+		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+		assert_ok!(Democracy::delegate(Origin::signed(3), 2, Conviction::None, 30));
+		Democracy::offchain_worker(2);
+		assert_eq!(voters(r), Some(vec![1]));
+
+		clear_events();
+		System::set_block_number(3);
+		assert_ok!(Democracy::remove_vote(Origin::signed(1), r));
+		assert_ok!(Democracy::undelegate(Origin::signed(3)));
+		Democracy::offchain_worker(3);
+		assert_eq!(voters(r), Some(vec![]));
+		assert_eq!(delegators(2), Some(vec![]));
+	});
+}
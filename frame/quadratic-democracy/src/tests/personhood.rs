@@ -0,0 +1,185 @@
+// Copyright 2017-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The tests for personhood-based sybil resistance.
+
+use super::*;
+
+fn the_lock(amount: u64) -> BalanceLock<u64> {
+	BalanceLock {
+		id: DEMOCRACY_ID,
+		amount,
+		reasons: pallet_balances::Reasons::Misc,
+	}
+}
+
+/// Make `sub` a sub-account of `person` that has agreed to be linked to them.
+fn link(sub: u64, person: u64) {
+	SUB_ACCOUNTS.with(|v| v.borrow_mut().push((sub, person)));
+	assert_ok!(Democracy::link_sub_account(Origin::signed(sub), person));
+}
+
+fn start_referendum(weight: VoteWeight) -> ReferendumIndex {
+	Democracy::inject_referendum(
+		2,
+		set_balance_proposal_hash_and_note(2),
+		VoteThreshold::SuperMajorityApprove,
+		weight,
+		0,
+	)
+}
+
+#[test]
+fn non_person_cannot_vote_quadratically() {
+	new_test_ext().execute_with(|| {
+		NOT_PERSONS.with(|v| *v.borrow_mut() = vec![1]);
+		let r = start_referendum(VoteWeight::Quadratic);
+		assert_noop!(Democracy::vote(Origin::signed(1), r, aye(1)), Error::<Test>::NotPerson);
+		assert_ok!(Democracy::vote(Origin::signed(2), r, aye(2)));
+	});
+}
+
+#[test]
+fn non_person_can_vote_on_standard_referendum() {
+	new_test_ext().execute_with(|| {
+		NOT_PERSONS.with(|v| *v.borrow_mut() = vec![1]);
+		let r = start_referendum(VoteWeight::Standard);
+		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+		assert_eq!(tally(r), Tally { ayes: 10, nays: 0, turnout: 100, weighted_turnout: 100, voters: 1 });
+	});
+}
+
+#[test]
+fn sub_account_cannot_vote_or_delegate() {
+	new_test_ext().execute_with(|| {
+		link(2, 1);
+		let r = start_referendum(VoteWeight::Quadratic);
+		assert_noop!(Democracy::vote(Origin::signed(2), r, aye(2)), Error::<Test>::SubAccount);
+		assert_noop!(
+			Democracy::delegate(Origin::signed(2), 3, Conviction::None, 20),
+			Error::<Test>::SubAccount,
+		);
+	});
+}
+
+#[test]
+fn linked_capital_is_added_before_square_root() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(0);
+		link(2, 1);
+		link(3, 1);
+		let r = start_referendum(VoteWeight::Quadratic);
+		assert_ok!(Democracy::vote(Origin::signed(1), r, big_aye(1)));
+		// sqrt(100 + 200 + 300) rather than sqrt(100) + sqrt(200) + sqrt(300).
+		assert_eq!(tally(r), Tally { ayes: 24, nays: 0, turnout: 100, weighted_turnout: 24, voters: 1 });
+
+		// The sub-accounts' capital is locked along with the vote.
+		assert_eq!(Balances::locks(2), vec![the_lock(200)]);
+		assert_eq!(Balances::locks(3), vec![the_lock(300)]);
+		assert_noop!(
+			Balances::transfer(Origin::signed(2), 4, 10),
+			BalancesError::<Test, _>::LiquidityRestrictions,
+		);
+
+		// Until the conviction of the vote would have expired.
+		fast_forward_to(3);
+		assert_ok!(Democracy::unlock(Origin::signed(2), 2));
+		assert_eq!(Balances::locks(2), vec![the_lock(200)]);
+		fast_forward_to(4);
+		assert_ok!(Democracy::unlock(Origin::signed(2), 2));
+		assert_eq!(Balances::locks(2), vec![]);
+	});
+}
+
+#[test]
+fn delegating_sub_account_is_not_linked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Democracy::delegate(Origin::signed(3), 4, Conviction::None, 30));
+		link(2, 1);
+		link(3, 1);
+		let r = start_referendum(VoteWeight::Quadratic);
+		assert_ok!(Democracy::vote(Origin::signed(1), r, big_aye(1)));
+		// sqrt(100 + 200)
+		assert_eq!(tally(r), Tally { ayes: 17, nays: 0, turnout: 100, weighted_turnout: 17, voters: 1 });
+	});
+}
+
+#[test]
+fn sub_account_is_only_linked_once_it_agrees() {
+	new_test_ext().execute_with(|| {
+		SUB_ACCOUNTS.with(|v| *v.borrow_mut() = vec![(2, 1)]);
+		let r = start_referendum(VoteWeight::Quadratic);
+		assert_ok!(Democracy::vote(Origin::signed(1), r, big_aye(1)));
+		// sqrt(100), leaving the capital of 2 alone.
+		assert_eq!(tally(r), Tally { ayes: 10, nays: 0, turnout: 100, weighted_turnout: 10, voters: 1 });
+		assert_eq!(Balances::locks(2), vec![]);
+		assert_ok!(Democracy::vote(Origin::signed(2), r, aye(2)));
+
+		assert_ok!(Democracy::remove_vote(Origin::signed(2), r));
+		assert_ok!(Democracy::link_sub_account(Origin::signed(2), 1));
+		assert_eq!(last_event(), RawEvent::SubAccountLinked(2, 1));
+		assert_ok!(Democracy::vote(Origin::signed(1), r, big_aye(1)));
+		// sqrt(100 + 200)
+		assert_eq!(tally(r), Tally { ayes: 17, nays: 0, turnout: 100, weighted_turnout: 17, voters: 1 });
+		assert_noop!(Democracy::vote(Origin::signed(2), r, aye(2)), Error::<Test>::SubAccount);
+	});
+}
+
+#[test]
+fn agreeing_to_a_link_the_personhood_doesnt_have_links_nothing() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Democracy::link_sub_account(Origin::signed(2), 1));
+		let r = start_referendum(VoteWeight::Quadratic);
+		assert_ok!(Democracy::vote(Origin::signed(2), r, aye(2)));
+		assert_ok!(Democracy::vote(Origin::signed(1), r, big_aye(1)));
+		assert_eq!(Balances::locks(2), vec![the_lock(200)]);
+		// sqrt(100) + sqrt(200)
+		assert_eq!(tally(r).weighted_turnout, 10 + 14);
+
+		assert_noop!(Democracy::link_sub_account(Origin::signed(3), 3), Error::<Test>::Nonsense);
+		assert_noop!(Democracy::unlink_sub_account(Origin::signed(3)), Error::<Test>::NotLinked);
+		assert_ok!(Democracy::unlink_sub_account(Origin::signed(2)));
+		assert_eq!(last_event(), RawEvent::SubAccountUnlinked(2, 1));
+		assert_eq!(Democracy::linked_to(2), None);
+	});
+}
+
+#[test]
+fn split_vote_neither_counts_nor_locks_linked_capital() {
+	new_test_ext().execute_with(|| {
+		link(2, 1);
+		let r = start_referendum(VoteWeight::Quadratic);
+		assert_ok!(Democracy::vote(Origin::signed(1), r, AccountVote::Split { aye: 64, nay: 36 }));
+		// sqrt(64) -> 8 and sqrt(36) -> 6, each * 0.1 (Conviction) -> 0
+		assert_eq!(tally(r), Tally { ayes: 0, nays: 0, turnout: 100, weighted_turnout: 14, voters: 1 });
+		assert_eq!(Balances::locks(2), vec![]);
+		assert_eq!(Balances::locks(1), vec![the_lock(100)]);
+	});
+}
+
+#[test]
+fn only_the_first_sub_accounts_are_looked_at() {
+	new_test_ext().execute_with(|| {
+		link(2, 1);
+		link(3, 1);
+		link(4, 1);
+		let r = start_referendum(VoteWeight::Quadratic);
+		assert_ok!(Democracy::vote(Origin::signed(1), r, big_aye(1)));
+		// sqrt(100 + 200 + 300), leaving out the 400 of the third sub-account.
+		assert_eq!(tally(r), Tally { ayes: 24, nays: 0, turnout: 100, weighted_turnout: 24, voters: 1 });
+		assert_eq!(Balances::locks(4), vec![]);
+	});
+}
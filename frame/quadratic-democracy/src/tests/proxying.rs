@@ -19,48 +19,22 @@
 use super::*;
 
 #[test]
-fn proxy_should_work() {
+fn only_voting_proxies_may_act_for_the_stash() {
 	new_test_ext().execute_with(|| {
-		assert_eq!(Democracy::proxy(10), None);
-		assert!(System::allow_death(&10));
-
-		assert_noop!(Democracy::activate_proxy(Origin::signed(1), 10), Error::<Test>::NotOpen);
-
-		assert_ok!(Democracy::open_proxy(Origin::signed(10), 1));
-		assert!(!System::allow_death(&10));
-		assert_eq!(Democracy::proxy(10), Some(ProxyState::Open(1)));
-
-		assert_noop!(Democracy::activate_proxy(Origin::signed(2), 10), Error::<Test>::WrongOpen);
-		assert_ok!(Democracy::activate_proxy(Origin::signed(1), 10));
-		assert_eq!(Democracy::proxy(10), Some(ProxyState::Active(1)));
-
-		// Can't set when already set.
-		assert_noop!(Democracy::activate_proxy(Origin::signed(2), 10), Error::<Test>::AlreadyProxy);
-
-		// But this works because 11 isn't proxying.
-		assert_ok!(Democracy::open_proxy(Origin::signed(11), 2));
-		assert_ok!(Democracy::activate_proxy(Origin::signed(2), 11));
-		assert_eq!(Democracy::proxy(10), Some(ProxyState::Active(1)));
-		assert_eq!(Democracy::proxy(11), Some(ProxyState::Active(2)));
-
-		// 2 cannot fire 1's proxy:
-		assert_noop!(Democracy::deactivate_proxy(Origin::signed(2), 10), Error::<Test>::WrongProxy);
-
-		// 1 deactivates their proxy:
-		assert_ok!(Democracy::deactivate_proxy(Origin::signed(1), 10));
-		assert_eq!(Democracy::proxy(10), Some(ProxyState::Open(1)));
-		// but the proxy account cannot be killed until the proxy is closed.
-		assert!(!System::allow_death(&10));
-
-		// and then 10 closes it completely:
-		assert_ok!(Democracy::close_proxy(Origin::signed(10)));
-		assert_eq!(Democracy::proxy(10), None);
-		assert!(System::allow_death(&10));
-
-		// 11 just closes without 2's "permission".
-		assert_ok!(Democracy::close_proxy(Origin::signed(11)));
-		assert_eq!(Democracy::proxy(11), None);
-		assert!(System::allow_death(&11));
+		let r = begin_referendum();
+		assert_noop!(Democracy::proxy_vote(Origin::signed(10), 1, r, aye(1)), Error::<Test>::NotProxy);
+		assert_ok!(TestProxies::add_voting_proxy(&1, &10));
+
+		// 10 is a proxy of 1, not the other way around, and not of 2.
+		assert_noop!(Democracy::proxy_vote(Origin::signed(1), 10, r, aye(1)), Error::<Test>::NotProxy);
+		assert_noop!(Democracy::proxy_vote(Origin::signed(10), 2, r, aye(2)), Error::<Test>::NotProxy);
+		assert_noop!(
+			Democracy::proxy_delegate(Origin::signed(10), 2, 1, Conviction::None, 100),
+			Error::<Test>::NotProxy,
+		);
+		assert_noop!(Democracy::proxy_undelegate(Origin::signed(10), 2), Error::<Test>::NotProxy);
+		assert_noop!(Democracy::proxy_remove_vote(Origin::signed(10), 2, r), Error::<Test>::NotProxy);
+		assert_ok!(Democracy::proxy_vote(Origin::signed(10), 1, r, aye(1)));
 	});
 }
 
@@ -72,14 +46,13 @@ fn voting_and_removing_votes_should_work_with_proxy() {
 
 		fast_forward_to(2);
 		let r = 0;
-		assert_ok!(Democracy::open_proxy(Origin::signed(10), 1));
-		assert_ok!(Democracy::activate_proxy(Origin::signed(1), 10));
+		assert_ok!(TestProxies::add_voting_proxy(&1, &10));
 
-		assert_ok!(Democracy::proxy_vote(Origin::signed(10), r, aye(1)));
-		assert_eq!(tally(r), Tally { ayes: 1, nays: 0, turnout: 100 });
+		assert_ok!(Democracy::proxy_vote(Origin::signed(10), 1, r, aye(1)));
+		assert_eq!(tally(r), Tally { ayes: 1, nays: 0, turnout: 100, weighted_turnout: 10, voters: 1 });
 
-		assert_ok!(Democracy::proxy_remove_vote(Origin::signed(10), r));
-		assert_eq!(tally(r), Tally { ayes: 0, nays: 0, turnout: 0 });
+		assert_ok!(Democracy::proxy_remove_vote(Origin::signed(10), 1, r));
+		assert_eq!(tally(r), Tally { ayes: 0, nays: 0, turnout: 0, weighted_turnout: 0, voters: 0 });
 	});
 }
 
@@ -90,15 +63,13 @@ fn delegation_and_undelegation_should_work_with_proxy() {
 		assert_ok!(propose_set_balance_and_note(1, 2, 1));
 		fast_forward_to(2);
 		let r = 0;
-		assert_ok!(Democracy::open_proxy(Origin::signed(10), 1));
-		assert_ok!(Democracy::activate_proxy(Origin::signed(1), 10));
+		assert_ok!(TestProxies::add_voting_proxy(&1, &10));
 		assert_ok!(Democracy::vote(Origin::signed(2), r, aye(2)));// 200 => 14 * 0.1 =1
 
-		assert_ok!(Democracy::proxy_delegate(Origin::signed(10), 2, Conviction::None, 100));
-		assert_eq!(tally(r), Tally { ayes: 4, nays: 0, turnout: 300 });
+		assert_ok!(Democracy::proxy_delegate(Origin::signed(10), 1, 2, Conviction::None, 100));
+		assert_eq!(tally(r), Tally { ayes: 4, nays: 0, turnout: 300, weighted_turnout: 24, voters: 1 });
 
-		assert_ok!(Democracy::proxy_undelegate(Origin::signed(10)));
-		assert_eq!(tally(r), Tally { ayes: 1, nays: 0, turnout: 200 });
+		assert_ok!(Democracy::proxy_undelegate(Origin::signed(10), 1));
+		assert_eq!(tally(r), Tally { ayes: 1, nays: 0, turnout: 200, weighted_turnout: 14, voters: 1 });
 	});
 }
-
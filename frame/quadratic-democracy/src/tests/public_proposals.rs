@@ -96,3 +96,47 @@ fn runners_up_should_come_after() {
 		assert_ok!(Democracy::vote(Origin::signed(1), 2, aye(1))); // 20
 	});
 }
+
+#[test]
+fn proposal_vote_weight_should_carry_into_referendum() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(0);
+		assert_ok!(Democracy::propose(
+			Origin::signed(1),
+			set_balance_proposal_hash_and_note(2),
+			2,
+			Some(VoteWeight::Standard),
+		));
+		assert_ok!(propose_set_balance_and_note(1, 3, 1));
+		fast_forward_to(2);
+		assert_eq!(Democracy::referendum_status(0).unwrap().weight, VoteWeight::Standard);
+		fast_forward_to(4);
+		assert_eq!(Democracy::referendum_status(1).unwrap().weight, VoteWeight::Quadratic);
+	});
+}
+
+#[test]
+fn proposals_are_capped_at_max_proposals() {
+	new_test_ext().execute_with(|| {
+		for value in 0 .. MaxProposals::get() as u64 {
+			assert_ok!(propose_set_balance(6, value, 1));
+		}
+		assert_noop!(propose_set_balance(6, 100, 1), Error::<Test>::TooManyProposals);
+
+		// Tabling a proposal makes room for another.
+		fast_forward_to(2);
+		assert_ok!(propose_set_balance(6, 100, 1));
+	});
+}
+
+#[test]
+fn seconds_are_capped_at_max_seconds() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(propose_set_balance_and_note(1, 2, 1));
+		for _ in 0 .. MaxSeconds::get() {
+			assert_ok!(Democracy::second(Origin::signed(6), 0));
+		}
+		assert_noop!(Democracy::second(Origin::signed(5), 0), Error::<Test>::TooManySeconds);
+		assert_eq!(Democracy::deposit_of(0).map(|d| d.1.len()), Some(MaxSeconds::get() as usize + 1));
+	});
+}
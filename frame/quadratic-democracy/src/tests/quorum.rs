@@ -0,0 +1,158 @@
+// Copyright 2017-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The tests for referenda with a quorum of voters and weighted turnout.
+
+use super::*;
+
+fn begin_quorum_referendum(voters: u32, turnout: Perbill) -> ReferendumIndex {
+	System::set_block_number(0);
+	assert_ok!(Democracy::external_propose_quorum(
+		Origin::signed(3),
+		set_balance_proposal_hash_and_note(2),
+		voters,
+		turnout,
+		VoteWeight::Quadratic,
+	));
+	fast_forward_to(2);
+	0
+}
+
+#[test]
+fn external_quorum_referendum_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(0);
+		assert_noop!(
+			Democracy::external_propose_quorum(
+				Origin::signed(1),
+				set_balance_proposal_hash(2),
+				2,
+				Perbill::from_percent(50),
+				VoteWeight::Quadratic,
+			),
+			BadOrigin,
+		);
+		let r = begin_quorum_referendum(2, Perbill::from_percent(50));
+		assert_eq!(
+			Democracy::referendum_status(r),
+			Ok(ReferendumStatus {
+				end: 4,
+				proposal_hash: set_balance_proposal_hash(2),
+				threshold: VoteThreshold::Quorum { voters: 2, turnout: Perbill::from_percent(50) },
+				weight: VoteWeight::Quadratic,
+				delay: 2,
+				tally: Tally { ayes: 0, nays: 0, turnout: 0, weighted_turnout: 0, voters: 0 },
+			})
+		);
+	});
+}
+
+#[test]
+fn tally_counts_voters_once() {
+	new_test_ext().execute_with(|| {
+		let r = begin_referendum();
+		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+		assert_ok!(Democracy::vote(Origin::signed(2), r, nay(2)));
+		assert_eq!(tally(r).voters, 2);
+
+		assert_ok!(Democracy::vote(Origin::signed(2), r, aye(2)));
+		assert_eq!(tally(r).voters, 2);
+
+		// Delegators are counted in the turnout but not as voters.
+		assert_ok!(Democracy::delegate(Origin::signed(3), 1, Conviction::None, 30));
+		assert_eq!(tally(r).voters, 2);
+
+		assert_ok!(Democracy::remove_vote(Origin::signed(1), r));
+		assert_eq!(tally(r).voters, 1);
+	});
+}
+
+#[test]
+fn too_few_voters_fail_the_quorum() {
+	new_test_ext().execute_with(|| {
+		let r = begin_quorum_referendum(3, Perbill::from_percent(50));
+		assert_ok!(Democracy::vote(Origin::signed(5), r, aye(5)));
+		assert_ok!(Democracy::vote(Origin::signed(6), r, aye(6)));
+
+		fast_forward_to(4);
+		assert_eq!(last_event(), RawEvent::NotPassed(r, Rejection::TooFewVoters));
+		fast_forward_to(6);
+		assert_eq!(Balances::free_balance(42), 0);
+	});
+}
+
+#[test]
+fn low_weighted_turnout_fails_the_quorum() {
+	new_test_ext().execute_with(|| {
+		let r = begin_quorum_referendum(2, Perbill::from_percent(40));
+		// sqrt(100) + sqrt(200) -> 24 out of sqrt(2100 * 6) -> 112.
+		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+		assert_ok!(Democracy::vote(Origin::signed(2), r, aye(2)));
+
+		fast_forward_to(4);
+		assert_eq!(last_event(), RawEvent::NotPassed(r, Rejection::LowTurnout));
+		fast_forward_to(6);
+		assert_eq!(Balances::free_balance(42), 0);
+	});
+}
+
+#[test]
+fn majority_carries_once_the_quorum_is_met() {
+	new_test_ext().execute_with(|| {
+		let r = begin_quorum_referendum(2, Perbill::from_percent(40));
+		// sqrt(500) + sqrt(600) -> 46 out of sqrt(2100 * 6) -> 112.
+		assert_ok!(Democracy::vote(Origin::signed(5), r, aye(5)));
+		assert_ok!(Democracy::vote(Origin::signed(6), r, aye(6)));
+
+		fast_forward_to(4);
+		assert!(System::events().iter().any(|e| e.event == Event::democracy(RawEvent::Passed(r))));
+		fast_forward_to(6);
+		assert_eq!(Balances::free_balance(42), 2);
+	});
+}
+
+#[test]
+fn outvoted_quorum_referendum_fails() {
+	new_test_ext().execute_with(|| {
+		let r = begin_quorum_referendum(2, Perbill::from_percent(40));
+		assert_ok!(Democracy::vote(Origin::signed(5), r, aye(5)));
+		assert_ok!(Democracy::vote(Origin::signed(6), r, big_nay(6)));
+
+		fast_forward_to(4);
+		assert_eq!(last_event(), RawEvent::NotPassed(r, Rejection::Outvoted));
+	});
+}
+
+#[test]
+fn many_small_voters_meet_the_quorum_where_one_large_voter_does_not() {
+	new_test_ext().execute_with(|| {
+		let r = begin_quorum_referendum(1, Perbill::from_percent(30));
+		// sqrt(100) + sqrt(200) + sqrt(300) -> 41 out of 112.
+		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+		assert_ok!(Democracy::vote(Origin::signed(2), r, aye(2)));
+		assert_ok!(Democracy::vote(Origin::signed(3), r, aye(3)));
+		fast_forward_to(4);
+		assert!(System::events().iter().any(|e| e.event == Event::democracy(RawEvent::Passed(r))));
+	});
+
+	new_test_ext().execute_with(|| {
+		let r = begin_quorum_referendum(1, Perbill::from_percent(30));
+		// The same 600 from one voter: sqrt(600) -> 24 out of 112.
+		assert_ok!(Democracy::vote(Origin::signed(6), r, aye(6)));
+		fast_forward_to(4);
+		assert_eq!(last_event(), RawEvent::NotPassed(r, Rejection::LowTurnout));
+	});
+}
@@ -29,7 +29,7 @@ fn simple_passing_should_work() {
 			0
 		);
 		assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
-		assert_eq!(tally(r), Tally { ayes: 1, nays: 0, turnout: 100 });
+		assert_eq!(tally(r), Tally { ayes: 1, nays: 0, turnout: 100, weighted_turnout: 10, voters: 1 });
 		next_block();
 		next_block();
 		assert_eq!(Balances::free_balance(42), 2);
@@ -47,7 +47,7 @@ fn simple_failing_should_work() {
 			0
 		);
 		assert_ok!(Democracy::vote(Origin::signed(1), r, nay(1)));
-		assert_eq!(tally(r), Tally { ayes: 0, nays: 1, turnout: 100 });
+		assert_eq!(tally(r), Tally { ayes: 0, nays: 1, turnout: 100, weighted_turnout: 10, voters: 1 });
 
 		next_block();
 		next_block();
@@ -75,13 +75,13 @@ fn ooo_inject_referendums_should_work() {
 		);
 
 		assert_ok!(Democracy::vote(Origin::signed(1), r2, aye(1)));
-		assert_eq!(tally(r2), Tally { ayes: 1, nays: 0, turnout: 100 });
+		assert_eq!(tally(r2), Tally { ayes: 1, nays: 0, turnout: 100, weighted_turnout: 10, voters: 1 });
 
 		next_block();
 		assert_eq!(Balances::free_balance(42), 2);
 
 		assert_ok!(Democracy::vote(Origin::signed(1), r1, aye(1)));
-		assert_eq!(tally(r1), Tally { ayes: 1, nays: 0, turnout: 100 });
+		assert_eq!(tally(r1), Tally { ayes: 1, nays: 0, turnout: 100, weighted_turnout: 10, voters: 1 });
 
 		next_block();
 		assert_eq!(Balances::free_balance(42), 3);
@@ -105,7 +105,7 @@ fn delayed_enactment_should_work() {
 		assert_ok!(Democracy::vote(Origin::signed(5), r, aye(5)));// 22 * 0.1 = 2
 		assert_ok!(Democracy::vote(Origin::signed(6), r, aye(6)));// 24 * 0.1 = 2
 
-		assert_eq!(tally(r), Tally { ayes: 9, nays: 0, turnout: 2100 });
+		assert_eq!(tally(r), Tally { ayes: 9, nays: 0, turnout: 2100, weighted_turnout: 107, voters: 6 });
 
 		next_block();
 		assert_eq!(Balances::free_balance(42), 0);
@@ -114,3 +114,31 @@ fn delayed_enactment_should_work() {
 		assert_eq!(Balances::free_balance(42), 2);
 	});
 }
+
+#[test]
+fn lowest_unbaked_moves_past_referenda_no_longer_ongoing() {
+	new_test_ext().execute_with(|| {
+		let h = set_balance_proposal_hash(2);
+		let cancelled = Democracy::inject_referendum(5, h, VoteThreshold::SuperMajorityApprove, VoteWeight::Quadratic, 0);
+		Democracy::internal_cancel_referendum(cancelled);
+		for _ in 0 .. 20 {
+			let end = System::block_number() + 1;
+			Democracy::inject_referendum(end, h, VoteThreshold::SuperMajorityApprove, VoteWeight::Quadratic, 0);
+			next_block();
+			// `on_initialize` is charged for the referenda from the lowest unbaked one on, which
+			// doesn't grow with the referenda that have come and gone.
+			assert_eq!(Democracy::lowest_unbaked(), Democracy::referendum_count());
+		}
+		assert_eq!(Democracy::referendum_count(), 21);
+
+		// An ongoing referendum holds it back until it is baked.
+		let now = System::block_number();
+		let long = Democracy::inject_referendum(now + 3, h, VoteThreshold::SuperMajorityApprove, VoteWeight::Quadratic, 0);
+		Democracy::inject_referendum(now + 1, h, VoteThreshold::SuperMajorityApprove, VoteWeight::Quadratic, 0);
+		next_block();
+		assert_eq!(Democracy::lowest_unbaked(), long);
+		next_block();
+		next_block();
+		assert_eq!(Democracy::lowest_unbaked(), Democracy::referendum_count());
+	});
+}
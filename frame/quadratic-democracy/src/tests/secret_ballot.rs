@@ -0,0 +1,273 @@
+// Copyright 2017-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The tests for secret ballots.
+
+use super::*;
+
+/// Table a majority-carries secret ballot at block 2, revealed from block 3 and ending at block 4.
+fn begin_secret_ballot() -> ReferendumIndex {
+	System::set_block_number(0);
+	assert_ok!(Democracy::external_propose_secret(
+		Origin::signed(3),
+		set_balance_proposal_hash_and_note(2),
+		VoteThreshold::SimpleMajority,
+		VoteWeight::Quadratic,
+	));
+	fast_forward_to(2);
+	0
+}
+
+fn commitment(who: u64, r: ReferendumIndex, vote: AccountVote<u64>, salt: H256) -> H256 {
+	BlakeTwo256::hash_of(&(who, r, vote, salt))
+}
+
+#[test]
+fn revealed_votes_are_counted() {
+	new_test_ext().execute_with(|| {
+		let r = begin_secret_ballot();
+		assert_eq!(Democracy::reveal_from(r), Some(3));
+		let v = AccountVote::Standard { vote: BIG_AYE, balance: 81 };
+		let salt = H256::repeat_byte(1);
+		assert_noop!(Democracy::vote(Origin::signed(1), r, v), Error::<Test>::SecretBallot);
+
+		assert_ok!(Democracy::commit_vote(Origin::signed(1), r, commitment(1, r, v, salt)));
+		assert_eq!(last_event(), RawEvent::VoteCommitted(r, 1));
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(tally(r), Tally { ayes: 0, nays: 0, turnout: 0, weighted_turnout: 0, voters: 0 });
+		assert_noop!(Democracy::reveal_vote(Origin::signed(1), r, v, salt), Error::<Test>::RevealNotOpen);
+
+		fast_forward_to(3);
+		assert_noop!(
+			Democracy::commit_vote(Origin::signed(2), r, commitment(2, r, v, salt)),
+			Error::<Test>::CommitClosed,
+		);
+		assert_noop!(Democracy::reveal_vote(Origin::signed(2), r, v, salt), Error::<Test>::NoCommitment);
+		assert_noop!(
+			Democracy::reveal_vote(Origin::signed(1), r, v, H256::repeat_byte(2)),
+			Error::<Test>::CommitmentMismatch,
+		);
+
+		assert_ok!(Democracy::reveal_vote(Origin::signed(1), r, v, salt));
+		// sqrt(81) -> 9 * 1 (Conviction) -> 9
+		assert_eq!(tally(r), Tally { ayes: 9, nays: 0, turnout: 81, weighted_turnout: 9, voters: 1 });
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Democracy::commitment_of(r, 1), None);
+		let lock = BalanceLock { id: DEMOCRACY_ID, amount: 81, reasons: pallet_balances::Reasons::Misc };
+		assert_eq!(Balances::locks(1), vec![lock]);
+
+		fast_forward_to(4);
+		assert_eq!(Democracy::reveal_from(r), None);
+		let result = ReferendumResult {
+			tally: Tally { ayes: 9, nays: 0, turnout: 81, weighted_turnout: 9, voters: 1 },
+			threshold: VoteThreshold::SimpleMajority,
+			weight: VoteWeight::Quadratic,
+		};
+		assert_eq!(
+			Democracy::referendum_info(r),
+			Some(ReferendumInfo::Finished { approved: true, end: 4, result: Some(result) }),
+		);
+	});
+}
+
+#[test]
+fn unrevealed_commitments_are_slashed() {
+	new_test_ext().execute_with(|| {
+		let r = begin_secret_ballot();
+		let v = AccountVote::Standard { vote: AYE, balance: 10 };
+		assert_ok!(Democracy::commit_vote(Origin::signed(1), r, commitment(1, r, v, H256::zero())));
+		// Changing the commitment doesn't take a second deposit.
+		assert_ok!(Democracy::commit_vote(Origin::signed(1), r, commitment(1, r, v, H256::repeat_byte(1))));
+		assert_eq!(Balances::reserved_balance(1), 10);
+		let issuance = Balances::total_issuance();
+
+		assert_noop!(Democracy::reap_commitments(Origin::signed(2), r, 1), Error::<Test>::BallotOngoing);
+
+		fast_forward_to(4);
+		assert_noop!(
+			Democracy::commit_vote(Origin::signed(1), r, H256::zero()),
+			Error::<Test>::ReferendumInvalid,
+		);
+		// Ending the ballot leaves the commitments to be reaped.
+		assert_eq!(Balances::reserved_balance(1), 10);
+
+		assert_ok!(Democracy::reap_commitments(Origin::signed(2), r, 1));
+		assert_eq!(last_event(), RawEvent::CommitmentSlashed(r, 1, 10));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 90);
+		assert_eq!(Balances::total_issuance(), issuance - 10);
+		assert_eq!(Democracy::commitment_of(r, 1), None);
+	});
+}
+
+#[test]
+fn commitments_are_reaped_a_bounded_number_at_a_time() {
+	new_test_ext().execute_with(|| {
+		let r = begin_secret_ballot();
+		for who in 1..=3 {
+			assert_ok!(Democracy::commit_vote(Origin::signed(who), r, H256::zero()));
+		}
+		let issuance = Balances::total_issuance();
+		let reserved = || (1..=3).map(|who| Balances::reserved_balance(who)).sum::<u64>();
+		fast_forward_to(4);
+
+		assert_ok!(Democracy::reap_commitments(Origin::signed(4), r, 2));
+		assert_eq!((1..=3).filter(|who| Democracy::commitment_of(r, who).is_some()).count(), 1);
+		assert_eq!(reserved(), 10);
+
+		assert_ok!(Democracy::reap_commitments(Origin::signed(4), r, 2));
+		assert_eq!(reserved(), 0);
+		assert_eq!(Balances::total_issuance(), issuance - 30);
+	});
+}
+
+#[test]
+fn cancelled_secret_ballot_returns_deposits() {
+	new_test_ext().execute_with(|| {
+		let r = begin_secret_ballot();
+		assert_ok!(Democracy::commit_vote(Origin::signed(1), r, H256::zero()));
+		assert_ok!(Democracy::cancel_referendum(Origin::ROOT, r.into()));
+		assert_eq!(Democracy::reveal_from(r), None);
+		assert_eq!(Balances::reserved_balance(1), 10);
+
+		assert_ok!(Democracy::reap_commitments(Origin::signed(2), r, 1));
+		assert_eq!(last_event(), RawEvent::CommitmentReturned(r, 1, 10));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 100);
+		assert_eq!(Democracy::commitment_of(r, 1), None);
+	});
+}
+
+#[test]
+fn external_propose_secret_checks_origin_and_weight() {
+	new_test_ext().execute_with(|| {
+		let h = set_balance_proposal_hash_and_note(2);
+		let majority = VoteThreshold::SimpleMajority;
+		assert_noop!(
+			Democracy::external_propose_secret(Origin::signed(2), h, majority, VoteWeight::Quadratic),
+			BadOrigin,
+		);
+		assert_noop!(
+			Democracy::external_propose_secret(Origin::signed(3), h, majority, VoteWeight::Credits),
+			Error::<Test>::WrongVoteWeight,
+		);
+		assert_ok!(Democracy::external_propose_secret(
+			Origin::signed(2),
+			h,
+			VoteThreshold::SuperMajorityApprove,
+			VoteWeight::Standard,
+		));
+		assert_eq!(
+			<NextExternal<Test>>::get(),
+			Some((h, VoteThreshold::SuperMajorityApprove, VoteWeight::Standard, Ballot::Secret)),
+		);
+		assert_noop!(
+			Democracy::external_propose_secret(
+				Origin::signed(2),
+				h,
+				VoteThreshold::SuperMajorityApprove,
+				VoteWeight::Standard,
+			),
+			Error::<Test>::DuplicateProposal,
+		);
+	});
+}
+
+#[test]
+fn fast_tracked_secret_ballot_stays_secret() {
+	new_test_ext().execute_with(|| {
+		let h = set_balance_proposal_hash_and_note(2);
+		assert_ok!(Democracy::external_propose_secret(
+			Origin::signed(3),
+			h,
+			VoteThreshold::SimpleMajority,
+			VoteWeight::Quadratic,
+		));
+		assert_ok!(Democracy::fast_track(Origin::signed(5), h, 2, 0));
+		// Ends at block 3, with the last block for revealing.
+		assert_eq!(Democracy::reveal_from(0), Some(2));
+		assert_noop!(Democracy::vote(Origin::signed(1), 0, aye(1)), Error::<Test>::SecretBallot);
+	});
+}
+
+#[test]
+fn short_secret_ballot_reveals_in_the_last_half_of_voting() {
+	new_test_ext().execute_with(|| {
+		REVEAL_PERIOD.with(|v| *v.borrow_mut() = 5);
+		let h = set_balance_proposal_hash_and_note(2);
+		assert_ok!(Democracy::external_propose_secret(
+			Origin::signed(3),
+			h,
+			VoteThreshold::SimpleMajority,
+			VoteWeight::Quadratic,
+		));
+		assert_ok!(Democracy::fast_track(Origin::signed(5), h, 4, 0));
+		// Ends at block 5, leaving blocks 1 and 2 to commit rather than none at all.
+		assert_eq!(Democracy::reveal_from(0), Some(3));
+		assert_ok!(Democracy::commit_vote(Origin::signed(1), 0, H256::zero()));
+	});
+}
+
+#[test]
+fn secret_ballot_cannot_be_fast_tracked_into_a_single_block() {
+	new_test_ext().execute_with(|| {
+		INSTANT_ALLOWED.with(|v| *v.borrow_mut() = true);
+		let h = set_balance_proposal_hash_and_note(2);
+		assert_ok!(Democracy::external_propose_secret(
+			Origin::signed(3),
+			h,
+			VoteThreshold::SimpleMajority,
+			VoteWeight::Quadratic,
+		));
+		assert_noop!(
+			Democracy::fast_track(Origin::signed(6), h, 1, 0),
+			Error::<Test>::VotingPeriodTooShort,
+		);
+		assert_ok!(Democracy::fast_track(Origin::signed(6), h, 2, 0));
+		assert_eq!(Democracy::reveal_from(0), Some(2));
+	});
+}
+
+#[test]
+fn public_proposal_may_be_voted_on_by_secret_ballot() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(0);
+		let h = set_balance_proposal_hash_and_note(2);
+		assert_noop!(
+			Democracy::propose_secret(Origin::signed(1), h, 2, Some(VoteWeight::Credits)),
+			Error::<Test>::WrongVoteWeight,
+		);
+		assert_ok!(Democracy::propose_secret(Origin::signed(1), h, 2, None));
+		assert_eq!(Democracy::public_props(), vec![(0, h, 1, VoteWeight::Quadratic, Ballot::Secret)]);
+
+		fast_forward_to(2);
+		assert_eq!(Democracy::referendum_source(0), Some(ProposalSource::Public));
+		assert_eq!(Democracy::reveal_from(0), Some(3));
+		assert_noop!(Democracy::vote(Origin::signed(2), 0, aye(2)), Error::<Test>::SecretBallot);
+		assert_ok!(Democracy::commit_vote(Origin::signed(2), 0, H256::zero()));
+	});
+}
+
+#[test]
+fn open_referenda_take_no_commitments() {
+	new_test_ext().execute_with(|| {
+		let r = begin_referendum();
+		assert_noop!(
+			Democracy::commit_vote(Origin::signed(1), r, H256::zero()),
+			Error::<Test>::NotSecretBallot,
+		);
+	});
+}
@@ -37,7 +37,7 @@ fn split_voting_should_work() {
 
 		// sqrt(300) -> ~17 * 0.1 (Conviction) -> 1
 		// sqrt(200) -> ~14 * 0.1 (Conviction) -> 1
-		assert_eq!(tally(r), Tally { ayes: 1, nays: 1, turnout: 500 });
+		assert_eq!(tally(r), Tally { ayes: 1, nays: 1, turnout: 500, weighted_turnout: 31, voters: 1 });
 	});
 }
 
@@ -48,7 +48,7 @@ fn split_vote_cancellation_should_work() {
 		let v = AccountVote::Split { aye: 300, nay: 200 };
 		assert_ok!(Democracy::vote(Origin::signed(5), r, v));
 		assert_ok!(Democracy::remove_vote(Origin::signed(5), r));
-		assert_eq!(tally(r), Tally { ayes: 0, nays: 0, turnout: 0 });
+		assert_eq!(tally(r), Tally { ayes: 0, nays: 0, turnout: 0, weighted_turnout: 0, weighted_turnout: 0, voters: 0 });
 		assert_ok!(Democracy::unlock(Origin::signed(5), 5));
 		assert_eq!(Balances::locks(5), vec![]);
 	});
@@ -75,7 +75,7 @@ fn single_proposal_should_work() {
 				threshold: VoteThreshold::SuperMajorityApprove,
 				weight: VoteWeight::Quadratic,
 				delay: 2,
-				tally: Tally { ayes: 1, nays: 0, turnout: 100 },
+				tally: Tally { ayes: 1, nays: 0, turnout: 100, weighted_turnout: 10, voters: 1 },
 			})
 		);
 
@@ -118,7 +118,7 @@ fn controversial_voting_should_work() {
 		// aye = 10 + 20 + 24 = 54
 		// nay = 14 + 17 + 22 = 53
 
-		assert_eq!(tally(r), Tally { ayes: 54, nays: 53, turnout: 2100 });
+		assert_eq!(tally(r), Tally { ayes: 54, nays: 53, turnout: 2100, weighted_turnout: 107, voters: 6 });
 
 		next_block();
 		next_block();
@@ -140,7 +140,7 @@ fn controversial_low_turnout_voting_should_work() {
 		assert_ok!(Democracy::vote(Origin::signed(5), r, big_nay(5))); // 500 * 1 => 22
 		assert_ok!(Democracy::vote(Origin::signed(6), r, big_aye(6))); // 600 * 1 => 24
 
-		assert_eq!(tally(r), Tally { ayes: 24, nays: 22, turnout: 1100 });
+		assert_eq!(tally(r), Tally { ayes: 24, nays: 22, turnout: 1100, weighted_turnout: 46, voters: 2 });
 
 		next_block();
 		next_block();
@@ -165,10 +165,118 @@ fn passing_low_turnout_voting_should_work() {
 		assert_ok!(Democracy::vote(Origin::signed(4), r, big_aye(4)));
 		assert_ok!(Democracy::vote(Origin::signed(5), r, big_nay(5)));
 		assert_ok!(Democracy::vote(Origin::signed(6), r, big_aye(6)));
-		assert_eq!(tally(r), Tally { ayes: 44, nays: 22, turnout: 1500 });
+		assert_eq!(tally(r), Tally { ayes: 44, nays: 22, turnout: 1500, weighted_turnout: 66, voters: 3 });
 
 		next_block();
 		next_block();
 		assert_eq!(Balances::free_balance(42), 2);
 	});
 }
+
+#[test]
+fn referendum_outcome_should_work() {
+	new_test_ext().execute_with(|| {
+		let r = Democracy::inject_referendum(
+			2,
+			set_balance_proposal_hash_and_note(2),
+			VoteThreshold::SuperMajorityApprove,
+			VoteWeight::Quadratic,
+			0
+		);
+		assert_ok!(Democracy::vote(Origin::signed(4), r, big_aye(4)));
+		assert_ok!(Democracy::vote(Origin::signed(5), r, big_nay(5)));
+		assert_ok!(Democracy::vote(Origin::signed(6), r, big_aye(6)));
+
+		assert_eq!(Democracy::referendum_outcome(r), Some((
+			Tally { ayes: 44, nays: 22, turnout: 1500, weighted_turnout: 66, voters: 3 },
+			// The square root of the issuance of 2100 shared by six persons.
+			112,
+			VoteWeight::Quadratic,
+			VoteThreshold::SuperMajorityApprove,
+			true,
+		)));
+		assert_eq!(Democracy::referendum_outcome(r + 1), None);
+	});
+}
+
+#[test]
+fn voting_power_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Democracy::delegate(Origin::signed(2), 1, Conviction::Locked1x, 200));
+
+		let (weighted, direct, delegated) =
+			Democracy::voting_power(&1, VoteWeight::Quadratic, 100, Conviction::Locked1x).unwrap();
+		assert_eq!((weighted, direct.votes, delegated.capital, delegated.votes), (10, 10, 200, 14));
+
+		let (weighted, direct, delegated) =
+			Democracy::voting_power(&1, VoteWeight::Standard, 100, Conviction::Locked1x).unwrap();
+		assert_eq!((weighted, direct.votes, delegated.capital, delegated.votes), (100, 100, 200, 200));
+
+		assert_eq!(Democracy::voting_power(&2, VoteWeight::Standard, 100, Conviction::None), None);
+	});
+}
+
+#[test]
+fn voting_and_unvoting_should_report_weighted_power() {
+	new_test_ext().execute_with(|| {
+		let r = begin_referendum();
+		assert_ok!(Democracy::delegate(Origin::signed(3), 1, Conviction::None, 100));
+		assert_ok!(Democracy::vote(Origin::signed(1), r, big_aye(1)));
+		// sqrt(100) -> 10 weighted, and sqrt(100 * 0.1) -> 3 delegated votes on sqrt(100) -> 10
+		// weighted capital.
+		let vote = AccountVoteWeight::Standard {
+			vote: BIG_AYE,
+			balance: 100,
+			weighted_balance: 10,
+			level: level(Conviction::Locked1x),
+		};
+		let delegated = Delegations { capital: 100, votes: 3, quadratic_votes: 1, weighted_capital: 10 };
+		assert_eq!(last_event(), RawEvent::Voted(r, 1, vote, Conviction::Locked1x, delegated));
+
+		assert_ok!(Democracy::remove_vote(Origin::signed(1), r));
+		assert_eq!(last_event(), RawEvent::VoteRemoved(r, 1, vote, Conviction::Locked1x, delegated));
+
+		let split = AccountVote::Split { aye: 16, nay: 9 };
+		assert_ok!(Democracy::vote(Origin::signed(1), r, split));
+		let vote = AccountVoteWeight::Split {
+			aye: 16,
+			nay: 9,
+			aye_weight: 4,
+			nay_weight: 3,
+			level: level(Conviction::None),
+		};
+		assert_eq!(last_event(), RawEvent::Voted(r, 1, vote, Conviction::None, Default::default()));
+
+		// Votes removed after the referendum carry no delegations.
+		fast_forward_to(4);
+		assert!(Democracy::referendum_status(r).is_err());
+		assert_ok!(Democracy::remove_vote(Origin::signed(1), r));
+		assert_eq!(last_event(), RawEvent::VoteRemoved(r, 1, vote, Conviction::None, Default::default()));
+	});
+}
+
+#[test]
+fn votes_are_capped_at_max_votes() {
+	new_test_ext().execute_with(|| {
+		let max = MaxVotes::get();
+		for _ in 0 ..= max {
+			Democracy::inject_referendum(
+				2,
+				set_balance_proposal_hash(2),
+				VoteThreshold::SuperMajorityApprove,
+				VoteWeight::Quadratic,
+				0,
+			);
+		}
+		for r in 0 .. max {
+			assert_ok!(Democracy::vote(Origin::signed(1), r, aye(1)));
+		}
+		assert_noop!(Democracy::vote(Origin::signed(1), max, aye(1)), Error::<Test>::MaxVotesReached);
+
+		// Existing votes may still be changed.
+		assert_ok!(Democracy::vote(Origin::signed(1), 0, nay(1)));
+		// Removing a vote makes room for another.
+		assert_ok!(Democracy::remove_vote(Origin::signed(1), 0));
+		assert_ok!(Democracy::vote(Origin::signed(1), max, aye(1)));
+	});
+}
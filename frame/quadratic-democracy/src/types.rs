@@ -16,30 +16,45 @@
 
 //! Miscellaneous additional datatypes.
 
+use sp_std::prelude::*;
 use codec::{Encode, Decode};
 use sp_runtime::RuntimeDebug;
-use sp_runtime::traits::{Zero, Bounded, CheckedAdd, CheckedSub, CheckedMul, CheckedDiv, Saturating};
-use crate::{Vote, VoteThreshold, AccountVoteWeight,Conviction, VoteWeight};
+use sp_runtime::traits::{
+	Zero, Bounded, CheckedAdd, CheckedSub, CheckedMul, CheckedDiv, Saturating, IntegerSquareRoot,
+};
+use crate::{Vote, VoteThreshold, AccountVoteWeight, Conviction, ConvictionLevel, VoteWeight};
 
 /// Info regarding an ongoing referendum.
 #[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug)]
 pub struct Tally<Balance> {
 	/// The number of aye votes, expressed in terms of post-conviction lock-vote.
-	pub (crate) ayes: Balance,
+	pub ayes: Balance,
 	/// The number of nay votes, expressed in terms of post-conviction lock-vote.
-	pub (crate) nays: Balance,
+	pub nays: Balance,
 	/// The amount of funds currently expressing its opinion. Pre-conviction.
-	pub (crate) turnout: Balance,
+	pub turnout: Balance,
+	/// The sum of each voter's balance as weighted by the referendum's vote weighting, and of the
+	/// weighted capital delegated to it. Pre-conviction. Unlike `turnout`, this is on the same
+	/// scale as the ayes and nays, e.g. a sum of square roots in quadratic referenda.
+	pub weighted_turnout: Balance,
+	/// The number of accounts whose votes are counted. Delegators aren't counted.
+	pub voters: u32,
 }
 
 /// Amount of votes and capital placed in delegation for an account.
 #[derive(Encode, Decode, Default, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
 pub struct 	Delegations<Balance> {
 	/// The amount of raw capital, used for the turnout.
-	pub (crate) capital: Balance,
+	pub capital: Balance,
 	/// The number of votes (this is post-conviction)
 	/// votes impact depends on referendum vote weight
-	pub (crate) votes: Balance
+	pub votes: Balance,
+	/// The sum of each delegator's post-conviction votes on the square root of its own capital.
+	pub quadratic_votes: Balance,
+	/// The pre-conviction capital as weighted for the weighted turnout. As delegated, the sum of
+	/// the square roots of each delegator's capital; once weighted for a referendum by
+	/// `Calculate::delegation`, the capital as that referendum's vote weighting counts it.
+	pub weighted_capital: Balance,
 }
 
 impl<Balance: Saturating> Saturating for Delegations<Balance> {
@@ -47,6 +62,8 @@ impl<Balance: Saturating> Saturating for Delegations<Balance> {
 		Self {
 			votes: self.votes.saturating_add(o.votes),
 			capital: self.capital.saturating_add(o.capital),
+			quadratic_votes: self.quadratic_votes.saturating_add(o.quadratic_votes),
+			weighted_capital: self.weighted_capital.saturating_add(o.weighted_capital),
 		}
 	}
 
@@ -54,6 +71,8 @@ impl<Balance: Saturating> Saturating for Delegations<Balance> {
 		Self {
 			votes: self.votes.saturating_sub(o.votes),
 			capital: self.capital.saturating_sub(o.capital),
+			quadratic_votes: self.quadratic_votes.saturating_sub(o.quadratic_votes),
+			weighted_capital: self.weighted_capital.saturating_sub(o.weighted_capital),
 		}
 	}
 
@@ -61,6 +80,8 @@ impl<Balance: Saturating> Saturating for Delegations<Balance> {
 		Self {
 			votes: self.votes.saturating_mul(o.votes),
 			capital: self.capital.saturating_mul(o.capital),
+			quadratic_votes: self.quadratic_votes.saturating_mul(o.quadratic_votes),
+			weighted_capital: self.weighted_capital.saturating_mul(o.weighted_capital),
 		}
 	}
 
@@ -68,6 +89,8 @@ impl<Balance: Saturating> Saturating for Delegations<Balance> {
 		Self {
 			votes: self.votes.saturating_pow(exp),
 			capital: self.capital.saturating_pow(exp),
+			quadratic_votes: self.quadratic_votes.saturating_pow(exp),
+			weighted_capital: self.weighted_capital.saturating_pow(exp),
 		}
 	}
 }
@@ -81,61 +104,81 @@ impl<
 		vote: Vote,
 		balance: Balance,
 		weighted_balance: Balance,
+		level: ConvictionLevel,
 	) -> Self {
-		let Delegations { votes, capital } = vote.conviction.votes(balance, weighted_balance);
+		let Delegations { votes, capital, weighted_capital, .. } = level.votes(balance, weighted_balance);
 		Self {
 			ayes: if vote.aye { votes } else { Zero::zero() },
 			nays: if vote.aye { Zero::zero() } else { votes },
 			turnout: capital,
+			weighted_turnout: weighted_capital,
+			voters: 1,
 		}
 	}
 
-	/// Add an account's vote into the tally.
-	pub fn add(
-		&mut self,
-		vote: AccountVoteWeight<Balance>,
-	) -> Option<()> {
+	/// Add an account's vote into the tally, at the level it is counted at.
+	pub fn add(&mut self, vote: AccountVoteWeight<Balance>) -> Option<()> {
+		self.voters = self.voters.checked_add(1)?;
 		match vote {
-			AccountVoteWeight::Standard { vote, balance, weighted_balance, } => {
-				let Delegations { votes, capital } = vote.conviction.votes(balance, weighted_balance);
+			AccountVoteWeight::Standard { vote, balance, weighted_balance, level } => {
+				let Delegations { votes, capital, weighted_capital, .. } = level.votes(balance, weighted_balance);
 				self.turnout = self.turnout.checked_add(&capital)?;
+				self.weighted_turnout = self.weighted_turnout.checked_add(&weighted_capital)?;
 				match vote.aye {
 					true => self.ayes = self.ayes.checked_add(&votes)?,
 					false => self.nays = self.nays.checked_add(&votes)?,
 				}
 			}
-			AccountVoteWeight::Split { aye, nay, aye_weight, nay_weight } => {
-				let aye = Conviction::None.votes(aye, aye_weight);
-				let nay = Conviction::None.votes(nay, nay_weight);
+			AccountVoteWeight::Split { aye, nay, aye_weight, nay_weight, level } => {
+				let aye = level.votes(aye, aye_weight);
+				let nay = level.votes(nay, nay_weight);
 				self.turnout = self.turnout.checked_add(&aye.capital)?.checked_add(&nay.capital)?;
+				self.weighted_turnout = self.weighted_turnout
+					.checked_add(&aye.weighted_capital)?
+					.checked_add(&nay.weighted_capital)?;
 				self.ayes = self.ayes.checked_add(&aye.votes)?;
 				self.nays = self.nays.checked_add(&nay.votes)?;
 			}
+			AccountVoteWeight::Credits { aye, votes, .. } => {
+				// Credit votes commit no capital, so they don't add to the turnout.
+				match aye {
+					true => self.ayes = self.ayes.checked_add(&votes)?,
+					false => self.nays = self.nays.checked_add(&votes)?,
+				}
+			}
 		}
 		Some(())
 	}
 
-	/// Remove an account's vote from the tally.
-	pub fn remove(
-		&mut self,
-		vote: AccountVoteWeight<Balance>,
-	) -> Option<()> {
+	/// Remove an account's vote from the tally, at the level it was counted at.
+	pub fn remove(&mut self, vote: AccountVoteWeight<Balance>) -> Option<()> {
+		self.voters = self.voters.checked_sub(1)?;
 		match vote {
-			AccountVoteWeight::Standard { vote, balance, weighted_balance } => {
-				let Delegations { votes, capital } = vote.conviction.votes(balance, weighted_balance);
+			AccountVoteWeight::Standard { vote, balance, weighted_balance, level } => {
+				let Delegations { votes, capital, weighted_capital, .. } = level.votes(balance, weighted_balance);
 				self.turnout = self.turnout.checked_sub(&capital)?;
+				self.weighted_turnout = self.weighted_turnout.checked_sub(&weighted_capital)?;
 				match vote.aye {
 					true => self.ayes = self.ayes.checked_sub(&votes)?,
 					false => self.nays = self.nays.checked_sub(&votes)?,
 				}
 			}
-			AccountVoteWeight::Split { aye, nay, aye_weight, nay_weight } => {
-				let aye = Conviction::None.votes(aye, aye_weight);
-				let nay = Conviction::None.votes(nay, nay_weight);
+			AccountVoteWeight::Split { aye, nay, aye_weight, nay_weight, level } => {
+				let aye = level.votes(aye, aye_weight);
+				let nay = level.votes(nay, nay_weight);
 				self.turnout = self.turnout.checked_sub(&aye.capital)?.checked_sub(&nay.capital)?;
+				self.weighted_turnout = self.weighted_turnout
+					.checked_sub(&aye.weighted_capital)?
+					.checked_sub(&nay.weighted_capital)?;
 				self.ayes = self.ayes.checked_sub(&aye.votes)?;
 				self.nays = self.nays.checked_sub(&nay.votes)?;
 			}
+			AccountVoteWeight::Credits { aye, votes, .. } => {
+				match aye {
+					true => self.ayes = self.ayes.checked_sub(&votes)?,
+					false => self.nays = self.nays.checked_sub(&votes)?,
+				}
+			}
 		}
 		Some(())
 	}
@@ -143,6 +186,7 @@ impl<
 	/// Increment some amount of votes.
 	pub fn increase(&mut self, approve: bool, delegations: Delegations<Balance>) -> Option<()> {
 		self.turnout = self.turnout.saturating_add(delegations.capital);
+		self.weighted_turnout = self.weighted_turnout.saturating_add(delegations.weighted_capital);
 		match approve {
 			true => self.ayes = self.ayes.saturating_add(delegations.votes),
 			false => self.nays = self.nays.saturating_add(delegations.votes),
@@ -153,6 +197,7 @@ impl<
 	/// Decrement some amount of votes.
 	pub fn reduce(&mut self, approve: bool, delegations: Delegations<Balance>) -> Option<()> {
 		self.turnout = self.turnout.saturating_sub(delegations.capital);
+		self.weighted_turnout = self.weighted_turnout.saturating_sub(delegations.weighted_capital);
 		match approve {
 			true => self.ayes = self.ayes.saturating_sub(delegations.votes),
 			false => self.nays = self.nays.saturating_sub(delegations.votes),
@@ -177,13 +222,96 @@ pub struct ReferendumStatus<BlockNumber, Hash, Balance> {
 	pub (crate) tally: Tally<Balance>,
 }
 
+/// How the votes of a referendum are cast.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum Ballot {
+	/// Votes are cast in the open, and counted as soon as they are cast.
+	Open,
+	/// Votes are committed as hashes until the last `RevealPeriod` blocks of voting, or its last
+	/// half if that is shorter, when they are revealed and only then counted.
+	Secret,
+}
+
+impl Default for Ballot {
+	fn default() -> Self {
+		Ballot::Open
+	}
+}
+
+/// Where the proposal of a referendum came from.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum ProposalSource {
+	/// The public proposal queue, `PublicProps`.
+	Public,
+	/// An external origin, through `NextExternal`.
+	External,
+}
+
+/// The referenda a delegation applies to: those of the given vote weighting whose proposal came
+/// from the given source, where `None` stands for any. The default class covers all referenda.
+#[derive(Encode, Decode, Default, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct DelegationClass {
+	/// The vote weighting of the referenda.
+	pub weight: Option<VoteWeight>,
+	/// Where the proposals of the referenda came from.
+	pub source: Option<ProposalSource>,
+}
+
+impl DelegationClass {
+	/// Whether the class covers all referenda.
+	pub fn is_all(&self) -> bool {
+		self.weight.is_none() && self.source.is_none()
+	}
+
+	/// Whether the class covers a referendum weighted by `weight` whose proposal came from
+	/// `source`. If the source isn't known, only classes open to any source cover it.
+	pub fn covers(&self, weight: VoteWeight, source: Option<ProposalSource>) -> bool {
+		self.weight.map_or(true, |w| w == weight) && self.source.map_or(true, |s| Some(s) == source)
+	}
+
+	/// Whether some referendum is covered by both this class and `other`.
+	pub fn overlaps(&self, other: &Self) -> bool {
+		fn meet<X: PartialEq>(a: Option<X>, b: Option<X>) -> bool {
+			a.is_none() || b.is_none() || a == b
+		}
+		meet(self.weight, other.weight) && meet(self.source, other.source)
+	}
+}
+
+/// A delegation of an account's voting power in a class of referenda.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ClassDelegation<Balance, AccountId> {
+	/// The referenda the delegation applies to.
+	pub class: DelegationClass,
+	/// The account whose voting the delegated power follows.
+	pub target: AccountId,
+	/// The conviction attached to the delegated votes.
+	pub conviction: Conviction,
+	/// The level the delegated votes are counted at.
+	pub level: ConvictionLevel,
+	/// The balance delegated.
+	pub balance: Balance,
+}
+
 /// Info regarding a referendum, present or past.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
 pub enum ReferendumInfo<BlockNumber, Hash, Balance> {
 	/// Referendum is happening, the arg is the block number at which it will end.
 	Ongoing(ReferendumStatus<BlockNumber, Hash, Balance>),
-	/// Referendum finished at `end`, and has been `approved` or rejected.
-	Finished{approved: bool, end: BlockNumber},
+	/// Referendum finished at `end`, and has been `approved` or rejected. The `result` it was
+	/// decided on is kept for audit; referenda finished before results were kept have none.
+	Finished{approved: bool, end: BlockNumber, result: Option<ReferendumResult<Balance>>},
+}
+
+/// The final tally of a referendum, with the rules it was decided by.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ReferendumResult<Balance> {
+	/// The tally of votes when the referendum ended.
+	pub tally: Tally<Balance>,
+	/// The thresholding mechanism that determined whether it passed.
+	pub threshold: VoteThreshold,
+	/// The vote weighting the tally was counted with.
+	pub weight: VoteWeight,
 }
 
 impl<BlockNumber, Hash, Balance: Default> ReferendumInfo<BlockNumber, Hash, Balance> {
@@ -200,22 +328,85 @@ impl<BlockNumber, Hash, Balance: Default> ReferendumInfo<BlockNumber, Hash, Bala
 	}
 }
 
-/// State of a proxy voting account.
-#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
-pub enum ProxyState<AccountId> {
-	/// Account is open to becoming a proxy but is not yet assigned.
-	Open(AccountId),
-	/// Account is actively being a proxy.
-	Active(AccountId),
+/// The tally of a multi-option referendum.
+#[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct MultiOptionTally<Balance> {
+	/// The number of votes for each option: the sum of the square roots of the capital each voter
+	/// placed on it.
+	pub options: Vec<Balance>,
+	/// The amount of funds currently expressing its opinion.
+	pub turnout: Balance,
 }
 
-impl<AccountId> ProxyState<AccountId> {
-	pub (crate) fn as_active(self) -> Option<AccountId> {
-		match self {
-			ProxyState::Active(a) => Some(a),
-			ProxyState::Open(_) => None,
+impl<
+	Balance: Zero + Copy + Ord + CheckedAdd + CheckedSub + IntegerSquareRoot
+> MultiOptionTally<Balance> {
+	/// Create an empty tally of `count` options.
+	pub fn new(count: usize) -> Self {
+		Self { options: vec![Zero::zero(); count], turnout: Zero::zero() }
+	}
+
+	/// Add an account's vote, the capital it placed on each option, into the tally.
+	pub fn add(&mut self, balances: &[Balance]) -> Option<()> {
+		if balances.len() != self.options.len() {
+			return None;
+		}
+		for (option, balance) in self.options.iter_mut().zip(balances.iter()) {
+			*option = option.checked_add(&balance.integer_sqrt())?;
+			self.turnout = self.turnout.checked_add(balance)?;
 		}
+		Some(())
+	}
+
+	/// Remove an account's vote from the tally.
+	pub fn remove(&mut self, balances: &[Balance]) -> Option<()> {
+		if balances.len() != self.options.len() {
+			return None;
+		}
+		for (option, balance) in self.options.iter_mut().zip(balances.iter()) {
+			*option = option.checked_sub(&balance.integer_sqrt())?;
+			self.turnout = self.turnout.checked_sub(balance)?;
+		}
+		Some(())
 	}
+
+	/// The index of the option with the most votes, or `None` if no option has any votes or the
+	/// lead is tied.
+	pub fn winner(&self) -> Option<u32> {
+		let mut winner = None;
+		let mut most = Zero::zero();
+		for (i, &votes) in self.options.iter().enumerate() {
+			if votes > most {
+				winner = Some(i as u32);
+				most = votes;
+			} else if votes == most {
+				winner = None;
+			}
+		}
+		winner
+	}
+}
+
+/// Info regarding an ongoing multi-option referendum.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct MultiOptionStatus<BlockNumber, Hash, Balance> {
+	/// When voting on this referendum will end.
+	pub (crate) end: BlockNumber,
+	/// The hashes of the alternative proposals being voted on.
+	pub (crate) proposal_hashes: Vec<Hash>,
+	/// The delay (in blocks) to wait after the referendum ends before deploying the winner.
+	pub (crate) delay: BlockNumber,
+	/// The current tally of votes in this referendum.
+	pub (crate) tally: MultiOptionTally<Balance>,
+}
+
+/// Info regarding a multi-option referendum, present or past.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum MultiOptionInfo<BlockNumber, Hash, Balance> {
+	/// Referendum is happening.
+	Ongoing(MultiOptionStatus<BlockNumber, Hash, Balance>),
+	/// Referendum finished at `end`, with the index of the `winner` if there was one.
+	Finished{winner: Option<u32>, end: BlockNumber},
 }
 
 /// Whether an `unvote` operation is able to make actions that are not strictly always in the
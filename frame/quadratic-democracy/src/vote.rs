@@ -19,7 +19,7 @@
 use sp_std::{prelude::*, result::Result, convert::TryFrom};
 use codec::{Encode, EncodeLike, Decode, Output, Input};
 use sp_runtime::{RuntimeDebug, traits::{Saturating, Zero}};
-use crate::{Conviction, ReferendumIndex, Delegations};
+use crate::{Conviction, ConvictionLevel, ReferendumIndex, Delegations};
 
 /// A number of lock periods, plus a vote, one way or the other.
 #[derive(Copy, Clone, Eq, PartialEq, Default, RuntimeDebug)]
@@ -61,25 +61,17 @@ pub enum AccountVote<Balance> {
 #[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, RuntimeDebug)]
 pub enum AccountVoteWeight<Balance> {
 	/// A standard vote, one-way (approve or reject) with a given amount of conviction and actual
-	/// vote weight committed to the referendum
-	Standard { vote: Vote, balance: Balance, weighted_balance: Balance},
+	/// vote weight committed to the referendum, counted at the conviction's `level`.
+	Standard { vote: Vote, balance: Balance, weighted_balance: Balance, level: ConvictionLevel },
 	/// A split vote with balances given for both ways, and with no conviction, useful for
-	/// parachains when voting.
-	Split { aye: Balance, nay: Balance, aye_weight: Balance, nay_weight: Balance},
+	/// parachains when voting. It is counted at the `level` of `Conviction::None`.
+	Split { aye: Balance, nay: Balance, aye_weight: Balance, nay_weight: Balance, level: ConvictionLevel },
+	/// A one-way vote of `votes` bought with voice credits in credit period `period`. It commits
+	/// no balance.
+	Credits { aye: bool, votes: Balance, period: u32 },
 }
 
 impl<Balance: Saturating> AccountVote<Balance> {
-	/// Returns `Some` of the lock periods that the account is locked for, assuming that the
-	/// referendum passed iff `approved` is `true`.
-	pub fn locked_if(self, approved: bool) -> Option<(u32, Balance)> {
-		// winning side: can only be removed after the lock period ends.
-		match self {
-			AccountVote::Standard { vote, balance } if vote.aye == approved =>
-				Some((vote.conviction.lock_periods(), balance)),
-			_ => None,
-		}
-	}
-
 	/// The total balance involved in this vote.
 	pub fn balance(self) -> Balance {
 		match self {
@@ -98,22 +90,22 @@ impl<Balance: Saturating> AccountVote<Balance> {
 	}
 }
 
-impl<Balance: Saturating> AccountVoteWeight<Balance> {
+impl<Balance: Saturating + Zero> AccountVoteWeight<Balance> {
 	/// Returns `Some` with whether the vote is an aye vote if it is standard, otherwise `None` if
-	/// it is split.
+	/// it is split or bought with credits.
 	pub fn as_standard(self) -> Option<bool> {
 		match self {
 			AccountVoteWeight::Standard { vote, .. } => Some(vote.aye),
 			_ => None,
 		}
 	}
-	/// Returns `Some` of the lock periods that the account is locked for, assuming that the
-	/// referendum passed iff `approved` is `true`.
+	/// Returns `Some` of the lock periods that the account is locked for at the level the vote
+	/// was counted at, assuming that the referendum passed iff `approved` is `true`.
 	pub fn locked_if(self, approved: bool) -> Option<(u32, Balance)> {
 		// winning side: can only be removed after the lock period ends.
 		match self {
-			AccountVoteWeight::Standard { vote, balance, .. } if vote.aye == approved =>
-				Some((vote.conviction.lock_periods(), balance)),
+			AccountVoteWeight::Standard { vote, balance, level, .. } if vote.aye == approved =>
+				Some((level.lock_periods, balance)),
 			_ => None,
 		}
 	}
@@ -123,8 +115,61 @@ impl<Balance: Saturating> AccountVoteWeight<Balance> {
 		match self {
 			AccountVoteWeight::Standard { balance, .. } => balance,
 			AccountVoteWeight::Split { aye, nay, .. } => aye.saturating_add(nay),
+			AccountVoteWeight::Credits { .. } => Zero::zero(),
 		}
 	}
+
+	/// The conviction the vote is counted with. Split votes and votes bought with credits carry
+	/// none.
+	pub fn conviction(self) -> Conviction {
+		match self {
+			AccountVoteWeight::Standard { vote, .. } => vote.conviction,
+			_ => Conviction::None,
+		}
+	}
+}
+
+/// The voice credits an account has spent in its latest credit period.
+#[derive(Encode, Decode, Default, Copy, Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct VoiceCredits<Balance> {
+	/// The index of the credit period.
+	period: u32,
+	/// The credits spent in that period.
+	spent: Balance,
+}
+
+impl<Balance: Saturating + Ord + Zero + Copy> VoiceCredits<Balance> {
+	/// Forget the credits spent before credit period `period`.
+	pub fn rejig(&mut self, period: u32) {
+		if period > self.period {
+			self.period = period;
+			self.spent = Zero::zero();
+		}
+	}
+
+	/// Spend `cost` credits, if that stays within `allowance`.
+	pub fn spend(&mut self, cost: Balance, allowance: Balance) -> Option<()> {
+		let spent = self.spent.saturating_add(cost);
+		if spent > allowance {
+			return None;
+		}
+		self.spent = spent;
+		Some(())
+	}
+
+	/// Refund the credits paid for `vote` if it was bought in the current credit period; credits
+	/// of past periods have expired anyway.
+	pub fn refund(&mut self, vote: AccountVoteWeight<Balance>) {
+		if let AccountVoteWeight::Credits { votes, period, .. } = vote {
+			if period == self.period {
+				self.spent = self.spent.saturating_sub(votes.saturating_mul(votes));
+			}
+		}
+	}
+
+	pub fn spent(&self) -> Balance {
+		self.spent
+	}
 }
 
 /// A "prior" lock, i.e. a lock for some now-forgotten reason.
@@ -162,12 +207,16 @@ pub enum Voting<Balance, AccountId, BlockNumber> {
 		delegations: Delegations<Balance>,
 		/// Any pre-existing locks from past voting/delegating activity.
 		prior: PriorLock<BlockNumber, Balance>,
+		/// The voice credits spent on votes in credit referenda.
+		credits: VoiceCredits<Balance>,
 	},
-	/// The account is delegating `balance` of its balance to a `target` account with `conviction`.
+	/// The account is delegating `balance` of its balance to a `target` account with `conviction`,
+	/// counted at the conviction's `level`.
 	Delegating {
 		balance: Balance,
 		target: AccountId,
 		conviction: Conviction,
+		level: ConvictionLevel,
 		/// The total amount of delegations that this account has received.
 			delegations: Delegations<Balance>,
 		/// Any pre-existing locks from past voting/delegating activity.
@@ -181,6 +230,7 @@ impl<Balance: Default, AccountId, BlockNumber: Zero> Default for Voting<Balance,
 			votes: Vec::new(),
 			delegations: Default::default(),
 			prior: PriorLock(Zero::zero(), Default::default()),
+			credits: Default::default(),
 		}
 	}
 }
@@ -19,9 +19,9 @@
 #[cfg(feature = "std")]
 use serde::{Serialize, Deserialize};
 use codec::{Encode, Decode};
-use sp_runtime::traits::{Zero, IntegerSquareRoot};
+use sp_runtime::{Perbill, PerThing, traits::{Zero, IntegerSquareRoot, Saturating}};
 use sp_std::ops::{Add, Mul, Div, Rem};
-use crate::Tally;
+use crate::{Tally, VoteWeight};
 
 /// A means of determining if a vote is past pass threshold.
 #[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, sp_runtime::RuntimeDebug)]
@@ -33,12 +33,42 @@ pub enum VoteThreshold {
 	SuperMajorityAgainst,
 	/// A simple majority of approvals is needed to pass this vote.
 	SimpleMajority,
+	/// A simple majority of approvals is needed to pass this vote, and it fails whatever the
+	/// ratio unless at least `voters` accounts voted and the weighted turnout, the sum of each
+	/// voter's weighted balance, is at least `turnout` of the weighted electorate.
+	Quorum { voters: u32, turnout: Perbill },
+}
+
+/// Why a referendum wasn't approved.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, sp_runtime::RuntimeDebug)]
+pub enum Rejection {
+	/// The votes fell short of the threshold.
+	Outvoted,
+	/// Fewer accounts voted than the quorum requires.
+	TooFewVoters,
+	/// The weighted turnout fell short of the quorum.
+	LowTurnout,
+	/// No option of a multi-option referendum had the most votes.
+	NoWinner,
 }
 
 pub trait Approved<Balance> {
-	/// Given a `tally` of votes and a total size of `electorate`, this returns `true` if the
-	/// overall outcome is in favor of approval according to `self`'s threshold method.
-	fn approved(&self, tally: Tally<Balance>, electorate: Balance) -> bool;
+	/// Given a `tally` of votes of a referendum weighted by `weight` and a weighted `electorate`,
+	/// this returns `true` if the overall outcome is in favor of approval according to `self`'s
+	/// threshold method.
+	///
+	/// The turnout bias compares the tally's `weighted_turnout`, the sum of each voter's weighted
+	/// balance, against `electorate`, the weighted capital of everyone who may vote. Both are on
+	/// the same scale as the weighted ayes and nays, so that e.g. many small voters make a higher
+	/// weighted turnout in a quadratic referendum than one large voter with the same capital.
+	/// Credit referenda have no turnout in capital and are decided by a simple majority; only the
+	/// number of voters of a quorum applies to them.
+	fn approved(&self, tally: Tally<Balance>, electorate: Balance, weight: VoteWeight) -> bool {
+		self.rejection(tally, electorate, weight).is_none()
+	}
+
+	/// As `approved`, but giving the reason the referendum fails, or `None` if it is approved.
+	fn rejection(&self, tally: Tally<Balance>, electorate: Balance, weight: VoteWeight) -> Option<Rejection>;
 }
 
 /// Return `true` iff `n1 / d1 < n2 / d2`. `d1` and `d2` may not be zero.
@@ -72,29 +102,195 @@ fn compare_rationals<T: Zero + Mul<T, Output = T> + Div<T, Output = T> + Rem<T,
 impl<
 	Balance: IntegerSquareRoot + Zero + Ord + Add<Balance, Output = Balance>
 		+ Mul<Balance, Output = Balance> + Div<Balance, Output = Balance>
-		+ Rem<Balance, Output = Balance> + Copy,
+		+ Rem<Balance, Output = Balance> + From<u8> + From<u32> + Saturating + Copy,
 > Approved<Balance> for VoteThreshold {
-	fn approved(&self, tally: Tally<Balance>, electorate: Balance) -> bool {
-		let sqrt_voters = tally.turnout.integer_sqrt();
-		let sqrt_electorate = electorate.integer_sqrt();
-		if sqrt_voters.is_zero() { return false; }
-		match *self {
-			VoteThreshold::SuperMajorityApprove =>
-				compare_rationals(tally.nays, sqrt_voters, tally.ayes, sqrt_electorate),
-			VoteThreshold::SuperMajorityAgainst =>
-				compare_rationals(tally.nays, sqrt_electorate, tally.ayes, sqrt_voters),
-			VoteThreshold::SimpleMajority => tally.ayes > tally.nays,
+	fn rejection(&self, tally: Tally<Balance>, electorate: Balance, weight: VoteWeight) -> Option<Rejection> {
+		if let VoteThreshold::Quorum { voters, turnout } = *self {
+			if tally.voters < voters {
+				return Some(Rejection::TooFewVoters);
+			}
+			let short = electorate.is_zero() || compare_rationals(
+				tally.weighted_turnout,
+				electorate,
+				turnout.deconstruct().into(),
+				Perbill::ACCURACY.into(),
+			);
+			if weight != VoteWeight::Credits && !turnout.is_zero() && short {
+				return Some(Rejection::LowTurnout);
+			}
 		}
+
+		let approved = if weight == VoteWeight::Credits {
+			// Credit votes commit no capital, so there is no turnout to bias by.
+			tally.ayes > tally.nays
+		} else {
+			let sqrt_voters = tally.weighted_turnout.integer_sqrt();
+			let sqrt_electorate = electorate.integer_sqrt();
+			!sqrt_voters.is_zero() && !sqrt_electorate.is_zero() && match *self {
+				VoteThreshold::SuperMajorityApprove =>
+					compare_rationals(tally.nays, sqrt_voters, tally.ayes, sqrt_electorate),
+				VoteThreshold::SuperMajorityAgainst =>
+					compare_rationals(tally.nays, sqrt_electorate, tally.ayes, sqrt_voters),
+				VoteThreshold::SimpleMajority | VoteThreshold::Quorum { .. } => tally.ayes > tally.nays,
+			}
+		};
+		if approved { None } else { Some(Rejection::Outvoted) }
 	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::{AccountVote, Calculate, Conviction, ConvictionSchedule, Vote};
+
+	const THRESHOLDS: [VoteThreshold; 3] = [
+		VoteThreshold::SuperMajorityApprove,
+		VoteThreshold::SuperMajorityAgainst,
+		VoteThreshold::SimpleMajority,
+	];
+	const WEIGHTS: [VoteWeight; 2] = [VoteWeight::Standard, VoteWeight::Quadratic];
+
+	/// The total issuance, the weighted electorate of standard referenda.
+	const ISSUANCE: u64 = 10_000;
+	/// The weighted electorate of quadratic referenda: 100 persons sharing `ISSUANCE` equally.
+	const QUADRATIC_ELECTORATE: u64 = 1_000;
+
+	/// The tally of the `Locked1x` votes of the given balances in a referendum weighted by
+	/// `weight`, each from its own voter.
+	fn tally_of(weight: VoteWeight, votes: &[(bool, u64)]) -> Tally<u64> {
+		let level = ConvictionSchedule::standard().level(Conviction::Locked1x).unwrap();
+		let mut tally = Tally::default();
+		for &(aye, balance) in votes.iter() {
+			let vote = Vote { aye, conviction: Conviction::Locked1x };
+			tally.add(weight.calculate(AccountVote::Standard { vote, balance }, level)).unwrap();
+		}
+		tally
+	}
+
+	fn voters(count: usize, aye: bool, balance: u64) -> Vec<(bool, u64)> {
+		vec![(aye, balance); count]
+	}
+
+	fn tally(ayes: u64, nays: u64, turnout: u64) -> Tally<u64> {
+		Tally { ayes, nays, turnout, weighted_turnout: turnout, voters: 0 }
+	}
 
 	#[test]
 	fn should_work() {
-		assert!(!VoteThreshold::SuperMajorityApprove.approved(Tally{ayes: 60, nays: 50, turnout: 110}, 210));
-		assert!(VoteThreshold::SuperMajorityApprove.approved(Tally{ayes: 100, nays: 50, turnout: 150}, 210));
+		assert!(!VoteThreshold::SuperMajorityApprove.approved(tally(60, 50, 110), 210, VoteWeight::Standard));
+		assert!(VoteThreshold::SuperMajorityApprove.approved(tally(100, 50, 150), 210, VoteWeight::Standard));
+	}
+
+	#[test]
+	fn credits_are_decided_by_simple_majority() {
+		for &threshold in THRESHOLDS.iter() {
+			assert!(threshold.approved(tally(6, 5, 0), 210, VoteWeight::Credits));
+			assert!(!threshold.approved(tally(5, 5, 0), 210, VoteWeight::Credits));
+		}
+	}
+
+	#[test]
+	fn many_small_voters_make_a_higher_weighted_turnout_than_one_large_voter() {
+		// The same 2_500 units of capital turn out either way.
+		let many = voters(25, true, 100);
+		let large = voters(1, true, 2_500);
+		for votes in [&many, &large].iter() {
+			assert_eq!(tally_of(VoteWeight::Standard, votes).weighted_turnout, 2_500);
+			assert_eq!(tally_of(VoteWeight::Quadratic, votes).turnout, 2_500);
+		}
+		assert_eq!(tally_of(VoteWeight::Quadratic, &many).weighted_turnout, 250);
+		assert_eq!(tally_of(VoteWeight::Quadratic, &large).weighted_turnout, 50);
+	}
+
+	#[test]
+	fn many_small_voters_outweigh_one_large_voter_only_in_quadratic_referenda() {
+		// 16 small voters for, against one voter of 900 units.
+		let mut many = voters(16, true, 100);
+		many.push((false, 900));
+		// One voter of 1_600 units for, against 9 small voters.
+		let mut large = voters(1, true, 1_600);
+		large.extend(voters(9, false, 100));
+		let threshold = VoteThreshold::SuperMajorityApprove;
+
+		// Coin-weighted, both are 1_600 ayes against 900 nays at a quarter turnout: rejected.
+		for votes in [&many, &large].iter() {
+			let standard = tally_of(VoteWeight::Standard, votes);
+			assert_eq!((standard.ayes, standard.nays, standard.weighted_turnout), (1_600, 900, 2_500));
+			assert!(!threshold.approved(standard, ISSUANCE, VoteWeight::Standard));
+		}
+
+		// Quadratically, the small voters carry the referendum: 160 ayes against 30 nays over a
+		// weighted turnout of 190, measured against a weighted electorate of 1_000...
+		let quadratic = tally_of(VoteWeight::Quadratic, &many);
+		assert_eq!((quadratic.ayes, quadratic.nays, quadratic.weighted_turnout), (160, 30, 190));
+		assert!(threshold.approved(quadratic, QUADRATIC_ELECTORATE, VoteWeight::Quadratic));
+		// ...while they turn the large voter down: 40 ayes against 90 nays.
+		let quadratic = tally_of(VoteWeight::Quadratic, &large);
+		assert_eq!((quadratic.ayes, quadratic.nays, quadratic.weighted_turnout), (40, 90, 130));
+		assert!(!threshold.approved(quadratic, QUADRATIC_ELECTORATE, VoteWeight::Quadratic));
+	}
+
+	#[test]
+	fn more_ayes_never_hurts_and_more_nays_never_helps() {
+		for &threshold in THRESHOLDS.iter() {
+			for &weight in WEIGHTS.iter() {
+				for turnout in (1u64..=400).step_by(13) {
+					let electorate = 400;
+					for ayes in 0u64..20 {
+						for nays in 0u64..20 {
+							let base = threshold.approved(tally(ayes, nays, turnout), electorate, weight);
+							let more_ayes = threshold.approved(tally(ayes + 1, nays, turnout), electorate, weight);
+							let more_nays = threshold.approved(tally(ayes, nays + 1, turnout), electorate, weight);
+							assert!(!base || more_ayes);
+							assert!(base || !more_nays);
+						}
+					}
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn quorum_floors_voters_and_weighted_turnout() {
+		let quorum = VoteThreshold::Quorum { voters: 3, turnout: Perbill::from_percent(10) };
+		let tally = |voters, weighted_turnout| Tally { ayes: 10, nays: 5, turnout: 0, weighted_turnout, voters };
+		assert_eq!(quorum.rejection(tally(2, 1_000), 1_000, VoteWeight::Standard), Some(Rejection::TooFewVoters));
+		assert_eq!(quorum.rejection(tally(3, 99), 1_000, VoteWeight::Standard), Some(Rejection::LowTurnout));
+		assert_eq!(quorum.rejection(tally(3, 100), 1_000, VoteWeight::Standard), None);
+		// Credit votes commit no capital, so only the number of voters is floored.
+		assert_eq!(quorum.rejection(tally(2, 0), 1_000, VoteWeight::Credits), Some(Rejection::TooFewVoters));
+		assert_eq!(quorum.rejection(tally(3, 0), 1_000, VoteWeight::Credits), None);
+		// Once the quorum is met, a simple majority decides.
+		let tied = Tally { ayes: 5, nays: 5, turnout: 0, weighted_turnout: 1_000, voters: 3 };
+		assert_eq!(quorum.rejection(tied, 1_000, VoteWeight::Standard), Some(Rejection::Outvoted));
+	}
+
+	#[test]
+	fn many_small_voters_meet_a_quorum_that_one_large_voter_does_not() {
+		let quorum = VoteThreshold::Quorum { voters: 1, turnout: Perbill::from_percent(20) };
+		// 250 out of 1_000.
+		let many = tally_of(VoteWeight::Quadratic, &voters(25, true, 100));
+		assert_eq!(quorum.rejection(many, QUADRATIC_ELECTORATE, VoteWeight::Quadratic), None);
+		// 50 out of 1_000, for the same capital.
+		let large = tally_of(VoteWeight::Quadratic, &voters(1, true, 2_500));
+		assert_eq!(
+			quorum.rejection(large, QUADRATIC_ELECTORATE, VoteWeight::Quadratic),
+			Some(Rejection::LowTurnout),
+		);
+		// Coin-weighted, both are 2_500 out of 10_000.
+		for votes in [voters(25, true, 100), voters(1, true, 2_500)].iter() {
+			let standard = tally_of(VoteWeight::Standard, votes);
+			assert_eq!(quorum.rejection(standard, ISSUANCE, VoteWeight::Standard), None);
+		}
+	}
+
+	#[test]
+	fn no_turnout_never_passes() {
+		for &threshold in THRESHOLDS.iter() {
+			for &weight in WEIGHTS.iter() {
+				assert!(!threshold.approved(tally(0, 0, 0), 1_000, weight));
+				assert!(!threshold.approved(tally(1, 0, 1), 0, weight));
+			}
+		}
 	}
 }
@@ -19,11 +19,22 @@
 #[cfg(feature = "std")]
 use serde::{Serialize, Deserialize};
 use codec::{Encode, Decode};
-use sp_runtime::traits::{Zero, IntegerSquareRoot};
-use crate::{AccountVote, Delegations};
+use sp_runtime::traits::{Zero, IntegerSquareRoot, Saturating};
+use crate::{AccountVote, ConvictionLevel, Delegations};
 use crate::vote::AccountVoteWeight;
 
 
+/// How the votes delegated to an account are weighted in a quadratic referendum.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, sp_runtime::RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum DelegationWeighting {
+	/// All delegated votes are added up and the square root is taken of the total.
+	RootOfSum,
+	/// Each delegator's votes are weighted on its own capital, as if it had voted directly, and
+	/// the results are added up.
+	SumOfRoots,
+}
+
 /// A means of determining what is weight of the vote.
 #[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, sp_runtime::RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -32,18 +43,41 @@ pub enum VoteWeight {
 	Standard,
 	/// Quadratic way of calculating vote weight
 	Quadratic,
+	/// Votes are bought with a per-period allowance of voice credits, `n` votes costing `n²`
+	/// credits, rather than weighed on balance. Balance and delegations carry no weight.
+	Credits,
 }
 
 pub trait Calculate<Balance> {
-	fn calculate(&self, vote: AccountVote<Balance>) -> AccountVoteWeight<Balance>;
-	fn delegation(&self, vote: Delegations<Balance>) -> Delegations<Balance>;
+	/// The weight of `vote`, to be counted at `level`: that of the vote's conviction if it is
+	/// standard, otherwise that of `Conviction::None`.
+	fn calculate(&self, vote: AccountVote<Balance>, level: ConvictionLevel) -> AccountVoteWeight<Balance>;
+	/// Like `calculate`, but under quadratic weighting the `linked` capital held by the voter's
+	/// sub-accounts is added to a standard vote's balance before its square root is taken.
+	fn calculate_linked(
+		&self,
+		vote: AccountVote<Balance>,
+		linked: Balance,
+		level: ConvictionLevel,
+	) -> AccountVoteWeight<Balance>;
+	/// The weighted votes of the `delegations` an account has received, according to `policy`.
+	fn delegation(&self, delegations: Delegations<Balance>, policy: DelegationWeighting) -> Delegations<Balance>;
 	fn calculate_weight(&self, amount: Balance) -> Balance;
 }
 
 impl<
-	Balance: From<u8> + Zero + Copy + IntegerSquareRoot
+	Balance: From<u8> + Zero + Copy + IntegerSquareRoot + Saturating
 > Calculate<Balance> for VoteWeight {
-	fn calculate(&self, vote: AccountVote<Balance>) -> AccountVoteWeight<Balance> {
+	fn calculate(&self, vote: AccountVote<Balance>, level: ConvictionLevel) -> AccountVoteWeight<Balance> {
+		self.calculate_linked(vote, Zero::zero(), level)
+	}
+
+	fn calculate_linked(
+		&self,
+		vote: AccountVote<Balance>,
+		linked: Balance,
+		level: ConvictionLevel,
+	) -> AccountVoteWeight<Balance> {
 		match *self {
 			// VoteWeight::Standard => vote,
 			VoteWeight::Quadratic => {
@@ -52,7 +86,8 @@ impl<
 						AccountVoteWeight::Standard {
 							vote,
 							balance,
-							weighted_balance: balance.integer_sqrt(),
+							weighted_balance: balance.saturating_add(linked).integer_sqrt(),
+							level,
 						}
 					},
 					AccountVote::Split { aye, nay } => {
@@ -61,6 +96,7 @@ impl<
 							nay,
 							aye_weight: aye.integer_sqrt(),
 							nay_weight: nay.integer_sqrt(),
+							level,
 						}
 					}
 				}
@@ -72,6 +108,7 @@ impl<
 							vote,
 							balance,
 							weighted_balance: balance,
+							level,
 						}
 					},
 					AccountVote::Split { aye, nay } => {
@@ -80,6 +117,28 @@ impl<
 							nay,
 							aye_weight: aye,
 							nay_weight: nay,
+							level,
+						}
+					}
+				}
+			}
+			VoteWeight::Credits => {
+				match vote {
+					AccountVote::Standard { vote, balance } => {
+						AccountVoteWeight::Standard {
+							vote,
+							balance,
+							weighted_balance: Zero::zero(),
+							level,
+						}
+					},
+					AccountVote::Split { aye, nay } => {
+						AccountVoteWeight::Split {
+							aye,
+							nay,
+							aye_weight: Zero::zero(),
+							nay_weight: Zero::zero(),
+							level,
 						}
 					}
 				}
@@ -87,20 +146,36 @@ impl<
 		}
 	}
 
-	fn delegation(&self, delegation: Delegations<Balance>) -> Delegations<Balance> {
-		let Delegations {capital, votes} = delegation;
+	fn delegation(&self, delegation: Delegations<Balance>, policy: DelegationWeighting) -> Delegations<Balance> {
+		let Delegations {capital, votes, quadratic_votes, weighted_capital} = delegation;
 		match *self {
 			// VoteWeight::Standard => vote,
 			VoteWeight::Quadratic => {
+				let (votes, weighted_capital) = match policy {
+					DelegationWeighting::RootOfSum => (votes.integer_sqrt(), capital.integer_sqrt()),
+					DelegationWeighting::SumOfRoots => (quadratic_votes, weighted_capital),
+				};
 				Delegations {
 					capital,
-					votes: votes.integer_sqrt()
+					votes,
+					quadratic_votes,
+					weighted_capital,
 				}
 			}
 			VoteWeight::Standard => {
 				Delegations {
 					capital,
-					votes
+					votes,
+					quadratic_votes,
+					weighted_capital: capital,
+				}
+			}
+			VoteWeight::Credits => {
+				Delegations {
+					capital: Zero::zero(),
+					votes: Zero::zero(),
+					quadratic_votes: Zero::zero(),
+					weighted_capital: Zero::zero(),
 				}
 			}
 		}
@@ -115,6 +190,7 @@ impl<
 			VoteWeight::Standard => {
 				amount
 			}
+			VoteWeight::Credits => Zero::zero(),
 		}
 	}
 }
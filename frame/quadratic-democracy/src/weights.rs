@@ -0,0 +1,132 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The weights of the calls of this pallet.
+
+use frame_support::weights::Weight;
+
+/// The weight of each call, or each path through a call, as measured by the benchmark of the
+/// same name in `benchmarking.rs`.
+///
+/// The parameters are the components of the benchmark:
+/// - `p`: the number of public proposals;
+/// - `s`: the number of seconds of the proposal;
+/// - `r`: the number of referenda that the voter (or its delegation target) has voted on;
+/// - `v`: the number of vetoers of the external proposal;
+/// - `b`: the length of the preimage in bytes;
+/// - `o`: the number of options of a multi-option referendum;
+/// - `l`: the number of sub-accounts linked to the voter.
+///
+/// The runtime should plug in weights generated with `benchmark` on its own hardware.
+pub trait WeightInfo {
+	fn propose(p: u32) -> Weight;
+	fn second(s: u32) -> Weight;
+	fn vote_new(r: u32, l: u32) -> Weight;
+	fn vote_existing(r: u32, l: u32) -> Weight;
+	fn proxy_vote_new(r: u32, l: u32) -> Weight;
+	fn proxy_vote_existing(r: u32, l: u32) -> Weight;
+	fn emergency_cancel(r: u32) -> Weight;
+	fn external_propose(p: u32) -> Weight;
+	fn external_propose_majority(p: u32) -> Weight;
+	fn external_propose_default(p: u32) -> Weight;
+	fn external_propose_with_weight(p: u32) -> Weight;
+	fn external_propose_majority_with_weight(p: u32) -> Weight;
+	fn external_propose_default_with_weight(p: u32) -> Weight;
+	fn external_propose_quorum(p: u32) -> Weight;
+	fn fast_track(p: u32) -> Weight;
+	fn veto_external(v: u32) -> Weight;
+	fn cancel_referendum(r: u32) -> Weight;
+	fn cancel_queued(r: u32) -> Weight;
+	fn on_initialize_external(r: u32) -> Weight;
+	fn on_initialize_public(r: u32) -> Weight;
+	fn delegate(r: u32) -> Weight;
+	fn undelegate(r: u32) -> Weight;
+	fn delegate_class(r: u32) -> Weight;
+	fn undelegate_class(r: u32) -> Weight;
+	fn clear_public_proposals(p: u32) -> Weight;
+	fn note_preimage(b: u32) -> Weight;
+	fn note_imminent_preimage(b: u32) -> Weight;
+	fn reap_preimage(b: u32) -> Weight;
+	fn unlock_remove(r: u32) -> Weight;
+	fn unlock_set(r: u32) -> Weight;
+	fn remove_vote(r: u32) -> Weight;
+	fn remove_other_vote(r: u32) -> Weight;
+	fn proxy_delegate(r: u32) -> Weight;
+	fn proxy_undelegate(r: u32) -> Weight;
+	fn proxy_remove_vote(r: u32) -> Weight;
+	fn vote_with_credits(r: u32) -> Weight;
+	fn start_multi_option_referendum(o: u32) -> Weight;
+	fn vote_options(o: u32) -> Weight;
+	fn external_propose_secret(p: u32) -> Weight;
+	fn commit_vote(r: u32) -> Weight;
+	fn reveal_vote(r: u32, l: u32) -> Weight;
+	fn prune_referendum(r: u32) -> Weight;
+	fn reap_commitments(c: u32) -> Weight;
+	fn reap_multi_option_votes(v: u32) -> Weight;
+	fn link_sub_account(r: u32) -> Weight;
+	fn unlink_sub_account(r: u32) -> Weight;
+}
+
+/// A flat weight for every call, for tests and chains that don't care about the weight of
+/// governance.
+impl WeightInfo for () {
+	fn propose(_p: u32) -> Weight { 1_000_000_000 }
+	fn second(_s: u32) -> Weight { 1_000_000_000 }
+	fn vote_new(_r: u32, _l: u32) -> Weight { 1_000_000_000 }
+	fn vote_existing(_r: u32, _l: u32) -> Weight { 1_000_000_000 }
+	fn proxy_vote_new(_r: u32, _l: u32) -> Weight { 1_000_000_000 }
+	fn proxy_vote_existing(_r: u32, _l: u32) -> Weight { 1_000_000_000 }
+	fn emergency_cancel(_r: u32) -> Weight { 1_000_000_000 }
+	fn external_propose(_p: u32) -> Weight { 1_000_000_000 }
+	fn external_propose_majority(_p: u32) -> Weight { 1_000_000_000 }
+	fn external_propose_default(_p: u32) -> Weight { 1_000_000_000 }
+	fn external_propose_with_weight(_p: u32) -> Weight { 1_000_000_000 }
+	fn external_propose_majority_with_weight(_p: u32) -> Weight { 1_000_000_000 }
+	fn external_propose_default_with_weight(_p: u32) -> Weight { 1_000_000_000 }
+	fn external_propose_quorum(_p: u32) -> Weight { 1_000_000_000 }
+	fn fast_track(_p: u32) -> Weight { 1_000_000_000 }
+	fn veto_external(_v: u32) -> Weight { 1_000_000_000 }
+	fn cancel_referendum(_r: u32) -> Weight { 1_000_000_000 }
+	fn cancel_queued(_r: u32) -> Weight { 1_000_000_000 }
+	fn on_initialize_external(_r: u32) -> Weight { 1_000_000_000 }
+	fn on_initialize_public(_r: u32) -> Weight { 1_000_000_000 }
+	fn delegate(_r: u32) -> Weight { 1_000_000_000 }
+	fn undelegate(_r: u32) -> Weight { 1_000_000_000 }
+	fn delegate_class(_r: u32) -> Weight { 1_000_000_000 }
+	fn undelegate_class(_r: u32) -> Weight { 1_000_000_000 }
+	fn clear_public_proposals(_p: u32) -> Weight { 1_000_000_000 }
+	fn note_preimage(_b: u32) -> Weight { 1_000_000_000 }
+	fn note_imminent_preimage(_b: u32) -> Weight { 1_000_000_000 }
+	fn reap_preimage(_b: u32) -> Weight { 1_000_000_000 }
+	fn unlock_remove(_r: u32) -> Weight { 1_000_000_000 }
+	fn unlock_set(_r: u32) -> Weight { 1_000_000_000 }
+	fn remove_vote(_r: u32) -> Weight { 1_000_000_000 }
+	fn remove_other_vote(_r: u32) -> Weight { 1_000_000_000 }
+	fn proxy_delegate(_r: u32) -> Weight { 1_000_000_000 }
+	fn proxy_undelegate(_r: u32) -> Weight { 1_000_000_000 }
+	fn proxy_remove_vote(_r: u32) -> Weight { 1_000_000_000 }
+	fn vote_with_credits(_r: u32) -> Weight { 1_000_000_000 }
+	fn start_multi_option_referendum(_o: u32) -> Weight { 1_000_000_000 }
+	fn vote_options(_o: u32) -> Weight { 1_000_000_000 }
+	fn external_propose_secret(_p: u32) -> Weight { 1_000_000_000 }
+	fn commit_vote(_r: u32) -> Weight { 1_000_000_000 }
+	fn reveal_vote(_r: u32, _l: u32) -> Weight { 1_000_000_000 }
+	fn prune_referendum(_r: u32) -> Weight { 1_000_000_000 }
+	fn reap_commitments(_c: u32) -> Weight { 1_000_000_000 }
+	fn reap_multi_option_votes(_v: u32) -> Weight { 1_000_000_000 }
+	fn link_sub_account(_r: u32) -> Weight { 1_000_000_000 }
+	fn unlink_sub_account(_r: u32) -> Weight { 1_000_000_000 }
+}
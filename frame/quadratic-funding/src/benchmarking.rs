@@ -0,0 +1,172 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Quadratic funding pallet benchmarking.
+
+use super::*;
+
+use frame_benchmarking::{benchmarks, account};
+use frame_support::traits::{Currency, Get, EnsureOrigin};
+use frame_system::{RawOrigin, Module as System};
+use sp_runtime::traits::{Bounded, Dispatchable, One};
+
+use crate::Module as QuadraticFunding;
+
+const SEED: u32 = 0;
+const MAX_CONTRIBUTIONS: u32 = 1_000;
+
+fn funded_account<T: Trait>(name: &'static str, index: u32) -> T::AccountId {
+	let caller: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 1_000u32.into());
+	caller
+}
+
+/// Start a round, as if the treasury had spent its matching to the funding account, and
+/// register `p` projects in it.
+fn add_round<T: Trait>(p: u32) -> Result<RoundIndex, &'static str> {
+	let matching = T::Currency::minimum_balance() * 1_000u32.into();
+	T::Currency::make_free_balance_be(
+		&QuadraticFunding::<T>::account_id(),
+		QuadraticFunding::<T>::held() + matching,
+	);
+	QuadraticFunding::<T>::start_round(T::RoundOrigin::successful_origin(), matching, 10u32.into())?;
+	let round = RoundCount::get() - 1;
+	for i in 0 .. p {
+		let owner = funded_account::<T>("owner", i);
+		let beneficiary = T::Lookup::unlookup(account("beneficiary", i, SEED));
+		QuadraticFunding::<T>::register_project(RawOrigin::Signed(owner).into(), round, beneficiary)?;
+	}
+	Ok(round)
+}
+
+fn contribute<T: Trait>(round: RoundIndex, project: ProjectIndex, i: u32) -> Result<(), &'static str> {
+	let contributor = funded_account::<T>("contributor", i);
+	QuadraticFunding::<T>::contribute(
+		RawOrigin::Signed(contributor).into(),
+		round,
+		project,
+		T::MinimumContribution::get().max(T::Currency::minimum_balance()),
+		Conviction::Locked1x,
+	)?;
+	Ok(())
+}
+
+benchmarks! {
+	_ { }
+
+	start_round {
+		let matching = T::Currency::minimum_balance() * 1_000u32.into();
+		T::Currency::make_free_balance_be(
+			&QuadraticFunding::<T>::account_id(),
+			QuadraticFunding::<T>::held() + matching,
+		);
+		let round = RoundCount::get();
+		let origin = T::RoundOrigin::successful_origin();
+		let call = Call::<T>::start_round(matching, 10u32.into());
+	}: { call.dispatch(origin)? }
+	verify {
+		ensure!(QuadraticFunding::<T>::rounds(round).is_some(), "Round not started");
+	}
+
+	register_project {
+		let round = add_round::<T>(0)?;
+		let owner = funded_account::<T>("owner", 0);
+		let beneficiary = T::Lookup::unlookup(account("beneficiary", 0, SEED));
+	}: _(RawOrigin::Signed(owner), round, beneficiary)
+	verify {
+		ensure!(QuadraticFunding::<T>::projects(round, 0).is_some(), "Project not registered");
+	}
+
+	contribute {
+		let round = add_round::<T>(1)?;
+		// Adding to an earlier contribution with a higher conviction is the heavier path.
+		let contributor = funded_account::<T>("contributor", 0);
+		let amount = T::MinimumContribution::get().max(T::Currency::minimum_balance());
+		QuadraticFunding::<T>::contribute(
+			RawOrigin::Signed(contributor.clone()).into(),
+			round,
+			0,
+			amount,
+			Conviction::None,
+		)?;
+	}: _(RawOrigin::Signed(contributor.clone()), round, 0, amount, Conviction::Locked1x)
+	verify {
+		ensure!(QuadraticFunding::<T>::locks(&contributor).is_some(), "Contribution not locked");
+	}
+
+	finalize_round {
+		let p in 0 .. T::MaxProjects::get();
+
+		let round = add_round::<T>(p)?;
+		for i in 0 .. p {
+			contribute::<T>(round, i, i)?;
+		}
+		System::<T>::set_block_number(System::<T>::block_number() + 10u32.into() + One::one());
+		let caller = funded_account::<T>("caller", 0);
+	}: _(RawOrigin::Signed(caller), round)
+	verify {
+		ensure!(QuadraticFunding::<T>::rounds(round).is_none(), "Round not finalized");
+	}
+
+	reap_contributions {
+		let c in 0 .. MAX_CONTRIBUTIONS;
+
+		let round = add_round::<T>(1)?;
+		for i in 0 .. c {
+			contribute::<T>(round, 0, i)?;
+		}
+		System::<T>::set_block_number(System::<T>::block_number() + 10u32.into() + One::one());
+		let caller = funded_account::<T>("caller", 0);
+		QuadraticFunding::<T>::finalize_round(RawOrigin::Signed(caller.clone()).into(), round)?;
+	}: _(RawOrigin::Signed(caller), round, 0, c)
+	verify {
+		ensure!(
+			Contributions::<T>::iter_prefix((round, 0)).next().is_none(),
+			"Contributions not reaped",
+		);
+	}
+
+	unlock {
+		let round = add_round::<T>(1)?;
+		contribute::<T>(round, 0, 0)?;
+		let contributor: T::AccountId = account("contributor", 0, SEED);
+		let (until, _) = QuadraticFunding::<T>::locks(&contributor).ok_or("Contribution not locked")?;
+		System::<T>::set_block_number(until);
+		let caller = funded_account::<T>("caller", 0);
+	}: _(RawOrigin::Signed(caller), contributor.clone())
+	verify {
+		ensure!(QuadraticFunding::<T>::locks(&contributor).is_none(), "Lock not removed");
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_start_round::<Test>());
+			assert_ok!(test_benchmark_register_project::<Test>());
+			assert_ok!(test_benchmark_contribute::<Test>());
+			assert_ok!(test_benchmark_finalize_round::<Test>());
+			assert_ok!(test_benchmark_reap_contributions::<Test>());
+			assert_ok!(test_benchmark_unlock::<Test>());
+		});
+	}
+}
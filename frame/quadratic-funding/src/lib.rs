@@ -0,0 +1,624 @@
+// Copyright 2017-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Quadratic Funding Module
+//!
+//! The Quadratic Funding module funds public goods out of the treasury through rounds of
+//! contributions, in which a matching pool is split between projects by the breadth of their
+//! support rather than its depth.
+//!
+//! - [`quadratic_funding::Trait`](./trait.Trait.html)
+//! - [`Call`](./enum.Call.html)
+//!
+//! ## Overview
+//!
+//! Matching is paid for by treasury spends to the funding account. A round is started by the
+//! `RoundOrigin`, which commits funds of that account that no other round holds to the round's
+//! matching pool. While the round is open anyone may register a project by reserving a deposit and
+//! anyone recognised as a person may contribute to projects. Once the round has ended it is
+//! finalized by anyone: each project receives its contributions plus a share of the matching pool
+//! proportional to the square of its score, and whatever matching is left over goes back to the
+//! treasury. The contributions recorded for each project are then cleared a bounded number at a
+//! time by `reap_contributions`.
+//!
+//! A project's score is the sum over its contributors of the square root of their total
+//! contribution, as calculated by quadratic `VoteWeight` of `pallet-quadratic-democracy`. Matching
+//! is therefore split in proportion to (Σ√contribution)².
+//!
+//! ### Terminology
+//!
+//! - **Round:** A period during which projects collect contributions for a given matching pool.
+//! - **Matching pool:** The treasury funds split between the projects of a round.
+//! - **Funding account:** The account derived from the `ModuleId`, to which the treasury spends
+//!   matching and which holds the matching pools and contributions of rounds until they are
+//!   finalized.
+//! - **Score:** The sum of the post-conviction square roots of each contribution to a project.
+//! - **Conviction:** As in quadratic democracy, an indication of a contributor's strength of
+//!   belief in a project. Higher conviction multiplies a contribution's square root, and in return
+//!   the contributor keeps as much again as it contributed locked for longer after the round. The
+//!   locks of an account's contributions add up.
+//! - **Personhood:** The sybil check contributors must pass, since one person splitting a
+//!   contribution over many accounts would otherwise gain matching.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `start_round` - Start a round, committing its matching pool out of the funds spent to the
+//!   funding account by the treasury.
+//! - `register_project` - Register a project in an ongoing round, reserving a deposit.
+//! - `contribute` - Contribute to a project with some conviction.
+//! - `finalize_round` - Pay out the projects of a round that has ended.
+//! - `reap_contributions` - Clear the contributions to a project of a finalized round.
+//! - `unlock` - Remove an expired conviction lock.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::prelude::*;
+use codec::{Encode, Decode};
+use sp_runtime::{
+	Perbill, ModuleId, RuntimeDebug, DispatchError,
+	traits::{Zero, Saturating, StaticLookup, AccountIdConversion},
+};
+use frame_support::{
+	decl_module, decl_storage, decl_event, decl_error, ensure,
+	dispatch::DispatchResultWithPostInfo,
+	storage::IterableStorageDoubleMap,
+};
+use frame_support::traits::{
+	Currency, ReservableCurrency, LockableCurrency, LockIdentifier, WithdrawReason, Get,
+	EnsureOrigin, ExistenceRequirement::{KeepAlive, AllowDeath},
+};
+use frame_system::{self as system, ensure_signed};
+use pallet_quadratic_democracy::{Calculate, Conviction, ConvictionLevel, ConvictionSchedule, Personhood, VoteWeight};
+
+mod weights;
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+
+pub use weights::WeightInfo;
+
+const FUNDING_ID: LockIdentifier = *b"qfunding";
+
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
+/// A round index.
+pub type RoundIndex = u32;
+
+/// A project index, unique within its round.
+pub type ProjectIndex = u32;
+
+pub trait Trait: frame_system::Trait + Sized {
+	/// The overarching event type.
+	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+	/// The funding module id, used for deriving the account that holds contributions and matching
+	/// pools until their round is finalized.
+	type ModuleId: Get<ModuleId>;
+
+	/// Currency type for this module.
+	type Currency: ReservableCurrency<Self::AccountId>
+		+ LockableCurrency<Self::AccountId, Moment=Self::BlockNumber>;
+
+	/// The treasury account, to which unspent matching is returned. Matching is paid for by
+	/// treasury spends to the funding account.
+	type Treasury: Get<Self::AccountId>;
+
+	/// Origin from which a round may be started, committing its matching pool out of the funds
+	/// that the treasury has spent to the funding account.
+	type RoundOrigin: EnsureOrigin<Self::Origin>;
+
+	/// The amount reserved for registering a project; returned once its round is finalized.
+	type ProjectDeposit: Get<BalanceOf<Self>>;
+
+	/// The maximum number of projects in a single round.
+	type MaxProjects: Get<ProjectIndex>;
+
+	/// The minimum amount of a single contribution.
+	type MinimumContribution: Get<BalanceOf<Self>>;
+
+	/// The period, per lock period of conviction, for which a contributor's stake stays locked
+	/// after its round has ended.
+	type LockPeriod: Get<Self::BlockNumber>;
+
+	/// The lock periods and score multiplier of each conviction level.
+	type ConvictionSchedule: Get<ConvictionSchedule>;
+
+	/// Who counts as a person and may contribute.
+	type Personhood: Personhood<Self::AccountId>;
+
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+}
+
+/// Info regarding a round that has not been finalized yet.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct RoundInfo<Balance, BlockNumber> {
+	/// The matching pool, committed out of the funding account when the round started.
+	matching: Balance,
+	/// The block at which contributions close.
+	end: BlockNumber,
+	/// The number of projects registered in the round.
+	project_count: ProjectIndex,
+}
+
+/// Info regarding a project of a round.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ProjectInfo<AccountId, Balance> {
+	/// The account that registered the project and placed the deposit.
+	owner: AccountId,
+	/// The account that is paid out once the round is finalized.
+	beneficiary: AccountId,
+	/// The amount reserved from the owner.
+	deposit: Balance,
+	/// The total amount contributed to the project.
+	contributed: Balance,
+	/// The sum of the post-conviction square roots of each contributor's total contribution.
+	score: Balance,
+}
+
+/// The total contribution of an account to a project.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Contribution<Balance> {
+	/// The amount contributed.
+	amount: Balance,
+	/// The conviction of the contribution.
+	conviction: Conviction,
+	/// The level the contribution is scored and locked at.
+	level: ConvictionLevel,
+}
+
+decl_storage! {
+	trait Store for Module<T: Trait> as QuadraticFunding {
+		/// The number of rounds that have been started so far.
+		pub RoundCount get(fn round_count): RoundIndex;
+
+		/// The funds of the funding account held for rounds that have not been finalized yet:
+		/// their matching pools and contributions.
+		pub Held get(fn held): BalanceOf<T>;
+
+		/// Rounds that have not been finalized yet.
+		pub Rounds get(fn rounds):
+			map hasher(twox_64_concat) RoundIndex
+			=> Option<RoundInfo<BalanceOf<T>, T::BlockNumber>>;
+
+		/// The projects of each round that has not been finalized yet.
+		pub Projects get(fn projects):
+			double_map hasher(twox_64_concat) RoundIndex, hasher(twox_64_concat) ProjectIndex
+			=> Option<ProjectInfo<T::AccountId, BalanceOf<T>>>;
+
+		/// The contributions to each project, by contributor. Those of a finalized round are left
+		/// to be reaped.
+		pub Contributions get(fn contributions):
+			double_map hasher(twox_64_concat) (RoundIndex, ProjectIndex), hasher(twox_64_concat) T::AccountId
+			=> Option<Contribution<BalanceOf<T>>>;
+
+		/// The block until which, and the amount by which, an account is locked due to the
+		/// conviction of its contributions.
+		pub Locks get(fn locks):
+			map hasher(twox_64_concat) T::AccountId
+			=> Option<(T::BlockNumber, BalanceOf<T>)>;
+	}
+}
+
+decl_event! {
+	pub enum Event<T> where
+		Balance = BalanceOf<T>,
+		<T as frame_system::Trait>::AccountId,
+		<T as frame_system::Trait>::BlockNumber,
+	{
+		/// A round has started with a matching pool committed out of the funding account, open
+		/// until the given block.
+		RoundStarted(RoundIndex, Balance, BlockNumber),
+		/// A project has been registered in a round by an account.
+		ProjectRegistered(RoundIndex, ProjectIndex, AccountId),
+		/// An account has contributed to a project.
+		Contributed(RoundIndex, ProjectIndex, AccountId, Balance),
+		/// A project's beneficiary has been paid its contributions and its share of matching.
+		Paid(RoundIndex, ProjectIndex, AccountId, Balance, Balance),
+		/// A round has been finalized and the unspent matching returned to the treasury.
+		RoundFinalized(RoundIndex, Balance),
+		/// An account's conviction lock has been removed.
+		Unlocked(AccountId),
+	}
+}
+
+decl_error! {
+	pub enum Error for Module<T: Trait> {
+		/// Round does not exist or has been finalized
+		RoundInvalid,
+		/// Round has ended and accepts no more projects or contributions
+		RoundEnded,
+		/// Round has not ended yet
+		RoundOngoing,
+		/// Project does not exist in the round
+		ProjectInvalid,
+		/// Round already has the maximum number of projects
+		TooManyProjects,
+		/// Contribution is below the minimum
+		ContributionTooLow,
+		/// Too high a balance was provided that the account cannot afford
+		InsufficientFunds,
+		/// The account is not recognised as a person
+		NotPerson,
+		/// The account is a sub-account and cannot contribute on its own
+		SubAccount,
+		/// The account has no conviction lock
+		NotLocked,
+		/// The conviction lock has not expired yet
+		NotExpired,
+		/// The conviction has no level in the conviction schedule
+		UnavailableConviction,
+		/// The funding account holds too little that isn't committed to other rounds
+		InsufficientMatching,
+		/// Round has not been finalized yet
+		RoundNotFinalized,
+	}
+}
+
+decl_module! {
+	pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+		type Error = Error<T>;
+
+		/// The funding module id.
+		const ModuleId: ModuleId = T::ModuleId::get();
+
+		/// The amount reserved for registering a project.
+		const ProjectDeposit: BalanceOf<T> = T::ProjectDeposit::get();
+
+		/// The maximum number of projects in a single round.
+		const MaxProjects: ProjectIndex = T::MaxProjects::get();
+
+		/// The minimum amount of a single contribution.
+		const MinimumContribution: BalanceOf<T> = T::MinimumContribution::get();
+
+		/// The period, per lock period of conviction, for which a contributor's stake stays locked
+		/// after its round has ended.
+		const LockPeriod: T::BlockNumber = T::LockPeriod::get();
+
+		/// The lock periods and score multiplier of each conviction level.
+		const ConvictionSchedule: ConvictionSchedule = T::ConvictionSchedule::get();
+
+		fn deposit_event() = default;
+
+		/// Start a round, committing its matching pool out of the funds that the treasury has
+		/// spent to the funding account and no other round holds.
+		///
+		/// The dispatch origin of this call must be `RoundOrigin`.
+		///
+		/// - `matching`: The amount of treasury funds to split between the round's projects.
+		/// - `duration`: The number of blocks for which the round accepts projects and
+		///   contributions.
+		///
+		/// Emits `RoundStarted`.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - One DB change, one extra DB entry.
+		/// # </weight>
+		#[weight = T::WeightInfo::start_round()]
+		fn start_round(origin, matching: BalanceOf<T>, duration: T::BlockNumber) {
+			T::RoundOrigin::ensure_origin(origin)?;
+			ensure!(matching <= Self::unallocated(), Error::<T>::InsufficientMatching);
+			<Held<T>>::mutate(|held| *held = held.saturating_add(matching));
+
+			let index = Self::round_count();
+			RoundCount::put(index + 1);
+			let end = system::Module::<T>::block_number() + duration;
+			<Rounds<T>>::insert(index, RoundInfo { matching, end, project_count: 0 });
+
+			Self::deposit_event(RawEvent::RoundStarted(index, matching, end));
+		}
+
+		/// Register a project in an ongoing round, reserving `ProjectDeposit` until the round is
+		/// finalized.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `round`: The index of the round.
+		/// - `beneficiary`: The account to be paid the project's contributions and matching.
+		///
+		/// Emits `ProjectRegistered`.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - One DB change, one extra DB entry.
+		/// # </weight>
+		#[weight = T::WeightInfo::register_project()]
+		fn register_project(origin,
+			#[compact] round: RoundIndex,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+		) {
+			let who = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			let mut info = Self::ongoing_round(round)?;
+			ensure!(info.project_count < T::MaxProjects::get(), Error::<T>::TooManyProjects);
+
+			let deposit = T::ProjectDeposit::get();
+			T::Currency::reserve(&who, deposit)?;
+
+			let index = info.project_count;
+			info.project_count += 1;
+			<Rounds<T>>::insert(round, info);
+			<Projects<T>>::insert(round, index, ProjectInfo {
+				owner: who.clone(),
+				beneficiary,
+				deposit,
+				contributed: Zero::zero(),
+				score: Zero::zero(),
+			});
+
+			Self::deposit_event(RawEvent::ProjectRegistered(round, index, who));
+		}
+
+		/// Contribute to a project of an ongoing round.
+		///
+		/// The dispatch origin of this call must be _Signed_ by a person that is not a
+		/// sub-account.
+		///
+		/// Contributing again to the same project adds to the previous contribution, so that the
+		/// square root is taken of the total, and keeps the higher of the two convictions. With
+		/// any conviction above `None`, as much again as the total contribution is locked, on top
+		/// of what is locked for the account's other contributions, until the conviction's lock
+		/// periods after the round's end have passed.
+		///
+		/// - `round`: The index of the round.
+		/// - `project`: The index of the project within the round.
+		/// - `amount`: The amount to contribute.
+		/// - `conviction`: The conviction of the contribution.
+		///
+		/// Emits `Contributed`.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - Three DB changes, two extra DB entries.
+		/// # </weight>
+		#[weight = T::WeightInfo::contribute()]
+		fn contribute(origin,
+			#[compact] round: RoundIndex,
+			#[compact] project: ProjectIndex,
+			#[compact] amount: BalanceOf<T>,
+			conviction: Conviction,
+		) {
+			let who = ensure_signed(origin)?;
+			let info = Self::ongoing_round(round)?;
+			let mut project_info = <Projects<T>>::get(round, project).ok_or(Error::<T>::ProjectInvalid)?;
+			ensure!(amount >= T::MinimumContribution::get(), Error::<T>::ContributionTooLow);
+			ensure!(T::Personhood::super_of(&who).is_none(), Error::<T>::SubAccount);
+			ensure!(T::Personhood::is_person(&who), Error::<T>::NotPerson);
+
+			let level = T::ConvictionSchedule::get().level(conviction)
+				.ok_or(Error::<T>::UnavailableConviction)?;
+			let old = <Contributions<T>>::get((round, project), &who).unwrap_or(Contribution {
+				amount: Zero::zero(),
+				conviction: Conviction::None,
+				level: Default::default(),
+			});
+			// The whole contribution is scored at the level of the higher conviction, which it
+			// keeps whatever the schedule becomes.
+			let (conviction, level) = if conviction >= old.conviction {
+				(conviction, level)
+			} else {
+				(old.conviction, old.level)
+			};
+			let contribution = Contribution { amount: old.amount.saturating_add(amount), conviction, level };
+			let lock_periods = contribution.level.lock_periods;
+			// The previous contribution is locked already if it had a conviction.
+			let newly_locked = if lock_periods == 0 {
+				Zero::zero()
+			} else if old.level.lock_periods > 0 {
+				amount
+			} else {
+				contribution.amount
+			};
+			let until = info.end + T::LockPeriod::get() * lock_periods.into();
+			let (until, locked) = match Self::locks(&who) {
+				Some((u, l)) => (u.max(until), l.saturating_add(newly_locked)),
+				None => (until, newly_locked),
+			};
+			if lock_periods > 0 {
+				ensure!(
+					amount.saturating_add(locked) <= T::Currency::free_balance(&who),
+					Error::<T>::InsufficientFunds,
+				);
+			}
+
+			T::Currency::transfer(&who, &Self::account_id(), amount, KeepAlive)?;
+			<Held<T>>::mutate(|held| *held = held.saturating_add(amount));
+
+			project_info.contributed = project_info.contributed.saturating_add(amount);
+			project_info.score = project_info.score
+				.saturating_sub(Self::score_of(old))
+				.saturating_add(Self::score_of(contribution));
+			<Projects<T>>::insert(round, project, project_info);
+			<Contributions<T>>::insert((round, project), &who, contribution);
+
+			if lock_periods > 0 {
+				<Locks<T>>::insert(&who, (until, locked));
+				T::Currency::set_lock(FUNDING_ID, &who, locked, WithdrawReason::Transfer.into());
+			}
+
+			Self::deposit_event(RawEvent::Contributed(round, project, who, amount));
+		}
+
+		/// Pay out the projects of a round that has ended and return its unspent matching to the
+		/// treasury.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// Each project is paid its contributions plus a share of the matching pool proportional
+		/// to the square of its score, and its deposit is returned. A payout that cannot be made,
+		/// e.g. because it would not reach the existential deposit of its beneficiary, goes to the
+		/// treasury instead. The contributions are left to `reap_contributions`.
+		///
+		/// - `round`: The index of the round.
+		///
+		/// Emits `Paid` for each project and then `RoundFinalized`.
+		///
+		/// # <weight>
+		/// - `O(P)`.
+		/// - P is the number of projects in the round, at most `MaxProjects`.
+		/// - P + 3 DB changes, P + 2 DB entries.
+		/// # </weight>
+		#[weight = T::WeightInfo::finalize_round(T::MaxProjects::get())]
+		fn finalize_round(origin, #[compact] round: RoundIndex) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let info = <Rounds<T>>::get(round).ok_or(Error::<T>::RoundInvalid)?;
+			ensure!(system::Module::<T>::block_number() >= info.end, Error::<T>::RoundOngoing);
+
+			let projects = (0..info.project_count)
+				.filter_map(|i| <Projects<T>>::get(round, i).map(|p| (i, p)))
+				.collect::<Vec<_>>();
+			let total_squares = projects.iter()
+				.fold(Zero::zero(), |a: BalanceOf<T>, (_, p)| a.saturating_add(p.score.saturating_mul(p.score)));
+
+			let pot = Self::account_id();
+			let count = projects.len() as u32;
+			let mut released = info.matching;
+			let mut remaining = info.matching;
+			let mut unspent = Zero::zero();
+			for (index, project) in projects.into_iter() {
+				let matched = if total_squares.is_zero() {
+					Zero::zero()
+				} else {
+					let share = Perbill::from_rational_approximation(
+						project.score.saturating_mul(project.score),
+						total_squares,
+					);
+					// Rounding must not let the shares exceed the pool.
+					(share * info.matching).min(remaining)
+				};
+				remaining = remaining.saturating_sub(matched);
+				released = released.saturating_add(project.contributed);
+
+				let payout = project.contributed.saturating_add(matched);
+				if T::Currency::transfer(&pot, &project.beneficiary, payout, AllowDeath).is_ok() {
+					Self::deposit_event(RawEvent::Paid(
+						round,
+						index,
+						project.beneficiary,
+						project.contributed,
+						matched,
+					));
+				} else {
+					unspent = unspent.saturating_add(payout);
+				}
+				T::Currency::unreserve(&project.owner, project.deposit);
+			}
+			unspent = unspent.saturating_add(remaining);
+
+			// Shouldn't be possible to fail, since the pot holds the round's funds.
+			let _ = T::Currency::transfer(&pot, &T::Treasury::get(), unspent, AllowDeath);
+			<Held<T>>::mutate(|held| *held = held.saturating_sub(released));
+			<Projects<T>>::remove_prefix(round);
+			<Rounds<T>>::remove(round);
+
+			Self::deposit_event(RawEvent::RoundFinalized(round, unspent));
+			Ok(Some(T::WeightInfo::finalize_round(count)).into())
+		}
+
+		/// Clear up to `count` of the contributions to a project of a finalized round.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `round`: The index of the round.
+		/// - `project`: The index of the project within the round.
+		/// - `count`: The most contributions to clear.
+		///
+		/// # <weight>
+		/// - `O(C)`.
+		/// - C is `count`.
+		/// - One DB entry, C DB changes.
+		/// # </weight>
+		#[weight = T::WeightInfo::reap_contributions(*count)]
+		fn reap_contributions(origin,
+			#[compact] round: RoundIndex,
+			#[compact] project: ProjectIndex,
+			#[compact] count: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			ensure!(round < Self::round_count(), Error::<T>::RoundInvalid);
+			ensure!(!<Rounds<T>>::contains_key(round), Error::<T>::RoundNotFinalized);
+
+			let contributors = <Contributions<T>>::iter_prefix((round, project))
+				.take(count as usize)
+				.map(|(who, _)| who)
+				.collect::<Vec<_>>();
+			for who in contributors.iter() {
+				<Contributions<T>>::remove((round, project), who);
+			}
+			Ok(Some(T::WeightInfo::reap_contributions(contributors.len() as u32)).into())
+		}
+
+		/// Remove the conviction lock of `target` once it has expired.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `target`: The account to unlock.
+		///
+		/// Emits `Unlocked`.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - Two DB changes, one DB entry.
+		/// # </weight>
+		#[weight = T::WeightInfo::unlock()]
+		fn unlock(origin, target: T::AccountId) {
+			ensure_signed(origin)?;
+			let (until, _) = Self::locks(&target).ok_or(Error::<T>::NotLocked)?;
+			ensure!(system::Module::<T>::block_number() >= until, Error::<T>::NotExpired);
+
+			<Locks<T>>::remove(&target);
+			T::Currency::remove_lock(FUNDING_ID, &target);
+
+			Self::deposit_event(RawEvent::Unlocked(target));
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	// exposed immutables.
+
+	/// The account that holds contributions and matching pools until their round is finalized.
+	pub fn account_id() -> T::AccountId {
+		T::ModuleId::get().into_account()
+	}
+
+	/// The funds of the funding account that no round holds, out of which the matching pools of
+	/// new rounds are committed.
+	pub fn unallocated() -> BalanceOf<T> {
+		T::Currency::free_balance(&Self::account_id()).saturating_sub(Self::held())
+	}
+
+	// private.
+
+	/// The round `round` if it still accepts projects and contributions.
+	fn ongoing_round(round: RoundIndex)
+		-> Result<RoundInfo<BalanceOf<T>, T::BlockNumber>, DispatchError>
+	{
+		let info = <Rounds<T>>::get(round).ok_or(Error::<T>::RoundInvalid)?;
+		ensure!(system::Module::<T>::block_number() < info.end, Error::<T>::RoundEnded);
+		Ok(info)
+	}
+
+	/// The post-conviction square root of a contribution.
+	fn score_of(contribution: Contribution<BalanceOf<T>>) -> BalanceOf<T> {
+		let root = VoteWeight::Quadratic.calculate_weight(contribution.amount);
+		contribution.level.votes(contribution.amount, root).votes
+	}
+}
@@ -0,0 +1,386 @@
+// Copyright 2017-2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Quadratic funding pallet tests.
+
+#![cfg(test)]
+
+use super::*;
+use std::cell::RefCell;
+use frame_support::{
+	assert_noop, assert_ok, impl_outer_origin, impl_outer_event, parameter_types, weights::Weight,
+};
+use sp_core::H256;
+use sp_runtime::{
+	Perbill, ModuleId,
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup, BadOrigin},
+};
+use pallet_balances::{BalanceLock, Error as BalancesError};
+use frame_system::EnsureRoot;
+
+impl_outer_origin! {
+	pub enum Origin for Test where system = frame_system {}
+}
+
+mod quadratic_funding {
+	// Re-export needed for `impl_outer_event!`.
+	pub use super::super::*;
+}
+
+impl_outer_event! {
+	pub enum Event for Test {
+		system<T>,
+		pallet_balances<T>,
+		quadratic_funding<T>,
+	}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+impl frame_system::Trait for Test {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = ();
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type MaximumBlockWeight = MaximumBlockWeight;
+	type DbWeight = ();
+	type BlockExecutionWeight = ();
+	type ExtrinsicBaseWeight = ();
+	type MaximumBlockLength = MaximumBlockLength;
+	type AvailableBlockRatio = AvailableBlockRatio;
+	type Version = ();
+	type ModuleToIndex = ();
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+}
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Trait for Test {
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+}
+parameter_types! {
+	pub const FundingModuleId: ModuleId = ModuleId(*b"py/qfund");
+	pub const TreasuryAccount: u64 = 100;
+	pub const ProjectDeposit: u64 = 5;
+	pub const MaxProjects: ProjectIndex = 2;
+	pub const MinimumContribution: u64 = 2;
+	pub const LockPeriod: u64 = 3;
+	pub const Convictions: ConvictionSchedule = ConvictionSchedule::standard();
+}
+thread_local! {
+	static NOT_PERSONS: RefCell<Vec<u64>> = RefCell::new(vec![]);
+	static SUB_ACCOUNTS: RefCell<Vec<(u64, u64)>> = RefCell::new(vec![]);
+}
+pub struct TestPersonhood;
+impl Personhood<u64> for TestPersonhood {
+	fn is_person(who: &u64) -> bool {
+		NOT_PERSONS.with(|v| !v.borrow().contains(who))
+	}
+	fn super_of(who: &u64) -> Option<u64> {
+		SUB_ACCOUNTS.with(|v| v.borrow().iter().find(|(sub, _)| sub == who).map(|(_, s)| *s))
+	}
+	fn sub_accounts(who: &u64) -> Vec<u64> {
+		SUB_ACCOUNTS.with(|v| v.borrow().iter().filter(|(_, s)| s == who).map(|(sub, _)| *sub).collect())
+	}
+	fn population() -> u32 { 5 }
+}
+impl Trait for Test {
+	type Event = Event;
+	type ModuleId = FundingModuleId;
+	type Currency = pallet_balances::Module<Test>;
+	type Treasury = TreasuryAccount;
+	type RoundOrigin = EnsureRoot<u64>;
+	type ProjectDeposit = ProjectDeposit;
+	type MaxProjects = MaxProjects;
+	type MinimumContribution = MinimumContribution;
+	type LockPeriod = LockPeriod;
+	type ConvictionSchedule = Convictions;
+	type Personhood = TestPersonhood;
+	type WeightInfo = ();
+}
+type System = frame_system::Module<Test>;
+type Balances = pallet_balances::Module<Test>;
+type QuadraticFunding = Module<Test>;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test>{
+		balances: vec![(1, 100), (2, 200), (3, 300), (4, 400), (5, 500), (100, 10_000)],
+	}.assimilate_storage(&mut t).unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		// The treasury has spent 5000 on matching.
+		assert_ok!(Balances::transfer(Origin::signed(100), QuadraticFunding::account_id(), 5_000));
+	});
+	ext
+}
+
+fn the_lock(amount: u64) -> BalanceLock<u64> {
+	BalanceLock {
+		id: FUNDING_ID,
+		amount,
+		reasons: pallet_balances::Reasons::Misc,
+	}
+}
+
+/// Start a round with a matching pool of 1000 ending at block 5, with two projects registered by
+/// 1 that pay out to 10 and 11.
+fn start_round_with_projects() -> RoundIndex {
+	assert_ok!(QuadraticFunding::start_round(Origin::ROOT, 1000, 4));
+	let r = QuadraticFunding::round_count() - 1;
+	assert_ok!(QuadraticFunding::register_project(Origin::signed(1), r, 10));
+	assert_ok!(QuadraticFunding::register_project(Origin::signed(1), r, 11));
+	r
+}
+
+#[test]
+fn start_round_commits_matching_spent_by_treasury() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(QuadraticFunding::start_round(Origin::signed(1), 1000, 4), BadOrigin);
+		assert_noop!(
+			QuadraticFunding::start_round(Origin::ROOT, 5_001, 4),
+			Error::<Test>::InsufficientMatching,
+		);
+
+		assert_ok!(QuadraticFunding::start_round(Origin::ROOT, 1000, 4));
+		assert_eq!(Balances::free_balance(100), 5_000);
+		assert_eq!(Balances::free_balance(QuadraticFunding::account_id()), 5_000);
+		assert_eq!(QuadraticFunding::held(), 1000);
+		assert_eq!(QuadraticFunding::unallocated(), 4_000);
+		assert_eq!(QuadraticFunding::rounds(0), Some(RoundInfo { matching: 1000, end: 5, project_count: 0 }));
+		assert_eq!(QuadraticFunding::round_count(), 1);
+
+		// The matching of the first round is not committed again.
+		assert_noop!(
+			QuadraticFunding::start_round(Origin::ROOT, 4_001, 4),
+			Error::<Test>::InsufficientMatching,
+		);
+		assert_ok!(QuadraticFunding::start_round(Origin::ROOT, 4_000, 4));
+		assert_eq!(QuadraticFunding::unallocated(), 0);
+	});
+}
+
+#[test]
+fn register_project_reserves_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(QuadraticFunding::register_project(Origin::signed(1), 0, 10), Error::<Test>::RoundInvalid);
+		let r = start_round_with_projects();
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_noop!(
+			QuadraticFunding::register_project(Origin::signed(2), r, 12),
+			Error::<Test>::TooManyProjects,
+		);
+
+		assert_ok!(QuadraticFunding::start_round(Origin::ROOT, 1000, 4));
+		System::set_block_number(5);
+		assert_noop!(QuadraticFunding::register_project(Origin::signed(2), 1, 12), Error::<Test>::RoundEnded);
+	});
+}
+
+#[test]
+fn matching_is_proportional_to_square_of_sum_of_roots() {
+	new_test_ext().execute_with(|| {
+		let r = start_round_with_projects();
+		// Three contributions of 4 each: (2 + 2 + 2)² = 36.
+		for who in 2..5 {
+			assert_ok!(QuadraticFunding::contribute(Origin::signed(who), r, 0, 4, Conviction::Locked1x));
+		}
+		// A single contribution of 9: 3² = 9.
+		assert_ok!(QuadraticFunding::contribute(Origin::signed(5), r, 1, 9, Conviction::Locked1x));
+		assert_eq!(QuadraticFunding::projects(r, 0).unwrap().score, 6);
+		assert_eq!(QuadraticFunding::projects(r, 1).unwrap().score, 3);
+
+		assert_noop!(QuadraticFunding::finalize_round(Origin::signed(1), r), Error::<Test>::RoundOngoing);
+		System::set_block_number(5);
+		assert_ok!(QuadraticFunding::finalize_round(Origin::signed(1), r));
+
+		assert_eq!(Balances::free_balance(10), 12 + 800);
+		assert_eq!(Balances::free_balance(11), 9 + 200);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(100), 5_000);
+		assert_eq!(QuadraticFunding::held(), 0);
+		assert_eq!(QuadraticFunding::unallocated(), 4_000);
+		assert_eq!(QuadraticFunding::rounds(r), None);
+		assert_eq!(QuadraticFunding::projects(r, 0), None);
+		assert_noop!(QuadraticFunding::finalize_round(Origin::signed(1), r), Error::<Test>::RoundInvalid);
+	});
+}
+
+#[test]
+fn repeated_contribution_is_rooted_as_a_whole() {
+	new_test_ext().execute_with(|| {
+		let r = start_round_with_projects();
+		assert_ok!(QuadraticFunding::contribute(Origin::signed(2), r, 0, 4, Conviction::Locked1x));
+		assert_ok!(QuadraticFunding::contribute(Origin::signed(2), r, 0, 5, Conviction::Locked2x));
+		// 2 * sqrt(4 + 5), the higher conviction applying to the whole.
+		assert_eq!(QuadraticFunding::projects(r, 0).unwrap().score, 6);
+		assert_eq!(QuadraticFunding::projects(r, 0).unwrap().contributed, 9);
+		assert_eq!(
+			QuadraticFunding::contributions((r, 0), 2),
+			Some(Contribution {
+				amount: 9,
+				conviction: Conviction::Locked2x,
+				level: ConvictionSchedule::standard().level(Conviction::Locked2x).unwrap(),
+			}),
+		);
+	});
+}
+
+#[test]
+fn unspent_matching_returns_to_treasury() {
+	new_test_ext().execute_with(|| {
+		let r = start_round_with_projects();
+		System::set_block_number(5);
+		assert_ok!(QuadraticFunding::finalize_round(Origin::signed(2), r));
+		assert_eq!(Balances::free_balance(100), 6_000);
+		assert_eq!(Balances::free_balance(10), 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn contribute_checks_should_work() {
+	new_test_ext().execute_with(|| {
+		let r = start_round_with_projects();
+		assert_noop!(
+			QuadraticFunding::contribute(Origin::signed(2), r, 2, 4, Conviction::None),
+			Error::<Test>::ProjectInvalid,
+		);
+		assert_noop!(
+			QuadraticFunding::contribute(Origin::signed(2), r, 0, 1, Conviction::None),
+			Error::<Test>::ContributionTooLow,
+		);
+		assert_noop!(
+			QuadraticFunding::contribute(Origin::signed(2), r, 0, 150, Conviction::Locked1x),
+			Error::<Test>::InsufficientFunds,
+		);
+
+		NOT_PERSONS.with(|v| *v.borrow_mut() = vec![2]);
+		SUB_ACCOUNTS.with(|v| *v.borrow_mut() = vec![(3, 4)]);
+		assert_noop!(
+			QuadraticFunding::contribute(Origin::signed(2), r, 0, 4, Conviction::None),
+			Error::<Test>::NotPerson,
+		);
+		assert_noop!(
+			QuadraticFunding::contribute(Origin::signed(3), r, 0, 4, Conviction::None),
+			Error::<Test>::SubAccount,
+		);
+
+		System::set_block_number(5);
+		assert_noop!(
+			QuadraticFunding::contribute(Origin::signed(4), r, 0, 4, Conviction::None),
+			Error::<Test>::RoundEnded,
+		);
+	});
+}
+
+#[test]
+fn conviction_locks_until_lock_periods_after_end() {
+	new_test_ext().execute_with(|| {
+		let r = start_round_with_projects();
+		assert_ok!(QuadraticFunding::contribute(Origin::signed(2), r, 0, 10, Conviction::None));
+		assert_eq!(Balances::locks(2), vec![]);
+
+		assert_ok!(QuadraticFunding::contribute(Origin::signed(3), r, 0, 10, Conviction::Locked2x));
+		// Until 5 + 2 * 3.
+		assert_eq!(QuadraticFunding::locks(3), Some((11, 10)));
+		assert_eq!(Balances::locks(3), vec![the_lock(10)]);
+		assert_noop!(
+			Balances::transfer(Origin::signed(3), 1, 285),
+			BalancesError::<Test, _>::LiquidityRestrictions,
+		);
+
+		assert_noop!(QuadraticFunding::unlock(Origin::signed(1), 2), Error::<Test>::NotLocked);
+		System::set_block_number(10);
+		assert_noop!(QuadraticFunding::unlock(Origin::signed(1), 3), Error::<Test>::NotExpired);
+		System::set_block_number(11);
+		assert_ok!(QuadraticFunding::unlock(Origin::signed(1), 3));
+		assert_eq!(Balances::locks(3), vec![]);
+		assert_eq!(QuadraticFunding::locks(3), None);
+	});
+}
+
+#[test]
+fn conviction_locks_of_contributions_add_up() {
+	new_test_ext().execute_with(|| {
+		let r = start_round_with_projects();
+		assert_ok!(QuadraticFunding::contribute(Origin::signed(3), r, 0, 10, Conviction::Locked1x));
+		assert_ok!(QuadraticFunding::contribute(Origin::signed(3), r, 1, 20, Conviction::Locked2x));
+		assert_ok!(QuadraticFunding::contribute(Origin::signed(3), r, 0, 5, Conviction::Locked1x));
+		assert_eq!(QuadraticFunding::locks(3), Some((11, 35)));
+		assert_eq!(Balances::locks(3), vec![the_lock(35)]);
+
+		// A contribution without conviction is locked as a whole once it gains one.
+		assert_ok!(QuadraticFunding::contribute(Origin::signed(4), r, 0, 10, Conviction::None));
+		assert_ok!(QuadraticFunding::contribute(Origin::signed(4), r, 0, 5, Conviction::Locked1x));
+		assert_eq!(QuadraticFunding::locks(4), Some((8, 15)));
+
+		// 60 is locked and 140 left, too little for 50 more along with its lock.
+		assert_ok!(QuadraticFunding::contribute(Origin::signed(2), r, 0, 60, Conviction::Locked1x));
+		assert_noop!(
+			QuadraticFunding::contribute(Origin::signed(2), r, 1, 50, Conviction::Locked1x),
+			Error::<Test>::InsufficientFunds,
+		);
+	});
+}
+
+#[test]
+fn contributions_are_reaped_a_bounded_number_at_a_time() {
+	new_test_ext().execute_with(|| {
+		let r = start_round_with_projects();
+		for who in 2..5 {
+			assert_ok!(QuadraticFunding::contribute(Origin::signed(who), r, 0, 4, Conviction::None));
+		}
+		assert_noop!(
+			QuadraticFunding::reap_contributions(Origin::signed(1), r, 0, 2),
+			Error::<Test>::RoundNotFinalized,
+		);
+		assert_noop!(
+			QuadraticFunding::reap_contributions(Origin::signed(1), r + 1, 0, 2),
+			Error::<Test>::RoundInvalid,
+		);
+
+		System::set_block_number(5);
+		assert_ok!(QuadraticFunding::finalize_round(Origin::signed(1), r));
+		assert_eq!(QuadraticFunding::contributions((r, 0), 2).map(|c| c.amount), Some(4));
+
+		assert_ok!(QuadraticFunding::reap_contributions(Origin::signed(1), r, 0, 2));
+		assert_eq!((2..5).filter(|who| QuadraticFunding::contributions((r, 0), who).is_some()).count(), 1);
+		assert_ok!(QuadraticFunding::reap_contributions(Origin::signed(1), r, 0, 2));
+		assert_eq!((2..5).filter(|who| QuadraticFunding::contributions((r, 0), who).is_some()).count(), 0);
+	});
+}
@@ -0,0 +1,46 @@
+// Copyright 2020 Parity Technologies (UK) Ltd.
+// This file is part of Substrate.
+
+// Substrate is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Substrate is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Substrate.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The weights of the calls of this pallet.
+
+use frame_support::weights::Weight;
+
+/// The weight of each call, as measured by the benchmark of the same name in `benchmarking.rs`.
+///
+/// The parameters are the components of the benchmark:
+/// - `p`: the number of projects in the round;
+/// - `c`: the number of contributions cleared.
+///
+/// The runtime should plug in weights generated with `benchmark` on its own hardware.
+pub trait WeightInfo {
+	fn start_round() -> Weight;
+	fn register_project() -> Weight;
+	fn contribute() -> Weight;
+	fn finalize_round(p: u32) -> Weight;
+	fn reap_contributions(c: u32) -> Weight;
+	fn unlock() -> Weight;
+}
+
+/// A flat weight for every call, for tests and chains that don't care about the weight of
+/// funding.
+impl WeightInfo for () {
+	fn start_round() -> Weight { 1_000_000_000 }
+	fn register_project() -> Weight { 1_000_000_000 }
+	fn contribute() -> Weight { 1_000_000_000 }
+	fn finalize_round(_p: u32) -> Weight { 1_000_000_000 }
+	fn reap_contributions(_c: u32) -> Weight { 1_000_000_000 }
+	fn unlock() -> Weight { 1_000_000_000 }
+}